@@ -10,10 +10,56 @@ pub const DEFAULT_MAX_M: u128 = 690_000_000_000_000; // 690B tokens for trading
 
 pub const MAX_MEME_TOKENS: u128 = DEFAULT_MAX_M_LP + DEFAULT_MAX_M;
 
-pub const DECIMALS_S: u128 = 1_000_000_000;
-
 pub const MAX_AIRDROPPED_TOKENS: u64 = 100_000_000_000_000;
 
+pub const MAX_CREATOR_ALLOCATION: u64 = 50_000_000_000_000; // vested creator tokens, at most 5% on top of the supply
+
+pub const MAX_CREATOR_ALLOCATION_BPS: u16 = 2_000; // creators take at most 20% of the trading supply
+
+pub const MAX_MIGRATION_FEE_BPS: u16 = 500; // protocol takes at most 5% of the migrated quote
+
+pub const FEE_DENOMINATOR: u64 = 1_000_000_000; // fee percents are parts per billion, 10_000_000 is 1%
+
+pub const MAX_BATCH_PREVIEW_AMOUNTS: usize = 32; // bounds compute of batch previews
+
+pub const MAX_AIRDROP_BATCH: usize = 20; // one transfer per recipient, fits the default compute budget
+
+pub const MAX_CALLBACK_ACCOUNTS: usize = 16; // accounts forwarded to a post-swap callback
+
+pub const TEMP_WSOL_PREFIX: &[u8; 9] = b"temp_wsol"; // per-owner WSOL account living within a native SOL buy
+
+pub const MAX_RECONCILE_DUST: u64 = 1_000; // raw units, larger vault surpluses are swept instead
+
+pub const FAST_PATH_MAX_TRADE_BPS: u64 = 10; // 0.1% of reserves, curve priced linearly below
+
+pub const PRICE_FACTOR_RAMP_PRECISION: u64 = 1_000; // ramp moves in 0.1% steps of supply sold
+
+pub const LP_LOCK_DURATION: i64 = 180 * 86_400; // LP locked for 180 days after migration
+
+pub const MIN_LP_LOCK_DURATION: i64 = 30 * 86_400; // creators lock their LP for at least 30 days
+
+pub const MAX_SELL_LOCK_DURATION: i64 = 7 * 86_400; // sells locked for at most 7 days after launch
+
+pub const MAX_WHITELIST_DURATION: i64 = 3 * 86_400; // whitelist phase lasts at most 3 days after launch
+
+pub const MAX_FIRST_BUYER_BONUS_BPS: u16 = 500; // first buyer gets at most 5% on top of their buy
+
+pub const MAX_BURN_ON_SELL_BPS: u16 = 1_000; // sells burn at most 10% of their meme input
+
+pub const MAX_FEE_BPS: u16 = 500; // configured quote fees take at most 5% of a trade
+
+pub const DEFAULT_MIN_QUOTE_LIQUIDITY_BPS: u64 = 10; // sells need 0.1% of the target in reserve
+
+pub const MAX_MIN_QUOTE_LIQUIDITY_BPS: u64 = 1_000; // sells open with at most 10% of the target in reserve
+
+pub const COMMIT_REVEAL_WINDOW_SLOTS: u64 = 150; // ~1 minute to reveal a committed buy
+
+pub const EXPONENTIAL_CURVE_DOUBLINGS: u32 = 4; // exponential curve prices rise 16x over the supply
+
+pub const SPOT_PRICE_PRECISION: u128 = 1_000_000_000_000; // spot prices scaled by 1e12
+
+pub const CURVE_INVARIANT_TOLERANCE_BPS: u64 = 10; // reserves may drift 0.1% of a trade by rounding
+
 #[cfg(feature = "localnet-testing")]
 pub const LOCK_TIME: i64 = 4; // 4 seconds
 #[cfg(feature = "mainnet-testing")]
@@ -51,6 +97,13 @@ pub const SWAP_AUTH_KEY: Pubkey =
 pub const SWAP_AUTH_KEY: Pubkey =
     solana_program::pubkey!("389y4YsTxFKpz2HxVHpvDk13FSXan48LZQtGv8pD4vQA");
 
+// Stablecoins a dual quote pool can pair, exchanged one for one so the fixed
+// rate of the second quote can't drift from the market's
+pub const PEGGED_QUOTE_MINTS: [Pubkey; 2] = [
+    solana_program::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), // USDC
+    solana_program::pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"), // USDT
+];
+
 pub const SWAP_FEE_KEY: Pubkey =
     solana_program::pubkey!("xqzvZzKFCjvPuRqkyg5rxA95avrvJxesZ41rCLfYwUM");
 pub const LP_FEE_KEY: Pubkey =
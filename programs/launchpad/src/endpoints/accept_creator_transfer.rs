@@ -0,0 +1,49 @@
+use crate::err;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+
+/// Accepts a pending creator transfer, making the signer the pool creator.
+///
+/// Can only be called by the key proposed through `propose_creator_transfer`.
+pub fn handle(ctx: Context<AcceptCreatorTransfer>) -> Result<()> {
+    let new_creator = ctx.accounts.new_creator.key();
+    let pool = &mut ctx.accounts.pool;
+
+    let previous_creator = pool.creator_addr;
+    pool.creator_addr = new_creator;
+    pool.pending_creator = None;
+
+    emit!(CreatorTransferAccepted {
+        pool: pool.key(),
+        previous_creator,
+        new_creator,
+    });
+
+    Ok(())
+}
+
+/// Represents the accounts required for accepting a creator transfer.
+#[derive(Accounts)]
+pub struct AcceptCreatorTransfer<'info> {
+    /// The pending creator
+    pub new_creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = pool.pending_creator == Some(new_creator.key())
+            @ err::acc("Only the pending creator can accept a creator transfer"),
+    )]
+    /// The pool whose creator is transferred
+    pub pool: Account<'info, BoundPool>,
+}
+
+/// Emitted when a pending creator accepts a creator transfer
+#[event]
+pub struct CreatorTransferAccepted {
+    /// Pool transferred
+    pub pool: Pubkey,
+    /// Creator before the transfer
+    pub previous_creator: Pubkey,
+    /// Creator after the transfer
+    pub new_creator: Pubkey,
+}
@@ -0,0 +1,47 @@
+use crate::err;
+use crate::models::protocol_config::{ProtocolConfig, MAX_FEE_EXEMPT};
+use anchor_lang::prelude::*;
+
+/// Exempts an account from admin fees on every pool.
+///
+/// Meant for designated market makers and the protocol treasury, so they can
+/// provide liquidity without paying retail fees.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `account` - The trader to exempt
+pub fn handle(ctx: Context<AddFeeExempt>, account: Pubkey) -> Result<()> {
+    let fee_exempt = &mut ctx.accounts.protocol_config.fee_exempt;
+
+    if fee_exempt.contains(&account) {
+        return Err(error!(err::arg("Account is already fee exempt")));
+    }
+
+    if fee_exempt.len() >= MAX_FEE_EXEMPT {
+        return Err(error!(err::arg(format!(
+            "At most {} fee exempt accounts are supported",
+            MAX_FEE_EXEMPT
+        ))));
+    }
+
+    fee_exempt.push(account);
+
+    Ok(())
+}
+
+/// Represents the accounts required for exempting an account from fees.
+#[derive(Accounts)]
+pub struct AddFeeExempt<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can exempt accounts from fees"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
@@ -0,0 +1,42 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::whitelist_entry::WhitelistEntry;
+use anchor_lang::prelude::*;
+
+/// Allows `buyer` to buy from the pool during its whitelist phase, spending
+/// at most `cap` quote. Calling it again for the same buyer updates the cap,
+/// keeping what was already spent. Creator only, until the pool migrates.
+pub fn handle(ctx: Context<AddToWhitelist>, buyer: Pubkey, cap: u64) -> Result<()> {
+    let entry = &mut ctx.accounts.whitelist_entry;
+    entry.pool = ctx.accounts.pool.key();
+    entry.buyer = buyer;
+    entry.cap = cap;
+
+    msg!("whitelisted: {}, cap: {}", buyer, cap);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(buyer: Pubkey)]
+pub struct AddToWhitelist<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        constraint = sender.key() == pool.creator_addr
+            @ err::acc("Only the pool creator can whitelist buyers"),
+        constraint = !pool.pool_migration @ AmmError::AlreadyMigrated,
+    )]
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + WhitelistEntry::INIT_SPACE,
+        seeds = [WhitelistEntry::WHITELIST_PREFIX, pool.key().as_ref(), buyer.as_ref()],
+        bump,
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,288 @@
+use crate::err;
+use crate::models::bound::BoundPool;
+use crate::models::target_config::TargetConfig;
+use crate::models::vesting_schedule::VestingSchedule;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount};
+
+impl<'info> CancelPool<'info> {
+    /// Creates a CPI context for burning the meme supply held by a pool vault
+    fn burn_meme(
+        &self,
+        vault: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.meme_mint.to_account_info(),
+            from: vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for closing a pool vault, rent goes to the creator
+    fn close_vault(
+        &self,
+        vault: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: vault.to_account_info(),
+            destination: self.creator.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Closes the vesting schedule, returning its rent to the creator. It's
+    /// passed unchecked, see `VestingSchedule::load`, so closed by hand the
+    /// way Anchor's `close` constraint does
+    fn close_vesting_schedule(&self) -> Result<()> {
+        let schedule = self.vesting_schedule.to_account_info();
+        let creator = self.creator.to_account_info();
+
+        **creator.try_borrow_mut_lamports()? += schedule.lamports();
+        **schedule.try_borrow_mut_lamports()? = 0;
+        schedule.assign(&System::id());
+        schedule.realloc(0, false)?;
+
+        Ok(())
+    }
+
+    /// Burns the meme held by a pool vault and closes it, returning the
+    /// amount burned
+    fn burn_and_close(
+        &self,
+        vault: &Account<'info, TokenAccount>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<u64> {
+        if vault.amount != 0 {
+            token::burn(
+                self.burn_meme(vault).with_signer(signer_seeds),
+                vault.amount,
+            )?;
+        }
+        token::close_account(self.close_vault(vault).with_signer(signer_seeds))?;
+
+        Ok(vault.amount)
+    }
+}
+
+/// Cancels a pool nobody traded against yet.
+///
+/// Burns the meme supply minted to the pool, including its first buyer
+/// bonus and vested creator allocation, closes its vaults, vesting schedule
+/// and the pool account and returns all rent to the creator. Quote vaults
+/// must be empty, tokens sent to them directly can be recovered with
+/// `sweep_excess` first. The pool no longer counts against its target
+/// config, so the same launch can be created again.
+///
+/// # Errors
+/// * `AmmError::PoolHasTraded` - If the pool has any volume or circulating meme
+/// * `AmmError::AlreadyMigrated` - If the pool was migrated
+pub fn handle(ctx: Context<CancelPool>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.pool.ensure_cancellable()?;
+
+    if accs.quote_vault.amount != 0 {
+        return Err(error!(err::acc("Quote vault isn't empty")));
+    }
+
+    let second_vault = match accs.pool.second_quote {
+        Some(_) => {
+            let vault = accs
+                .second_quote_vault
+                .as_ref()
+                .ok_or_else(|| error!(err::acc("Second quote vault is required")))?;
+
+            if vault.amount != 0 {
+                return Err(error!(err::acc("Second quote vault isn't empty")));
+            }
+
+            Some(vault)
+        }
+        None => None,
+    };
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[accs.pool.signer_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let mut meme_amount = accs.burn_and_close(&accs.meme_vault, signer_seeds)?;
+
+    // The first buyer bonus was minted into its own vault
+    if accs.pool.first_buyer_bonus_bps != 0 {
+        let vault = accs
+            .bonus_vault
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Bonus vault is required")))?;
+        meme_amount += accs.burn_and_close(vault, signer_seeds)?;
+    } else if accs.bonus_vault.is_some() {
+        return Err(error!(err::acc(
+            "Bonus vault is only used with a first buyer bonus"
+        )));
+    }
+
+    // So was the vested creator allocation, closed along with its schedule
+    // so a recreated pool doesn't inherit it
+    if let Some(schedule) = VestingSchedule::load(&accs.vesting_schedule)? {
+        let vault = accs
+            .vesting_vault
+            .as_ref()
+            .filter(|vault| vault.key() == schedule.vault)
+            .ok_or_else(|| error!(err::acc("Vesting vault of the schedule is required")))?;
+        meme_amount += accs.burn_and_close(vault, signer_seeds)?;
+        accs.close_vesting_schedule()?;
+    } else if accs.vesting_vault.is_some() {
+        return Err(error!(err::acc(
+            "Vesting vault is only used with a vesting schedule"
+        )));
+    }
+
+    token::close_account(
+        accs.close_vault(&accs.quote_vault)
+            .with_signer(signer_seeds),
+    )?;
+    if let Some(vault) = second_vault {
+        token::close_account(accs.close_vault(vault).with_signer(signer_seeds))?;
+    }
+
+    accs.target_config.release_pool();
+
+    msg!("cancelled pool: {}, burned: {}", pool_key, meme_amount);
+
+    Ok(())
+}
+
+/// Represents the accounts required for cancelling an untraded pool.
+#[derive(Accounts)]
+pub struct CancelPool<'info> {
+    #[account(
+        mut,
+        constraint = creator.key() == pool.creator_addr
+            @ err::acc("Only the pool creator can cancel the pool"),
+    )]
+    /// The pool creator receiving the rent
+    pub creator: Signer<'info>,
+
+    #[account(mut, close = creator)]
+    /// The pool being cancelled
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(mut, address = pool.meme_reserve.mint)]
+    /// The meme token mint
+    pub meme_mint: Account<'info, Mint>,
+
+    #[account(mut, address = pool.meme_reserve.vault)]
+    /// The pool's meme vault
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, address = pool.quote_reserve.vault)]
+    /// The pool's quote vault
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_vault(second_quote_vault.key())
+            @ err::acc("Not the pool's second quote vault"),
+    )]
+    /// The pool's second quote vault, required if the pool has one
+    pub second_quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            TargetConfig::CONFIG_PREFIX,
+            pool.quote_reserve.mint.as_ref(),
+            pool.meme_reserve.mint.as_ref(),
+        ],
+        bump,
+    )]
+    /// The target config the pool was created against, no longer counting it
+    pub target_config: Account<'info, TargetConfig>,
+
+    #[account(
+        mut,
+        seeds = [BoundPool::BONUS_VAULT_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    /// The pool's first buyer bonus vault, required if the pool has a bonus
+    pub bonus_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: vesting schedule PDA of the pool, always passed and closed
+    /// once `new_pool` created it, see `VestingSchedule::load`
+    #[account(
+        mut,
+        seeds = [VestingSchedule::VESTING_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    pub vesting_schedule: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// The vault of the vesting schedule, required if the pool has one
+    pub vesting_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump = pool.signer_bump)]
+    /// The pool signer owning the vaults
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+    use solana_sdk::signature::Signer;
+
+    #[tokio::test]
+    async fn test_cancel_then_recreate_integration() {
+        // ARRANGE: Untraded pool with a first buyer bonus and a vesting
+        // creator allocation
+        let mut test = LaunchpadTest::start().await;
+        test.init_target_config(DEFAULT_TARGET).await.unwrap();
+        test.new_pool_with(100, 1_000_000).await.unwrap();
+        let vesting_vault = test.vesting_vault.unwrap();
+        assert_eq!(test.target_config_account().await.pools_created, 1);
+
+        // ACT
+        test.cancel_pool().await.unwrap();
+
+        // ASSERT: Every meme minted at creation is burned, every account
+        // closed and the config no longer counts the pool
+        let meme_mint = test
+            .ctx
+            .banks_client
+            .get_packed_account_data::<spl_token::state::Mint>(test.meme_mint.pubkey())
+            .await
+            .unwrap();
+        assert_eq!(meme_mint.supply, 0);
+        for account in [
+            test.pool,
+            test.meme_vault,
+            test.quote_vault,
+            test.bonus_vault(),
+            test.vesting_schedule(),
+            vesting_vault,
+        ] {
+            assert!(!test.account_exists(account).await);
+        }
+        assert_eq!(test.target_config_account().await.pools_created, 0);
+
+        // ACT & ASSERT: The same launch can be created again
+        test.new_pool_with(100, 1_000_000).await.unwrap();
+        assert!(test.account_exists(test.bonus_vault()).await);
+        assert!(test.account_exists(test.vesting_schedule()).await);
+        assert_eq!(test.target_config_account().await.pools_created, 1);
+
+        println!("✅ Cancel then recreate integration test passed!");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> ClaimLpAfterLock<'info> {
+    /// Creates a CPI context for releasing the locked LP tokens to the creator
+    fn send_lp_to_creator(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.lp_lock_vault.to_account_info(),
+            to: self.creator_lp_account.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Releases the Raydium LP tokens locked at migration to the pool creator.
+///
+/// The LP stays locked under the pool signer for the pool's
+/// `lp_lock_duration` after migration, giving buyers a liquidity lock
+/// guarantee. `unlock_ts` must
+/// match the pool's `lp_unlock_ts`, so a client can't act on a stale lock.
+///
+/// # Errors
+/// * `AmmError::LpStillLocked` - If the lock hasn't expired yet
+/// * `AmmError::NoTokensToWithdraw` - If the LP was already claimed
+pub fn handle(ctx: Context<ClaimLpAfterLock>, unlock_ts: i64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if unlock_ts != accs.pool.lp_unlock_ts {
+        return Err(error!(err::arg(
+            "Unlock timestamp doesn't match the pool's LP lock"
+        )));
+    }
+
+    let lp_amount = accs.pool.claimable_lp(Clock::get()?.unix_timestamp)?;
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        accs.send_lp_to_creator().with_signer(signer_seeds),
+        lp_amount,
+    )?;
+
+    accs.pool.lp_locked = 0;
+
+    emit!(LpClaimed {
+        pool: pool_key,
+        creator: accs.creator.key(),
+        amount: lp_amount,
+    });
+
+    Ok(())
+}
+
+/// Represents the accounts required for claiming the locked LP tokens.
+#[derive(Accounts)]
+pub struct ClaimLpAfterLock<'info> {
+    /// The pool creator
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = pool.creator_addr == creator.key()
+            @ err::acc("Only the pool creator can claim the locked LP"),
+        constraint = pool.pool_migration @ AmmError::InvalidStatus,
+    )]
+    /// The migrated pool
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        constraint = lp_lock_vault.mint == pool.lp_mint @ AmmError::InvalidTokenMints,
+        constraint = lp_lock_vault.owner == pool_signer.key()
+            @ err::acc("LP lock vault must be owned by the pool signer"),
+    )]
+    /// The pool signer's LP token account holding the locked LP
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_lp_account.mint == pool.lp_mint @ AmmError::InvalidTokenMints,
+        constraint = creator_lp_account.owner == creator.key()
+            @ err::acc("LP can only be claimed to the creator"),
+    )]
+    /// The creator's LP token account
+    pub creator_lp_account: Account<'info, TokenAccount>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    /// The pool signer owning the LP lock vault
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Emitted when the creator claims the LP tokens after the lock
+#[event]
+pub struct LpClaimed {
+    /// Migrated bonding curve pool
+    pub pool: Pubkey,
+    /// Creator receiving the LP
+    pub creator: Pubkey,
+    /// LP tokens released
+    pub amount: u64,
+}
@@ -0,0 +1,196 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::buyer_record::BuyerRecord;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+impl<'info> ClaimRefund<'info> {
+    /// Creates a CPI context for burning the buyer's meme tokens
+    fn burn_user_meme(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.meme_mint.to_account_info(),
+            from: self.user_meme.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for refunding quote tokens out of a pool vault
+    fn send_refund(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Burns a buyer's meme tokens and refunds their share of a refunding pool.
+///
+/// Only meme the wallet got from the curve and still holds is refunded, as
+/// tracked by its `BuyerRecord`, so tokens that never paid into the reserve,
+/// e.g. a creator allocation or an airdrop, can't claim it. The refund is
+/// `quote_reserve * burned / meme_circulating`, and the same share of the
+/// second quote reserve when the pool has one. Both are removed from the pool
+/// together with the burned tokens, so later claims stay proportional. Each
+/// wallet can claim once.
+///
+/// # Errors
+/// * `AmmError::NoZeroTokens` - If the buyer holds no meme bought from the
+///   curve
+/// * `AmmError::RefundAlreadyClaimed` - If the wallet already claimed
+pub fn handle(ctx: Context<ClaimRefund>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let meme_amount = accs.buyer_record.refundable(accs.user_meme.amount);
+    if meme_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    if accs.pool.second_quote.is_some()
+        && (accs.second_quote_vault.is_none() || accs.user_second_quote.is_none())
+    {
+        return Err(error!(err::acc(
+            "Second quote accounts are required to refund a dual quote pool"
+        )));
+    }
+
+    let (refund_quote, refund_second_quote) = accs.pool.refund_amounts(meme_amount)?;
+
+    token::burn(accs.burn_user_meme(), meme_amount)?;
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if refund_quote > 0 {
+        token::transfer(
+            accs.send_refund(&accs.quote_vault, &accs.user_sol)
+                .with_signer(signer_seeds),
+            refund_quote,
+        )?;
+    }
+
+    if let (Some(vault), Some(destination)) = (&accs.second_quote_vault, &accs.user_second_quote) {
+        if refund_second_quote > 0 {
+            token::transfer(
+                accs.send_refund(vault, destination)
+                    .with_signer(signer_seeds),
+                refund_second_quote,
+            )?;
+        }
+    }
+
+    let pool = &mut accs.pool;
+    pool.quote_reserve.tokens -= refund_quote;
+    if let Some(second) = pool.second_quote.as_mut() {
+        second.reserve.tokens -= refund_second_quote;
+    }
+    pool.meme_circulating = pool.meme_circulating.saturating_sub(meme_amount);
+    pool.meme_burned += meme_amount;
+
+    let buyer_record = &mut accs.buyer_record;
+    buyer_record.record_sell(meme_amount);
+    buyer_record.refunded = true;
+
+    msg!(
+        "burned_meme: {}\n refunded_quote: {}\n refunded_second_quote: {}",
+        meme_amount,
+        refund_quote,
+        refund_second_quote
+    );
+
+    Ok(())
+}
+
+/// Represents the accounts required for claiming a refund.
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    /// The buyer burning their meme tokens
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = pool.refunding @ AmmError::InvalidStatus,
+    )]
+    /// The refunding pool
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        seeds = [BuyerRecord::RECORD_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+        constraint = !buyer_record.refunded @ AmmError::RefundAlreadyClaimed,
+    )]
+    /// The buyer's record, bounding the refundable meme and guarding
+    /// against double claims
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    #[account(
+        mut,
+        constraint = meme_mint.key() == pool.meme_reserve.mint @ AmmError::InvalidTokenMints
+    )]
+    /// The meme token mint
+    pub meme_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_meme.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = user_meme.owner == owner.key()
+    )]
+    /// The buyer's meme token account, burning the refundable meme
+    pub user_meme: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    /// The pool's quote vault
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_sol.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints,
+    )]
+    /// The buyer's quote token account receiving the refund
+    pub user_sol: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_vault(second_quote_vault.key())
+            @ err::acc("Second quote vault doesn't belong to the pool"),
+    )]
+    /// The pool's second quote vault, if the pool raises in two assets
+    pub second_quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = pool.second_quote.is_some_and(|second| second.reserve.mint == user_second_quote.mint)
+            @ AmmError::InvalidTokenMints,
+    )]
+    /// The buyer's second quote token account receiving the refund
+    pub user_second_quote: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    /// The pool signer owning the vaults
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,113 @@
+use crate::err;
+use crate::models::bound::BoundPool;
+use crate::models::vesting_schedule::VestingSchedule;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> ClaimVested<'info> {
+    /// Creates a CPI context for releasing vested meme tokens to the creator
+    fn send_vested_to_creator(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.vesting_vault.to_account_info(),
+            to: self.creator_meme.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Releases the vested part of the creator allocation to the pool creator.
+///
+/// The allocation vests linearly over the schedule's duration, with nothing
+/// released before the cliff. Each claim sends everything vested and not
+/// yet claimed.
+///
+/// # Errors
+/// * `AmmError::NothingVested` - If nothing new vested since the last claim
+pub fn handle(ctx: Context<ClaimVested>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let amount = accs.vesting_schedule.claim(Clock::get()?.unix_timestamp)?;
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[accs.pool.signer_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        accs.send_vested_to_creator().with_signer(signer_seeds),
+        amount,
+    )?;
+
+    emit!(VestedClaimed {
+        pool: pool_key,
+        creator: accs.creator.key(),
+        amount,
+        claimed: accs.vesting_schedule.claimed,
+        total: accs.vesting_schedule.total,
+    });
+
+    Ok(())
+}
+
+/// Represents the accounts required for claiming the vested creator allocation.
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    /// The pool creator
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = pool.creator_addr == creator.key()
+            @ err::acc("Only the pool creator can claim the vested tokens"),
+    )]
+    /// The pool the allocation belongs to
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        seeds = [VestingSchedule::VESTING_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    /// The vesting schedule of the creator allocation
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(mut, address = vesting_schedule.vault)]
+    /// The pool signer's vault holding the unclaimed allocation
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_meme.mint == pool.meme_reserve.mint
+            @ err::acc("Creator token account must be of meme mint"),
+    )]
+    /// The creator's meme token account
+    pub creator_meme: Account<'info, TokenAccount>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump = pool.signer_bump)]
+    /// The pool signer owning the vesting vault
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Emitted on every claim of the vested creator allocation
+#[event]
+pub struct VestedClaimed {
+    /// Pool the allocation belongs to
+    pub pool: Pubkey,
+    /// Creator receiving the tokens
+    pub creator: Pubkey,
+    /// Meme tokens released by this claim
+    pub amount: u64,
+    /// Meme tokens claimed so far, this claim included
+    pub claimed: u64,
+    /// Whole allocation
+    pub total: u64,
+}
@@ -0,0 +1,53 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::pending_buy::PendingBuy;
+use anchor_lang::prelude::*;
+
+/// Commits to a hidden buy, first step of a sandwich resistant buy.
+///
+/// Only `PendingBuy::commitment(owner, amount, min_out, nonce)` is stored, so
+/// the size of the buy can't be front-run. The buy is executed by
+/// `reveal_buy` in a later slot, within `COMMIT_REVEAL_WINDOW_SLOTS`.
+///
+/// # Arguments
+/// * `ctx` - The context containing all necessary accounts
+/// * `commitment` - Hash of the buy, see [`PendingBuy::commitment`]
+pub fn handle(ctx: Context<CommitBuy>, commitment: [u8; 32]) -> Result<()> {
+    let pending_buy = &mut ctx.accounts.pending_buy;
+
+    pending_buy.pool = ctx.accounts.pool.key();
+    pending_buy.owner = ctx.accounts.owner.key();
+    pending_buy.commitment = commitment;
+    pending_buy.slot = Clock::get()?.slot;
+
+    Ok(())
+}
+
+/// Represents the accounts required for committing a buy.
+#[derive(Accounts)]
+pub struct CommitBuy<'info> {
+    #[account(mut)]
+    /// The buyer
+    pub owner: Signer<'info>,
+
+    #[account(
+        constraint = !pool.locked @ AmmError::PoolIsLocked,
+        constraint = !pool.refunding @ AmmError::PoolIsRefunding,
+    )]
+    /// The pool to buy from
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + PendingBuy::INIT_SPACE,
+        seeds = [PendingBuy::PENDING_BUY_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    /// The buyer's pending buy, one per pool at a time
+    pub pending_buy: Account<'info, PendingBuy>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
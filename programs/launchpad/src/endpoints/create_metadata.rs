@@ -1,10 +1,13 @@
-use crate::err;
+use crate::err::{self, AmmError};
 use crate::models::bound::BoundPool;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::metadata::create_metadata_accounts_v3;
 use anchor_spl::metadata::mpl_token_metadata::types::Creator;
 use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::mpl_token_metadata::{
+    MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+};
 use anchor_spl::metadata::CreateMetadataAccountsV3;
 use anchor_spl::metadata::Metadata;
 use anchor_spl::token::{Mint, Token};
@@ -35,9 +38,9 @@ impl<'info> CreateMetadata<'info> {
                 collection: None,
                 uses: None,
             },
-            false, // is_mutable
-            true,  // update_authority_is_signer
-            None,  // collection_details
+            true, // is_mutable, so the URI can be fixed until migration
+            true, // update_authority_is_signer
+            None, // collection_details
         )?;
 
         Ok(())
@@ -60,13 +63,50 @@ impl<'info> CreateMetadata<'info> {
     }
 }
 
+/// Checks the metadata against the Metaplex limits before the CPI, which
+/// would otherwise fail with an opaque error. Name and symbol are trimmed
+/// first, a blank name is rejected.
+fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(error!(err::arg("Name can't be blank")));
+    }
+
+    for (field, value, max_len) in [
+        ("Name", name, MAX_NAME_LENGTH),
+        ("Symbol", symbol, MAX_SYMBOL_LENGTH),
+        ("URI", uri, MAX_URI_LENGTH),
+    ] {
+        if value.len() > max_len {
+            msg!("{} must be at most {} bytes", field, max_len);
+            return Err(error!(AmmError::MetadataTooLong));
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the Metaplex metadata of the pool's meme mint.
+///
+/// # Errors
+/// * `AmmError::Unauthorized` - If the signer isn't the pool creator
+/// * `AmmError::MetadataTooLong` - If a field exceeds the Metaplex limits
 pub fn handle(
     ctx: Context<CreateMetadata>,
     name: String,
     symbol: String,
     uri: String,
 ) -> Result<()> {
+    let name = name.trim().to_string();
+    let symbol = symbol.trim().to_string();
+    validate_metadata(&name, &symbol, &uri)?;
+
     let accs = ctx.accounts;
+    accs.pool.ensure_creator(accs.sender.key())?;
+
+    // Metadata is created once per mint, `update_metadata` changes it
+    if !accs.meme_mpl_metadata.data_is_empty() {
+        return Err(error!(err::acc("Metadata already exists for this mint")));
+    }
 
     let signer_bump_seed = ctx.bumps.pool_signer;
     accs.create_nft_with_metadata(name, symbol, uri, &[signer_bump_seed])?;
@@ -78,10 +118,10 @@ pub fn handle(
 pub struct CreateMetadata<'info> {
     #[account(mut)]
     pub sender: Signer<'info>,
-    #[account(constraint = sender.key() == pool.creator_addr)]
     pub pool: Account<'info, BoundPool>,
     #[account(
         mut,
+        address = pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
         constraint = meme_mint.mint_authority == COption::Some(pool_signer.key())
             @ err::acc("meme mint authority must be the pool signer"),
         constraint = meme_mint.freeze_authority == COption::None
@@ -103,3 +143,42 @@ pub struct CreateMetadata<'info> {
     pub metadata_program: Program<'info, Metadata>,
     pub rent: Sysvar<'info, Rent>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_length_limits() {
+        // ARRANGE: Strings at the Metaplex limits
+        let name = "n".repeat(MAX_NAME_LENGTH);
+        let symbol = "s".repeat(MAX_SYMBOL_LENGTH);
+        let uri = "u".repeat(MAX_URI_LENGTH);
+
+        // ACT & ASSERT: Metadata at the limits passes
+        assert!(validate_metadata(&name, &symbol, &uri).is_ok());
+
+        // ACT & ASSERT: One byte over any limit is rejected
+        let too_long = error!(AmmError::MetadataTooLong);
+        assert_eq!(
+            validate_metadata(&format!("{}n", name), &symbol, &uri).unwrap_err(),
+            too_long
+        );
+        assert_eq!(
+            validate_metadata(&name, &format!("{}s", symbol), &uri).unwrap_err(),
+            too_long
+        );
+        assert_eq!(
+            validate_metadata(&name, &symbol, &format!("{}u", uri)).unwrap_err(),
+            too_long
+        );
+
+        // ACT & ASSERT: A whitespace-only name is rejected
+        assert_eq!(
+            validate_metadata(" \t ", &symbol, &uri).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+
+        println!("✅ Metadata length limits test passed!");
+    }
+}
@@ -0,0 +1,129 @@
+use crate::consts::MAX_AIRDROP_BATCH;
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Airdrops meme out of the pool's LP allocation to up to
+/// `MAX_AIRDROP_BATCH` recipients at once.
+///
+/// Recipient meme token accounts are passed as remaining accounts, in the
+/// order of `amounts`. The curve's reserve is untouched: airdrops come out of
+/// the LP allocation kept in the meme vault, all batches together at most
+/// [`BoundPool::airdrop_allocation`].
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `amounts` - Meme sent to each recipient
+///
+/// # Errors
+/// * `AmmError::Unauthorized` - If the signer isn't the pool creator
+/// * `AmmError::InvalidArg` - If the batch is empty, larger than
+///   `MAX_AIRDROP_BATCH`, or airdrops nothing to a recipient
+/// * `AmmError::InvalidAccountInput` - If the recipients don't match the
+///   amounts or aren't meme token accounts
+/// * `AmmError::AirdroppedTokensOvercap` - If the batch exceeds what's left
+///   of the airdrop allocation
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeAirdropBatch<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    let accs = ctx.accounts;
+    let recipients = ctx.remaining_accounts;
+
+    accs.pool.ensure_creator(accs.creator.key())?;
+
+    if amounts.is_empty() || amounts.len() > MAX_AIRDROP_BATCH {
+        return Err(error!(err::arg(format!(
+            "Airdrop batches hold 1 to {} recipients",
+            MAX_AIRDROP_BATCH
+        ))));
+    }
+    if recipients.len() != amounts.len() {
+        return Err(error!(err::acc("Expected one recipient per amount")));
+    }
+    if amounts.contains(&0) {
+        return Err(error!(err::arg("Airdrop amounts must be positive")));
+    }
+
+    let total = amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or_else(|| error!(AmmError::AirdroppedTokensOvercap))?;
+    accs.pool.record_airdrop(total)?;
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[accs.pool.signer_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    for (recipient, amount) in recipients.iter().zip(amounts) {
+        let account = Account::<TokenAccount>::try_from(recipient)?;
+        if account.mint != accs.pool.meme_reserve.mint {
+            return Err(error!(AmmError::InvalidTokenMints));
+        }
+
+        let cpi_accounts = Transfer {
+            from: accs.meme_vault.to_account_info(),
+            to: recipient.clone(),
+            authority: accs.pool_signer.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    emit!(AirdropDistributed {
+        pool: pool_key,
+        recipients: recipients.len() as u32,
+        total,
+        airdropped: accs.pool.airdropped,
+    });
+
+    Ok(())
+}
+
+/// Emitted once per airdrop batch, for indexers
+#[event]
+pub struct AirdropDistributed {
+    /// The pool airdropping
+    pub pool: Pubkey,
+    /// Recipients of the batch
+    pub recipients: u32,
+    /// Meme sent by the batch
+    pub total: u64,
+    /// Meme airdropped by the pool so far, this batch included
+    pub airdropped: u64,
+}
+
+/// Represents the accounts required for airdropping a batch of meme tokens.
+#[derive(Accounts)]
+pub struct DistributeAirdropBatch<'info> {
+    /// The pool creator
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    /// The pool whose LP allocation is airdropped
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(mut, address = pool.meme_reserve.vault)]
+    /// The pool's meme vault, holding the LP allocation
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump = pool.signer_bump)]
+    /// The pool signer owning the vaults
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,78 @@
+use crate::endpoints::swap_y;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use crate::models::referrer::Referrer;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::TokenAccount;
+
+/// Runs the validation and pricing of a `swap_y` buy without executing it,
+/// returning the [`crate::models::SwapQuote`] it would execute.
+///
+/// Takes only read-only accounts, so nothing is created or paid for. The
+/// whitelist phase and the circuit breaker, which need the buy applied,
+/// aren't checked.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    ctx: Context<DryRunSwapY>,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+    expected_out: u64,
+    tolerance_bps: u16,
+    platform_fee_bps: u16,
+    reference_price: u128,
+    max_deviation_bps: u16,
+) -> Result<()> {
+    let accs = &ctx.accounts;
+    let protocol_config = accs.protocol_config.as_deref();
+    let fee_exempt = protocol_config.is_some_and(|config| config.is_fee_exempt(&accs.owner.key()));
+
+    let (swap_amount, _) = swap_y::price_buy(
+        &accs.pool,
+        accs.pool.is_second_quote_vault(accs.quote_vault.key()),
+        protocol_config,
+        fee_exempt,
+        coin_in_amount,
+        coin_x_min_value,
+        expected_out,
+        tolerance_bps,
+        platform_fee_bps,
+        reference_price,
+        max_deviation_bps,
+    )?;
+
+    swap_y::ensure_valid_referrer(
+        &accs.pool,
+        accs.owner.key(),
+        accs.referrer_quote.as_deref(),
+        accs.referrer.as_deref(),
+    )?;
+
+    let quote = swap_amount.to_quote(accs.pool.config.decimals.quote)?;
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DryRunSwapY<'info> {
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        constraint = quote_vault.key() == pool.derived_quote_vault(pool.key())
+            || pool.is_second_quote_vault(quote_vault.key())
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+    /// CHECK: The buyer the buy is priced for, e.g. for its fee exemption.
+    /// Doesn't need to sign
+    pub owner: UncheckedAccount<'info>,
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
+    #[account(constraint = referrer_quote.mint == quote_vault.mint @ AmmError::InvalidTokenMints)]
+    pub referrer_quote: Option<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [Referrer::REFERRER_PREFIX, referrer.wallet.as_ref()],
+        bump,
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+}
@@ -0,0 +1,33 @@
+use crate::err::AmmError;
+use crate::models::pending_buy::PendingBuy;
+use anchor_lang::prelude::*;
+
+/// Closes a committed buy that wasn't revealed in time, refunding its rent
+/// to the buyer. Can be called by anyone once the reveal window passed.
+///
+/// # Errors
+/// * `AmmError::CommitmentNotExpired` - If the buy can still be revealed
+pub fn handle(ctx: Context<ExpireBuy>) -> Result<()> {
+    if Clock::get()?.slot <= ctx.accounts.pending_buy.expiry_slot() {
+        return Err(error!(AmmError::CommitmentNotExpired));
+    }
+
+    Ok(())
+}
+
+/// Represents the accounts required for expiring a committed buy.
+#[derive(Accounts)]
+pub struct ExpireBuy<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PendingBuy::PENDING_BUY_PREFIX, pending_buy.pool.as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    /// The expired pending buy
+    pub pending_buy: Account<'info, PendingBuy>,
+
+    #[account(mut, address = pending_buy.owner)]
+    /// The buyer, refunded the rent of the pending buy
+    pub owner: SystemAccount<'info>,
+}
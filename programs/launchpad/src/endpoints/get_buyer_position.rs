@@ -0,0 +1,55 @@
+use crate::models::bound::BoundPool;
+use crate::models::buyer_record::{BuyerPosition, BuyerRecord};
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns a buyer's position in a pool as a [`BuyerPosition`].
+///
+/// The current value is the quote the whole position would sell for right
+/// now, so comparing it with the cost basis gives the unrealized result.
+/// Read-only, no state is mutated.
+pub fn handle(ctx: Context<GetBuyerPosition>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let record = &ctx.accounts.buyer_record;
+
+    let protocol_config = ctx.accounts.protocol_config.as_deref();
+    let fee_exempt = protocol_config.is_some_and(|config| config.is_fee_exempt(&record.buyer));
+
+    let current_value = match record.meme_held {
+        0 => 0,
+        meme_held => {
+            pool.swap_amounts(meme_held, 0, false, protocol_config, fee_exempt)
+                .amount_out
+        }
+    };
+
+    let position = BuyerPosition {
+        meme_held: record.meme_held,
+        cost_basis: record.cost_basis,
+        current_value,
+    };
+
+    msg!(
+        "meme_held: {}\n cost_basis: {}\n current_value: {}",
+        position.meme_held,
+        position.cost_basis,
+        position.current_value
+    );
+
+    set_return_data(&position.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetBuyerPosition<'info> {
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        seeds = [BuyerRecord::RECORD_PREFIX, pool.key().as_ref(), buyer_record.buyer.as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
+}
@@ -0,0 +1,20 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the pool's circulating meme supply (a borsh `u64`) in the return
+/// data, see [`BoundPool::circulating_supply`] for what it counts.
+pub fn handle(ctx: Context<GetCirculatingSupply>) -> Result<()> {
+    let circulating_supply = ctx.accounts.pool.circulating_supply();
+
+    msg!("circulating_supply: {}", circulating_supply);
+
+    set_return_data(&circulating_supply.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetCirculatingSupply<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
@@ -0,0 +1,26 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns how the pool's reserves would be split between the Raydium pool
+/// and the curve if it migrated now, with the price the Raydium pool would
+/// open at (a `MigrationQuote` in the return data). Read-only.
+pub fn handle(ctx: Context<GetMigrationQuote>) -> Result<()> {
+    let quote = ctx.accounts.pool.migration_quote();
+
+    msg!(
+        "meme_to_amm: {}\n quote_to_amm: {}\n initial_price: {}",
+        quote.meme_to_amm,
+        quote.quote_to_amm,
+        quote.initial_price
+    );
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetMigrationQuote<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
@@ -0,0 +1,27 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the pool's meme and quote decimals with both reserves (a borsh
+/// `PoolDisplayInfo`) in the return data. Decimals come from the pool's
+/// stored config, which is checked against the mints at creation. Read-only.
+pub fn handle(ctx: Context<GetPoolDisplayInfo>) -> Result<()> {
+    let info = ctx.accounts.pool.display_info();
+
+    msg!(
+        "meme_decimals: {}\n quote_decimals: {}\n meme_reserve: {}\n quote_reserve: {}",
+        info.meme_decimals,
+        info.quote_decimals,
+        info.meme_reserve,
+        info.quote_reserve
+    );
+
+    set_return_data(&info.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetPoolDisplayInfo<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
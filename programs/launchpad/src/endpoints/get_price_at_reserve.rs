@@ -0,0 +1,33 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the spot price the curve would quote with `meme_reserve_hypothetical`
+/// meme left on it (a borsh `u128` in the return data). Read-only.
+///
+/// The price is raw quote units per raw meme unit, scaled by
+/// `SPOT_PRICE_PRECISION` (1e12), as the pool's own spot price is.
+///
+/// # Errors
+/// * `AmmError::InvalidArg` - If the reserve exceeds the pool's trading supply
+pub fn handle(ctx: Context<GetPriceAtReserve>, meme_reserve_hypothetical: u64) -> Result<()> {
+    let price = ctx
+        .accounts
+        .pool
+        .spot_price_at_reserve(meme_reserve_hypothetical)?;
+
+    msg!(
+        "meme_reserve: {}\n price: {}",
+        meme_reserve_hypothetical,
+        price
+    );
+
+    set_return_data(&price.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetPriceAtReserve<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
@@ -0,0 +1,27 @@
+use crate::models::migration_registry::MigrationRegistry;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the most recent pool migrations, newest first (a borsh
+/// `Vec<MigrationEntry>`) in the return data, at most
+/// `MIGRATION_REGISTRY_SIZE`. Read-only.
+pub fn handle(ctx: Context<GetRecentMigrations>) -> Result<()> {
+    let registry = &ctx.accounts.migration_registry;
+    let migrations = registry.recent();
+
+    msg!(
+        "migrations: {}\n returned: {}",
+        registry.migration_count,
+        migrations.len()
+    );
+
+    set_return_data(&migrations.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetRecentMigrations<'info> {
+    #[account(seeds = [MigrationRegistry::REGISTRY_PREFIX], bump)]
+    pub migration_registry: Account<'info, MigrationRegistry>,
+}
@@ -0,0 +1,30 @@
+use crate::models::bound::BoundPool;
+use crate::models::trade_history::TradeHistory;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the pool's most recent trades, newest first (a borsh
+/// `Vec<TradeEntry>`) in the return data, at most `TRADE_HISTORY_SIZE`.
+/// Read-only.
+pub fn handle(ctx: Context<GetRecentTrades>) -> Result<()> {
+    let history = &ctx.accounts.trade_history;
+    let trades = history.recent();
+
+    msg!(
+        "trades: {}\n returned: {}",
+        history.trade_count,
+        trades.len()
+    );
+
+    set_return_data(&trades.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetRecentTrades<'info> {
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(seeds = [TradeHistory::TRADE_HISTORY_PREFIX, pool.key().as_ref()], bump)]
+    pub trade_history: Account<'info, TradeHistory>,
+}
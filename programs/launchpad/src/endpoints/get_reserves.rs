@@ -0,0 +1,42 @@
+use crate::err;
+use crate::models::bound::{BoundPool, PoolReserves};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the reserves and spot price of every pool passed in the remaining
+/// accounts (a borsh `Vec<PoolReserves>` in the return data), in the order
+/// they were passed. Read-only.
+///
+/// # Errors
+/// * `AmmError::InvalidAccountInput` - If more than `PoolReserves::MAX_BATCH`
+///   pools are passed, or one of the accounts isn't a pool
+pub fn handle(ctx: Context<GetReserves>) -> Result<()> {
+    let pools = ctx.remaining_accounts;
+
+    if pools.len() > PoolReserves::MAX_BATCH {
+        return Err(error!(err::acc(format!(
+            "At most {} pools fit in the return data",
+            PoolReserves::MAX_BATCH
+        ))));
+    }
+
+    let reserves = pools
+        .iter()
+        .map(|info| {
+            if info.owner != &crate::ID {
+                return Err(error!(err::acc("Pool isn't owned by the program")));
+            }
+
+            BoundPool::try_deserialize(&mut &info.try_borrow_data()?[..])?.reserves()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    msg!("pools: {}", reserves.len());
+
+    set_return_data(&reserves.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetReserves {}
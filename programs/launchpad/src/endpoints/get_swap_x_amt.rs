@@ -6,11 +6,16 @@
 // to the project's memecoin token via the bonding curve.
 
 use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token::TokenAccount;
 
 /// Calculates and logs the expected swap amounts for a given input amount
 ///
+/// The resulting [`SwapQuote`](crate::models::SwapQuote), with the trade's
+/// price impact, is also set as the instruction return data.
+///
 /// # Arguments
 /// * `ctx` - The context containing accounts
 /// * `coin_in_amount` - The amount of input tokens to swap
@@ -19,10 +24,26 @@ use anchor_spl::token::TokenAccount;
 /// # Returns
 /// * `Result<()>` - Returns Ok if calculation succeeds
 pub fn handle(ctx: Context<GetSwapXAmt>, coin_in_amount: u64, coin_y_min_value: u64) -> Result<()> {
-    let swap_amount = ctx
-        .accounts
-        .pool
-        .swap_amounts(coin_in_amount, coin_y_min_value, false);
+    let pool = &ctx.accounts.pool;
+    let protocol_config = ctx.accounts.protocol_config.as_deref();
+    let second_quote = pool.is_second_quote_vault(ctx.accounts.quote_vault.key());
+    let swap_amount = if second_quote {
+        pool.second_quote_swap_amounts(
+            coin_in_amount,
+            coin_y_min_value,
+            false,
+            protocol_config,
+            false,
+        )?
+    } else {
+        pool.swap_amounts(
+            coin_in_amount,
+            coin_y_min_value,
+            false,
+            protocol_config,
+            false,
+        )
+    };
 
     msg!(
         "swapped_in: {}\n swapped_out: {}",
@@ -30,6 +51,11 @@ pub fn handle(ctx: Context<GetSwapXAmt>, coin_in_amount: u64, coin_y_min_value:
         swap_amount.amount_out
     );
 
+    let mut quote = swap_amount.to_quote(pool.config.decimals.meme)?;
+    quote.price_impact_bps = pool.price_impact_bps(&swap_amount, false, second_quote)?;
+
+    set_return_data(&quote.try_to_vec()?);
+
     Ok(())
 }
 /// Account validation struct for getting swap amounts
@@ -39,6 +65,13 @@ pub struct GetSwapXAmt<'info> {
     pub pool: Account<'info, BoundPool>,
 
     /// The quote token vault, must match the pool's quote reserve vault
-    #[account(constraint = pool.quote_reserve.vault == quote_vault.key())]
+    #[account(
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+            || pool.is_second_quote_vault(quote_vault.key())
+    )]
     pub quote_vault: Account<'info, TokenAccount>,
+
+    /// The protocol configuration holding the volume fee tiers, if any
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
 }
@@ -0,0 +1,54 @@
+use crate::consts::MAX_BATCH_PREVIEW_AMOUNTS;
+use crate::err;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::TokenAccount;
+
+/// Previews buys of several sizes in a single call.
+///
+/// Each amount is priced independently against the current reserves, so the
+/// outputs describe the price impact curve rather than consecutive trades.
+/// A zero amount yields a zero output.
+pub fn handle(ctx: Context<GetSwapYAmounts>, amounts: Vec<u64>) -> Result<()> {
+    if amounts.is_empty() || amounts.len() > MAX_BATCH_PREVIEW_AMOUNTS {
+        return Err(error!(err::arg(format!(
+            "Batch preview takes between 1 and {} amounts",
+            MAX_BATCH_PREVIEW_AMOUNTS
+        ))));
+    }
+
+    let pool = &ctx.accounts.pool;
+    let protocol_config = ctx.accounts.protocol_config.as_deref();
+    let second_quote = pool.is_second_quote_vault(ctx.accounts.quote_vault.key());
+
+    let amounts_out = amounts
+        .into_iter()
+        .map(|coin_in_amount| match coin_in_amount {
+            0 => Ok(0),
+            _ if second_quote => pool
+                .second_quote_swap_amounts(coin_in_amount, 0, true, protocol_config, false)
+                .map(|swap| swap.amount_out),
+            _ => Ok(pool
+                .swap_amounts(coin_in_amount, 0, true, protocol_config, false)
+                .amount_out),
+        })
+        .collect::<Result<Vec<u64>>>()?;
+
+    set_return_data(&amounts_out.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetSwapYAmounts<'info> {
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+            || pool.is_second_quote_vault(quote_vault.key())
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
+}
@@ -1,12 +1,49 @@
 use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token::TokenAccount;
 
-pub fn handle(ctx: Context<GetSwapYAmt>, coin_in_amount: u64, coin_x_min_value: u64) -> Result<()> {
-    let swap_amount = ctx
+pub fn handle(
+    ctx: Context<GetSwapYAmt>,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+    assume_prior_in: u64,
+) -> Result<()> {
+    let protocol_config = ctx.accounts.protocol_config.as_deref();
+    let second_quote = ctx
         .accounts
         .pool
-        .swap_amounts(coin_in_amount, coin_x_min_value, true);
+        .is_second_quote_vault(ctx.accounts.quote_vault.key());
+
+    let after_prior;
+    let pool = if assume_prior_in == 0 {
+        &ctx.accounts.pool
+    } else {
+        after_prior =
+            ctx.accounts
+                .pool
+                .after_buy(assume_prior_in, second_quote, protocol_config)?;
+        &after_prior
+    };
+
+    let swap_amount = if second_quote {
+        pool.second_quote_swap_amounts(
+            coin_in_amount,
+            coin_x_min_value,
+            true,
+            protocol_config,
+            false,
+        )?
+    } else {
+        pool.swap_amounts(
+            coin_in_amount,
+            coin_x_min_value,
+            true,
+            protocol_config,
+            false,
+        )
+    };
 
     msg!(
         "swapped_in: {}\n swapped_out: {}",
@@ -14,11 +51,21 @@ pub fn handle(ctx: Context<GetSwapYAmt>, coin_in_amount: u64, coin_x_min_value:
         swap_amount.amount_out
     );
 
+    let mut quote = swap_amount.to_quote(pool.config.decimals.quote)?;
+    quote.price_impact_bps = pool.price_impact_bps(&swap_amount, true, second_quote)?;
+
+    set_return_data(&quote.try_to_vec()?);
+
     Ok(())
 }
 #[derive(Accounts)]
 pub struct GetSwapYAmt<'info> {
     pub pool: Account<'info, BoundPool>,
-    #[account(constraint = pool.quote_reserve.vault == quote_vault.key())]
+    #[account(
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+            || pool.is_second_quote_vault(quote_vault.key())
+    )]
     pub quote_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
 }
@@ -0,0 +1,25 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the meme left to sell before the pool can migrate, with an
+/// estimate of the quote buying it (a `MigrationProgress` in the return
+/// data). Read-only.
+pub fn handle(ctx: Context<GetTokensUntilMigration>) -> Result<()> {
+    let progress = ctx.accounts.pool.tokens_until_migration()?;
+
+    msg!(
+        "meme_remaining_until_threshold: {}\n quote_needed: {}",
+        progress.meme_remaining_until_threshold,
+        progress.quote_needed
+    );
+
+    set_return_data(&progress.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetTokensUntilMigration<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
@@ -0,0 +1,37 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::models::bound::BoundPool;
+use crate::models::buyer_record::BuyerRecord;
+use anchor_lang::prelude::*;
+
+/// Creates the owner's position record in a pool, the owner paying its rent.
+/// Swaps track the owner's buys and sells in it from then on, which refunds
+/// rely on: only meme bought while it exists can be refunded.
+pub fn handle(ctx: Context<InitBuyerRecord>) -> Result<()> {
+    ctx.accounts.buyer_record.buyer = ctx.accounts.owner.key();
+
+    Ok(())
+}
+
+/// Represents the accounts required for creating a buyer record.
+#[derive(Accounts)]
+pub struct InitBuyerRecord<'info> {
+    #[account(mut)]
+    /// The wallet whose position is tracked, paying for the record
+    pub owner: Signer<'info>,
+
+    /// The pool the position is in
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + BuyerRecord::INIT_SPACE,
+        seeds = [BuyerRecord::RECORD_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    /// The buyer record being created
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,56 @@
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Creates the fee treasury of a quote mint, the token account swaps in that
+/// mint pay the protocol's fee share to while `fee_treasury_enabled` is on.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+pub fn handle(ctx: Context<InitFeeTreasury>) -> Result<()> {
+    msg!(
+        "fee_treasury: {}\n mint: {}",
+        ctx.accounts.fee_treasury.key(),
+        ctx.accounts.quote_mint.key()
+    );
+
+    Ok(())
+}
+
+/// Represents the accounts required for creating the fee treasury of a mint.
+#[derive(Accounts)]
+pub struct InitFeeTreasury<'info> {
+    #[account(mut)]
+    /// The protocol fee authority, paying for the treasury account
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can create a fee treasury"),
+    )]
+    /// The global protocol configuration, owning the treasury
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The quote mint of the treasury
+    pub quote_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [ProtocolConfig::FEE_TREASURY_PREFIX, quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = protocol_config,
+    )]
+    /// The fee treasury being created
+    pub fee_treasury: Account<'info, TokenAccount>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,57 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use crate::program::Launchpad;
+use anchor_lang::prelude::*;
+
+/// Handles the one-time initialization of the global protocol configuration.
+///
+/// Only the program's upgrade authority can initialize it, so the protocol
+/// fee authority can't be front-run by an arbitrary first caller.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `protocol_fee_authority` - The authority receiving protocol fees
+pub fn handle(ctx: Context<InitProtocolConfig>, protocol_fee_authority: Pubkey) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.protocol_fee_authority = protocol_fee_authority;
+    protocol_config.pool_creation_enabled = true;
+
+    Ok(())
+}
+
+/// Represents the accounts required for initializing the protocol configuration.
+#[derive(Accounts)]
+pub struct InitProtocolConfig<'info> {
+    #[account(mut)]
+    /// The program upgrade authority, paying for the config account
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + ProtocolConfig::INIT_SPACE,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump
+    )]
+    /// The global protocol configuration being created
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ err::acc("Program data doesn't belong to this program"),
+    )]
+    /// This program, used to locate its upgrade authority
+    pub program: Program<'info, Launchpad>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ err::acc("Only the upgrade authority can initialize the protocol config"),
+    )]
+    /// The program data account holding the upgrade authority
+    pub program_data: Account<'info, ProgramData>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
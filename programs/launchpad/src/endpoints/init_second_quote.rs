@@ -0,0 +1,110 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::{BoundPool, SecondQuote};
+use crate::models::protocol_config::ProtocolConfig;
+use crate::models::Reserve;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{Mint, TokenAccount};
+
+/// Adds a second quote asset to a pool for dual-asset raises.
+///
+/// Buys and sells in the second asset trade against the same meme curve: its
+/// reserve is converted into primary quote units at the fixed rate
+/// `price_num / price_denom` and summed with the primary quote reserve.
+/// Since the asset and its rate price the curve, only the protocol fee
+/// authority configures them, and only for stablecoins of
+/// `PEGGED_QUOTE_MINTS` exchanged one for one, which a moved market rate
+/// can't arbitrage against the curve. Can only be configured once, before any
+/// trade happened.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `price_num` - Primary quote units per second quote unit (numerator)
+/// * `price_denom` - Primary quote units per second quote unit
+///   (denominator), one whole token for one
+pub fn handle(ctx: Context<InitSecondQuote>, price_num: u64, price_denom: u64) -> Result<()> {
+    if price_num == 0 || price_denom == 0 {
+        return Err(error!(err::arg("Second quote price must be non-zero")));
+    }
+
+    let accs = ctx.accounts;
+    let pool = &mut accs.pool;
+
+    if pool.second_quote.is_some() {
+        return Err(error!(err::acc("Second quote is already configured")));
+    }
+
+    // The curve must still be untouched, otherwise existing trades would be
+    // re-priced by the new quote measure
+    if pool.quote_reserve.tokens != 0 || pool.meme_reserve.tokens != pool.config.gamma_m {
+        return Err(error!(AmmError::InvalidStatus));
+    }
+
+    let second_quote = SecondQuote {
+        reserve: Reserve {
+            tokens: 0,
+            mint: accs.second_quote_mint.key(),
+            vault: accs.second_quote_vault.key(),
+        },
+        admin_fees: 0,
+        price_num,
+        price_denom,
+    };
+
+    // A floating rate would let buys in one asset be sold for the other
+    let second_quote_precision = 10_u64
+        .checked_pow(accs.second_quote_mint.decimals as u32)
+        .ok_or(error!(AmmError::MathOverflow))?;
+    second_quote.ensure_pegged(
+        pool.quote_reserve.mint,
+        pool.config.decimals.quote,
+        second_quote_precision,
+    )?;
+
+    pool.second_quote = Some(second_quote);
+
+    Ok(())
+}
+
+/// Represents the accounts required for adding a second quote asset to a pool.
+#[derive(Accounts)]
+pub struct InitSecondQuote<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can add a second quote"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    /// The pool receiving the second quote reserve
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        constraint = second_quote_mint.key() != pool.quote_reserve.mint
+            @ err::acc("Second quote mint must differ from the quote mint"),
+        constraint = second_quote_mint.key() != pool.meme_reserve.mint
+            @ err::acc("Second quote mint must differ from the meme mint"),
+    )]
+    /// The mint of the second quote asset (e.g. USDC)
+    pub second_quote_mint: Account<'info, Mint>,
+    #[account(
+        constraint = second_quote_vault.mint == second_quote_mint.key()
+            @ err::acc("Second quote vault must be of second quote mint"),
+        constraint = second_quote_vault.owner == pool_signer.key()
+            @ err::acc("Second quote vault authority must match the pool signer"),
+        constraint = second_quote_vault.close_authority == COption::None
+            @ err::acc("Second quote vault must not have close authority"),
+        constraint = second_quote_vault.delegate == COption::None
+            @ err::acc("Second quote vault must not have delegate"),
+    )]
+    /// The vault holding the second quote reserve
+    pub second_quote_vault: Account<'info, TokenAccount>,
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    /// The account representing the pool signer.
+    pub pool_signer: AccountInfo<'info>,
+}
@@ -1,4 +1,6 @@
 use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
+use crate::models::fees::FeeShares;
 use crate::models::target_config::TargetConfig;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
@@ -10,9 +12,88 @@ use anchor_spl::token::Mint;
 /// # Parameters
 /// * `ctx` - The context containing all necessary accounts
 /// * `token_target_amount` - The target amount of tokens (in lamports/smallest unit)
-pub fn handle(ctx: Context<InitTargetConfig>, token_target_amount: u64) -> Result<()> {
+/// * `price_factor_num_end` - Price factor numerator ramped to as the supply
+///   sells out, zero to keep the static price factor
+/// * `min_quote_liquidity` - Quote reserve below which sells are rejected,
+///   at most `MAX_MIN_QUOTE_LIQUIDITY_BPS` of the target, zero for the
+///   default share of it
+/// * `fee_start_bps` - Quote fee with no meme sold, in bps
+/// * `fee_end_bps` - Quote fee at the migration threshold, in bps. Both zero
+///   keep the flat quote fee, either at most `MAX_FEE_BPS`
+/// * `circuit_breaker_bps` - Largest price move allowed within a slot, in
+///   bps, zero to disable the circuit breaker
+/// * `fee_shares` - Split of the quote fee between protocol, creator and
+///   referral, adding up to 10000 bps or all zero for the protocol only
+/// * `max_quote_raise` - Quote reserve above which buys are rejected, zero
+///   for no cap
+/// * `post_migration_fee_bps` - Quote fee of trades against the reserves
+///   retained after migration, in bps, at most `MAX_FEE_BPS`, zero for the
+///   regular fee
+/// * `lp_lock_duration` - Seconds the LP stays locked after migration, at
+///   least `MIN_LP_LOCK_DURATION`, zero for the default `LP_LOCK_DURATION`
+/// * `max_fee_quote` - Largest quote fee charged on a single buy, zero for
+///   no cap
+/// * `max_fee_meme` - Largest meme fee charged on a single sell, zero for no
+///   cap
+/// * `virtual_quote_reserve` - Quote the curve prices as already raised,
+///   flattening the price impact of the first buys, zero for none
+/// * `virtual_meme_reserve` - Meme the curve prices as already sold, zero
+///   for none
+/// * `min_pool_age_secs` - Seconds a pool must have traded before it can
+///   migrate, zero for none
+///
+/// # Errors
+/// * `AmmError::UnsupportedMintExtension` - If either mint isn't an SPL
+///   Token mint
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    ctx: Context<InitTargetConfig>,
+    token_target_amount: u64,
+    price_factor_num_end: u64,
+    min_quote_liquidity: u64,
+    fee_start_bps: u16,
+    fee_end_bps: u16,
+    circuit_breaker_bps: u16,
+    fee_shares: FeeShares,
+    max_quote_raise: u64,
+    post_migration_fee_bps: u16,
+    lp_lock_duration: i64,
+    max_fee_quote: u64,
+    max_fee_meme: u64,
+    virtual_quote_reserve: u64,
+    virtual_meme_reserve: u64,
+    min_pool_age_secs: i64,
+) -> Result<()> {
+    TargetConfig::ensure_distinct_mints(
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.pair_token_mint.key(),
+    )?;
+    TargetConfig::ensure_spl_token_mints(
+        ctx.accounts.token_mint.to_account_info().owner,
+        ctx.accounts.pair_token_mint.to_account_info().owner,
+    )?;
+
+    TargetConfig::ensure_fee_bps(fee_start_bps, fee_end_bps, post_migration_fee_bps)?;
+
+    TargetConfig::ensure_min_quote_liquidity(min_quote_liquidity, token_target_amount)?;
+
+    TargetConfig::ensure_lp_lock_duration(lp_lock_duration)?;
+
+    if min_pool_age_secs < 0 {
+        return Err(error!(err::arg("Minimum pool age can't be negative")));
+    }
+
+    if fee_end_bps > fee_start_bps {
+        return Err(error!(err::arg("Graduated fee can only decrease")));
+    }
+
+    fee_shares.validate()?;
+
     let target_config = &mut ctx.accounts.target_config;
 
+    // Only the creator can correct the config, until a pool uses it
+    target_config.creator = ctx.accounts.creator.key();
+
     // Set the target amount (e.g., 100 SOL = 100_000_000_000 lamports)
     target_config.token_target_amount = token_target_amount;
 
@@ -22,6 +103,46 @@ pub fn handle(ctx: Context<InitTargetConfig>, token_target_amount: u64) -> Resul
     // Associate with the meme mint (e.g., DOG mint)
     target_config.pair_token_mint = ctx.accounts.pair_token_mint.key();
 
+    // Record the mint decimals the curve will be priced with
+    target_config.token_decimals = ctx.accounts.token_mint.decimals;
+    target_config.pair_token_decimals = ctx.accounts.pair_token_mint.decimals;
+
+    // Optional price factor ramp, validated against the curve by new_pool
+    target_config.price_factor_num_end = price_factor_num_end;
+
+    // Sell floor of the pool, defaulted by new_pool when unset
+    target_config.min_quote_liquidity = min_quote_liquidity;
+
+    // Optional fee ramp towards migration
+    target_config.fee_start_bps = fee_start_bps;
+    target_config.fee_end_bps = fee_end_bps;
+
+    // Optional per-slot price move limit
+    target_config.circuit_breaker_bps = circuit_breaker_bps;
+
+    // Quote fee recipients, their vaults are given to new_pool
+    target_config.fee_shares = fee_shares;
+
+    // Optional hard cap on the quote raised
+    target_config.max_quote_raise = max_quote_raise;
+
+    // Fee of the trading continuing after migration
+    target_config.post_migration_fee_bps = post_migration_fee_bps;
+
+    // LP lock committed to buyers, defaulted by new_pool when unset
+    target_config.lp_lock_duration = lp_lock_duration;
+
+    // Optional caps on the fee of a single swap
+    target_config.max_fee_quote = max_fee_quote;
+    target_config.max_fee_meme = max_fee_meme;
+
+    // Optional virtual reserves, validated against the curve by new_pool
+    target_config.virtual_quote_reserve = virtual_quote_reserve;
+    target_config.virtual_meme_reserve = virtual_meme_reserve;
+
+    // Optional price discovery window before migration
+    target_config.min_pool_age_secs = min_pool_age_secs;
+
     Ok(())
 }
 
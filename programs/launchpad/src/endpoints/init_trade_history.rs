@@ -0,0 +1,38 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::models::bound::BoundPool;
+use crate::models::trade_history::TradeHistory;
+use anchor_lang::prelude::*;
+
+/// Creates the trade history of a pool, which every swap must then pass and
+/// append to. Anyone can create it, paying its rent.
+pub fn handle(ctx: Context<InitTradeHistory>) -> Result<()> {
+    ctx.accounts.trade_history.pool = ctx.accounts.pool.key();
+    ctx.accounts.pool.has_trade_history = true;
+
+    Ok(())
+}
+
+/// Represents the accounts required for creating a pool's trade history.
+#[derive(Accounts)]
+pub struct InitTradeHistory<'info> {
+    #[account(mut)]
+    /// The account paying for the trade history
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// The pool whose trades are recorded
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + TradeHistory::INIT_SPACE,
+        seeds = [TradeHistory::TRADE_HISTORY_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    /// The trade history being created
+    pub trade_history: Account<'info, TradeHistory>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,50 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+/// Winds down a stalled launch so buyers can reclaim their quote tokens.
+///
+/// Disables trading and migration for good. Buyers then burn their meme
+/// tokens through `claim_refund` for a pro-rata share of the quote reserve.
+/// Can only be called by the pool creator or the protocol fee authority,
+/// while the pool is still trading on the curve.
+pub fn handle(ctx: Context<InitiateRefund>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    pool.refunding = true;
+
+    msg!(
+        "refund initiated, meme_circulating: {}",
+        pool.meme_circulating
+    );
+
+    Ok(())
+}
+
+/// Represents the accounts required for initiating a pool refund.
+#[derive(Accounts)]
+pub struct InitiateRefund<'info> {
+    /// The pool creator or the protocol fee authority
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        constraint = sender.key() == pool.creator_addr
+            || sender.key() == protocol_config.protocol_fee_authority
+            @ err::acc("Only the pool creator or the protocol fee authority can initiate a refund"),
+        constraint = !pool.pool_migration @ AmmError::AlreadyMigrated,
+        constraint = !pool.locked @ AmmError::PoolIsLocked,
+        constraint = !pool.refunding @ AmmError::PoolIsRefunding,
+    )]
+    /// The pool being wound down
+    pub pool: Account<'info, BoundPool>,
+}
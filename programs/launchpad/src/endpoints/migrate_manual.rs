@@ -0,0 +1,229 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::endpoints::migrate_to_raydium::MigrationCompleted;
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::{BoundPool, MigrationAmm};
+use crate::models::migration_registry::MigrationRegistry;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> MigrateManual<'info> {
+    /// Creates a CPI context for draining a pool vault to the creator
+    fn drain(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Graduates a pool created with the `Manual` migration target.
+///
+/// Runs the same checks as the Raydium migrations and pays the same migration
+/// fee out of the quote reserve, then drains the meme, rest of the quote and
+/// second quote reserves to the creator, who deploys them on an AMM of their
+/// choice. Admin fees stay in the vaults for the protocol. The curve is left
+/// locked, as it holds no reserves anymore.
+///
+/// # Errors
+/// * `AmmError::WrongMigrationTarget` - If the pool migrates to Raydium
+/// * `AmmError::PoolTooYoung` - If the pool's minimum age didn't pass yet
+/// * `AmmError::InsufficientTokenSupply` - If the meme vault holds less than
+///   the pool accounts for
+pub fn handle(ctx: Context<MigrateManual>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.pool.ensure_migratable(
+        MigrationAmm::Manual,
+        accs.pool.meme_reserve.mint,
+        accs.pool.quote_reserve.mint,
+    )?;
+    accs.pool.ensure_min_age(Clock::get()?.unix_timestamp)?;
+    accs.pool.ensure_meme_supply(accs.meme_vault.amount)?;
+
+    // Persist the lock before any CPI runs, like the Raydium migrations
+    accs.pool.locked = true;
+    accs.pool.exit(ctx.program_id)?;
+
+    let meme_amount = accs.pool.meme_reserve.tokens;
+    let quote_amount = accs.pool.quote_reserve.tokens;
+    let migration_fee = ProtocolConfig::migration_fee_of(&accs.protocol_config, quote_amount)?;
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[accs.pool.signer_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        accs.drain(&accs.meme_vault, &accs.creator_meme)
+            .with_signer(signer_seeds),
+        meme_amount,
+    )?;
+    if migration_fee != 0 {
+        let fee_treasury = accs
+            .fee_treasury
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Fee treasury is required")))?;
+        token::transfer(
+            accs.drain(&accs.quote_vault, fee_treasury)
+                .with_signer(signer_seeds),
+            migration_fee,
+        )?;
+    }
+    token::transfer(
+        accs.drain(&accs.quote_vault, &accs.creator_quote)
+            .with_signer(signer_seeds),
+        quote_amount - migration_fee,
+    )?;
+
+    let second_quote_migrated = accs.pool.migrate_second_quote()?;
+    if second_quote_migrated != 0 {
+        let (Some(vault), Some(destination)) =
+            (&accs.second_quote_vault, &accs.creator_second_quote)
+        else {
+            return Err(error!(err::acc(
+                "Second quote accounts are required to migrate a dual quote pool"
+            )));
+        };
+        token::transfer(
+            accs.drain(vault, destination).with_signer(signer_seeds),
+            second_quote_migrated,
+        )?;
+    }
+
+    let creator = accs.creator.key();
+    let clock = Clock::get()?;
+    let pool = &mut accs.pool;
+    pool.complete_migration(
+        MigrationAmm::Manual,
+        creator,
+        meme_amount,
+        quote_amount,
+        clock.slot,
+    );
+    pool.locked = true;
+    accs.migration_registry.record(pool_key, clock.slot);
+
+    emit!(MigrationCompleted {
+        pool: pool_key,
+        raydium_pool: Pubkey::default(),
+        meme_migrated: meme_amount,
+        quote_migrated: quote_amount - migration_fee,
+        meme_retained: 0,
+        timestamp: clock.unix_timestamp,
+        migration_fee,
+        second_quote_migrated,
+    });
+
+    Ok(())
+}
+
+/// Represents the accounts required for a manual migration.
+#[derive(Accounts)]
+pub struct MigrateManual<'info> {
+    #[account(
+        mut,
+        constraint = creator.key() == pool.creator_addr
+            @ err::acc("Only the pool creator can migrate manually"),
+    )]
+    /// The pool creator receiving the reserves, paying for the migration
+    /// registry if it doesn't exist yet
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    /// The pool being migrated
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(mut, address = pool.meme_reserve.vault)]
+    /// The pool's meme vault
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, address = pool.quote_reserve.vault)]
+    /// The pool's quote vault
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_meme.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = creator_meme.owner == creator.key()
+            @ err::acc("Meme reserve can only be sent to the creator"),
+    )]
+    /// The creator's meme token account
+    pub creator_meme: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_quote.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = creator_quote.owner == creator.key()
+            @ err::acc("Quote reserve can only be sent to the creator"),
+    )]
+    /// The creator's quote token account
+    pub creator_quote: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_vault(second_quote_vault.key())
+            @ err::acc("Second quote vault doesn't belong to the pool"),
+    )]
+    /// The pool's second quote vault, required if the pool raised any
+    pub second_quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = pool.second_quote.is_some_and(|second| pool.is_creator_account(
+            creator_second_quote.owner,
+            creator_second_quote.mint,
+            second.reserve.mint,
+        )) @ AmmError::InvalidCreatorAccount,
+    )]
+    /// The creator's second quote token account
+    pub creator_second_quote: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump = pool.signer_bump)]
+    /// The pool signer owning the vaults
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + MigrationRegistry::INIT_SPACE,
+        seeds = [MigrationRegistry::REGISTRY_PREFIX],
+        bump
+    )]
+    /// Recent migrations across pools, created with the first migration
+    pub migration_registry: Box<Account<'info, MigrationRegistry>>,
+
+    /// The system program, to create the migration registry
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: deserialized in the handler when initialized
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    /// The global protocol configuration, read for the migration fee.
+    /// Required rather than optional so the fee can't be skipped.
+    pub protocol_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::FEE_TREASURY_PREFIX, pool.quote_reserve.mint.as_ref()],
+        bump,
+    )]
+    /// The fee treasury of the quote mint, required when the protocol
+    /// charges a migration fee
+    pub fee_treasury: Option<Account<'info, TokenAccount>>,
+}
@@ -0,0 +1,102 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err::{self, AmmError};
+use crate::models::bound::BoundPool;
+use crate::models::bound_v0::BoundPoolV0;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token::Mint;
+
+/// Upgrades a pool account written by an older program version to the
+/// current `BoundPool` layout.
+///
+/// The account must carry the `BoundPool` discriminator and the size of a
+/// known layout. A version 0 account, told apart by its size, is decoded
+/// through `BoundPoolV0`. Later layouts only appended fields, so they're
+/// read with the new fields zeroed. The account is grown to the current
+/// size and written back at [`BoundPool::VERSION`], the payer topping up
+/// the rent for the extra space. Anyone can migrate a pool, nothing but the
+/// layout changes.
+///
+/// # Errors
+/// * If the account isn't a pool of a known layout
+/// * `AmmError::InvalidTokenMints` - If `meme_mint` isn't the pool's
+pub fn handle(ctx: Context<MigratePoolAccount>) -> Result<()> {
+    let accs = ctx.accounts;
+    let pool_info = accs.pool.to_account_info();
+
+    let space = ANCHOR_DISCRIMINATOR + BoundPool::INIT_SPACE;
+    let old_len = pool_info.data_len();
+    if old_len < BoundPoolV0::SPACE || old_len > space {
+        return Err(error!(err::acc("Unknown pool account layout")));
+    }
+    if pool_info.try_borrow_data()?[..ANCHOR_DISCRIMINATOR] != BoundPool::DISCRIMINATOR {
+        return Err(error!(ErrorCode::AccountDiscriminatorMismatch));
+    }
+
+    if old_len < space {
+        let rent = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(pool_info.lamports());
+        if rent != 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    accs.system_program.to_account_info(),
+                    Transfer {
+                        from: accs.payer.to_account_info(),
+                        to: pool_info.clone(),
+                    },
+                ),
+                rent,
+            )?;
+        }
+
+        pool_info.realloc(space, true)?;
+    }
+
+    let mut pool = if old_len == BoundPoolV0::SPACE {
+        let data = pool_info.try_borrow_data()?;
+        BoundPool::from(BoundPoolV0::deserialize(
+            &mut &data[ANCHOR_DISCRIMINATOR..],
+        )?)
+    } else {
+        BoundPool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?
+    };
+
+    if pool.meme_reserve.mint != accs.meme_mint.key() {
+        return Err(error!(AmmError::InvalidTokenMints));
+    }
+
+    let from_version = pool.version;
+    pool.upgrade(pool_info.key(), accs.meme_mint.decimals)?;
+    pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "migrated pool: {}, version {} -> {}",
+        pool_info.key(),
+        from_version,
+        pool.version
+    );
+
+    Ok(())
+}
+
+/// Represents the accounts required for migrating a pool account's layout.
+#[derive(Accounts)]
+pub struct MigratePoolAccount<'info> {
+    #[account(mut)]
+    /// The account paying the rent of the grown pool account
+    pub payer: Signer<'info>,
+
+    /// CHECK: an older layout can't be read as `BoundPool`, the handler
+    /// checks its discriminator and size
+    #[account(mut, owner = crate::ID)]
+    /// The pool account to migrate
+    pub pool: UncheckedAccount<'info>,
+
+    /// The pool's meme mint, its decimals backfill version 0 pools
+    pub meme_mint: Account<'info, Mint>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
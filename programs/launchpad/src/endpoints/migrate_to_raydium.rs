@@ -1,8 +1,11 @@
-use crate::consts::*;
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
 use crate::err::AmmError;
-use crate::models::bound::BoundPool;
+use crate::models::bound::{BoundPool, MigrationAmm};
+use crate::models::migration_registry::MigrationRegistry;
+use crate::models::protocol_config::ProtocolConfig;
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::associated_token::{self, get_associated_token_address, AssociatedToken, Create};
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use raydium_cpmm_cpi::{
     cpi,
@@ -26,15 +29,11 @@ pub struct MigrateToRaydium<'info> {
             quote_mint.key().as_ref()
         ],
         bump,
-        constraint = !pool.locked @ AmmError::PoolIsLocked,
     )]
     pub pool: Account<'info, BoundPool>,
 
     /// Meme token mint (must be smaller key than quote_mint for CPMM)
-    #[account(
-        mut,
-        constraint = meme_mint.key() < quote_mint.key() @ AmmError::InvalidTokenOrder
-    )]
+    #[account(mut)]
     pub meme_mint: Account<'info, Mint>,
 
     /// Quote token mint (WSOL - must be larger key than meme_mint)
@@ -116,16 +115,22 @@ pub struct MigrateToRaydium<'info> {
     /// Creator's meme token account (for initial liquidity)
     #[account(
         mut,
-        token::mint = meme_mint,
-        token::authority = signer,
+        constraint = pool.is_creator_account(
+            creator_meme_account.owner,
+            creator_meme_account.mint,
+            pool.meme_reserve.mint,
+        ) @ AmmError::InvalidCreatorAccount,
     )]
     pub creator_meme_account: Account<'info, TokenAccount>,
 
     /// Creator's quote token account (for initial liquidity)
     #[account(
         mut,
-        token::mint = quote_mint,
-        token::authority = signer,
+        constraint = pool.is_creator_account(
+            creator_quote_account.owner,
+            creator_quote_account.mint,
+            pool.quote_reserve.mint,
+        ) @ AmmError::InvalidCreatorAccount,
     )]
     pub creator_quote_account: Account<'info, TokenAccount>,
 
@@ -133,6 +138,17 @@ pub struct MigrateToRaydium<'info> {
     #[account(mut)]
     pub creator_lp_token: UncheckedAccount<'info>,
 
+    /// CHECK: pool_pda, owner of the LP lock vault
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: UncheckedAccount<'info>,
+
+    /// CHECK: Pool signer's LP token account locking the LP, created after the Raydium pool
+    #[account(
+        mut,
+        address = get_associated_token_address(&pool_signer.key(), &raydium_lp_mint.key()),
+    )]
+    pub lp_lock_vault: UncheckedAccount<'info>,
+
     /// CHECK: Raydium token_0 vault to be created, seeds are verified by Raydium program
     #[account(
         mut,
@@ -182,25 +198,75 @@ pub struct MigrateToRaydium<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = ANCHOR_DISCRIMINATOR + MigrationRegistry::INIT_SPACE,
+        seeds = [MigrationRegistry::REGISTRY_PREFIX],
+        bump
+    )]
+    /// Recent migrations across pools, created with the first migration
+    pub migration_registry: Box<Account<'info, MigrationRegistry>>,
+
+    /// CHECK: deserialized in the handler when initialized
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    /// The global protocol configuration, read for the migration fee.
+    /// Required rather than optional so the fee can't be skipped.
+    pub protocol_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::FEE_TREASURY_PREFIX, quote_mint.key().as_ref()],
+        bump,
+    )]
+    /// The fee treasury of the quote mint, required when the protocol
+    /// charges a migration fee
+    pub fee_treasury: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The pool's second quote vault, required if the pool raised any
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_vault(second_quote_vault.key())
+            @ err::acc("Second quote vault doesn't belong to the pool"),
+    )]
+    pub second_quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The fee treasury of the second quote mint, receiving the second quote
+    /// reserve the Raydium pool can't hold, so it never reaches the creator unlocked
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_treasury(second_quote_treasury.key())
+            @ err::acc("Second quote reserve can only be sent to its fee treasury"),
+    )]
+    pub second_quote_treasury: Option<Box<Account<'info, TokenAccount>>>,
 }
 
 pub fn handle(ctx: Context<MigrateToRaydium>) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
 
-    // 1. Check migration threshold
-    let migration_threshold = calculate_migration_threshold(pool)?;
-    let current_meme_supply = pool.meme_reserve.tokens - ctx.accounts.meme_vault.amount;
-
-    require!(
-        current_meme_supply >= migration_threshold,
-        AmmError::MigrationThresholdNotReached
-    );
-
-    // 2. Lock the pool to prevent further trading
+    // 1. Check the pool can migrate: its target, still trading, token order,
+    // threshold, age
+    pool.ensure_migratable(
+        MigrationAmm::Cpmm,
+        ctx.accounts.meme_mint.key(),
+        ctx.accounts.quote_mint.key(),
+    )?;
+    pool.ensure_min_age(Clock::get()?.unix_timestamp)?;
+    pool.ensure_meme_supply(ctx.accounts.meme_vault.amount)?;
+
+    // 2. Lock the pool to prevent further trading. Anchor only writes account
+    // data back when the instruction exits, so persist the lock explicitly
+    // before any CPI runs: swaps must never observe the pool mid-migration.
     pool.locked = true;
+    pool.exit(ctx.program_id)?;
 
-    // 3. Calculate liquidity amounts for Raydium pool
-    let (meme_amount, quote_amount) = calculate_migration_amounts(pool)?;
+    // 3. Calculate liquidity amounts for Raydium pool, the protocol's
+    // migration fee coming out of the quote, once its config exists
+    let (meme_amount, quote_amount) = pool.migration_amounts();
+    let migration_fee =
+        ProtocolConfig::migration_fee_of(&ctx.accounts.protocol_config, quote_amount)?;
+    let quote_seeded = quote_amount - migration_fee;
 
     // 4. Prepare authority seeds for token transfers
     let pool_key = pool.key();
@@ -224,6 +290,25 @@ pub fn handle(ctx: Context<MigrateToRaydium>) -> Result<()> {
     );
     token::transfer(transfer_meme_ctx, meme_amount)?;
 
+    // Pay the migration fee to the fee treasury
+    if migration_fee != 0 {
+        let fee_treasury = ctx
+            .accounts
+            .fee_treasury
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Fee treasury is required")))?;
+        let transfer_fee_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.quote_vault.to_account_info(),
+                to: fee_treasury.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_fee_ctx, migration_fee)?;
+    }
+
     // Transfer quote tokens
     let transfer_quote_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
@@ -234,7 +319,31 @@ pub fn handle(ctx: Context<MigrateToRaydium>) -> Result<()> {
         },
         signer_seeds,
     );
-    token::transfer(transfer_quote_ctx, quote_amount)?;
+    token::transfer(transfer_quote_ctx, quote_seeded)?;
+
+    // Pay the second quote reserve to the protocol's treasury, the Raydium
+    // pool only pairs meme with the primary quote
+    let second_quote_migrated = pool.migrate_second_quote()?;
+    if second_quote_migrated != 0 {
+        let (Some(vault), Some(destination)) = (
+            &ctx.accounts.second_quote_vault,
+            &ctx.accounts.second_quote_treasury,
+        ) else {
+            return Err(error!(err::acc(
+                "Second quote accounts are required to migrate a dual quote pool"
+            )));
+        };
+        let transfer_second_quote_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: destination.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_second_quote_ctx, second_quote_migrated)?;
+    }
 
     // 6. Calculate open time (can trade immediately)
     let clock = Clock::get()?;
@@ -267,46 +376,110 @@ pub fn handle(ctx: Context<MigrateToRaydium>) -> Result<()> {
     let cpi_context = CpiContext::new(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts);
 
     // Call Raydium's initialize function
-    cpi::initialize(cpi_context, meme_amount, quote_amount, open_time)?;
+    cpi::initialize(cpi_context, meme_amount, quote_seeded, open_time)?;
+
+    // 8. Lock the LP tokens under the pool signer, the creator can only
+    // claim them through claim_lp_after_lock once the lock expires
+    let lp_amount = {
+        let data = ctx.accounts.creator_lp_token.try_borrow_data()?;
+        TokenAccount::try_deserialize(&mut &data[..])?.amount
+    };
+
+    associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        Create {
+            payer: ctx.accounts.signer.to_account_info(),
+            associated_token: ctx.accounts.lp_lock_vault.to_account_info(),
+            authority: ctx.accounts.pool_signer.to_account_info(),
+            mint: ctx.accounts.raydium_lp_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+
+    let lock_lp_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.creator_lp_token.to_account_info(),
+            to: ctx.accounts.lp_lock_vault.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        },
+    );
+    token::transfer(lock_lp_ctx, lp_amount)?;
+
+    pool.lp_mint = ctx.accounts.raydium_lp_mint.key();
+    pool.lp_locked = lp_amount;
+    pool.lp_unlock_ts = clock.unix_timestamp + pool.lp_lock_duration;
 
-    // 8. Update pool state
-    pool.meme_reserve.tokens = ctx.accounts.meme_vault.amount - meme_amount;
-    pool.quote_reserve.tokens = ctx.accounts.quote_vault.amount - quote_amount;
-    pool.pool_migration = true;
-    pool.migration_pool_key = ctx.accounts.raydium_pool_state.key();
+    emit!(LpLocked {
+        pool: pool.key(),
+        lp_mint: pool.lp_mint,
+        amount: lp_amount,
+        unlock_ts: pool.lp_unlock_ts,
+    });
+
+    // 9. Update pool state, trading resumes against the retained reserves.
+    // The fee left the quote reserve along with the seeded quote
+    pool.complete_migration(
+        MigrationAmm::Cpmm,
+        ctx.accounts.raydium_pool_state.key(),
+        meme_amount,
+        quote_amount,
+        clock.slot,
+    );
+    ctx.accounts
+        .migration_registry
+        .record(pool.key(), clock.slot);
 
-    // 9. Emit migration event
-    emit!(MigrationEvent {
+    // 10. Emit migration event
+    emit!(MigrationCompleted {
         pool: pool.key(),
         raydium_pool: ctx.accounts.raydium_pool_state.key(),
-        meme_amount_migrated: meme_amount,
-        quote_amount_migrated: quote_amount,
+        meme_migrated: meme_amount,
+        quote_migrated: quote_seeded,
+        meme_retained: pool.meme_reserve.tokens,
         timestamp: clock.unix_timestamp,
+        migration_fee,
+        second_quote_migrated,
     });
 
     Ok(())
 }
 
-fn calculate_migration_threshold(pool: &BoundPool) -> Result<u64> {
-    // 80% of max supply sold = ready for migration
-    let threshold = (DEFAULT_MAX_M as u64 * 80) / 100;
-    Ok(threshold)
-}
-
-fn calculate_migration_amounts(pool: &BoundPool) -> Result<(u64, u64)> {
-    // Use most of the remaining liquidity for the Raydium pool
-    // Reserve 5% for potential continued bonding curve trading
-    let meme_amount = (pool.meme_reserve.tokens * 95) / 100;
-    let quote_amount = (pool.quote_reserve.tokens * 95) / 100;
-
-    Ok((meme_amount, quote_amount))
-}
-
+/// Emitted once a bonding curve pool graduates to Raydium
 #[event]
-pub struct MigrationEvent {
+pub struct MigrationCompleted {
+    /// Bonding curve pool that was migrated
     pub pool: Pubkey,
+    /// Newly created Raydium pool, CPMM or CLMM, default for manual
+    /// migrations
     pub raydium_pool: Pubkey,
-    pub meme_amount_migrated: u64,
-    pub quote_amount_migrated: u64,
+    /// Meme tokens seeded into the Raydium pool
+    pub meme_migrated: u64,
+    /// Quote tokens seeded into the Raydium pool
+    pub quote_migrated: u64,
+    /// Meme tokens retained on the bonding curve (the 5% kept for continued trading)
+    pub meme_retained: u64,
+    /// Unix timestamp of the migration
     pub timestamp: i64,
+    /// Quote paid to the fee treasury out of the migrated quote, zero
+    /// without a migration fee
+    pub migration_fee: u64,
+    /// Second quote reserve paid out, to its fee treasury on Raydium and to
+    /// the creator on manual migrations, zero for single quote pools
+    pub second_quote_migrated: u64,
+}
+
+/// Emitted when the Raydium LP tokens, or the CLMM position NFT, of a
+/// migrated pool are locked
+#[event]
+pub struct LpLocked {
+    /// Migrated bonding curve pool
+    pub pool: Pubkey,
+    /// Raydium LP mint, or CLMM position NFT mint
+    pub lp_mint: Pubkey,
+    /// LP tokens locked under the pool signer
+    pub amount: u64,
+    /// Unix timestamp from which the creator can claim the LP
+    pub unlock_ts: i64,
 }
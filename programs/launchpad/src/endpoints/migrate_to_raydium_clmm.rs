@@ -0,0 +1,640 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::endpoints::migrate_to_raydium::{LpLocked, MigrationCompleted};
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::{BoundPool, MigrationAmm};
+use crate::models::migration_registry::MigrationRegistry;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use raydium_clmm_cpi::{
+    cpi,
+    program::AmmV3,
+    states::{
+        AmmConfig, OBSERVATION_SEED, POOL_SEED, POOL_TICK_ARRAY_BITMAP_SEED, POOL_VAULT_SEED,
+        POSITION_SEED, TICK_ARRAY_SIZE,
+    },
+};
+use spl_math::uint::U256;
+
+#[derive(Accounts)]
+#[instruction(tick_lower_index: i32, tick_upper_index: i32)]
+pub struct MigrateToRaydiumClmm<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Raydium CLMM program (must be declared early to be referenced in other constraints)
+    pub clmm_program: Program<'info, AmmV3>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bound_pool",
+            meme_mint.key().as_ref(),
+            quote_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub pool: Box<Account<'info, BoundPool>>,
+
+    /// Meme token mint (must be smaller key than quote_mint for CLMM)
+    #[account(mut)]
+    pub meme_mint: Box<Account<'info, Mint>>,
+
+    /// Quote token mint (WSOL - must be larger key than meme_mint)
+    #[account(mut)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    /// Pool's meme token vault
+    #[account(
+        mut,
+        seeds = [
+            b"meme_vault",
+            pool.key().as_ref()
+        ],
+        bump,
+    )]
+    pub meme_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Pool's quote token vault
+    #[account(
+        mut,
+        seeds = [
+            b"quote_vault",
+            pool.key().as_ref()
+        ],
+        bump,
+    )]
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Authority PDA that controls the bonding curve vaults, seeds are verified for security
+    #[account(
+        seeds = [
+            b"bound_pool_auth",
+            pool.key().as_ref()
+        ],
+        bump,
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    // === RAYDIUM CLMM ACCOUNTS ===
+    /// Raydium CLMM config account, fixing the fee tier and tick spacing
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// CHECK: Raydium pool state account to be created, seeds are verified by Raydium program
+    #[account(
+        mut,
+        seeds = [
+            POOL_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+            meme_mint.key().as_ref(),  // token_0 (smaller key)
+            quote_mint.key().as_ref(), // token_1 (larger key)
+        ],
+        seeds::program = clmm_program.key(),
+        bump,
+    )]
+    pub raydium_pool_state: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium token_0 vault to be created, seeds are verified by Raydium program
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            raydium_pool_state.key().as_ref(),
+            meme_mint.key().as_ref()
+        ],
+        seeds::program = clmm_program.key(),
+        bump,
+    )]
+    pub token_0_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium token_1 vault to be created, seeds are verified by Raydium program
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            raydium_pool_state.key().as_ref(),
+            quote_mint.key().as_ref()
+        ],
+        seeds::program = clmm_program.key(),
+        bump,
+    )]
+    pub token_1_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Oracle observation account to be created, seeds are verified by Raydium program
+    #[account(
+        mut,
+        seeds = [
+            OBSERVATION_SEED.as_bytes(),
+            raydium_pool_state.key().as_ref(),
+        ],
+        seeds::program = clmm_program.key(),
+        bump,
+    )]
+    pub observation_state: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array bitmap extension to be created, seeds are verified by Raydium program
+    #[account(
+        mut,
+        seeds = [
+            POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+            raydium_pool_state.key().as_ref(),
+        ],
+        seeds::program = clmm_program.key(),
+        bump,
+    )]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// Creator's meme token account (for initial liquidity)
+    #[account(
+        mut,
+        constraint = pool.is_creator_account(
+            creator_meme_account.owner,
+            creator_meme_account.mint,
+            pool.meme_reserve.mint,
+        ) @ AmmError::InvalidCreatorAccount,
+    )]
+    pub creator_meme_account: Box<Account<'info, TokenAccount>>,
+
+    /// Creator's quote token account (for initial liquidity)
+    #[account(
+        mut,
+        constraint = pool.is_creator_account(
+            creator_quote_account.owner,
+            creator_quote_account.mint,
+            pool.quote_reserve.mint,
+        ) @ AmmError::InvalidCreatorAccount,
+    )]
+    pub creator_quote_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: pool_pda, owner of the position NFT
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: UncheckedAccount<'info>,
+
+    /// Position NFT mint to be created by Raydium
+    #[account(mut)]
+    pub position_nft_mint: Signer<'info>,
+
+    /// CHECK: Pool signer's position NFT account locking the position, created by Raydium
+    #[account(
+        mut,
+        address = get_associated_token_address(&pool_signer.key(), &position_nft_mint.key()),
+    )]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: Position NFT metadata, left empty as the position is opened without metadata
+    #[account(mut)]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium protocol position to be created, seeds are verified by Raydium program
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            raydium_pool_state.key().as_ref(),
+            &tick_lower_index.to_be_bytes(),
+            &tick_upper_index.to_be_bytes(),
+        ],
+        seeds::program = clmm_program.key(),
+        bump,
+    )]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array holding the lower tick, seeds are verified by Raydium program
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array holding the upper tick, seeds are verified by Raydium program
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium personal position to be created, seeds are verified by Raydium program
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_nft_mint.key().as_ref(),
+        ],
+        seeds::program = clmm_program.key(),
+        bump,
+    )]
+    pub personal_position: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Token 2022 program, verified by Raydium program
+    pub token_program_2022: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = ANCHOR_DISCRIMINATOR + MigrationRegistry::INIT_SPACE,
+        seeds = [MigrationRegistry::REGISTRY_PREFIX],
+        bump
+    )]
+    /// Recent migrations across pools, created with the first migration
+    pub migration_registry: Box<Account<'info, MigrationRegistry>>,
+
+    /// CHECK: deserialized in the handler when initialized
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    /// The global protocol configuration, read for the migration fee.
+    /// Required rather than optional so the fee can't be skipped.
+    pub protocol_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::FEE_TREASURY_PREFIX, quote_mint.key().as_ref()],
+        bump,
+    )]
+    /// The fee treasury of the quote mint, required when the protocol
+    /// charges a migration fee
+    pub fee_treasury: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The pool's second quote vault, required if the pool raised any
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_vault(second_quote_vault.key())
+            @ err::acc("Second quote vault doesn't belong to the pool"),
+    )]
+    pub second_quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The fee treasury of the second quote mint, receiving the second quote
+    /// reserve the CLMM position can't hold, so it never reaches the creator unlocked
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_treasury(second_quote_treasury.key())
+            @ err::acc("Second quote reserve can only be sent to its fee treasury"),
+    )]
+    pub second_quote_treasury: Option<Box<Account<'info, TokenAccount>>>,
+}
+
+/// Migrates a pool to a Raydium CLMM pool holding a single position.
+///
+/// Follows the same checks as `migrate_to_raydium`, takes the same migration
+/// fee and seeds the same retained liquidity, priced at the seeded quote per
+/// meme. The position spans `[tick_lower_index, tick_upper_index]`, which
+/// must be the tick range bounds aligned to the config's tick spacing so the
+/// position is full range. Its liquidity is sized from the meme side, tokens
+/// it doesn't use go back to the bonding curve vaults.
+///
+/// The position NFT is locked under the pool signer like CPMM LP tokens, and
+/// released to the creator through `claim_lp_after_lock`.
+///
+/// # Arguments
+/// * `ctx` - The context containing all necessary accounts
+/// * `tick_lower_index` - Lower tick of the position, see `full_range_ticks`
+/// * `tick_upper_index` - Upper tick of the position, see `full_range_ticks`
+pub fn handle(
+    ctx: Context<MigrateToRaydiumClmm>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    // The ticks also seed the protocol position, so they are passed in, but
+    // a narrower range would leave the migrated liquidity out of range
+    let tick_spacing = ctx.accounts.amm_config.tick_spacing;
+    if (tick_lower_index, tick_upper_index) != full_range_ticks(tick_spacing) {
+        return Err(error!(err::arg("Position must span the full tick range")));
+    }
+
+    let pool = &mut ctx.accounts.pool;
+
+    // 1. Check the pool can migrate: its target, still trading, token order,
+    // threshold, age
+    pool.ensure_migratable(
+        MigrationAmm::Clmm,
+        ctx.accounts.meme_mint.key(),
+        ctx.accounts.quote_mint.key(),
+    )?;
+    pool.ensure_min_age(Clock::get()?.unix_timestamp)?;
+    pool.ensure_meme_supply(ctx.accounts.meme_vault.amount)?;
+
+    // 2. Lock the pool to prevent further trading, persisted before any CPI
+    // runs like in `migrate_to_raydium`
+    pool.locked = true;
+    pool.exit(ctx.program_id)?;
+
+    // 3. Calculate liquidity amounts for Raydium pool, the protocol's
+    // migration fee coming out of the quote like in `migrate_to_raydium`
+    let (meme_amount, quote_amount) = pool.migration_amounts();
+    let migration_fee =
+        ProtocolConfig::migration_fee_of(&ctx.accounts.protocol_config, quote_amount)?;
+    let quote_seeded = quote_amount - migration_fee;
+
+    // 4. Prepare authority seeds for token transfers
+    let pool_key = pool.key();
+    let auth_seeds = &[
+        b"bound_pool_auth",
+        pool_key.as_ref(),
+        &[ctx.bumps.pool_authority],
+    ];
+    let signer_seeds = &[&auth_seeds[..]];
+
+    // 5. Transfer tokens from bonding curve to creator accounts, remembering
+    // what the creator held before to return what the position leaves
+    let creator_meme_before = ctx.accounts.creator_meme_account.amount;
+    let creator_quote_before = ctx.accounts.creator_quote_account.amount;
+
+    let transfer_meme_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.meme_vault.to_account_info(),
+            to: ctx.accounts.creator_meme_account.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_meme_ctx, meme_amount)?;
+
+    // Pay the migration fee to the fee treasury
+    if migration_fee != 0 {
+        let fee_treasury = ctx
+            .accounts
+            .fee_treasury
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Fee treasury is required")))?;
+        let transfer_fee_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.quote_vault.to_account_info(),
+                to: fee_treasury.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_fee_ctx, migration_fee)?;
+    }
+
+    let transfer_quote_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.quote_vault.to_account_info(),
+            to: ctx.accounts.creator_quote_account.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_quote_ctx, quote_seeded)?;
+
+    // Pay the second quote reserve to the protocol's treasury, the CLMM pool
+    // only pairs meme with the primary quote
+    let second_quote_migrated = pool.migrate_second_quote()?;
+    if second_quote_migrated != 0 {
+        let (Some(vault), Some(destination)) = (
+            &ctx.accounts.second_quote_vault,
+            &ctx.accounts.second_quote_treasury,
+        ) else {
+            return Err(error!(err::acc(
+                "Second quote accounts are required to migrate a dual quote pool"
+            )));
+        };
+        let transfer_second_quote_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: destination.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_second_quote_ctx, second_quote_migrated)?;
+    }
+
+    // 6. Initialize Raydium CLMM pool via CPI, tradable immediately
+    let clock = Clock::get()?;
+    let open_time = clock.unix_timestamp as u64;
+    let sqrt_price_x64 = sqrt_price_x64(meme_amount, quote_seeded)?;
+
+    let create_pool_accounts = cpi::accounts::CreatePool {
+        pool_creator: ctx.accounts.signer.to_account_info(),
+        amm_config: ctx.accounts.amm_config.to_account_info(),
+        pool_state: ctx.accounts.raydium_pool_state.to_account_info(),
+        token_mint_0: ctx.accounts.meme_mint.to_account_info(), // smaller key
+        token_mint_1: ctx.accounts.quote_mint.to_account_info(), // larger key
+        token_vault_0: ctx.accounts.token_0_vault.to_account_info(),
+        token_vault_1: ctx.accounts.token_1_vault.to_account_info(),
+        observation_state: ctx.accounts.observation_state.to_account_info(),
+        tick_array_bitmap: ctx.accounts.tick_array_bitmap.to_account_info(),
+        token_program_0: ctx.accounts.token_program.to_account_info(),
+        token_program_1: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+
+    cpi::create_pool(
+        CpiContext::new(
+            ctx.accounts.clmm_program.to_account_info(),
+            create_pool_accounts,
+        ),
+        sqrt_price_x64,
+        open_time,
+    )?;
+
+    // 7. Open the position, its NFT minted straight to the pool signer
+    let open_position_accounts = cpi::accounts::OpenPositionV2 {
+        payer: ctx.accounts.signer.to_account_info(),
+        position_nft_owner: ctx.accounts.pool_signer.to_account_info(),
+        position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+        position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+        metadata_account: ctx.accounts.metadata_account.to_account_info(),
+        pool_state: ctx.accounts.raydium_pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        token_account_0: ctx.accounts.creator_meme_account.to_account_info(),
+        token_account_1: ctx.accounts.creator_quote_account.to_account_info(),
+        token_vault_0: ctx.accounts.token_0_vault.to_account_info(),
+        token_vault_1: ctx.accounts.token_1_vault.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        metadata_program: ctx.accounts.metadata_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        vault_0_mint: ctx.accounts.meme_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.quote_mint.to_account_info(),
+    };
+
+    cpi::open_position_v2(
+        CpiContext::new(
+            ctx.accounts.clmm_program.to_account_info(),
+            open_position_accounts,
+        ),
+        tick_lower_index,
+        tick_upper_index,
+        tick_array_start_index(tick_lower_index, tick_spacing),
+        tick_array_start_index(tick_upper_index, tick_spacing),
+        0,
+        meme_amount,
+        quote_seeded,
+        false,
+        Some(true),
+    )?;
+
+    // 8. Return what the position didn't take to the bonding curve vaults
+    ctx.accounts.creator_meme_account.reload()?;
+    ctx.accounts.creator_quote_account.reload()?;
+    let meme_unused = ctx
+        .accounts
+        .creator_meme_account
+        .amount
+        .saturating_sub(creator_meme_before);
+    let quote_unused = ctx
+        .accounts
+        .creator_quote_account
+        .amount
+        .saturating_sub(creator_quote_before);
+
+    if meme_unused > 0 {
+        let return_meme_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator_meme_account.to_account_info(),
+                to: ctx.accounts.meme_vault.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        );
+        token::transfer(return_meme_ctx, meme_unused)?;
+    }
+
+    if quote_unused > 0 {
+        let return_quote_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator_quote_account.to_account_info(),
+                to: ctx.accounts.quote_vault.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        );
+        token::transfer(return_quote_ctx, quote_unused)?;
+    }
+
+    let meme_migrated = meme_amount - meme_unused;
+    let quote_migrated = quote_seeded - quote_unused;
+
+    // 9. Lock the position NFT under the pool signer, the creator can only
+    // claim it through claim_lp_after_lock once the lock expires
+    let pool = &mut ctx.accounts.pool;
+    pool.lp_mint = ctx.accounts.position_nft_mint.key();
+    pool.lp_locked = 1;
+    pool.lp_unlock_ts = clock.unix_timestamp + pool.lp_lock_duration;
+
+    emit!(LpLocked {
+        pool: pool.key(),
+        lp_mint: pool.lp_mint,
+        amount: pool.lp_locked,
+        unlock_ts: pool.lp_unlock_ts,
+    });
+
+    // 10. Update pool state, trading resumes against the retained reserves.
+    // The fee left the quote reserve along with the seeded quote
+    pool.complete_migration(
+        MigrationAmm::Clmm,
+        ctx.accounts.raydium_pool_state.key(),
+        meme_migrated,
+        quote_migrated + migration_fee,
+        clock.slot,
+    );
+    ctx.accounts
+        .migration_registry
+        .record(pool.key(), clock.slot);
+
+    // 11. Emit migration event
+    emit!(MigrationCompleted {
+        pool: pool.key(),
+        raydium_pool: ctx.accounts.raydium_pool_state.key(),
+        meme_migrated,
+        quote_migrated,
+        meme_retained: pool.meme_reserve.tokens,
+        timestamp: clock.unix_timestamp,
+        migration_fee,
+        second_quote_migrated,
+    });
+
+    Ok(())
+}
+
+/// Raydium's initial price, `sqrt(amount_1 / amount_0)` as a Q64.64 number
+fn sqrt_price_x64(amount_0: u64, amount_1: u64) -> Result<u128> {
+    if amount_0 == 0 {
+        return Err(error!(AmmError::DivideByZero));
+    }
+
+    let price_x128 = (U256::from(amount_1) << 128) / U256::from(amount_0);
+
+    u128::try_from(price_x128.integer_sqrt()).map_err(|_| error!(AmmError::MathOverflow))
+}
+
+/// Tick bounds of Raydium CLMM pools
+const MAX_TICK: i32 = 443_636;
+const MIN_TICK: i32 = -MAX_TICK;
+
+/// Lowest and highest ticks usable with `tick_spacing`, spanning every price
+fn full_range_ticks(tick_spacing: u16) -> (i32, i32) {
+    let tick_spacing = tick_spacing as i32;
+    (
+        MIN_TICK / tick_spacing * tick_spacing,
+        MAX_TICK / tick_spacing * tick_spacing,
+    )
+}
+
+/// Start tick of the Raydium tick array holding `tick`
+fn tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_price_x64() {
+        // ARRANGE & ACT & ASSERT: Equal amounts price at 1.0, i.e. 2^64
+        assert_eq!(sqrt_price_x64(1_000, 1_000).unwrap(), 1u128 << 64);
+
+        // ARRANGE & ACT & ASSERT: 4x more quote prices the meme at 4.0
+        assert_eq!(sqrt_price_x64(1_000, 4_000).unwrap(), 2u128 << 64);
+
+        // ARRANGE & ACT & ASSERT: No meme can't be priced
+        assert!(sqrt_price_x64(0, 1_000).is_err());
+
+        println!("✅ CLMM initial price test passed!");
+    }
+
+    #[test]
+    fn test_tick_array_start_index() {
+        // ARRANGE: Tick spacing 10, 600 ticks per array
+        let tick_spacing = 10;
+
+        // ACT & ASSERT: Arrays start on multiples of 600, rounding down
+        assert_eq!(tick_array_start_index(0, tick_spacing), 0);
+        assert_eq!(tick_array_start_index(599, tick_spacing), 0);
+        assert_eq!(tick_array_start_index(600, tick_spacing), 600);
+        assert_eq!(tick_array_start_index(-1, tick_spacing), -600);
+        assert_eq!(tick_array_start_index(-600, tick_spacing), -600);
+
+        println!("✅ Tick array start index test passed!");
+    }
+
+    #[test]
+    fn test_full_range_ticks() {
+        // ARRANGE & ACT & ASSERT: Bounds round towards zero onto the spacing
+        assert_eq!(full_range_ticks(1), (MIN_TICK, MAX_TICK));
+        assert_eq!(full_range_ticks(10), (-443_630, 443_630));
+        assert_eq!(full_range_ticks(60), (-443_580, 443_580));
+
+        // ARRANGE & ACT & ASSERT: Both stay within Raydium's tick range
+        let (lower, upper) = full_range_ticks(120);
+        assert!(lower >= MIN_TICK && upper <= MAX_TICK);
+        assert_eq!(lower % 120, 0);
+        assert_eq!(upper % 120, 0);
+
+        println!("✅ Full range ticks test passed!");
+    }
+}
@@ -1,17 +1,122 @@
+pub use accept_creator_transfer::*;
+pub use add_fee_exempt::*;
+pub use add_to_whitelist::*;
+pub use cancel_pool::*;
+pub use claim_lp_after_lock::*;
+pub use claim_refund::*;
+pub use claim_vested::*;
+pub use commit_buy::*;
 pub use create_metadata::*;
+pub use distribute_airdrop_batch::*;
+pub use dry_run_swap_y::*;
+pub use expire_buy::*;
+pub use get_buyer_position::*;
+pub use get_circulating_supply::*;
+pub use get_migration_quote::*;
+pub use get_pool_display_info::*;
+pub use get_price_at_reserve::*;
+pub use get_recent_migrations::*;
+pub use get_recent_trades::*;
+pub use get_reserves::*;
 pub use get_swap_x_amt::*;
+pub use get_swap_y_amounts::*;
 pub use get_swap_y_amt::*;
+pub use get_tokens_until_migration::*;
+pub use init_buyer_record::*;
+pub use init_fee_treasury::*;
+pub use init_protocol_config::*;
+pub use init_second_quote::*;
 pub use init_target_config::*;
+pub use init_trade_history::*;
+pub use initiate_refund::*;
+pub use migrate_manual::*;
+pub use migrate_pool_account::*;
 pub use migrate_to_raydium::*;
+pub use migrate_to_raydium_clmm::*;
 pub use new_pool::*;
+pub use preview_fee::*;
+pub use propose_creator_transfer::*;
+pub use reconcile_dust::*;
+pub use register_referrer::*;
+pub use remove_fee_exempt::*;
+pub use reveal_buy::*;
+pub use revoke_mint_authority::*;
+pub use set_fee_tiers::*;
+pub use set_fee_treasury_enabled::*;
+pub use set_migration_fee_bps::*;
+pub use set_pool_creation_enabled::*;
+pub use set_pool_socials::*;
+pub use set_trade_flags::*;
 pub use swap_x::*;
 pub use swap_y::*;
+pub use sweep_excess::*;
+pub use unregister_referrer::*;
+pub use update_metadata::*;
+pub use update_protocol_fee_authority::*;
+pub use update_target_config::*;
+pub use verify_pool_integrity::*;
+pub use withdraw_admin_fees::*;
+pub use withdraw_fee_treasury::*;
 
+pub mod accept_creator_transfer;
+pub mod add_fee_exempt;
+pub mod add_to_whitelist;
+pub mod cancel_pool;
+pub mod claim_lp_after_lock;
+pub mod claim_refund;
+pub mod claim_vested;
+pub mod commit_buy;
 pub mod create_metadata;
+pub mod distribute_airdrop_batch;
+pub mod dry_run_swap_y;
+pub mod expire_buy;
+pub mod get_buyer_position;
+pub mod get_circulating_supply;
+pub mod get_migration_quote;
+pub mod get_pool_display_info;
+pub mod get_price_at_reserve;
+pub mod get_recent_migrations;
+pub mod get_recent_trades;
+pub mod get_reserves;
 pub mod get_swap_x_amt;
+pub mod get_swap_y_amounts;
 pub mod get_swap_y_amt;
+pub mod get_tokens_until_migration;
+pub mod init_buyer_record;
+pub mod init_fee_treasury;
+pub mod init_protocol_config;
+pub mod init_second_quote;
 pub mod init_target_config;
+pub mod init_trade_history;
+pub mod initiate_refund;
+pub mod migrate_manual;
+pub mod migrate_pool_account;
 pub mod migrate_to_raydium;
+pub mod migrate_to_raydium_clmm;
 pub mod new_pool;
+pub mod preview_fee;
+pub mod propose_creator_transfer;
+pub mod reconcile_dust;
+pub mod register_referrer;
+pub mod remove_fee_exempt;
+pub mod reveal_buy;
+pub mod revoke_mint_authority;
+pub mod set_fee_tiers;
+pub mod set_fee_treasury_enabled;
+pub mod set_migration_fee_bps;
+pub mod set_pool_creation_enabled;
+pub mod set_pool_socials;
+pub mod set_trade_flags;
 pub mod swap_x;
 pub mod swap_y;
+pub mod swap_y_bps;
+pub mod swap_y_exact_out;
+pub mod swap_y_lite;
+pub mod sweep_excess;
+pub mod unregister_referrer;
+pub mod update_metadata;
+pub mod update_protocol_fee_authority;
+pub mod update_target_config;
+pub mod verify_pool_integrity;
+pub mod withdraw_admin_fees;
+pub mod withdraw_fee_treasury;
@@ -1,32 +1,42 @@
 use crate::consts::{
-    ANCHOR_DISCRIMINATOR, BP_FEE_KEY, DEFAULT_MAX_M, DEFAULT_MAX_M_LP,
-    DEFAULT_PRICE_FACTOR_DENOMINATOR, DEFAULT_PRICE_FACTOR_NUMERATOR, MAX_AIRDROPPED_TOKENS,
-    MAX_MEME_TOKENS,
+    ANCHOR_DISCRIMINATOR, BP_FEE_KEY, DEFAULT_MIN_QUOTE_LIQUIDITY_BPS,
+    DEFAULT_PRICE_FACTOR_DENOMINATOR, DEFAULT_PRICE_FACTOR_NUMERATOR, LP_LOCK_DURATION,
+    MAX_AIRDROPPED_TOKENS, MAX_BURN_ON_SELL_BPS, MAX_CREATOR_ALLOCATION, MAX_FIRST_BUYER_BONUS_BPS,
+    MAX_SELL_LOCK_DURATION,
 };
 use crate::err;
 use crate::err::AmmError;
-use crate::models::bound::{compute_alpha_abs, compute_beta, BoundPool, Config, Decimals};
+use crate::models::bound::{
+    compute_alpha_abs, compute_beta, BoundPool, Config, CurveType, Decimals, MigrationAmm,
+};
+use crate::models::creator_index::{CreatorIndex, CreatorPool};
 use crate::models::fees::FEE;
-use crate::models::fees::{Fees, MEME_FEE};
+use crate::models::fees::{Fees, BPS_PRECISION, MEME_FEE, REFERRAL_FEE_BPS};
+use crate::models::pool_registry::PoolRegistry;
+use crate::models::protocol_config::ProtocolConfig;
 use crate::models::target_config::TargetConfig;
+use crate::models::vesting_schedule::VestingSchedule;
 use crate::models::Reserve;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 impl<'info> NewPool<'info> {
-    /// Creates a CPI context for minting meme tokens to the meme vault.
+    /// Creates a CPI context for minting meme tokens to a pool vault.
     ///
-    /// This function prepares the necessary accounts and program for minting meme tokens to the meme vault.
-    /// It ensures that the minting operation is performed by the pool signer and that the tokens are minted to the correct vault.
+    /// This function prepares the necessary accounts and program for minting meme tokens to `vault`,
+    /// the meme vault, the vesting vault or the creator's account. It ensures that the minting operation is performed by the pool signer.
     ///
     /// # Returns
     ///
-    /// A CPI context for minting meme tokens to the meme vault.
-    fn mint_meme_tokens(&self) -> CpiContext<'_, '_, '_, 'info, token::MintTo<'info>> {
+    /// A CPI context for minting meme tokens to the vault.
+    fn mint_meme_tokens(
+        &self,
+        vault: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, token::MintTo<'info>> {
         let cpi_accounts = token::MintTo {
             mint: self.meme_mint.to_account_info(),
-            to: self.meme_vault.to_account_info(),
+            to: vault.to_account_info(),
             authority: self.pool_signer.to_account_info(),
         };
 
@@ -39,9 +49,14 @@ impl<'info> NewPool<'info> {
 ///
 /// This function initializes a new pool by minting meme tokens, setting up the pool's configuration,
 /// and updating the pool's state. It ensures that the meme mint authority is the pool signer, and
-/// that the meme mint does not have a freeze authority. It also verifies that the quote vault is of
-/// the correct mint, owned by the pool signer, and does not have close or delegate authorities.
-/// Additionally, it checks the fee quote vault's mint, ownership, and authorities.
+/// that the meme mint does not have a freeze authority. The meme and quote vaults are created as
+/// pool signer owned PDAs, seeded by `BoundPool::MEME_VAULT_PREFIX` / `QUOTE_VAULT_PREFIX` and the
+/// pool key, so clients can derive them. It also checks the fee quote vault's mint, ownership, and
+/// authorities.
+///
+/// Both mints must be owned by the SPL Token program: Token-2022 mints, whose
+/// transfer-fee extension would make vaults receive less than the reserves
+/// account for, are rejected.
 ///
 /// # Parameters
 ///
@@ -61,10 +76,70 @@ impl<'info> NewPool<'info> {
 ///
 /// # Parameters
 /// * `ctx` - The context containing all necessary accounts
-pub fn handle(ctx: Context<NewPool>) -> Result<()> {
+/// * `migration_target` - Path the pool graduates through, only that
+///   migration instruction is accepted
+/// * `whitelist_until` - Unix timestamp until which only whitelisted wallets
+///   can buy and sells are disabled, at most `MAX_WHITELIST_DURATION` from
+///   now, zero for no whitelist phase
+/// * `creator_allocation` - Meme tokens minted on top of the supply into the
+///   vesting vault, zero for none
+/// * `creator_allocation_bps` - Share of the trading supply taken off the
+///   curve for the creator, up to `MAX_CREATOR_ALLOCATION_BPS`. Vested along
+///   with `creator_allocation` when `vesting_duration` is set, paid to the
+///   creator's meme account otherwise. Zero for none
+/// * `vesting_cliff` - Seconds from now before any of the allocation vests
+/// * `vesting_duration` - Seconds from now until all of it vested
+/// * `sell_lock_until` - Unix timestamp until which sells are rejected,
+///   at most `MAX_SELL_LOCK_DURATION` from now, zero for no lock
+/// * `first_buyer_bonus_bps` - Meme paid to the first buyer on top of their
+///   buy, in bps of it, up to `MAX_FIRST_BUYER_BONUS_BPS`. Zero for none
+/// * `curve_type` - Shape of the curve, see `CurveType`
+/// * `trading_supply` - Meme sold on the curve, zero for `DEFAULT_MAX_M`
+/// * `lp_supply` - Meme reserved for the liquidity seeded at migration, zero
+///   for `DEFAULT_MAX_M_LP`. Both together at most `MAX_MEME_TOKENS`
+/// * `price_floor` - Quote per meme, scaled by `SPOT_PRICE_PRECISION`, below
+///   which sells are rejected. Zero for none
+/// * `burn_on_sell_bps` - Share of every sell's meme input burned, up to
+///   `MAX_BURN_ON_SELL_BPS`. Zero for none
+///
+/// # Errors
+/// * `AmmError::IdenticalMints` - If the quote and meme mints are the same
+/// * `AmmError::UnsupportedMintExtension` - If either mint isn't an SPL Token
+///   mint
+/// * `AmmError::PoolCreationDisabled` - If the protocol turned off new launches
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    ctx: Context<NewPool>,
+    migration_target: MigrationAmm,
+    whitelist_until: i64,
+    creator_allocation: u64,
+    creator_allocation_bps: u16,
+    vesting_cliff: i64,
+    vesting_duration: i64,
+    sell_lock_until: i64,
+    first_buyer_bonus_bps: u16,
+    curve_type: CurveType,
+    trading_supply: u64,
+    lp_supply: u64,
+    price_floor: u128,
+    burn_on_sell_bps: u16,
+) -> Result<()> {
     let accs = ctx.accounts;
 
     // Step 1: Initial Checks
+    // The protocol can stop new launches, once its config exists
+    if accs.protocol_config.owner == &crate::ID {
+        ProtocolConfig::try_deserialize(&mut &accs.protocol_config.try_borrow_data()?[..])?
+            .ensure_pool_creation_enabled()?;
+    }
+
+    // A pool needs two different assets
+    TargetConfig::ensure_distinct_mints(accs.quote_mint.key(), accs.meme_mint.key())?;
+    TargetConfig::ensure_spl_token_mints(
+        accs.quote_mint.to_account_info().owner,
+        accs.meme_mint.to_account_info().owner,
+    )?;
+
     // Ensure we're starting with a fresh token mint
     if accs.meme_mint.supply != 0 {
         return Err(error!(AmmError::NonZeroInitialMemeSupply));
@@ -80,19 +155,136 @@ pub fn handle(ctx: Context<NewPool>) -> Result<()> {
 
     let signer_seeds = &[&seeds[..]];
 
-    // Mint all 1B tokens to pool vault
+    // Split of the supply between the curve and the LP, 690M / 310M unless
+    // the creator picked another within the 1B total
+    let (trading_supply, lp_supply) = BoundPool::supply_split(trading_supply, lp_supply)?;
+
+    // Optional creator share, taken out of the trading supply so the curve
+    // only prices what buyers can actually get
+    let (trading_supply, creator_share) =
+        BoundPool::creator_allocation_split(trading_supply, creator_allocation_bps)?;
+
+    // Mint the whole split to pool vault
     token::mint_to(
-        accs.mint_meme_tokens().with_signer(signer_seeds),
-        MAX_MEME_TOKENS as u64, // 1B total tokens
-    )
-    .unwrap();
+        accs.mint_meme_tokens(&accs.meme_vault)
+            .with_signer(signer_seeds),
+        trading_supply + lp_supply,
+    )?;
+
+    // Without vesting the creator share is paid out right away. It circulates
+    // from launch but never paid into the curve, so refunds exclude it, see
+    // `BuyerRecord::refundable`
+    let (vested, creator_share_minted) = match vesting_duration {
+        0 if creator_share != 0 => {
+            let account = accs
+                .creator_meme_account
+                .as_ref()
+                .ok_or_else(|| error!(err::acc("Creator meme account is required")))?;
+            token::mint_to(
+                accs.mint_meme_tokens(account).with_signer(signer_seeds),
+                creator_share,
+            )?;
+
+            (creator_allocation, creator_share)
+        }
+        _ if accs.creator_meme_account.is_some() => {
+            return Err(error!(err::acc(
+                "Creator meme account is only used with an unvested creator share"
+            )));
+        }
+        _ => (creator_allocation + creator_share, 0),
+    };
+
+    // Optional creator allocation, minted on top, and the vested creator
+    // share, both vesting from now
+    if vested != 0 {
+        if creator_allocation > MAX_CREATOR_ALLOCATION {
+            return Err(error!(err::arg("Creator allocation exceeds the maximum")));
+        }
+        if vesting_duration <= 0 || !(0..=vesting_duration).contains(&vesting_cliff) {
+            return Err(error!(err::arg(
+                "Vesting needs a positive duration and a cliff within it"
+            )));
+        }
+
+        let vault = accs
+            .vesting_vault
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Vesting vault is required")))?;
+        token::mint_to(
+            accs.mint_meme_tokens(vault).with_signer(signer_seeds),
+            vested,
+        )?;
+
+        let vault = vault.key();
+        let start = Clock::get()?.unix_timestamp;
+        let pool = accs.pool.key();
+        accs.vesting_schedule
+            .as_mut()
+            .ok_or_else(|| error!(err::acc("Vesting schedule is required")))?
+            .set_inner(VestingSchedule {
+                pool,
+                vault,
+                total: vested,
+                claimed: 0,
+                start,
+                cliff: start + vesting_cliff,
+                duration: vesting_duration,
+            });
+    } else if accs.vesting_schedule.is_some() || accs.vesting_vault.is_some() {
+        return Err(error!(err::acc(
+            "Vesting accounts are only used with a creator allocation"
+        )));
+    }
+
+    // Optional first buyer bonus, minted on top into its own vault so it
+    // never comes out of the reserves other buyers trade against
+    if first_buyer_bonus_bps != 0 {
+        if first_buyer_bonus_bps > MAX_FIRST_BUYER_BONUS_BPS {
+            return Err(error!(err::arg("First buyer bonus exceeds the maximum")));
+        }
+
+        let vault = accs
+            .bonus_vault
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Bonus vault is required")))?;
+        token::mint_to(
+            accs.mint_meme_tokens(vault).with_signer(signer_seeds),
+            BoundPool::first_buyer_bonus_supply(trading_supply, first_buyer_bonus_bps),
+        )?;
+    } else if accs.bonus_vault.is_some() {
+        return Err(error!(err::acc(
+            "Bonus vault is only used with a first buyer bonus"
+        )));
+    }
 
     // Step 3: Configuring Pool Settings
     let pool = &mut accs.pool;
 
+    // Canonical signer bump, found by the `pool_signer` seeds constraint
+    pool.signer_bump = ctx.bumps.pool_signer;
+
+    // Vault bumps, so swaps check the derived vault addresses cheaply
+    pool.meme_vault_bump = ctx.bumps.meme_vault;
+    pool.quote_vault_bump = ctx.bumps.quote_vault;
+
     // Set up fee collection vault
     pool.fee_vault_quote = accs.fee_quote_vault.key();
 
+    // Creator and referral shares of the quote fee need a vault to go to
+    let fee_shares = accs.target_config.fee_shares;
+    pool.fee_shares = fee_shares;
+    pool.creator_fee_vault = match (&accs.creator_fee_vault, fee_shares.creator_bps) {
+        (Some(vault), _) => vault.key(),
+        (None, 0) => Pubkey::default(),
+        (None, _) => return Err(error!(err::acc("Creator fee vault is required"))),
+    };
+    pool.referral_fee_vault = match (&accs.referral_fee_vault, fee_shares.referral_bps) {
+        (Some(vault), _) => vault.key(),
+        (None, 0) => Pubkey::default(),
+        (None, _) => return Err(error!(err::acc("Referral fee vault is required"))),
+    };
+
     // Initialize SOL reserve
     pool.quote_reserve = Reserve {
         tokens: 0,                     // Start with 0 SOL
@@ -100,11 +292,17 @@ pub fn handle(ctx: Context<NewPool>) -> Result<()> {
         vault: accs.quote_vault.key(), // SOL vault address
     };
 
-    // Configure trading fees
+    // Configure trading fees, with the creator's optional sell burn
+    if burn_on_sell_bps > MAX_BURN_ON_SELL_BPS {
+        return Err(error!(err::arg("Burn on sell exceeds the maximum")));
+    }
     pool.fees = Fees {
         fee_meme_percent: MEME_FEE,
         fee_quote_percent: FEE,
+        burn_on_sell_bps,
+        referral_fee_bps: REFERRAL_FEE_BPS,
     };
+    pool.fees.validate()?;
 
     // Step 4: Setting Up Price Mathematics
     // Calculate SOL decimal precision (1B = 1 SOL)
@@ -114,10 +312,32 @@ pub fn handle(ctx: Context<NewPool>) -> Result<()> {
 
     // Configure bonding curve parameters
     let gamma_s = accs.target_config.token_target_amount as u128; // SOL target
-    let gamma_m = DEFAULT_MAX_M; // 690M trading tokens
-    let omega_m = DEFAULT_MAX_M_LP; // 310M LP tokens
+    let gamma_m = trading_supply as u128; // 690M trading tokens by default
+    let omega_m = lp_supply as u128; // 310M LP tokens by default
     let price_factor_num = DEFAULT_PRICE_FACTOR_NUMERATOR; // Price adjustment
     let price_factor_denom = DEFAULT_PRICE_FACTOR_DENOMINATOR; // factors
+    let price_factor_num_end = accs.target_config.price_factor_num_end; // Optional ramp
+
+    // A ramped curve must be valid at both ends, and so everywhere between
+    if price_factor_num_end != 0 {
+        let (_, end_decimals) = compute_alpha_abs(
+            gamma_s,
+            mint_decimals,
+            gamma_m,
+            omega_m,
+            price_factor_num_end,
+            price_factor_denom,
+        )?;
+        compute_beta(
+            gamma_s,
+            mint_decimals,
+            gamma_m,
+            omega_m,
+            price_factor_num_end,
+            price_factor_denom,
+            end_decimals,
+        )?;
+    }
 
     // Calculate price curve slope (α)
     let (alpha_abs, decimals) = compute_alpha_abs(
@@ -142,28 +362,120 @@ pub fn handle(ctx: Context<NewPool>) -> Result<()> {
             price_factor_denom,
             decimals,
         )?,
-        gamma_s: gamma_s as u64, // SOL target amount
-        gamma_m: gamma_m as u64, // Trading token amount
-        omega_m: omega_m as u64, // LP token amount
-        price_factor_num,        // Price numerator
-        price_factor_denom,      // Price denominator
+        gamma_s: gamma_s as u64,                         // SOL target amount
+        gamma_m: gamma_m as u64,                         // Trading token amount
+        omega_m: omega_m as u64,                         // LP token amount
+        price_factor_num,                                // Price numerator
+        price_factor_denom,                              // Price denominator
+        price_factor_num_end,                            // Ramp end numerator
+        fee_start_bps: accs.target_config.fee_start_bps, // Graduated fee
+        fee_end_bps: accs.target_config.fee_end_bps,
+        max_fee_quote: accs.target_config.max_fee_quote, // Per swap fee caps
+        max_fee_meme: accs.target_config.max_fee_meme,
+        virtual_quote_reserve: 0, // Set below, once the curve is known
+        virtual_meme_reserve: 0,
         decimals: Decimals {
             // Precision settings
-            alpha: decimals,             // For slope
-            beta: decimals,              // For starting price
-            quote: mint_decimals as u64, // For SOL
+            alpha: decimals,                                  // For slope
+            beta: decimals,                                   // For starting price
+            quote: mint_decimals as u64,                      // For SOL
+            meme: 10_u64.pow(accs.meme_mint.decimals as u32), // For meme
         },
     };
 
+    // The curve is scaled by the actual mints, never by caller input
+    pool.config
+        .decimals
+        .ensure_mint_decimals(accs.quote_mint.decimals, accs.meme_mint.decimals)?;
+
+    // Optional virtual reserves, shifting where the curve starts pricing
+    pool.set_virtual_reserves(
+        accs.target_config.virtual_quote_reserve,
+        accs.target_config.virtual_meme_reserve,
+    )?;
+
     // Step 6: Setting Up Token Distribution
     // Configure token reserve
-    pool.meme_reserve.tokens = DEFAULT_MAX_M as u64; // 690M for trading
+    pool.meme_reserve.tokens = trading_supply; // Tradeable on the curve
+    pool.initial_supply = trading_supply; // Curve supply, LP share excluded
     pool.meme_reserve.mint = accs.meme_mint.key(); // Token mint address
     pool.meme_reserve.vault = accs.meme_vault.key(); // Token vault address
 
+    // Sells need a seeded quote reserve, by default a share of the target
+    pool.min_quote_liquidity = match accs.target_config.min_quote_liquidity {
+        0 => {
+            accs.target_config.token_target_amount * DEFAULT_MIN_QUOTE_LIQUIDITY_BPS / BPS_PRECISION
+        }
+        min_quote_liquidity => min_quote_liquidity,
+    };
+
+    // Per-slot price move limit, disabled when zero
+    pool.circuit_breaker_bps = accs.target_config.circuit_breaker_bps;
+
+    // Hard cap on the quote raised, uncapped when zero
+    pool.max_quote_raise = accs.target_config.max_quote_raise;
+
+    // Fee of the trading continuing after migration
+    pool.post_migration_fee_bps = accs.target_config.post_migration_fee_bps;
+
+    // LP lock committed to buyers, applied at migration
+    pool.lp_lock_duration = match accs.target_config.lp_lock_duration {
+        0 => LP_LOCK_DURATION,
+        lp_lock_duration => lp_lock_duration,
+    };
+
+    // Migration path, fixed for the life of the pool
+    pool.migration_target = migration_target;
+
+    // Curve shape, the alternatives price from gamma_s and gamma_m alone
+    pool.curve_type = curve_type;
+
+    // Optional sell price floor, fixed at creation so holders know it upfront
+    pool.price_floor = price_floor;
+
+    // Optional presale to whitelisted wallets before public trading, bounded
+    // since sells stay disabled throughout
+    BoundPool::ensure_whitelist_duration(whitelist_until, Clock::get()?.unix_timestamp)?;
+    pool.whitelist_until = whitelist_until;
+
+    // Optional grace period without sells, bounded so holders aren't stuck
+    if sell_lock_until > Clock::get()?.unix_timestamp + MAX_SELL_LOCK_DURATION {
+        return Err(error!(err::arg("Sell lock exceeds the maximum duration")));
+    }
+    pool.sell_lock_until = sell_lock_until;
+
+    // Bonus of the first buy, paid out of the bonus vault
+    pool.first_buyer_bonus_bps = first_buyer_bonus_bps;
+
+    // Optional price discovery window, counted from now
+    pool.created_at = Clock::get()?.unix_timestamp;
+    pool.min_pool_age_secs = accs.target_config.min_pool_age_secs;
+
+    // Counted in the circulating supply from launch
+    pool.creator_share_minted = creator_share_minted;
+
     // Final settings
+    pool.version = BoundPool::VERSION; // Current account layout
     pool.locked = false; // Pool ready for trading
     pool.creator_addr = accs.sender.key(); // Creator address
+    pool.index = accs.pool_registry.register_pool(); // Creation order
+
+    // The target config priced this pool, it can't change anymore
+    accs.target_config.record_pool();
+
+    // Listed under the creator's next index
+    accs.creator_index.register_pool();
+    accs.creator_pool.pool = pool.key();
+
+    emit!(PoolCreated {
+        pool: pool.key(),
+        index: pool.index,
+        creator: pool.creator_addr,
+        meme_mint: pool.meme_reserve.mint,
+        quote_mint: pool.quote_reserve.mint,
+        sell_lock_until: pool.sell_lock_until,
+        creator_allocation_bps,
+    });
 
     Ok(())
 }
@@ -189,6 +501,37 @@ pub struct NewPool<'info> {
     )]
     /// The account representing the pool being created.
     pub pool: Account<'info, BoundPool>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + PoolRegistry::INIT_SPACE,
+        seeds = [PoolRegistry::REGISTRY_PREFIX],
+        bump
+    )]
+    /// The registry counting every pool, created with the first pool.
+    pub pool_registry: Account<'info, PoolRegistry>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + CreatorIndex::INIT_SPACE,
+        seeds = [CreatorIndex::CREATOR_INDEX_PREFIX, sender.key().as_ref()],
+        bump
+    )]
+    /// The count of the sender's pools, created with its first pool.
+    pub creator_index: Account<'info, CreatorIndex>,
+    #[account(
+        init,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + CreatorPool::INIT_SPACE,
+        seeds = [
+            CreatorPool::CREATOR_POOL_PREFIX,
+            sender.key().as_ref(),
+            &creator_index.pool_count.to_le_bytes(),
+        ],
+        bump
+    )]
+    /// The entry pointing to this pool from the sender's next index.
+    pub creator_pool: Account<'info, CreatorPool>,
     #[account(
         mut,
         constraint = meme_mint.mint_authority == COption::Some(pool_signer.key())
@@ -199,18 +542,16 @@ pub struct NewPool<'info> {
     /// The account representing the meme mint.
     pub meme_mint: Account<'info, Mint>,
     #[account(
-        constraint = quote_vault.mint == quote_mint.key()
-            @ err::acc("Quote vault must be of ticket mint"),
-        constraint = quote_vault.owner == pool_signer.key()
-            @ err::acc("Quote vault authority must match the pool signer"),
-        constraint = quote_vault.close_authority == COption::None
-            @ err::acc("Quote vault must not have close authority"),
-        constraint = quote_vault.delegate == COption::None
-            @ err::acc("Quote vault must not have delegate"),
+        init,
+        payer = sender,
+        seeds = [BoundPool::QUOTE_VAULT_PREFIX, pool.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = pool_signer,
     )]
-    /// The account representing the quote vault.
-    pub quote_vault: Account<'info, TokenAccount>,
-    /// The account representing the quote mint.
+    /// The account representing the quote vault, derived from the pool.
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
+    /// The account representing the quote mint, an SPL Token mint.
     pub quote_mint: Account<'info, Mint>,
     #[account(
         constraint = fee_quote_vault.mint == quote_mint.key()
@@ -225,23 +566,25 @@ pub struct NewPool<'info> {
     /// The account representing the fee quote vault.
     pub fee_quote_vault: Account<'info, TokenAccount>,
     #[account(
-        mut,
-        constraint = meme_vault.mint == meme_mint.key()
-            @ err::acc("Meme vault must be of meme mint"),
-        constraint = meme_vault.owner == pool_signer.key()
-            @ err::acc("Meme vault authority must match the pool signer"),
-        constraint = meme_vault.close_authority == COption::None
-            @ err::acc("Meme vault must not have close authority"),
-        constraint = meme_vault.delegate == COption::None
-            @ err::acc("Meme vault must not have delegate"),
+        init,
+        payer = sender,
+        seeds = [BoundPool::MEME_VAULT_PREFIX, pool.key().as_ref()],
+        bump,
+        token::mint = meme_mint,
+        token::authority = pool_signer,
     )]
-    /// The account representing the meme vault.
-    pub meme_vault: Account<'info, TokenAccount>,
+    /// The account representing the meme vault, derived from the pool.
+    pub meme_vault: Box<Account<'info, TokenAccount>>,
     #[account(
+        mut,
         constraint = target_config.token_mint == quote_mint.key()
             @ err::acc("Target config token mint must match quote mint"),
         constraint = target_config.pair_token_mint == meme_mint.key()
             @ err::acc("Target config pair token mint must match meme mint"),
+        constraint = target_config.token_decimals == quote_mint.decimals
+            @ AmmError::DecimalsMismatch,
+        constraint = target_config.pair_token_decimals == meme_mint.decimals
+            @ AmmError::DecimalsMismatch,
     )]
     /// The account representing the target configuration.
     pub target_config: Account<'info, TargetConfig>,
@@ -253,4 +596,90 @@ pub struct NewPool<'info> {
     pub system_program: Program<'info, System>,
     /// The token program account.
     pub token_program: Program<'info, Token>,
+    #[account(
+        constraint = creator_fee_vault.mint == quote_mint.key()
+            @ err::acc("Creator fee vault must be of quote mint"),
+    )]
+    /// The account receiving the creator share of the quote fee, required
+    /// when the target config gives the creator a share.
+    pub creator_fee_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        constraint = referral_fee_vault.mint == quote_mint.key()
+            @ err::acc("Referral fee vault must be of quote mint"),
+    )]
+    /// The account receiving the referral share of the quote fee, required
+    /// when the target config gives the referral a share.
+    pub referral_fee_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + VestingSchedule::INIT_SPACE,
+        seeds = [VestingSchedule::VESTING_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    /// The vesting schedule of the creator allocation, required when the
+    /// creator is allocated tokens.
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+    #[account(
+        mut,
+        constraint = vesting_vault.mint == meme_mint.key()
+            @ err::acc("Vesting vault must be of meme mint"),
+        constraint = vesting_vault.owner == pool_signer.key()
+            @ err::acc("Vesting vault authority must match the pool signer"),
+        constraint = vesting_vault.key() != meme_vault.key()
+            @ err::acc("Vesting vault must differ from the meme vault"),
+        constraint = vesting_vault.close_authority == COption::None
+            @ err::acc("Vesting vault must not have close authority"),
+        constraint = vesting_vault.delegate == COption::None
+            @ err::acc("Vesting vault must not have delegate"),
+    )]
+    /// The account holding the creator allocation until it's claimed,
+    /// required when the creator is allocated tokens.
+    pub vesting_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = creator_meme_account.mint == meme_mint.key()
+            @ err::acc("Creator meme account must be of meme mint"),
+        constraint = creator_meme_account.owner == sender.key()
+            @ err::acc("Creator meme account must be the sender's"),
+    )]
+    /// The sender's account receiving the creator share of the trading
+    /// supply, required when that share isn't vested.
+    pub creator_meme_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        init,
+        payer = sender,
+        seeds = [BoundPool::BONUS_VAULT_PREFIX, pool.key().as_ref()],
+        bump,
+        token::mint = meme_mint,
+        token::authority = pool_signer,
+    )]
+    /// The account holding the first buyer bonus, derived from the pool and
+    /// required when the pool has a bonus.
+    pub bonus_vault: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: deserialized in the handler when initialized
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    /// The global protocol configuration, checked for the pool creation
+    /// kill switch. Required rather than optional so it can't be left out,
+    /// pool creation is open while it isn't initialized.
+    pub protocol_config: UncheckedAccount<'info>,
+}
+
+/// Emitted when a pool is created, for indexers
+#[event]
+pub struct PoolCreated {
+    /// The new pool
+    pub pool: Pubkey,
+    /// Creation order of the pool
+    pub index: u64,
+    /// Pool creator
+    pub creator: Pubkey,
+    /// Meme token mint
+    pub meme_mint: Pubkey,
+    /// Quote token mint
+    pub quote_mint: Pubkey,
+    /// Unix timestamp until which sells are rejected, zero for none
+    pub sell_lock_until: i64,
+    /// Share of the trading supply allocated to the creator, zero for none
+    pub creator_allocation_bps: u16,
 }
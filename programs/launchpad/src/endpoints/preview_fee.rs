@@ -0,0 +1,75 @@
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::TokenAccount;
+
+/// Previews the fees a swap of `amount_in` would be charged at the pool's
+/// current state, as a [`FeePreview`](crate::models::FeePreview) in the
+/// return data.
+///
+/// The swap is priced exactly like `swap_y` / `swap_x` do, so fee tiers,
+/// the graduated fee, the per swap caps and the sell tax all apply. Fee
+/// exemptions are per trader and aren't reflected.
+///
+/// # Arguments
+/// * `amount_in` - Quote spent by a buy, or meme sold by a sell
+/// * `is_buy` - True for a buy, false for a sell
+///
+/// # Errors
+/// * `AmmError::NoZeroTokens` - If `amount_in` is zero
+pub fn handle(ctx: Context<PreviewFee>, amount_in: u64, is_buy: bool) -> Result<()> {
+    if amount_in == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    let pool = &ctx.accounts.pool;
+    let protocol_config = ctx.accounts.protocol_config.as_deref();
+    let second_quote = pool.is_second_quote_vault(ctx.accounts.quote_vault.key());
+
+    // Sells burn their tax before the rest is traded, as in `swap_x`
+    let burn = if is_buy {
+        0
+    } else {
+        pool.fees.get_burn_on_sell_amount(amount_in)?
+    };
+
+    let swap_amount = if second_quote {
+        pool.second_quote_swap_amounts(amount_in - burn, 0, is_buy, protocol_config, false)?
+    } else {
+        pool.swap_amounts(amount_in - burn, 0, is_buy, protocol_config, false)
+    };
+
+    let preview = swap_amount.to_fee_preview(is_buy, burn, pool.fee_quote_percent(protocol_config));
+
+    msg!(
+        "quote_fee: {}\n meme_fee: {}\n burn: {}",
+        preview.quote_fee,
+        preview.meme_fee,
+        preview.burn
+    );
+
+    set_return_data(&preview.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Account validation struct for previewing swap fees
+#[derive(Accounts)]
+pub struct PreviewFee<'info> {
+    /// The bonding pool the swap would trade against
+    pub pool: Account<'info, BoundPool>,
+
+    /// The quote token vault the swap would use, the pool's primary or
+    /// second quote vault
+    #[account(
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+            || pool.is_second_quote_vault(quote_vault.key())
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    /// The protocol configuration holding the volume fee tiers, if any
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
+}
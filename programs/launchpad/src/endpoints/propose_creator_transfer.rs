@@ -0,0 +1,55 @@
+use crate::err;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+
+/// Proposes a new creator for the pool, first step of a creator transfer.
+///
+/// The transfer only completes once `new_creator` calls
+/// `accept_creator_transfer`, so a mistyped or dead address can never end up
+/// owning the pool. Proposing again replaces the pending creator.
+///
+/// # Arguments
+/// * `ctx` - The context containing all necessary accounts
+/// * `new_creator` - The key that will be able to accept the transfer
+pub fn handle(ctx: Context<ProposeCreatorTransfer>, new_creator: Pubkey) -> Result<()> {
+    if new_creator == Pubkey::default() {
+        return Err(error!(err::arg("New creator can't be the default pubkey")));
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    pool.pending_creator = Some(new_creator);
+
+    emit!(CreatorTransferProposed {
+        pool: pool.key(),
+        creator: pool.creator_addr,
+        pending_creator: new_creator,
+    });
+
+    Ok(())
+}
+
+/// Represents the accounts required for proposing a creator transfer.
+#[derive(Accounts)]
+pub struct ProposeCreatorTransfer<'info> {
+    /// The current pool creator
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = pool.creator_addr == creator.key()
+            @ err::acc("Only the pool creator can propose a creator transfer"),
+    )]
+    /// The pool whose creator is transferred
+    pub pool: Account<'info, BoundPool>,
+}
+
+/// Emitted when the creator of a pool proposes a new creator
+#[event]
+pub struct CreatorTransferProposed {
+    /// Pool being transferred
+    pub pool: Pubkey,
+    /// Current creator
+    pub creator: Pubkey,
+    /// Proposed creator, which must accept the transfer
+    pub pending_creator: Pubkey,
+}
@@ -0,0 +1,48 @@
+use crate::err;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+/// Folds rounding dust left in one of a pool's vaults into the admin fees of
+/// its asset.
+///
+/// Only a surplus up to `MAX_RECONCILE_DUST` over what the pool tracks in the
+/// vault is accepted, see [`BoundPool::reconcile_dust`]. Larger surpluses are
+/// tokens sent to the vault and are recovered with `sweep_excess` instead.
+///
+/// # Errors
+/// * If the vault holds more than dust over its tracked amount
+pub fn handle(ctx: Context<ReconcileDust>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let vault = accs.vault.key();
+    let dust = accs.pool.reconcile_dust(vault, accs.vault.amount)?;
+
+    msg!("reconciled dust: {}, vault: {}", dust, vault);
+
+    Ok(())
+}
+
+/// Represents the accounts required for reconciling a vault's dust.
+#[derive(Accounts)]
+pub struct ReconcileDust<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can reconcile dust"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    /// The pool owning the vault
+    pub pool: Account<'info, BoundPool>,
+
+    /// The pool vault holding the dust, quote, meme or second quote
+    pub vault: Account<'info, TokenAccount>,
+}
@@ -0,0 +1,50 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use crate::models::referrer::Referrer;
+use anchor_lang::prelude::*;
+
+/// Registers a wallet as referrer, so buys can rebate part of the protocol's
+/// fee to it.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `wallet` - The referrer's wallet
+pub fn handle(ctx: Context<RegisterReferrer>, wallet: Pubkey) -> Result<()> {
+    ctx.accounts.referrer.wallet = wallet;
+
+    msg!("referrer registered: {}", wallet);
+
+    Ok(())
+}
+
+/// Represents the accounts required for registering a referrer.
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    /// The protocol fee authority, paying for the registration
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can register referrers"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + Referrer::INIT_SPACE,
+        seeds = [Referrer::REFERRER_PREFIX, wallet.as_ref()],
+        bump,
+    )]
+    /// The referrer's registration
+    pub referrer: Account<'info, Referrer>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
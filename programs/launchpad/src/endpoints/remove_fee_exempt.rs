@@ -0,0 +1,38 @@
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+/// Removes an account's admin fee exemption, it pays regular fees again.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `account` - The exempt trader
+pub fn handle(ctx: Context<RemoveFeeExempt>, account: Pubkey) -> Result<()> {
+    let fee_exempt = &mut ctx.accounts.protocol_config.fee_exempt;
+
+    let index = fee_exempt
+        .iter()
+        .position(|exempt| *exempt == account)
+        .ok_or_else(|| error!(err::arg("Account is not fee exempt")))?;
+
+    fee_exempt.swap_remove(index);
+
+    Ok(())
+}
+
+/// Represents the accounts required for removing a fee exemption.
+#[derive(Accounts)]
+pub struct RemoveFeeExempt<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can remove fee exemptions"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
@@ -0,0 +1,40 @@
+use crate::endpoints::swap_y::{self, *};
+use crate::models::pending_buy::PendingBuy;
+use anchor_lang::prelude::*;
+
+/// Reveals a committed buy and executes it like `swap_y`.
+///
+/// The revealed values must hash to the commitment, and the reveal must
+/// happen in a later slot than the commitment, before it expires. The
+/// pending buy is closed and its rent returned to the buyer.
+///
+/// # Arguments
+/// * `ctx` - The context containing all necessary accounts
+/// * `amount` - Amount of SOL to swap
+/// * `min_out` - Minimum amount of meme tokens to receive
+/// * `nonce` - Secret salt of the commitment
+pub fn handle(ctx: Context<RevealBuy>, amount: u64, min_out: u64, nonce: [u8; 32]) -> Result<()> {
+    ctx.accounts
+        .pending_buy
+        .verify_reveal(amount, min_out, &nonce, Clock::get()?.slot)?;
+
+    swap_y::execute(&mut ctx.accounts.swap, amount, min_out, 0, 0, 0, 0, 0, true)?;
+
+    let owner = ctx.accounts.swap.owner_info();
+    ctx.accounts.pending_buy.close(owner)
+}
+
+/// Represents the accounts required for revealing a committed buy.
+#[derive(Accounts)]
+pub struct RevealBuy<'info> {
+    /// The accounts of the executed buy, as for `swap_y`
+    pub swap: SwapCoinY<'info>,
+
+    #[account(
+        mut,
+        seeds = [PendingBuy::PENDING_BUY_PREFIX, swap.pool_key().as_ref(), swap.owner_key().as_ref()],
+        bump
+    )]
+    /// The buyer's pending buy
+    pub pending_buy: Account<'info, PendingBuy>,
+}
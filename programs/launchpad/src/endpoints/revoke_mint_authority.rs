@@ -0,0 +1,81 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{self, Mint, SetAuthority, Token};
+
+impl<'info> RevokeMintAuthority<'info> {
+    /// Creates a CPI context for changing the meme mint authority
+    fn set_mint_authority(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
+        let cpi_accounts = SetAuthority {
+            current_authority: self.pool_signer.to_account_info(),
+            account_or_mint: self.meme_mint.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Permanently revokes the meme mint authority, capping the meme supply.
+///
+/// Opt-in, so creators keeping the mint authority for airdrops can still
+/// launch. Metadata must be created first, as it requires the mint authority.
+/// Sets `BoundPool::mint_authority_revoked` for front-ends to display.
+pub fn handle(ctx: Context<RevokeMintAuthority>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::set_authority(
+        accs.set_mint_authority().with_signer(signer_seeds),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    accs.pool.mint_authority_revoked = true;
+
+    msg!("meme mint authority revoked");
+
+    Ok(())
+}
+
+/// Represents the accounts required for revoking the meme mint authority.
+#[derive(Accounts)]
+pub struct RevokeMintAuthority<'info> {
+    /// The pool creator
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = sender.key() == pool.creator_addr
+            @ err::acc("Only the pool creator can revoke the mint authority"),
+    )]
+    /// The pool of the meme mint
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        constraint = meme_mint.key() == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = meme_mint.mint_authority == COption::Some(pool_signer.key())
+            @ err::acc("Meme mint authority must be the pool signer"),
+    )]
+    /// The meme mint losing its mint authority
+    pub meme_mint: Account<'info, Mint>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    /// The pool signer, current mint authority
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,58 @@
+use crate::consts::FEE_DENOMINATOR;
+use crate::err;
+use crate::models::protocol_config::{FeeTier, ProtocolConfig, MAX_FEE_TIERS};
+use anchor_lang::prelude::*;
+
+/// Replaces the volume based quote fee tiers applied to every pool.
+///
+/// An empty list restores the default behavior, each pool paying its own
+/// quote fee.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `fee_tiers` - Tiers sorted by strictly ascending volume threshold
+pub fn handle(ctx: Context<SetFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    if fee_tiers.len() > MAX_FEE_TIERS {
+        return Err(error!(err::arg(format!(
+            "At most {} fee tiers are supported",
+            MAX_FEE_TIERS
+        ))));
+    }
+
+    if fee_tiers
+        .windows(2)
+        .any(|tiers| tiers[0].volume_threshold >= tiers[1].volume_threshold)
+    {
+        return Err(error!(err::arg(
+            "Fee tier thresholds must be strictly ascending"
+        )));
+    }
+
+    if fee_tiers
+        .iter()
+        .any(|tier| tier.fee_quote_percent > FEE_DENOMINATOR)
+    {
+        return Err(error!(err::arg("Fee tier fee can't exceed 100%")));
+    }
+
+    ctx.accounts.protocol_config.fee_tiers = fee_tiers;
+
+    Ok(())
+}
+
+/// Represents the accounts required for updating the fee tiers.
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can set fee tiers"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
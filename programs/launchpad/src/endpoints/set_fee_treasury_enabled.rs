@@ -0,0 +1,39 @@
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+/// Switches routing the protocol's fee share of swaps to the fee treasury.
+///
+/// While it's on, swaps passing the protocol config must pass the fee
+/// treasury of their quote mint and pay it the protocol fee, one more token
+/// transfer CPI per swap. In exchange, `withdraw_fee_treasury` collects the
+/// fees of every pool at once. Swaps without the protocol config, and meme
+/// fees, keep accruing per pool for `withdraw_admin_fees`.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `enabled` - Whether protocol fees are paid to the fee treasury
+pub fn handle(ctx: Context<SetFeeTreasuryEnabled>, enabled: bool) -> Result<()> {
+    ctx.accounts.protocol_config.fee_treasury_enabled = enabled;
+
+    msg!("fee_treasury_enabled: {}", enabled);
+
+    Ok(())
+}
+
+/// Represents the accounts required for switching fee treasury routing.
+#[derive(Accounts)]
+pub struct SetFeeTreasuryEnabled<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can switch fee routing"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
@@ -0,0 +1,43 @@
+use crate::consts::MAX_MIGRATION_FEE_BPS;
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+/// Sets the protocol's one-time fee on graduating pools.
+///
+/// Every migration path pays that share of the quote it moves out of the
+/// curve to the fee treasury of the quote mint, which must then exist, and
+/// migrates the rest. Zero disables the fee.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `migration_fee_bps` - Fee in bps of the migrated quote, at most
+///   `MAX_MIGRATION_FEE_BPS`
+pub fn handle(ctx: Context<SetMigrationFeeBps>, migration_fee_bps: u16) -> Result<()> {
+    if migration_fee_bps > MAX_MIGRATION_FEE_BPS {
+        return Err(error!(err::arg("Migration fee exceeds the maximum")));
+    }
+
+    ctx.accounts.protocol_config.migration_fee_bps = migration_fee_bps;
+
+    msg!("migration_fee_bps: {}", migration_fee_bps);
+
+    Ok(())
+}
+
+/// Represents the accounts required for setting the migration fee.
+#[derive(Accounts)]
+pub struct SetMigrationFeeBps<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can set the migration fee"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
@@ -0,0 +1,36 @@
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+/// Turns new pool creation on or off for the whole protocol.
+///
+/// Meant as an incident kill switch: `new_pool` is rejected while it's off,
+/// existing pools and their trading are unaffected.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `enabled` - Whether new pools can be created
+pub fn handle(ctx: Context<SetPoolCreationEnabled>, enabled: bool) -> Result<()> {
+    ctx.accounts.protocol_config.pool_creation_enabled = enabled;
+
+    msg!("pool_creation_enabled: {}", enabled);
+
+    Ok(())
+}
+
+/// Represents the accounts required for switching pool creation on or off.
+#[derive(Accounts)]
+pub struct SetPoolCreationEnabled<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can switch pool creation"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
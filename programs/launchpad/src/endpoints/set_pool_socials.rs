@@ -0,0 +1,41 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::pool_socials::PoolSocials;
+use anchor_lang::prelude::*;
+
+/// Sets the pool's on-chain social links, creating their account on the
+/// first call. Creator only, until the pool migrates.
+pub fn handle(
+    ctx: Context<SetPoolSocials>,
+    website: String,
+    twitter: String,
+    telegram: String,
+) -> Result<()> {
+    let socials = &mut ctx.accounts.pool_socials;
+    socials.pool = ctx.accounts.pool.key();
+
+    socials.set(website, twitter, telegram)
+}
+
+#[derive(Accounts)]
+pub struct SetPoolSocials<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        constraint = sender.key() == pool.creator_addr
+            @ err::acc("Only the pool creator can set the socials"),
+        constraint = !pool.pool_migration @ AmmError::AlreadyMigrated,
+    )]
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + PoolSocials::INIT_SPACE,
+        seeds = [PoolSocials::SOCIALS_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_socials: Account<'info, PoolSocials>,
+    pub system_program: Program<'info, System>,
+}
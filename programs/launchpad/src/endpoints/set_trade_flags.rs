@@ -0,0 +1,41 @@
+use crate::err;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+/// Pauses buys, sells or both on a pool, e.g. halting sells during an
+/// incident while buys continue.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `trade_flags` - `BoundPool::BUYS_PAUSED` and `BoundPool::SELLS_PAUSED`
+///   bits, zero to resume trading both ways
+pub fn handle(ctx: Context<SetTradeFlags>, trade_flags: u8) -> Result<()> {
+    BoundPool::validate_trade_flags(trade_flags)?;
+
+    ctx.accounts.pool.trade_flags = trade_flags;
+
+    msg!("trade_flags: {}", trade_flags);
+
+    Ok(())
+}
+
+/// Represents the accounts required for pausing trading directions of a pool.
+#[derive(Accounts)]
+pub struct SetTradeFlags<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can pause trading"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    /// The pool whose trading is paused or resumed
+    pub pool: Account<'info, BoundPool>,
+}
@@ -1,7 +1,17 @@
+use crate::consts::TEMP_WSOL_PREFIX;
+use crate::err;
 use crate::err::AmmError;
 use crate::models::bound::BoundPool;
+use crate::models::buyer_record::BuyerRecord;
+use crate::models::fees::get_platform_fee;
+use crate::models::protocol_config::ProtocolConfig;
+use crate::models::trade_history::{TradeEntry, TradeHistory};
+use crate::models::{ensure_before_deadline, ensure_not_frozen, SwapEvent};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::{
+    self, spl_token::native_mint, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer,
+};
 
 impl<'info> SwapCoinX<'info> {
     /// Creates a CPI context for transferring meme tokens from user to pool
@@ -22,23 +32,156 @@ impl<'info> SwapCoinX<'info> {
         CpiContext::new(cpi_program, cpi_accounts)
     }
 
+    /// Returns the account receiving the proceeds: the temporary WSOL account
+    /// when unwrapping to native SOL, `user_sol` otherwise
+    ///
+    /// # Errors
+    /// * `AmmError::InvalidAccountInput` - If only one of
+    ///   `native_sol_destination` and `temp_wsol` is provided
+    /// * `AmmError::InvalidTokenMints` - If native SOL is asked for out of a
+    ///   vault of another quote
+    fn quote_destination(&self) -> Result<AccountInfo<'info>> {
+        match (&self.native_sol_destination, &self.temp_wsol) {
+            (None, None) => Ok(self.user_sol.to_account_info()),
+            (Some(_), Some(_)) if self.quote_vault.mint != native_mint::ID => {
+                Err(error!(AmmError::InvalidTokenMints))
+            }
+            (Some(_), Some(temp_wsol)) => Ok(temp_wsol.to_account_info()),
+            (Some(_), None) => Err(error!(err::acc(
+                "Temporary WSOL account is required to receive native SOL"
+            ))),
+            (None, Some(_)) => Err(error!(err::acc(
+                "Temporary WSOL account is only used to receive native SOL"
+            ))),
+        }
+    }
+
     /// Creates a CPI context for transferring SOL tokens to the user
     ///
     /// This helper function prepares the CPI context needed to transfer SOL tokens
-    /// from the pool's quote vault to the user's SOL token account.
+    /// from the pool's quote vault to `destination`, see `quote_destination`.
     ///
     /// # Returns
     /// * `CpiContext` - The context for the token transfer CPI
-    fn send_sol_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+    fn send_sol_to_user(
+        &self,
+        destination: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
         let cpi_accounts = Transfer {
             from: self.quote_vault.to_account_info(),
-            to: self.user_sol.to_account_info(),
+            to: destination,
             authority: self.pool_signer.to_account_info(),
         };
 
         let cpi_program = self.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    /// Creates a CPI context for burning meme tokens held by the pool
+    ///
+    /// Used to burn the sell tax portion of the meme tokens a user sold to
+    /// the pool, permanently reducing the meme supply.
+    ///
+    /// # Returns
+    /// * `CpiContext` - The context for the token burn CPI
+    fn burn_meme_from_pool(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.meme_mint.to_account_info(),
+            from: self.meme_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Pays `amount` of the quote fee out of the quote vault to one of the
+    /// pool's fee vaults
+    ///
+    /// # Errors
+    /// * `AmmError::InvalidAccountInput` - If there is something to pay and
+    ///   the fee vault isn't provided
+    fn pay_fee_share(
+        &self,
+        fee_vault: &Option<Account<'info, TokenAccount>>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let fee_vault = fee_vault
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Fee vault of the pool is missing")))?;
+        if fee_vault.mint != self.quote_vault.mint {
+            return Err(error!(AmmError::InvalidTokenMints));
+        }
+
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: fee_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            amount,
+        )
+    }
+
+    /// Pays the routing aggregator's fee out of the quote vault, from the
+    /// seller's proceeds
+    ///
+    /// # Errors
+    /// * `AmmError::InvalidAccountInput` - If there is a fee to pay and the
+    ///   platform fee account isn't provided
+    fn pay_platform_fee(&self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let platform_fee_account = self
+            .platform_fee_account
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Platform fee account is required")))?;
+
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: platform_fee_account.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            amount,
+        )
+    }
+
+    /// Creates a CPI context for closing the temporary WSOL account `account`
+    ///
+    /// Closing a WSOL account unwraps its whole balance, so the quote tokens
+    /// received from the swap reach `destination` as native SOL, along with
+    /// the account's rent.
+    ///
+    /// # Returns
+    /// * `CpiContext` - The context for the close account CPI
+    fn close_temp_wsol(
+        &self,
+        account: AccountInfo<'info>,
+        destination: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account,
+            destination,
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
 }
 
 /// Handles the swap of meme tokens for SOL with direct transfer
@@ -51,6 +194,8 @@ impl<'info> SwapCoinX<'info> {
 /// * `ctx` - The context containing all required accounts
 /// * `coin_in_amount` - The amount of meme tokens to swap
 /// * `coin_y_min_value` - The minimum amount of SOL to receive (slippage protection)
+/// * `platform_fee_bps` - Aggregator fee paid to `platform_fee_account` out
+///   of the proceeds, at most `MAX_PLATFORM_FEE_BPS`
 ///
 /// # Returns
 /// * `Result<()>` - Result indicating success or containing error
@@ -58,9 +203,27 @@ impl<'info> SwapCoinX<'info> {
 /// # Errors
 /// * `AmmError::NoZeroTokens` - If attempting to swap 0 tokens
 /// * `AmmError::PoolIsLocked` - If the pool is currently locked
-pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u64) -> Result<()> {
+/// * `AmmError::InsufficientQuoteLiquidity` - If the quote reserve is below
+///   the pool's `min_quote_liquidity`
+/// * `AmmError::SellsLocked` - If the pool's post-launch sell lock is active
+/// * `AmmError::BelowPriceFloor` - If the sell executes below the pool's
+///   price floor
+/// * `AmmError::SlippageExceeded` - If the proceeds after the platform fee
+///   are below `coin_y_min_value`, equal to it is accepted
+/// * `AmmError::DeadlineExceeded` - If the sell lands after `deadline`
+pub fn handle(
+    ctx: Context<SwapCoinX>,
+    coin_in_amount: u64,
+    coin_y_min_value: u64,
+    platform_fee_bps: u16,
+    deadline: i64,
+) -> Result<()> {
     let accs = ctx.accounts;
 
+    // Don't execute a sell held back past the client's deadline
+    let now = Clock::get()?.unix_timestamp;
+    ensure_before_deadline(deadline, now)?;
+
     // Validate that the input amount is not zero
     if coin_in_amount == 0 {
         return Err(error!(AmmError::NoZeroTokens));
@@ -71,47 +234,177 @@ pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u6
         return Err(error!(AmmError::InsufficientBalance));
     }
 
-    // Check if the pool is locked
-    if accs.pool.locked {
-        return Err(error!(AmmError::PoolIsLocked));
+    // Check if the pool is locked or takes no sells
+    accs.pool.ensure_version()?;
+    accs.pool.ensure_unlocked()?;
+    accs.pool.ensure_sells_enabled()?;
+
+    // Fail clearly rather than in the transfers below
+    ensure_not_frozen(&accs.user_meme, "Meme")?;
+    ensure_not_frozen(&accs.user_sol, "Quote")?;
+
+    // Only whitelisted buys are open during the whitelist phase
+    if accs.pool.in_whitelist_phase(now) {
+        return Err(error!(AmmError::WhitelistPhaseActive));
     }
 
+    // Creators can keep sells closed for a while after launch
+    accs.pool.ensure_sells_unlocked(now)?;
+
+    // Selling into the second quote asset is priced against the same curve
+    let second_quote = accs.pool.is_second_quote_vault(accs.quote_vault.key());
+
+    // Don't pay dust out of a reserve buys haven't seeded yet
+    accs.pool.ensure_quote_liquidity(second_quote)?;
+
+    // The sell tax is burned, only the remainder is sold into the curve
+    let burn_amount = accs.pool.fees.get_burn_on_sell_amount(coin_in_amount)?;
+    let coin_in_sold = coin_in_amount - burn_amount;
+
+    // Quote fee of the volume tier the pool reached, none for exempt traders
+    let protocol_config = accs.protocol_config.as_deref();
+    let fee_exempt = protocol_config.is_some_and(|config| config.is_fee_exempt(&accs.owner.key()));
+    let fee_quote_percent = if fee_exempt {
+        0
+    } else {
+        accs.pool.fee_quote_percent(protocol_config)
+    };
+
     // Calculate swap amounts based on bonding curve
-    let swap_amount = accs
-        .pool
-        .swap_amounts(coin_in_amount, coin_y_min_value, false);
+    let swap_amount = if second_quote {
+        accs.pool
+            .second_quote_swap_amounts(coin_in_sold, 0, false, protocol_config, fee_exempt)?
+    } else {
+        accs.pool
+            .swap_amounts(coin_in_sold, 0, false, protocol_config, fee_exempt)
+    };
+
+    // Dust sells round to no quote, don't charge a fee for nothing
+    swap_amount.ensure_nonzero_output()?;
+
+    // Creators can stop sells from executing below a price floor
+    accs.pool
+        .ensure_above_price_floor(&swap_amount, second_quote)?;
+
+    // Aggregator fee out of the proceeds, which must still meet the minimum.
+    // The floor is checked here rather than in swap_amounts, which panics
+    let platform_fee = get_platform_fee(swap_amount.amount_out, platform_fee_bps)?;
+    if swap_amount.amount_out - platform_fee < coin_y_min_value {
+        return Err(error!(AmmError::SlippageExceeded));
+    }
+
+    // The curve's quote measure spans both reserves, so make sure the one
+    // paying out actually holds enough
+    if !second_quote
+        && swap_amount.amount_out + swap_amount.admin_fee_out > accs.pool.quote_reserve.tokens
+    {
+        return Err(error!(AmmError::InsufficientBalance));
+    }
 
     // Transfer meme tokens from user to pool
     token::transfer(
         accs.send_meme_to_pool(),
-        swap_amount.amount_in + swap_amount.admin_fee_in,
+        swap_amount.amount_in + swap_amount.admin_fee_in + burn_amount,
     )?;
 
-    let pool_state = &mut accs.pool;
-
-    // Update admin fees
-    pool_state.admin_fees_meme += swap_amount.admin_fee_in;
-    pool_state.admin_fees_quote += swap_amount.admin_fee_out;
-
-    // Update pool reserves
-    pool_state.meme_reserve.tokens += swap_amount.amount_in;
-    pool_state.quote_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
-
     // Create signer seeds for pool PDA
+    let pool_key = accs.pool.key();
     let seeds = &[
         BoundPool::SIGNER_PDA_PREFIX,
-        &accs.pool.key().to_bytes()[..],
-        &[ctx.bumps.pool_signer],
+        &pool_key.to_bytes()[..],
+        &[accs.pool.signer_bump],
     ];
 
     let signer_seeds = &[&seeds[..]];
 
-    // Transfer SOL to user
+    // Burn the sell tax before reserve accounting, so the burned tokens
+    // never reach the meme reserve nor the admin fees
+    if burn_amount > 0 {
+        token::burn(
+            accs.burn_meme_from_pool().with_signer(signer_seeds),
+            burn_amount,
+        )?;
+
+        emit!(TokensBurned {
+            pool: pool_key,
+            amount: burn_amount,
+        });
+    }
+
+    // Route the creator and referral shares of the quote fee to their vaults
+    // The creator and referral vaults hold the primary quote, their shares
+    // of a second quote sell accrue to the second quote admin fees instead
+    let mut quote_fee = swap_amount.quote_fee;
+    if second_quote {
+        quote_fee.fold_vault_shares();
+    }
+    accs.pay_fee_share(&accs.creator_fee_vault, quote_fee.creator, signer_seeds)?;
+    accs.pay_fee_share(&accs.referral_fee_vault, quote_fee.referral, signer_seeds)?;
+
+    // Pay the protocol's share to the fee treasury while routed there
+    let treasury_fee = accs
+        .protocol_config
+        .as_ref()
+        .map_or(0, |config| config.treasury_fee(quote_fee.protocol));
+    accs.pay_fee_share(&accs.fee_treasury, treasury_fee, signer_seeds)?;
+
+    let pool_state = &mut accs.pool;
+
+    // Price the slot started at, before this trade moves it
+    pool_state.open_slot(Clock::get()?.slot)?;
+    let pool_before = BoundPool::clone(pool_state);
+
+    // Update the reserves, admin fees, meme held by buyers, burned meme and
+    // volume
+    pool_state.apply_sell(
+        &swap_amount,
+        &quote_fee,
+        treasury_fee,
+        second_quote,
+        coin_in_amount,
+        burn_amount,
+    );
+
+    // Catch reserves drifting off the curve before they compound
+    pool_state.ensure_curve_invariant(&pool_before)?;
+
+    // Reject fat-finger and manipulative dumps within the slot
+    pool_state.ensure_circuit_breaker()?;
+
+    // Release the sold share of the buyer's position
+    if let Some(mut buyer_record) = BuyerRecord::load(&accs.buyer_record)? {
+        buyer_record.record_sell(coin_in_amount);
+        buyer_record.store(&accs.buyer_record)?;
+    }
+
+    // Transfer SOL to user, less the aggregator's fee
+    let quote_destination = accs.quote_destination()?;
+    accs.pay_platform_fee(platform_fee, signer_seeds)?;
     token::transfer(
-        accs.send_sol_to_user().with_signer(signer_seeds),
-        swap_amount.amount_out,
+        accs.send_sol_to_user(quote_destination.clone())
+            .with_signer(signer_seeds),
+        swap_amount.amount_out - platform_fee,
     )?;
 
+    // Unwrap the proceeds by closing the temporary WSOL account to the
+    // user's system account. WSOL the user holds in `user_sol` stays wrapped
+    if let Some(destination) = &accs.native_sol_destination {
+        token::close_account(
+            accs.close_temp_wsol(quote_destination, destination.to_account_info()),
+        )?;
+    }
+
+    // Feed the pool's recent trades, required once its history exists
+    match accs.trade_history.as_mut() {
+        Some(history) => history.record(TradeEntry {
+            amount_in: swap_amount.amount_in,
+            amount_out: swap_amount.amount_out,
+            is_buy: false,
+            timestamp: now,
+        }),
+        None => accs.pool.ensure_trade_history_passed(false)?,
+    }
+
     // Log swap amounts
     msg!(
         "swapped_in: {}\n swapped_out: {}",
@@ -119,6 +412,24 @@ pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u6
         swap_amount.amount_out
     );
 
+    emit!(SwapEvent {
+        pool: pool_key,
+        user: accs.owner.key(),
+        buy_meme: false,
+        amount_in: swap_amount.amount_in,
+        amount_out: swap_amount.amount_out,
+        admin_fee_in: swap_amount.admin_fee_in,
+        admin_fee_out: swap_amount.admin_fee_out,
+        fee_quote_percent,
+        fee_capped: swap_amount.fee_capped,
+        platform_fee,
+    });
+
+    // Expose the executed amounts and effective rate, per whole meme sold,
+    // to the caller
+    let quote = swap_amount.to_quote(accs.pool.config.decimals.meme)?;
+    set_return_data(&quote.try_to_vec()?);
+
     Ok(())
 }
 /// Account validation struct for swapping meme tokens for SOL
@@ -129,12 +440,30 @@ pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u6
 /// # Account Requirements
 /// * `pool` - The mutable bonding curve pool account
 /// * `meme_vault` - The pool's meme token vault account
-/// * `quote_vault` - The pool's SOL vault account
+/// * `quote_vault` - The pool's SOL vault account (or second quote vault)
 /// * `user_meme` - The user's meme token account
 /// * `user_sol` - The user's SOL token account to receive swapped tokens
 /// * `owner` - The signer/owner of the meme tokens
+/// * `meme_mint` - The meme token mint, for burning the sell tax
 /// * `pool_signer` - PDA with authority over pool accounts
 /// * `token_program` - The Solana Token Program
+/// * `protocol_config` - Optional protocol config holding the volume fee tiers
+/// * `native_sol_destination` - Optional owner system account; when provided
+///   the proceeds are paid into `temp_wsol` and unwrapped to it, `user_sol`
+///   left untouched
+/// * `buyer_record` - Position record PDA of the seller, always required and
+///   kept in sync once `init_buyer_record` created it
+/// * `creator_fee_vault` - The pool's creator fee vault, required when the
+///   creator gets a share of the quote fee
+/// * `referral_fee_vault` - The pool's referral fee vault, required when the
+///   referral gets a share of the quote fee
+/// * `platform_fee_account` - The routing aggregator's quote token account,
+///   required when it charges a platform fee
+/// * `native_mint` - The native mint, required with `temp_wsol`
+/// * `temp_wsol` - The owner's temporary WSOL account, required with
+///   `native_sol_destination`. Created by the sell (the owner paying rent)
+///   and closed again before it ends
+/// * `system_program` - The system program, required with `temp_wsol`
 #[derive(Accounts)]
 pub struct SwapCoinX<'info> {
     #[account(mut)]
@@ -142,13 +471,15 @@ pub struct SwapCoinX<'info> {
 
     #[account(
         mut,
-        constraint = pool.meme_reserve.vault == meme_vault.key()
+        seeds = [BoundPool::MEME_VAULT_PREFIX, pool.key().as_ref()],
+        bump = pool.meme_vault_bump,
     )]
     pub meme_vault: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        constraint = pool.quote_reserve.vault == quote_vault.key()
+        constraint = quote_vault.key() == pool.derived_quote_vault(pool.key())
+            || pool.is_second_quote_vault(quote_vault.key())
     )]
     pub quote_vault: Account<'info, TokenAccount>,
 
@@ -162,13 +493,103 @@ pub struct SwapCoinX<'info> {
     #[account(mut)]
     pub user_sol: Account<'info, TokenAccount>,
 
+    #[account(mut)]
     pub owner: Signer<'info>,
 
+    #[account(
+        mut,
+        constraint = meme_mint.key() == pool.meme_reserve.mint @ AmmError::InvalidTokenMints
+    )]
+    pub meme_mint: Account<'info, Mint>,
+
     /// CHECK: pda signer
-    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    #[account(
+        seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()],
+        bump = pool.signer_bump,
+    )]
     pub pool_signer: AccountInfo<'info>,
 
     pub token_program: Program<'info, Token>,
+
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        constraint = native_sol_destination.key() == owner.key()
+            @ err::acc("Native SOL must be sent to the owner"),
+    )]
+    pub native_sol_destination: Option<SystemAccount<'info>>,
+
+    /// CHECK: position record PDA of the seller, always passed and kept in
+    /// sync once created, see `BuyerRecord::load`
+    #[account(
+        mut,
+        seeds = [BuyerRecord::RECORD_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub buyer_record: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = pool.creator_fee_vault
+            @ err::acc("Creator fee vault doesn't belong to the pool"),
+    )]
+    pub creator_fee_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = pool.referral_fee_vault
+            @ err::acc("Referral fee vault doesn't belong to the pool"),
+    )]
+    pub referral_fee_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.mint == quote_vault.mint @ AmmError::InvalidTokenMints,
+    )]
+    pub platform_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// The fee treasury of the quote asset paid out, required while the
+    /// protocol routes its fee share there
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::FEE_TREASURY_PREFIX, quote_vault.mint.as_ref()],
+        bump,
+    )]
+    pub fee_treasury: Option<Account<'info, TokenAccount>>,
+
+    /// The pool's trade history recording the sell, required once created
+    #[account(
+        mut,
+        seeds = [TradeHistory::TRADE_HISTORY_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    pub trade_history: Option<Account<'info, TradeHistory>>,
+
+    #[account(address = native_mint::ID @ AmmError::InvalidTokenMints)]
+    pub native_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [TEMP_WSOL_PREFIX, owner.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = owner,
+    )]
+    pub temp_wsol: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Option<Program<'info, System>>,
+}
+
+/// Emitted when the sell tax of a swap is burned
+#[event]
+pub struct TokensBurned {
+    /// Pool the meme tokens were sold to
+    pub pool: Pubkey,
+    /// Meme tokens burned
+    pub amount: u64,
 }
 
 #[cfg(test)]
@@ -241,6 +662,8 @@ mod tests {
         let fees = Fees {
             fee_meme_percent: 0,           // 0% for meme tokens
             fee_quote_percent: 10_000_000, // 1% for quote tokens
+            burn_on_sell_bps: 0,
+            referral_fee_bps: 0,
         };
 
         let amount = 1000;
@@ -253,6 +676,113 @@ mod tests {
 
         println!("✅ Fee calculation test passed!");
     }
+
+    /// Integration test: buys then sells back through the full instruction flow
+    #[tokio::test]
+    async fn test_sell_on_fresh_pool_integration() {
+        use crate::test_harness::{assert_amm_error, LaunchpadTest, DEFAULT_TARGET};
+
+        // ARRANGE: Fresh pool, seeded by a buy below the 0.1 SOL sell floor
+        let mut test = LaunchpadTest::setup_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(1_000_000_000).await;
+        let buy = test.swap_y(&user, 10_000_000, 1).await.unwrap();
+        let pool = test.pool().await;
+        assert!(pool.quote_reserve.tokens < pool.min_quote_liquidity);
+
+        // ACT: Sell the meme tokens back
+        let result = test.swap_x(&user, buy.amount_out, 0).await;
+
+        // ASSERT: The sell is rejected until buys seed the quote reserve
+        assert_amm_error(result, AmmError::InsufficientQuoteLiquidity);
+
+        println!("✅ Fresh pool sell rejection test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_full_swap_x_integration() {
+        use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+
+        // ARRANGE: Create the pool and a user holding meme tokens
+        let mut test = LaunchpadTest::setup_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(10_000_000_000).await;
+        let buy = test.swap_y(&user, 1_000_000_000, 1).await.unwrap();
+
+        let pool_before = test.pool().await;
+        let user_sol_before = test.token_balance(user.user_sol).await;
+        let coin_in_amount = buy.amount_out / 2;
+
+        // ACT: Sell half of the meme tokens
+        let quote = test.swap_x(&user, coin_in_amount, 1).await.unwrap();
+
+        // ASSERT: User sent meme tokens and received SOL
+        assert_eq!(quote.amount_in + quote.admin_fee_in, coin_in_amount);
+        assert!(quote.amount_out > 0);
+        assert_eq!(
+            test.token_balance(user.user_meme).await,
+            buy.amount_out - coin_in_amount
+        );
+        assert_eq!(
+            test.token_balance(user.user_sol).await,
+            user_sol_before + quote.amount_out
+        );
+
+        // ASSERT: Selling back returns less than was paid
+        assert!(quote.amount_out < buy.amount_in);
+
+        // ASSERT: Pool reserves and admin fees updated
+        let pool = test.pool().await;
+        assert_eq!(
+            pool.meme_reserve.tokens,
+            pool_before.meme_reserve.tokens + quote.amount_in
+        );
+        assert_eq!(
+            pool.quote_reserve.tokens,
+            pool_before.quote_reserve.tokens - quote.amount_out - quote.admin_fee_out
+        );
+        assert_eq!(
+            pool.admin_fees_quote,
+            pool_before.admin_fees_quote + quote.admin_fee_out
+        );
+
+        println!("✅ Full swap X integration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_swap_x_native_sol_keeps_held_wsol_integration() {
+        use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+        use solana_sdk::signature::Signer;
+
+        // ARRANGE: SOL quoted pool, a seller holding WSOL left over from a
+        // 1 SOL buy and 5 native SOL
+        let mut test = LaunchpadTest::setup_native_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(2_000_000_000).await;
+        let owner = user.owner.pubkey();
+        let buy = test.swap_y(&user, 1_000_000_000, 1).await.unwrap();
+        test.fund(&owner, 5_000_000_000).await;
+        let held_wsol = test.token_balance(user.user_sol).await;
+        assert!(held_wsol > 0);
+
+        // ACT: Sell half of the meme for native SOL
+        let quote = test
+            .swap_x_native(&user, buy.amount_out / 2, 1)
+            .await
+            .unwrap();
+
+        // ASSERT: The proceeds arrived as native SOL, the temporary account's
+        // rent came back, the held WSOL stayed wrapped
+        assert_eq!(test.lamports(owner).await, 5_000_000_000 + quote.amount_out);
+        assert_eq!(test.token_balance(user.user_sol).await, held_wsol);
+        assert!(!test.account_exists(test.temp_wsol(&owner)).await);
+
+        // ACT & ASSERT: The next native sell creates the temporary account anew
+        test.swap_x_native(&user, buy.amount_out / 4, 1)
+            .await
+            .unwrap();
+        assert_eq!(test.token_balance(user.user_sol).await, held_wsol);
+        assert!(!test.account_exists(test.temp_wsol(&owner)).await);
+
+        println!("✅ Swap X native SOL integration test passed!");
+    }
 }
 
 /// Additional test utilities module
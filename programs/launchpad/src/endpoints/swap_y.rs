@@ -1,19 +1,215 @@
 // Import necessary constants from the crate
+use crate::consts::{MAX_CALLBACK_ACCOUNTS, TEMP_WSOL_PREFIX};
 // Import error handling
+use crate::err;
 use crate::err::AmmError;
 // Import math utilities
 // Import bonding curve pool model
 use crate::models::bound::BoundPool;
+use crate::models::buyer_record::BuyerRecord;
+use crate::models::fees::get_platform_fee;
+use crate::models::protocol_config::ProtocolConfig;
+use crate::models::referrer::Referrer;
+use crate::models::trade_history::{TradeEntry, TradeHistory};
+use crate::models::whitelist_entry::WhitelistEntry;
+use crate::models::{ensure_before_deadline, ensure_not_frozen, SwapAmount, SwapEvent, SwapQuote};
 // Import Anchor lang prelude
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, set_return_data};
+use anchor_lang::system_program;
 // Import SPL token program types
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{
+    self, spl_token::native_mint, Burn, CloseAccount, Mint, SyncNative, Token, TokenAccount,
+    Transfer,
+};
+
+// Buy of `coin_in_amount` at the pool's current reserves, buys in the
+// primary quote being partially filled up to the pool's raise cap
+pub fn buy_amounts(
+    pool: &BoundPool,
+    second_quote: bool,
+    protocol_config: Option<&ProtocolConfig>,
+    fee_exempt: bool,
+    coin_in_amount: u64,
+) -> Result<SwapAmount> {
+    if second_quote {
+        return pool.second_quote_swap_amounts(
+            coin_in_amount,
+            0,
+            true,
+            protocol_config,
+            fee_exempt,
+        );
+    }
+
+    let fee_quote_percent = if fee_exempt {
+        0
+    } else {
+        pool.fee_quote_percent(protocol_config)
+    };
+    let coin_in_amount = pool.capped_quote_in(coin_in_amount, fee_quote_percent)?;
+
+    Ok(pool.swap_amounts(coin_in_amount, 0, true, protocol_config, fee_exempt))
+}
+
+// Rejects a rebate to an unregistered referrer, the buyer or the creator
+pub fn ensure_valid_referrer(
+    pool: &BoundPool,
+    owner: Pubkey,
+    referrer_quote: Option<&TokenAccount>,
+    referrer: Option<&Referrer>,
+) -> Result<()> {
+    let Some(referrer_quote) = referrer_quote else {
+        return Ok(());
+    };
+
+    let registered = referrer.is_some_and(|referrer| referrer.wallet == referrer_quote.owner);
+    if !registered {
+        return Err(error!(err::acc("Referrer is not registered")));
+    }
+
+    pool.ensure_valid_referrer(referrer_quote.owner, owner)
+}
+
+// Validates a buy of `coin_in_amount` and prices it at current reserves,
+// running every check made before tokens move. Shared by `execute` and
+// `dry_run_swap_y`. Returns the buy's amounts and the platform fee charged
+// on top of it.
+#[allow(clippy::too_many_arguments)]
+pub fn price_buy(
+    pool: &BoundPool,
+    second_quote: bool,
+    protocol_config: Option<&ProtocolConfig>,
+    fee_exempt: bool,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+    expected_out: u64,
+    tolerance_bps: u16,
+    platform_fee_bps: u16,
+    reference_price: u128,
+    max_deviation_bps: u16,
+) -> Result<(SwapAmount, u64)> {
+    // Check that input amount is not zero
+    if coin_in_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    // Check that pool is not locked and takes buys
+    pool.ensure_version()?;
+    pool.ensure_unlocked()?;
+    pool.ensure_buys_enabled()?;
+
+    // Calculate swap amounts achievable at current reserves
+    let swap_amount = buy_amounts(
+        pool,
+        second_quote,
+        protocol_config,
+        fee_exempt,
+        coin_in_amount,
+    )?;
+
+    // On slippage, return the achievable meme output (a borsh `u64`) before
+    // reverting, so the client can resubmit with a correct minimum. This is
+    // the only error path setting return data.
+    if swap_amount.amount_out < coin_x_min_value {
+        set_return_data(&swap_amount.amount_out.try_to_vec()?);
+        return Err(error!(AmmError::SlippageExceeded));
+    }
+
+    // Dust buys round to no meme, don't charge a fee for nothing
+    swap_amount.ensure_nonzero_output()?;
+
+    // Charge only the quote the meme output needs, the surplus of an input
+    // overpaid through client rounding stays with the buyer
+    let swap_amount =
+        pool.without_overpayment(swap_amount, second_quote, protocol_config, fee_exempt);
+
+    // Reserves moved too much since the caller's preview, either way
+    swap_amount.ensure_near_preview(expected_out, tolerance_bps)?;
+
+    // The curve's price strays too far from the caller's own price feed
+    swap_amount.ensure_near_reference_price(reference_price, max_deviation_bps)?;
+
+    // Aggregator fee on what the buy costs, charged on top of it
+    let platform_fee = get_platform_fee(
+        swap_amount.amount_in + swap_amount.admin_fee_in,
+        platform_fee_bps,
+    )?;
+
+    Ok((swap_amount, platform_fee))
+}
 
 impl<'info> SwapCoinY<'info> {
-    // Helper function to create CPI context for transferring WSOL from user to pool quote vault
-    fn send_user_tokens(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+    // Whether the owner trades without the quote fee
+    fn fee_exempt(&self) -> bool {
+        self.protocol_config
+            .as_ref()
+            .is_some_and(|config| config.is_fee_exempt(&self.owner.key()))
+    }
+
+    // Buy of `coin_in_amount` at current reserves, priced like `execute`
+    // does
+    pub fn buy_amounts(&self, coin_in_amount: u64) -> Result<SwapAmount> {
+        buy_amounts(
+            &self.pool,
+            self.pool.is_second_quote_vault(self.quote_vault.key()),
+            self.protocol_config.as_deref(),
+            self.fee_exempt(),
+            coin_in_amount,
+        )
+    }
+
+    // Rejects a rebate to an unregistered referrer, the buyer or the creator
+    fn ensure_valid_referrer(&self) -> Result<()> {
+        ensure_valid_referrer(
+            &self.pool,
+            self.owner.key(),
+            self.referrer_quote.as_deref(),
+            self.referrer.as_deref(),
+        )
+    }
+
+    // Key of the pool being bought from
+    pub fn pool_key(&self) -> Pubkey {
+        self.pool.key()
+    }
+
+    // Key of the buyer
+    pub fn owner_key(&self) -> Pubkey {
+        self.owner.key()
+    }
+
+    // Account of the buyer, e.g. to receive rent back
+    pub fn owner_info(&self) -> AccountInfo<'info> {
+        self.owner.to_account_info()
+    }
+
+    // Account paying the quote: the temporary WSOL account when paying with
+    // native SOL, `user_sol` otherwise
+    fn quote_source(&self) -> Result<AccountInfo<'info>> {
+        match (&self.native_sol_source, &self.temp_wsol) {
+            (None, None) => Ok(self.user_sol.to_account_info()),
+            (Some(_), Some(temp_wsol)) => Ok(temp_wsol.to_account_info()),
+            (Some(_), None) => Err(error!(err::acc(
+                "Temporary WSOL account is required to pay with native SOL"
+            ))),
+            (None, Some(_)) => Err(error!(err::acc(
+                "Temporary WSOL account is only used to pay with native SOL"
+            ))),
+        }
+    }
+
+    // Helper function to create CPI context for transferring the quote from
+    // `from` to the pool quote vault
+    fn send_user_tokens(
+        &self,
+        from: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
         let cpi_accounts = Transfer {
-            from: self.user_sol.to_account_info(),
+            from,
             to: self.quote_vault.to_account_info(),
             authority: self.owner.to_account_info(),
         };
@@ -22,17 +218,163 @@ impl<'info> SwapCoinY<'info> {
         CpiContext::new(cpi_program, cpi_accounts)
     }
 
+    // Account receiving the bought meme, the recipient's if one was passed
+    fn meme_destination(&self) -> &Account<'info, TokenAccount> {
+        self.recipient.as_deref().unwrap_or(&self.user_meme)
+    }
+
     // Helper function to create CPI context for transferring meme tokens to user wallet
     fn send_meme_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
         let cpi_accounts = Transfer {
             from: self.meme_vault.to_account_info(),
-            to: self.user_meme.to_account_info(),
+            to: self.meme_destination().to_account_info(),
             authority: self.pool_signer_pda.to_account_info(),
         };
 
         let cpi_program = self.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    // Pays `amount` of the quote fee out of the quote vault to one of its
+    // recipients, which must be provided when there is anything to pay
+    fn pay_fee_share(
+        &self,
+        fee_vault: &Option<Account<'info, TokenAccount>>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let fee_vault = fee_vault
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Fee vault of the pool is missing")))?;
+        if fee_vault.mint != self.quote_vault.mint {
+            return Err(error!(AmmError::InvalidTokenMints));
+        }
+
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: fee_vault.to_account_info(),
+            authority: self.pool_signer_pda.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            amount,
+        )
+    }
+
+    // Pays the first buyer's bonus out of the bonus vault and burns what it
+    // doesn't use, so the bonus supply never outlives the first buy
+    fn pay_first_buyer_bonus(
+        &self,
+        amount_out: u64,
+        signer_seeds: &[&[&[u8]]],
+        emit_events: bool,
+    ) -> Result<u64> {
+        let bonus_vault = self
+            .bonus_vault
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Bonus vault of the pool is missing")))?;
+        let bonus = self.pool.first_buyer_bonus(amount_out, bonus_vault.amount);
+        let cpi_program = self.token_program.to_account_info();
+
+        if bonus != 0 {
+            let cpi_accounts = Transfer {
+                from: bonus_vault.to_account_info(),
+                to: self.meme_destination().to_account_info(),
+                authority: self.pool_signer_pda.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds),
+                bonus,
+            )?;
+        }
+
+        let unused = bonus_vault.amount - bonus;
+        if unused != 0 {
+            let cpi_accounts = Burn {
+                mint: self.meme_mint.to_account_info(),
+                from: bonus_vault.to_account_info(),
+                authority: self.pool_signer_pda.to_account_info(),
+            };
+            token::burn(
+                CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+                unused,
+            )?;
+        }
+
+        if emit_events {
+            emit!(FirstBuyerBonus {
+                pool: self.pool.key(),
+                buyer: self.owner.key(),
+                bonus,
+            });
+        }
+
+        Ok(bonus)
+    }
+
+    // Pays the routing aggregator's fee out of the user's quote account
+    // `from`, on top of what the swap costs
+    fn pay_platform_fee(&self, from: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let platform_fee_account = self
+            .platform_fee_account
+            .as_ref()
+            .ok_or_else(|| error!(err::acc("Platform fee account is required")))?;
+
+        let cpi_accounts = Transfer {
+            from,
+            to: platform_fee_account.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)
+    }
+
+    // Wraps `amount` native SOL of the owner into the WSOL account `to`
+    fn wrap_native_sol(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.owner.to_account_info(),
+                    to: to.clone(),
+                },
+            ),
+            amount,
+        )?;
+
+        token::sync_native(CpiContext::new(
+            self.token_program.to_account_info(),
+            SyncNative { account: to },
+        ))
+    }
+
+    // Helper function to create CPI context for closing the temporary WSOL
+    // account `account`, returning its rent to the native SOL source
+    fn close_temp_wsol(
+        &self,
+        account: AccountInfo<'info>,
+        destination: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account,
+            destination,
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
 }
 
 // Handler function for swapping SOL for meme tokens
@@ -41,69 +383,393 @@ impl<'info> SwapCoinY<'info> {
 // * `ctx` - The context containing all required accounts
 // * `coin_in_amount` - Amount of SOL to swap
 // * `coin_x_min_value` - Minimum amount of meme tokens to receive
-pub fn handle(ctx: Context<SwapCoinY>, coin_in_amount: u64, coin_x_min_value: u64) -> Result<()> {
-    // Get accounts from context
-    let accs = ctx.accounts;
+// * `expected_out` - Meme output of a previous `get_swap_y_amt` preview
+// * `tolerance_bps` - Largest drift from `expected_out` either way, zero to
+//   skip the check
+// * `platform_fee_bps` - Aggregator fee paid to `platform_fee_account` on
+//   top of the buy, at most `MAX_PLATFORM_FEE_BPS`
+// * `reference_price` - Client's price of the meme, in quote paid in per
+//   meme (raw units) scaled by `SPOT_PRICE_PRECISION`, zero to skip the check
+// * `max_deviation_bps` - Largest deviation of the execution price from
+//   `reference_price` either way, zero to skip the check
+// * `deadline` - Unix timestamp after which the buy is rejected, zero for none
+//
+// With `callback_program`, the executed buy is then passed to it, see
+// `SwapCallback`, with the remaining accounts
+#[allow(clippy::too_many_arguments)]
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapCoinY<'info>>,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+    expected_out: u64,
+    tolerance_bps: u16,
+    platform_fee_bps: u16,
+    reference_price: u128,
+    max_deviation_bps: u16,
+    deadline: i64,
+) -> Result<()> {
+    ensure_before_deadline(deadline, Clock::get()?.unix_timestamp)?;
+
+    let quote = execute(
+        ctx.accounts,
+        coin_in_amount,
+        coin_x_min_value,
+        expected_out,
+        tolerance_bps,
+        platform_fee_bps,
+        reference_price,
+        max_deviation_bps,
+        true,
+    )?;
 
-    // Check that input amount is not zero
-    if coin_in_amount == 0 {
+    match &ctx.accounts.callback_program {
+        Some(program) => invoke_callback(ctx.accounts, program, ctx.remaining_accounts, &quote),
+        None => Ok(()),
+    }
+}
+
+// Passes the executed buy to the caller's program, forwarding the remaining
+// accounts as they were passed. Its failure fails the buy. The callback may
+// set return data of its own, the buy's `SwapQuote` is restored after it.
+// The accounts the buy changed are written back first, so the callback reads
+// the settled pool rather than its state before the buy.
+fn invoke_callback<'info>(
+    accs: &SwapCoinY<'info>,
+    program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    quote: &SwapQuote,
+) -> Result<()> {
+    if accounts.len() > MAX_CALLBACK_ACCOUNTS {
+        return Err(error!(err::acc(format!(
+            "At most {} accounts are forwarded to the callback",
+            MAX_CALLBACK_ACCOUNTS
+        ))));
+    }
+
+    let callback = SwapCallback {
+        pool: accs.pool.key(),
+        user: accs.owner.key(),
+        buy_meme: true,
+        amount_in: quote.amount_in + quote.admin_fee_in,
+        amount_out: quote.amount_out,
+    };
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: callback.instruction_data()?,
+    };
+
+    // Anchor only serializes accounts when the instruction returns
+    accs.pool.exit(&crate::ID)?;
+    accs.whitelist_entry.exit(&crate::ID)?;
+    accs.trade_history.exit(&crate::ID)?;
+
+    let mut infos = accounts.to_vec();
+    infos.push(program.clone());
+    invoke(&ix, &infos)?;
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
+
+// Executes a buy on already validated accounts, shared by swap_y, swap_y_lite
+// and the reveal step of a commit-reveal buy. Without `emit_events`, the buy
+// neither logs nor emits events. Returns the quote set as return data.
+#[allow(clippy::too_many_arguments)]
+pub fn execute<'info>(
+    accs: &mut SwapCoinY<'info>,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+    expected_out: u64,
+    tolerance_bps: u16,
+    platform_fee_bps: u16,
+    reference_price: u128,
+    max_deviation_bps: u16,
+    emit_events: bool,
+) -> Result<SwapQuote> {
+    // Buying with the second quote asset is priced against the same curve
+    let second_quote = accs.pool.is_second_quote_vault(accs.quote_vault.key());
+
+    // Quote fee of the volume tier the pool reached, none for exempt traders
+    let protocol_config = accs.protocol_config.as_deref();
+    let fee_exempt = accs.fee_exempt();
+    let fee_quote_percent = if fee_exempt {
+        0
+    } else {
+        accs.pool.fee_quote_percent(protocol_config)
+    };
+
+    let (swap_amount, platform_fee) = price_buy(
+        &accs.pool,
+        second_quote,
+        protocol_config,
+        fee_exempt,
+        coin_in_amount,
+        coin_x_min_value,
+        expected_out,
+        tolerance_bps,
+        platform_fee_bps,
+        reference_price,
+        max_deviation_bps,
+    )?;
+
+    // A rebate can only go to a registered referrer other than the buyer and
+    // the creator
+    accs.ensure_valid_referrer()?;
+
+    settle(
+        accs,
+        swap_amount,
+        second_quote,
+        fee_quote_percent,
+        platform_fee,
+        emit_events,
+    )
+}
+
+// Executes a buy of exactly `coin_out_amount` meme tokens on already
+// validated accounts, paying at most `coin_in_max`
+pub fn execute_exact_out<'info>(
+    accs: &mut SwapCoinY<'info>,
+    coin_out_amount: u64,
+    coin_in_max: u64,
+) -> Result<()> {
+    // Check that output amount is not zero
+    if coin_out_amount == 0 {
         return Err(error!(AmmError::NoZeroTokens));
     }
 
-    // Check that pool is not locked
-    if accs.pool.locked {
-        return Err(error!(AmmError::PoolIsLocked));
+    // Check that pool is not locked and takes buys
+    accs.pool.ensure_version()?;
+    accs.pool.ensure_unlocked()?;
+    accs.pool.ensure_buys_enabled()?;
+
+    // A rebate can only go to a registered referrer other than the buyer and
+    // the creator
+    accs.ensure_valid_referrer()?;
+
+    // Buying with the second quote asset is priced against the same curve
+    let second_quote = accs.pool.is_second_quote_vault(accs.quote_vault.key());
+
+    // Quote fee of the volume tier the pool reached, none for exempt traders
+    let protocol_config = accs.protocol_config.as_deref();
+    let fee_exempt = accs.fee_exempt();
+    let fee_quote_percent = if fee_exempt {
+        0
+    } else {
+        accs.pool.fee_quote_percent(protocol_config)
+    };
+
+    // Invert the curve for the input buying exactly the requested output
+    let swap_amount = accs.pool.exact_out_swap_amounts(
+        coin_out_amount,
+        coin_in_max,
+        second_quote,
+        protocol_config,
+        fee_exempt,
+    )?;
+
+    // An exact output can't be partially filled
+    if !second_quote {
+        accs.pool.ensure_raise_cap(swap_amount.amount_in)?;
     }
 
-    // Calculate swap amounts
-    let swap_amount = accs
-        .pool
-        .swap_amounts(coin_in_amount, coin_x_min_value, true);
+    settle(accs, swap_amount, second_quote, fee_quote_percent, 0, true).map(|_| ())
+}
+
+// Moves the tokens of a priced buy and updates the pool and buyer record
+fn settle<'info>(
+    accs: &mut SwapCoinY<'info>,
+    swap_amount: SwapAmount,
+    second_quote: bool,
+    fee_quote_percent: u64,
+    platform_fee: u64,
+    emit_events: bool,
+) -> Result<SwapQuote> {
+    // Fail clearly rather than in the transfers below. A temporary WSOL
+    // account was just created, the native mint can't freeze it
+    let quote_source = accs.quote_source()?;
+    if accs.native_sol_source.is_none() {
+        ensure_not_frozen(&accs.user_sol, "Quote")?;
+    }
+    ensure_not_frozen(accs.meme_destination(), "Meme")?;
+
+    // Paying with native SOL, wrap exactly what the buy costs into the
+    // temporary WSOL account first
+    if accs.native_sol_source.is_some() {
+        accs.wrap_native_sol(
+            quote_source.clone(),
+            swap_amount.amount_in + swap_amount.admin_fee_in + platform_fee,
+        )?;
+    }
 
     // Transfer SOL from user to pool
     token::transfer(
-        accs.send_user_tokens(),
+        accs.send_user_tokens(quote_source.clone()),
         swap_amount.amount_in + swap_amount.admin_fee_in,
     )?;
 
+    // Pay the aggregator routing the buy, if any
+    accs.pay_platform_fee(quote_source.clone(), platform_fee)?;
+
+    // Close the emptied temporary WSOL account, returning its rent. WSOL the
+    // user holds in `user_sol` is never unwrapped
+    if let Some(source) = &accs.native_sol_source {
+        token::close_account(accs.close_temp_wsol(quote_source, source.to_account_info()))?;
+    }
+
     // Create pool signer PDA seeds for meme token transfer
     let pool_signer_seeds = &[
         BoundPool::SIGNER_PDA_PREFIX,
         &accs.pool.key().to_bytes()[..],
-        &[ctx.bumps.pool_signer_pda],
+        &[accs.pool.signer_bump],
     ];
 
-    // Transfer meme tokens directly to user's wallet
+    // Transfer meme tokens directly to user's wallet, or the recipient's
     token::transfer(
         accs.send_meme_to_user()
             .with_signer(&[&pool_signer_seeds[..]]),
         swap_amount.amount_out,
     )?;
 
+    // The very first buy of a pool with a bonus gets it on top
+    let bonus = if accs.pool.first_buyer_bonus_bps != 0 && !accs.pool.first_buy_claimed {
+        accs.pay_first_buyer_bonus(
+            swap_amount.amount_out,
+            &[&pool_signer_seeds[..]],
+            emit_events,
+        )?
+    } else {
+        0
+    };
+
+    // Rebate part of the protocol's fee to the buy's referrer, if any
+    let mut quote_fee = swap_amount.quote_fee;
+    if accs.referrer_quote.is_some() {
+        quote_fee.carve_rebate(accs.pool.fees.referral_fee_bps);
+    }
+
+    // The creator and referral vaults hold the primary quote, their shares
+    // of a second quote buy accrue to the second quote admin fees instead
+    if second_quote {
+        quote_fee.fold_vault_shares();
+    }
+
+    // Route the creator and referral shares of the quote fee to their vaults
+    accs.pay_fee_share(
+        &accs.creator_fee_vault,
+        quote_fee.creator,
+        &[&pool_signer_seeds[..]],
+    )?;
+    accs.pay_fee_share(
+        &accs.referral_fee_vault,
+        quote_fee.referral,
+        &[&pool_signer_seeds[..]],
+    )?;
+    accs.pay_fee_share(
+        &accs.referrer_quote,
+        quote_fee.rebate,
+        &[&pool_signer_seeds[..]],
+    )?;
+
+    // Pay the protocol's share to the fee treasury while routed there
+    let treasury_fee = accs
+        .protocol_config
+        .as_ref()
+        .map_or(0, |config| config.treasury_fee(quote_fee.protocol));
+    accs.pay_fee_share(&accs.fee_treasury, treasury_fee, &[&pool_signer_seeds[..]])?;
+
     // Get mutable reference to pool
     let pool = &mut accs.pool;
 
-    // Update pool admin fees
-    pool.admin_fees_quote += swap_amount.admin_fee_in;
-    pool.admin_fees_meme += swap_amount.admin_fee_out;
+    // Price the slot started at, before this trade moves it
+    pool.open_slot(Clock::get()?.slot)?;
+    let pool_before = BoundPool::clone(pool);
+
+    // Update the reserves, admin fees, meme held by buyers and volume
+    pool.apply_buy(&swap_amount, &quote_fee, treasury_fee, second_quote, bonus);
 
-    // Update pool reserves
-    pool.quote_reserve.tokens += swap_amount.amount_in;
-    pool.meme_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
+    // Track the buyer's position, cost in primary quote units
+    let quote_paid = pool.quote_units(
+        swap_amount.amount_in + swap_amount.admin_fee_in,
+        second_quote,
+    );
+    if let Some(mut buyer_record) = BuyerRecord::load(&accs.buyer_record)? {
+        if buyer_record.is_new() {
+            // First tracked buy of this wallet, counted once towards the
+            // pool's buyers
+            pool.unique_buyers = pool.unique_buyers.saturating_add(1);
+        }
+        buyer_record.pool = pool.key();
+        buyer_record.record_buy(swap_amount.amount_out + bonus, quote_paid);
+        buyer_record.store(&accs.buyer_record)?;
+    }
+
+    // During the whitelist phase, only whitelisted wallets buy, up to their cap
+    if pool.in_whitelist_phase(Clock::get()?.unix_timestamp) {
+        accs.whitelist_entry
+            .as_mut()
+            .ok_or_else(|| error!(AmmError::NotWhitelisted))?
+            .record_buy(quote_paid)?;
+    }
+
+    // Catch reserves drifting off the curve before they compound
+    pool.ensure_curve_invariant(&pool_before)?;
+
+    // Reject fat-finger and manipulative moves within the slot
+    pool.ensure_circuit_breaker()?;
 
     // Lock pool if meme tokens depleted
     if pool.meme_reserve.tokens == 0 {
         pool.locked = true;
     };
 
-    // Log swap amounts
-    msg!(
-        "swapped_in: {}\n swapped_out: {}",
-        swap_amount.amount_in,
-        swap_amount.amount_out
-    );
+    // Feed the pool's recent trades, required once its history exists
+    match accs.trade_history.as_mut() {
+        Some(history) => history.record(TradeEntry {
+            amount_in: swap_amount.amount_in,
+            amount_out: swap_amount.amount_out,
+            is_buy: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        }),
+        None => accs.pool.ensure_trade_history_passed(false)?,
+    }
 
-    Ok(())
+    if emit_events {
+        // Log swap amounts
+        msg!(
+            "swapped_in: {}\n swapped_out: {}",
+            swap_amount.amount_in,
+            swap_amount.amount_out
+        );
+
+        emit!(SwapEvent {
+            pool: accs.pool.key(),
+            user: accs.owner.key(),
+            buy_meme: true,
+            amount_in: swap_amount.amount_in,
+            amount_out: swap_amount.amount_out,
+            admin_fee_in: swap_amount.admin_fee_in,
+            admin_fee_out: swap_amount.admin_fee_out,
+            fee_quote_percent,
+            fee_capped: swap_amount.fee_capped,
+            platform_fee,
+        });
+    }
+
+    // Expose the executed amounts and effective rate to the caller
+    let quote = swap_amount.to_quote(accs.pool.config.decimals.quote)?;
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(quote)
 }
 
 // Account validation struct for swapping SOL for meme tokens
@@ -117,14 +783,16 @@ pub struct SwapCoinY<'info> {
     // The pool's meme token vault that holds meme tokens
     #[account(
         mut,
-        constraint = pool.meme_reserve.vault == meme_vault.key()
+        seeds = [BoundPool::MEME_VAULT_PREFIX, pool.key().as_ref()],
+        bump = pool.meme_vault_bump,
     )]
     meme_vault: Account<'info, TokenAccount>,
 
-    // The pool's quote token vault that holds SOL
+    // The pool's quote token vault that holds SOL (or the second quote asset)
     #[account(
         mut,
-        constraint = pool.quote_reserve.vault == quote_vault.key()
+        constraint = quote_vault.key() == pool.derived_quote_vault(pool.key())
+            || pool.is_second_quote_vault(quote_vault.key())
     )]
     quote_vault: Account<'info, TokenAccount>,
 
@@ -132,24 +800,222 @@ pub struct SwapCoinY<'info> {
     #[account(mut)]
     user_sol: Account<'info, TokenAccount>,
 
-    // The user's meme token account that will receive tokens directly
+    // The user's associated meme token account receiving tokens directly,
+    // created on the buy (paid by the owner) if it doesn't exist yet
     #[account(
-        mut,
-        constraint = user_meme.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
-        constraint = user_meme.owner == owner.key()
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = meme_mint,
+        associated_token::authority = owner,
     )]
-    user_meme: Account<'info, TokenAccount>,
+    user_meme: Box<Account<'info, TokenAccount>>,
 
     // The owner/signer of the transaction
     #[account(mut)]
     owner: Signer<'info>,
 
     /// CHECK: PDA signer for the pool - seeds validation ensures this is the correct pool authority
-    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    #[account(
+        seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()],
+        bump = pool.signer_bump,
+    )]
     pool_signer_pda: AccountInfo<'info>,
 
     // The SPL token program
     token_program: Program<'info, Token>,
+
+    // The protocol configuration holding the volume fee tiers, if any
+    #[account(seeds = [ProtocolConfig::CONFIG_PREFIX], bump)]
+    protocol_config: Option<Account<'info, ProtocolConfig>>,
+
+    // The owner's system account, when paying with native SOL. It's wrapped
+    // into `temp_wsol` then, and `user_sol` left untouched
+    #[account(
+        mut,
+        constraint = native_sol_source.key() == owner.key()
+            @ err::acc("Native SOL must be paid by the owner"),
+    )]
+    native_sol_source: Option<SystemAccount<'info>>,
+
+    /// CHECK: The buyer's position record PDA, always passed and tracking
+    /// the buy once `init_buyer_record` created it, see `BuyerRecord::load`
+    #[account(
+        mut,
+        seeds = [BuyerRecord::RECORD_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    buyer_record: UncheckedAccount<'info>,
+
+    // The system program, to create the user's meme token account and wrap
+    // native SOL
+    system_program: Program<'info, System>,
+
+    // The associated token program, to create the user's meme token account
+    associated_token_program: Program<'info, AssociatedToken>,
+
+    // The pool's creator fee vault, required when it gets a share of the fee
+    #[account(
+        mut,
+        address = pool.creator_fee_vault
+            @ err::acc("Creator fee vault doesn't belong to the pool"),
+    )]
+    creator_fee_vault: Option<Account<'info, TokenAccount>>,
+
+    // The pool's referral fee vault, required when it gets a share of the fee
+    #[account(
+        mut,
+        address = pool.referral_fee_vault
+            @ err::acc("Referral fee vault doesn't belong to the pool"),
+    )]
+    referral_fee_vault: Option<Account<'info, TokenAccount>>,
+
+    // The referrer's token account of the quote asset paid in, receiving a
+    // rebate carved from the protocol's share of the fee
+    #[account(
+        mut,
+        constraint = referrer_quote.mint == quote_vault.mint @ AmmError::InvalidTokenMints,
+    )]
+    referrer_quote: Option<Account<'info, TokenAccount>>,
+
+    // The registration of the referrer owning `referrer_quote`, required
+    // with it
+    #[account(
+        seeds = [Referrer::REFERRER_PREFIX, referrer.wallet.as_ref()],
+        bump,
+    )]
+    referrer: Option<Account<'info, Referrer>>,
+
+    // The owner's whitelist entry, required during the pool's whitelist phase
+    #[account(
+        mut,
+        seeds = [WhitelistEntry::WHITELIST_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    whitelist_entry: Option<Account<'info, WhitelistEntry>>,
+
+    // The routing aggregator's token account of the quote asset paid in,
+    // required when it charges a platform fee
+    #[account(
+        mut,
+        constraint = platform_fee_account.mint == quote_vault.mint @ AmmError::InvalidTokenMints,
+    )]
+    platform_fee_account: Option<Account<'info, TokenAccount>>,
+
+    // The pool's first buyer bonus vault, required on the first buy of a
+    // pool with a bonus
+    #[account(
+        mut,
+        seeds = [BoundPool::BONUS_VAULT_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    bonus_vault: Option<Account<'info, TokenAccount>>,
+
+    // The meme mint of the user's meme token account, also burning what the
+    // first buyer bonus doesn't use
+    #[account(mut, address = pool.meme_reserve.mint @ AmmError::InvalidTokenMints)]
+    meme_mint: Box<Account<'info, Mint>>,
+
+    // The fee treasury of the quote asset paid in, required while the
+    // protocol routes its fee share there
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::FEE_TREASURY_PREFIX, quote_vault.mint.as_ref()],
+        bump,
+    )]
+    fee_treasury: Option<Account<'info, TokenAccount>>,
+
+    // The pool's trade history recording the buy, required once created
+    #[account(
+        mut,
+        seeds = [TradeHistory::TRADE_HISTORY_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    trade_history: Option<Account<'info, TradeHistory>>,
+
+    // A meme token account receiving the buy (and any first buyer bonus)
+    // instead of `user_meme`, e.g. to gift it. The owner still pays
+    #[account(
+        mut,
+        constraint = recipient.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+    )]
+    recipient: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: any program but this one, invoked with the remaining accounts
+    // The program called back with the executed buy, see `SwapCallback`
+    #[account(
+        executable,
+        constraint = callback_program.key() != crate::ID
+            @ err::acc("Callback program can't be the launchpad"),
+    )]
+    callback_program: Option<UncheckedAccount<'info>>,
+
+    // The native mint, required with `temp_wsol`
+    #[account(address = native_mint::ID @ AmmError::InvalidTokenMints)]
+    native_mint: Option<Box<Account<'info, Mint>>>,
+
+    // The owner's temporary WSOL account, required with `native_sol_source`.
+    // Created by the buy (the owner paying rent) and closed again before it
+    // ends
+    #[account(
+        init,
+        payer = owner,
+        seeds = [TEMP_WSOL_PREFIX, owner.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = owner,
+    )]
+    temp_wsol: Option<Box<Account<'info, TokenAccount>>>,
+}
+
+/// Instruction data of the post-swap callback of `swap_y`: the 8 byte
+/// [`SwapCallback::discriminator`] followed by this struct, borsh encoded.
+/// An Anchor program receives it as an instruction
+/// `on_swap(pool: Pubkey, user: Pubkey, buy_meme: bool, amount_in: u64, amount_out: u64)`,
+/// with the remaining accounts of the swap as its accounts, in order. The
+/// pool and the buy's other launchpad accounts already hold their state after
+/// the buy when it's called.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SwapCallback {
+    /// Pool traded against
+    pub pool: Pubkey,
+    /// Owner who signed the swap
+    pub user: Pubkey,
+    /// True for buys, the only swaps calling back for now
+    pub buy_meme: bool,
+    /// Quote paid, fees included
+    pub amount_in: u64,
+    /// Meme received, any first buyer bonus excluded
+    pub amount_out: u64,
+}
+
+impl SwapCallback {
+    /// Preimage of the discriminator, as Anchor derives it for `on_swap`
+    pub const DISCRIMINATOR_PREIMAGE: &'static [u8] = b"global:on_swap";
+
+    /// First 8 bytes of the sha256 of `DISCRIMINATOR_PREIMAGE`
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(Self::DISCRIMINATOR_PREIMAGE).to_bytes()[..8]);
+        discriminator
+    }
+
+    /// Instruction data of the callback
+    pub fn instruction_data(&self) -> Result<Vec<u8>> {
+        let mut data = Self::discriminator().to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Emitted when the first buyer of a pool receives its bonus
+#[event]
+pub struct FirstBuyerBonus {
+    /// Pool bought from
+    pub pool: Pubkey,
+    /// The first buyer
+    pub buyer: Pubkey,
+    /// Meme paid on top of the buy
+    pub bonus: u64,
 }
 
 ////////////////////// TEST ///////////////////////////////////
@@ -157,8 +1023,9 @@ pub struct SwapCoinY<'info> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::bound::{BoundPool, Config, Decimals};
-    use crate::models::fees::Fees;
+    use crate::consts::SPOT_PRICE_PRECISION;
+    use crate::models::bound::{BoundPool, Config, CurveType, Decimals, MigrationAmm};
+    use crate::models::fees::{FeeShares, FeeSplit, Fees};
     use crate::models::Reserve;
 
     /// Helper function to create a test pool with meme tokens available
@@ -177,16 +1044,28 @@ mod tests {
             admin_fees_meme: 0,
             admin_fees_quote: 0,
             fee_vault_quote: Pubkey::new_unique(),
+            fee_shares: FeeShares::default(),
+            creator_fee_vault: Pubkey::default(),
+            referral_fee_vault: Pubkey::default(),
             creator_addr: Pubkey::new_unique(),
             fees: Fees {
                 fee_meme_percent: 0,           // 0% for meme tokens
                 fee_quote_percent: 10_000_000, // 1% for quote tokens
+                burn_on_sell_bps: 0,
+                referral_fee_bps: 0,
             },
             config: Config {
                 alpha_abs: 1_000_000,
                 beta: 1_000_000_000,
                 price_factor_num: 1,
                 price_factor_denom: 10,
+                price_factor_num_end: 0,
+                fee_start_bps: 0,
+                fee_end_bps: 0,
+                max_fee_quote: 0,
+                max_fee_meme: 0,
+                virtual_quote_reserve: 0,
+                virtual_meme_reserve: 0,
                 gamma_s: 1_000_000_000_000,
                 gamma_m: 3_000_000_000_000,
                 omega_m: 3_000_000_000_000,
@@ -194,11 +1073,56 @@ mod tests {
                     alpha: 1_000_000,
                     beta: 1_000_000_000,
                     quote: 1_000_000_000,
+                    meme: 1_000_000,
                 },
             },
             locked: false,
             pool_migration: false,
             migration_pool_key: Pubkey::default(),
+            second_quote: None,
+            cumulative_quote_volume: 0,
+            cumulative_meme_volume: 0,
+            refunding: false,
+            meme_circulating: 0,
+            initial_supply: 500_000_000,
+            meme_burned: 0,
+            pending_creator: None,
+            mint_authority_revoked: false,
+            lp_mint: Pubkey::default(),
+            lp_locked: 0,
+            lp_unlock_ts: 0,
+            lp_lock_duration: 0,
+            min_quote_liquidity: 0,
+            index: 0,
+            signer_bump: 0,
+            migration_amm: None,
+            migration_target: MigrationAmm::Cpmm,
+            circuit_breaker_bps: 0,
+            last_slot: 0,
+            slot_start_price: 0,
+            max_quote_raise: 0,
+            post_migration_fee_bps: 0,
+            quote_migrated: 0,
+            referral_fees_paid: 0,
+            whitelist_until: 0,
+            version: BoundPool::VERSION,
+            meme_vault_bump: 0,
+            quote_vault_bump: 0,
+            unique_buyers: 0,
+            sell_lock_until: 0,
+            first_buyer_bonus_bps: 0,
+            first_buy_claimed: false,
+            migration_slot: 0,
+            curve_type: CurveType::Standard,
+            price_floor: 0,
+            airdropped: 0,
+            meme_reserve_at_migration: Reserve::default(),
+            quote_reserve_at_migration: Reserve::default(),
+            trade_flags: 0,
+            created_at: 0,
+            min_pool_age_secs: 0,
+            creator_share_minted: 0,
+            has_trade_history: false,
         }
     }
 
@@ -339,6 +1263,193 @@ mod tests {
         println!("✅ Pool reserve updates test passed!");
     }
 
+    #[test]
+    fn test_frozen_account_is_rejected() {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState};
+
+        // ARRANGE: A user token account frozen by its mint, and an open one
+        let token_account = |state| {
+            let mut data = vec![0; SplAccount::LEN];
+            SplAccount::pack(
+                SplAccount {
+                    mint: Pubkey::new_unique(),
+                    owner: Pubkey::new_unique(),
+                    amount: 1_000,
+                    state,
+                    ..Default::default()
+                },
+                &mut data,
+            )
+            .unwrap();
+            TokenAccount::try_deserialize_unchecked(&mut &data[..]).unwrap()
+        };
+        let frozen = token_account(AccountState::Frozen);
+        let open = token_account(AccountState::Initialized);
+
+        // ACT & ASSERT: The frozen account fails before any transfer
+        assert_eq!(
+            ensure_not_frozen(&frozen, "Quote").unwrap_err(),
+            error!(AmmError::AccountFrozen)
+        );
+        assert!(ensure_not_frozen(&open, "Quote").is_ok());
+
+        println!("✅ Frozen account test passed!");
+    }
+
+    #[test]
+    fn test_deadline() {
+        // ARRANGE: A swap signed to land by timestamp 1_000
+        let deadline = 1_000;
+
+        // ACT & ASSERT: Up to the deadline it executes
+        assert!(ensure_before_deadline(deadline, 999).is_ok());
+        assert!(ensure_before_deadline(deadline, 1_000).is_ok());
+
+        // ACT & ASSERT: Past it the swap is rejected
+        assert_eq!(
+            ensure_before_deadline(deadline, 1_001).unwrap_err(),
+            error!(AmmError::DeadlineExceeded)
+        );
+
+        // ACT & ASSERT: Zero means no deadline
+        assert!(ensure_before_deadline(0, i64::MAX).is_ok());
+
+        println!("✅ Deadline test passed!");
+    }
+
+    #[test]
+    fn test_preview_drift() {
+        // ARRANGE: Buy returning 1_000 meme, previewed at 1_000
+        let swap_amount = SwapAmount {
+            amount_in: 100,
+            amount_out: 1_000,
+            admin_fee_in: 1,
+            admin_fee_out: 0,
+            quote_fee: FeeSplit::protocol_only(1),
+            fee_capped: false,
+        };
+
+        // ACT & ASSERT: Within 1% either way passes
+        assert!(swap_amount.ensure_near_preview(1_000, 100).is_ok());
+        assert!(swap_amount.ensure_near_preview(991, 100).is_ok());
+        assert!(swap_amount.ensure_near_preview(1_010, 100).is_ok());
+
+        // ACT & ASSERT: Beyond 1% either way drifts
+        assert_eq!(
+            swap_amount.ensure_near_preview(980, 100).unwrap_err(),
+            error!(AmmError::PreviewDrift)
+        );
+        assert_eq!(
+            swap_amount.ensure_near_preview(1_020, 100).unwrap_err(),
+            error!(AmmError::PreviewDrift)
+        );
+
+        // ACT & ASSERT: Zero tolerance disables the check
+        assert!(swap_amount.ensure_near_preview(1, 0).is_ok());
+
+        println!("✅ Preview drift test passed!");
+    }
+
+    #[test]
+    fn test_reference_price_deviation() {
+        // ARRANGE: Buy paying 101 quote for 1_000 meme, 0.101 quote per meme
+        let swap_amount = SwapAmount {
+            amount_in: 100,
+            amount_out: 1_000,
+            admin_fee_in: 1,
+            admin_fee_out: 0,
+            quote_fee: FeeSplit::protocol_only(1),
+            fee_capped: false,
+        };
+        let price =
+            |quote_per_meme_milli: u128| quote_per_meme_milli * SPOT_PRICE_PRECISION / 1_000;
+
+        // ACT & ASSERT: Within 1% of the reference either way passes
+        assert!(swap_amount
+            .ensure_near_reference_price(price(101), 100)
+            .is_ok());
+        assert!(swap_amount
+            .ensure_near_reference_price(price(100), 100)
+            .is_ok());
+        assert!(swap_amount
+            .ensure_near_reference_price(price(102), 100)
+            .is_ok());
+
+        // ACT & ASSERT: Beyond 1% either way deviates
+        assert_eq!(
+            swap_amount
+                .ensure_near_reference_price(price(99), 100)
+                .unwrap_err(),
+            error!(AmmError::ReferencePriceDeviation)
+        );
+        assert_eq!(
+            swap_amount
+                .ensure_near_reference_price(price(103), 100)
+                .unwrap_err(),
+            error!(AmmError::ReferencePriceDeviation)
+        );
+
+        // ACT & ASSERT: Zero price or tolerance disables the check
+        assert!(swap_amount.ensure_near_reference_price(0, 100).is_ok());
+        assert!(swap_amount.ensure_near_reference_price(price(1), 0).is_ok());
+
+        // ACT & ASSERT: An absurd reference price deviates without overflowing
+        assert!(swap_amount
+            .ensure_near_reference_price(u128::MAX, 100)
+            .is_err());
+
+        println!("✅ Reference price deviation test passed!");
+    }
+
+    #[test]
+    fn test_min_out_from_slippage_bps() {
+        // ARRANGE: Buy returning 1_000 meme
+        let swap_amount = SwapAmount {
+            amount_in: 100,
+            amount_out: 1_000,
+            admin_fee_in: 1,
+            admin_fee_out: 0,
+            quote_fee: FeeSplit::protocol_only(1),
+            fee_capped: false,
+        };
+
+        // ACT & ASSERT: The minimum is the output less the slippage
+        assert_eq!(swap_amount.min_out(0).unwrap(), 1_000);
+        assert_eq!(swap_amount.min_out(50).unwrap(), 995);
+        assert_eq!(swap_amount.min_out(10_000).unwrap(), 0);
+
+        // ACT & ASSERT: More than 100% is rejected
+        assert!(swap_amount.min_out(10_001).is_err());
+
+        println!("✅ Min out from slippage bps test passed!");
+    }
+
+    #[test]
+    fn test_swap_callback_instruction_data() {
+        // ARRANGE: A buy to call back about
+        let callback = SwapCallback {
+            pool: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            buy_meme: true,
+            amount_in: 1_000_000_000,
+            amount_out: 42,
+        };
+
+        // ACT
+        let data = callback.instruction_data().unwrap();
+
+        // ASSERT: Anchor's discriminator of `on_swap`, then the fields in order
+        assert_eq!(&data[..8], &hash(b"global:on_swap").to_bytes()[..8]);
+        assert_eq!(data.len(), 8 + 32 + 32 + 1 + 8 + 8);
+        assert_eq!(&data[8..40], callback.pool.as_ref());
+        assert_eq!(&data[40..72], callback.user.as_ref());
+        assert_eq!(data[72], 1);
+        assert_eq!(SwapCallback::try_from_slice(&data[8..]).unwrap(), callback);
+
+        println!("✅ Swap callback instruction data test passed!");
+    }
+
     #[test]
     fn test_pda_derivation() {
         let pool_key = Pubkey::new_unique();
@@ -354,23 +1465,189 @@ mod tests {
         println!("✅ PDA derivation test passed! Pool bump: {}", pool_bump);
     }
 
-    /// Integration test template
-    #[test]
-    fn test_full_swap_y_integration() {
-        println!("Setting up SOL-to-meme swap integration test...");
-
-        // This would include:
-        // 1. Set up program test environment
-        // 2. Create all necessary accounts (pool, user accounts)
-        // 3. Initialize pool with meme tokens
-        // 4. Execute swap instruction
-        // 5. Verify:
-        //    - User SOL decreased
-        //    - User meme tokens increased
-        //    - Pool reserves updated correctly
-        //    - Admin fees updated
-
-        println!("✅ Integration test framework ready!");
+    /// Integration test: buys meme tokens through the full instruction flow
+    #[tokio::test]
+    async fn test_full_swap_y_integration() {
+        use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+
+        // ARRANGE: Create the pool and a user holding 10 SOL
+        let mut test = LaunchpadTest::setup_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(10_000_000_000).await;
+        let coin_in_amount = 1_000_000_000; // 1 SOL
+
+        // ARRANGE: Track the user's position, counting them as a buyer
+        test.init_buyer_record(&user).await.unwrap();
+
+        let pool_before = test.pool().await;
+        let meme_vault_before = test.token_balance(test.meme_vault).await;
+
+        // ASSERT: The pool stored the canonical signer bump at creation
+        let (pool_signer, signer_bump) = Pubkey::find_program_address(
+            &[BoundPool::SIGNER_PDA_PREFIX, test.pool.as_ref()],
+            &crate::ID,
+        );
+        assert_eq!(pool_signer, test.pool_signer);
+        assert_eq!(pool_before.signer_bump, signer_bump);
+
+        // ACT: Buy meme tokens
+        let quote = test.swap_y(&user, coin_in_amount, 1).await.unwrap();
+
+        // ASSERT: User paid SOL and received meme tokens
+        assert_eq!(quote.amount_in + quote.admin_fee_in, coin_in_amount);
+        assert!(quote.amount_out > 0);
+        assert_eq!(
+            test.token_balance(user.user_sol).await,
+            10_000_000_000 - coin_in_amount
+        );
+        assert_eq!(test.token_balance(user.user_meme).await, quote.amount_out);
+
+        // ASSERT: Pool reserves and admin fees updated
+        let pool = test.pool().await;
+        assert_eq!(pool.quote_reserve.tokens, quote.amount_in);
+        assert_eq!(pool.admin_fees_quote, quote.admin_fee_in);
+        assert_eq!(pool.unique_buyers, 1);
+        assert_eq!(
+            pool.meme_reserve.tokens,
+            pool_before.meme_reserve.tokens - quote.amount_out - quote.admin_fee_out
+        );
+        assert!(!pool.locked);
+        assert_eq!(pool.cumulative_quote_volume, coin_in_amount);
+        assert_eq!(
+            pool.cumulative_meme_volume,
+            quote.amount_out + quote.admin_fee_out
+        );
+
+        // ASSERT: Vaults hold the traded amounts
+        assert_eq!(test.token_balance(test.quote_vault).await, coin_in_amount);
+        assert_eq!(
+            test.token_balance(test.meme_vault).await,
+            meme_vault_before - quote.amount_out
+        );
+
+        println!("✅ Full swap Y integration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_swap_y_dry_run_integration() {
+        use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+
+        // ARRANGE: Create the pool and a user holding 10 SOL
+        let mut test = LaunchpadTest::setup_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(10_000_000_000).await;
+        let coin_in_amount = 1_000_000_000; // 1 SOL
+
+        let pool_before = test.pool().await;
+
+        // ACT: Dry-run the buy
+        let dry_quote = test.dry_run_swap_y(&user, coin_in_amount, 1).await.unwrap();
+
+        // ASSERT: Nothing moved, not even the meme token account got created
+        let pool = test.pool().await;
+        assert_eq!(pool.quote_reserve.tokens, pool_before.quote_reserve.tokens);
+        assert_eq!(pool.meme_reserve.tokens, pool_before.meme_reserve.tokens);
+        assert_eq!(test.token_balance(user.user_sol).await, 10_000_000_000);
+        assert!(!test.account_exists(user.user_meme).await);
+
+        // ACT & ASSERT: The real buy executes what the dry run returned
+        let quote = test.swap_y(&user, coin_in_amount, 1).await.unwrap();
+        assert_eq!(quote, dry_quote);
+
+        println!("✅ Swap Y dry run integration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_swap_y_to_recipient_integration() {
+        use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+
+        // ARRANGE: A buyer and a third party's meme token account
+        let mut test = LaunchpadTest::setup_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(10_000_000_000).await;
+        let pool = test.pool().await;
+        let recipient = test
+            .create_token_account(&pool.meme_reserve.mint, &Pubkey::new_unique())
+            .await;
+
+        // ACT: Buy on behalf of the third party
+        let quote = test
+            .swap_y_to(&user, 1_000_000_000, 1, Some(recipient))
+            .await
+            .unwrap();
+
+        // ASSERT: The owner paid, the recipient got the meme
+        assert_eq!(
+            test.token_balance(user.user_sol).await,
+            10_000_000_000 - quote.amount_in - quote.admin_fee_in
+        );
+        assert_eq!(test.token_balance(recipient).await, quote.amount_out);
+        assert_eq!(test.token_balance(user.user_meme).await, 0);
+
+        // ACT & ASSERT: A recipient of another mint is rejected
+        let quote_account = test
+            .create_token_account(&pool.quote_reserve.mint, &Pubkey::new_unique())
+            .await;
+        let result = test
+            .swap_y_to(&user, 1_000_000_000, 1, Some(quote_account))
+            .await;
+        crate::test_harness::assert_amm_error(result, AmmError::InvalidTokenMints);
+
+        println!("✅ Swap Y to recipient integration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_swap_y_native_sol_keeps_held_wsol_integration() {
+        use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+        use anchor_lang::solana_program::program_pack::Pack;
+        use solana_sdk::signature::Signer;
+
+        // ARRANGE: SOL quoted pool, a buyer holding 2 WSOL and 5 native SOL
+        let mut test = LaunchpadTest::setup_native_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(2_000_000_000).await;
+        let owner = user.owner.pubkey();
+        test.fund(&owner, 5_000_000_000).await;
+        let rent = test.ctx.banks_client.get_rent().await.unwrap();
+        let meme_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+
+        // ACT: Buy with 1 native SOL
+        let quote = test.swap_y_native(&user, 1_000_000_000, 1).await.unwrap();
+
+        // ASSERT: Native SOL paid the buy and the new meme account, the held
+        // WSOL stayed wrapped and the temporary account is gone
+        assert_eq!(
+            test.lamports(owner).await,
+            5_000_000_000 - quote.amount_in - quote.admin_fee_in - meme_account_rent
+        );
+        assert_eq!(test.token_balance(user.user_sol).await, 2_000_000_000);
+        assert_eq!(test.token_balance(user.user_meme).await, quote.amount_out);
+        assert!(!test.account_exists(test.temp_wsol(&owner)).await);
+
+        // ACT & ASSERT: The next native buy creates the temporary account anew
+        test.swap_y_native(&user, 500_000_000, 1).await.unwrap();
+        assert_eq!(test.token_balance(user.user_sol).await, 2_000_000_000);
+        assert!(!test.account_exists(test.temp_wsol(&owner)).await);
+
+        println!("✅ Swap Y native SOL integration test passed!");
+    }
+
+    #[tokio::test]
+    async fn test_swap_y_callback_sees_settled_pool_integration() {
+        use crate::test_harness::{LaunchpadTest, DEFAULT_TARGET};
+
+        // ARRANGE: A pool and a buyer
+        let mut test = LaunchpadTest::setup_pool(DEFAULT_TARGET).await;
+        let user = test.create_user(10_000_000_000).await;
+
+        // ACT: Buy with a callback that fails unless the pool it reads
+        // already counts the bought meme as circulating
+        let quote = test
+            .swap_y_with_callback(&user, 1_000_000_000, 1)
+            .await
+            .unwrap();
+
+        // ASSERT: The callback succeeded and the buy went through
+        assert!(quote.amount_out > 0);
+        assert_eq!(test.token_balance(user.user_meme).await, quote.amount_out);
+
+        println!("✅ Swap Y callback integration test passed!");
     }
 }
 
@@ -0,0 +1,37 @@
+use crate::endpoints::swap_y::{self, *};
+use anchor_lang::prelude::*;
+
+/// Buys meme tokens like `swap_y`, taking the slippage in basis points
+/// instead of an absolute minimum.
+///
+/// The minimum is `expected * (10_000 - slippage_bps) / 10_000`, the expected
+/// output being priced like `get_swap_y_amt` does. It's priced on the
+/// reserves the buy executes against, so it doesn't guard against them
+/// moving between signing and execution. Callers needing that pass the
+/// minimum of an earlier preview to `swap_y` instead.
+///
+/// # Arguments
+/// * `ctx` - The context containing all necessary accounts
+/// * `coin_in_amount` - Amount of SOL to swap
+/// * `slippage_bps` - Largest shortfall from the expected output, at most
+///   10_000
+pub fn handle(ctx: Context<SwapCoinY>, coin_in_amount: u64, slippage_bps: u16) -> Result<()> {
+    let coin_x_min_value = ctx
+        .accounts
+        .buy_amounts(coin_in_amount)?
+        .min_out(slippage_bps)?;
+
+    swap_y::execute(
+        ctx.accounts,
+        coin_in_amount,
+        coin_x_min_value,
+        0,
+        0,
+        0,
+        0,
+        0,
+        true,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::endpoints::swap_y::{self, *};
+use anchor_lang::prelude::*;
+
+/// Buys exactly `coin_out_amount` meme tokens, paying at most `coin_in_max`.
+///
+/// Uses the same accounts as `swap_y`. The required input is found by
+/// inverting the bonding curve, fees included.
+///
+/// # Arguments
+/// * `ctx` - The context containing all necessary accounts
+/// * `coin_out_amount` - Amount of meme tokens to receive
+/// * `coin_in_max` - Maximum amount of SOL to spend
+///
+/// # Errors
+/// * `AmmError::SlippageExceeded` - If more than `coin_in_max` is needed
+pub fn handle(ctx: Context<SwapCoinY>, coin_out_amount: u64, coin_in_max: u64) -> Result<()> {
+    swap_y::execute_exact_out(ctx.accounts, coin_out_amount, coin_in_max)
+}
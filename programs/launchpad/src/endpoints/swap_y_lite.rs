@@ -0,0 +1,34 @@
+use crate::endpoints::swap_y::{self, *};
+use anchor_lang::prelude::*;
+
+/// Buys meme tokens like `swap_y`, without logging or emitting events.
+///
+/// For latency sensitive integrators such as arbitrage bots, which read the
+/// outcome from the return data instead. Skipping the `SwapEvent` and the log
+/// line saves their borsh serialization, the string formatting of the log
+/// and the `sol_log_data` and `sol_log` syscalls, each charged 100 compute
+/// units plus its per-byte cost. Compare the `consumed` units of both
+/// instructions in the program logs for the exact saving of a build.
+///
+/// Uses the same accounts as `swap_y`. Indexers won't see these buys as
+/// events, only through the pool's state and the transfers.
+///
+/// # Arguments
+/// * `ctx` - The context containing all necessary accounts
+/// * `coin_in_amount` - Amount of SOL to swap
+/// * `coin_x_min_value` - Minimum amount of meme tokens to receive
+pub fn handle(ctx: Context<SwapCoinY>, coin_in_amount: u64, coin_x_min_value: u64) -> Result<()> {
+    swap_y::execute(
+        ctx.accounts,
+        coin_in_amount,
+        coin_x_min_value,
+        0,
+        0,
+        0,
+        0,
+        0,
+        false,
+    )?;
+
+    Ok(())
+}
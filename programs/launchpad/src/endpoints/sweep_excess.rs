@@ -0,0 +1,92 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> SweepExcess<'info> {
+    /// Creates a CPI context for transferring the excess out of the vault
+    fn send_excess(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.recovery_account.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Recovers tokens sent straight to one of a pool's vaults instead of through
+/// a swap.
+///
+/// Only the balance above what the pool accounts for in the vault is moved,
+/// see [`BoundPool::vault_surplus`], so reserves and admin fees are never
+/// touched.
+///
+/// # Errors
+/// * `AmmError::NoTokensToWithdraw` - If the vault holds no excess
+pub fn handle(ctx: Context<SweepExcess>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let excess = accs
+        .pool
+        .vault_surplus(accs.vault.key(), accs.vault.amount)?;
+
+    if excess == 0 {
+        return Err(error!(AmmError::NoTokensToWithdraw));
+    }
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[accs.pool.signer_bump],
+    ];
+
+    token::transfer(accs.send_excess().with_signer(&[&seeds[..]]), excess)?;
+
+    msg!("swept: {}", excess);
+
+    Ok(())
+}
+
+/// Represents the accounts required for sweeping a vault's excess tokens.
+#[derive(Accounts)]
+pub struct SweepExcess<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can sweep excess tokens"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The pool owning the vault
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(mut)]
+    /// The pool vault holding the excess, quote, meme or second quote
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = recovery_account.mint == vault.mint @ AmmError::InvalidTokenMints,
+    )]
+    /// The token account receiving the excess
+    pub recovery_account: Account<'info, TokenAccount>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump = pool.signer_bump)]
+    /// The pool signer owning the vaults
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,41 @@
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use crate::models::referrer::Referrer;
+use anchor_lang::prelude::*;
+
+/// Unregisters a referrer, buys can't rebate to it anymore. The registration
+/// rent goes back to the authority.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+pub fn handle(ctx: Context<UnregisterReferrer>) -> Result<()> {
+    msg!("referrer unregistered: {}", ctx.accounts.referrer.wallet);
+
+    Ok(())
+}
+
+/// Represents the accounts required for unregistering a referrer.
+#[derive(Accounts)]
+pub struct UnregisterReferrer<'info> {
+    /// The protocol fee authority, receiving the registration rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can unregister referrers"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [Referrer::REFERRER_PREFIX, referrer.wallet.as_ref()],
+        bump,
+    )]
+    /// The referrer's registration
+    pub referrer: Account<'info, Referrer>,
+}
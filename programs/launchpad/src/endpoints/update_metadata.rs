@@ -0,0 +1,99 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::mpl_token_metadata::MAX_URI_LENGTH;
+use anchor_spl::metadata::update_metadata_accounts_v2;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::metadata::UpdateMetadataAccountsV2;
+use anchor_spl::token::Mint;
+
+impl<'info> UpdateMetadata<'info> {
+    fn update_metadata_account_v2(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, UpdateMetadataAccountsV2<'info>> {
+        let cpi_accounts = UpdateMetadataAccountsV2 {
+            metadata: self.meme_mpl_metadata.to_account_info(),
+            update_authority: self.pool_signer.to_account_info(),
+        };
+        let cpi_program = self.metadata_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Replaces the URI of a meme token's metadata, e.g. to fix a broken image.
+///
+/// Name, symbol and creators are kept as they are. Updates stop once the pool
+/// migrated: the pool signer is the only update authority and this is the
+/// only instruction signing for it, so the metadata is frozen after
+/// graduation.
+pub fn handle(ctx: Context<UpdateMetadata>, uri: String) -> Result<()> {
+    if uri.len() > MAX_URI_LENGTH {
+        return Err(error!(err::arg(format!(
+            "URI must be at most {} bytes",
+            MAX_URI_LENGTH
+        ))));
+    }
+
+    let accs = ctx.accounts;
+    let metadata = &accs.meme_mpl_metadata;
+
+    let pool_key = accs.pool.key();
+    let seeds: &[&[u8]] = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        pool_key.as_ref(),
+        &[ctx.bumps.pool_signer],
+    ];
+
+    // Metaplex stores strings padded with null bytes
+    update_metadata_accounts_v2(
+        accs.update_metadata_account_v2().with_signer(&[seeds]),
+        None, // new_update_authority
+        Some(DataV2 {
+            name: metadata.name.trim_end_matches('\0').to_string(),
+            symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+            uri,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators: metadata.creators.clone(),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        }),
+        None, // primary_sale_happened
+        None, // is_mutable
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    pub sender: Signer<'info>,
+    #[account(
+        constraint = sender.key() == pool.creator_addr
+            @ err::acc("Only the pool creator can update the metadata"),
+        constraint = !pool.pool_migration @ AmmError::AlreadyMigrated,
+        constraint = pool.meme_reserve.mint == meme_mint.key() @ AmmError::InvalidTokenMints,
+    )]
+    pub pool: Account<'info, BoundPool>,
+    pub meme_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), meme_mint.key().as_ref()],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = meme_mpl_metadata.is_mutable
+            @ err::acc("Metadata is immutable"),
+        constraint = meme_mpl_metadata.update_authority == pool_signer.key()
+            @ err::acc("Metadata update authority must be the pool signer"),
+    )]
+    pub meme_mpl_metadata: Account<'info, MetadataAccount>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+    /// Program to update NFT metadata
+    pub metadata_program: Program<'info, Metadata>,
+}
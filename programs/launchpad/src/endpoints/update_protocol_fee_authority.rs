@@ -0,0 +1,34 @@
+use crate::err;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+/// Hands protocol revenue over to a new fee authority.
+///
+/// The new authority also manages the protocol config. It may be a multisig
+/// PDA, approving admin instructions and signing for them via CPI.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `new_authority` - The authority receiving protocol fees from now on
+pub fn handle(ctx: Context<UpdateProtocolFeeAuthority>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.protocol_fee_authority = new_authority;
+
+    Ok(())
+}
+
+/// Represents the accounts required for updating the protocol fee authority.
+#[derive(Accounts)]
+pub struct UpdateProtocolFeeAuthority<'info> {
+    /// The current protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can update it"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
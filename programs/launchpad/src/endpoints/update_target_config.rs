@@ -0,0 +1,54 @@
+use crate::err::AmmError;
+use crate::models::target_config::TargetConfig;
+use anchor_lang::prelude::*;
+
+/// Corrects the quote target of a target config set up with a wrong amount.
+///
+/// Pools are priced from the config at creation, so it only changes until
+/// the first pool is created against it.
+///
+/// # Parameters
+/// * `ctx` - The context containing all necessary accounts
+/// * `new_amount` - The new target amount of quote tokens, in their smallest
+///   unit
+///
+/// # Errors
+/// * `AmmError::Unauthorized` - If the signer didn't create the config
+/// * `AmmError::TargetConfigInUse` - If a pool was created against it
+pub fn handle(ctx: Context<UpdateTargetConfig>, new_amount: u64) -> Result<()> {
+    let target_config = &mut ctx.accounts.target_config;
+
+    target_config.ensure_unused()?;
+
+    // A lower target mustn't push the configured sell floor past its cap
+    TargetConfig::ensure_min_quote_liquidity(target_config.min_quote_liquidity, new_amount)?;
+
+    msg!(
+        "token_target_amount: {} -> {}",
+        target_config.token_target_amount,
+        new_amount
+    );
+    target_config.token_target_amount = new_amount;
+
+    Ok(())
+}
+
+/// Represents the accounts required for updating a target configuration.
+#[derive(Accounts)]
+pub struct UpdateTargetConfig<'info> {
+    /// The creator of the target config
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            TargetConfig::CONFIG_PREFIX,
+            target_config.token_mint.as_ref(),
+            target_config.pair_token_mint.as_ref(),
+        ],
+        bump,
+        constraint = target_config.creator == creator.key() @ AmmError::Unauthorized,
+    )]
+    /// The target configuration being updated
+    pub target_config: Account<'info, TargetConfig>,
+}
@@ -0,0 +1,37 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::TokenAccount;
+
+/// Compares what the pool tracks in its meme and primary quote vaults with
+/// their actual balances, and returns both with a consistency flag (a borsh
+/// `PoolIntegrity`) in the return data, see [`BoundPool::integrity`]. Meant
+/// for monitoring, read-only.
+pub fn handle(ctx: Context<VerifyPoolIntegrity>) -> Result<()> {
+    let accs = ctx.accounts;
+    let report = accs
+        .pool
+        .integrity(accs.meme_vault.amount, accs.quote_vault.amount);
+
+    msg!(
+        "consistent: {}\n meme: {} / {}\n quote: {} / {}",
+        report.consistent,
+        report.meme_actual,
+        report.meme_tracked,
+        report.quote_actual,
+        report.quote_tracked
+    );
+
+    set_return_data(&report.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VerifyPoolIntegrity<'info> {
+    pub pool: Account<'info, BoundPool>,
+    #[account(address = pool.meme_reserve.vault)]
+    pub meme_vault: Account<'info, TokenAccount>,
+    #[account(address = pool.quote_reserve.vault)]
+    pub quote_vault: Account<'info, TokenAccount>,
+}
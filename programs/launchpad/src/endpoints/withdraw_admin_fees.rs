@@ -0,0 +1,179 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> WithdrawAdminFees<'info> {
+    /// Creates a CPI context for transferring accumulated fees out of a pool vault
+    fn send_fees(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Withdraws a pool's accumulated admin fees to the protocol fee authority.
+///
+/// Admin fees are protocol revenue, independent from the pool creator, so they
+/// can only be paid out to token accounts owned by
+/// `ProtocolConfig::protocol_fee_authority`. Second quote fees are withdrawn
+/// too when both optional second quote accounts are provided.
+///
+/// # Errors
+/// * `AmmError::NoTokensToWithdraw` - If the pool has no accumulated fees
+pub fn handle(ctx: Context<WithdrawAdminFees>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let pool_key = accs.pool.key();
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &pool_key.to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let fees_quote = accs.pool.admin_fees_quote;
+    let fees_meme = accs.pool.admin_fees_meme;
+    let fees_second_quote = match (&accs.second_quote_vault, &accs.fee_second_quote_account) {
+        (Some(_), Some(_)) => accs.pool.second_quote.map_or(0, |second| second.admin_fees),
+        _ => 0,
+    };
+
+    if fees_quote == 0 && fees_meme == 0 && fees_second_quote == 0 {
+        return Err(error!(AmmError::NoTokensToWithdraw));
+    }
+
+    if fees_quote > 0 {
+        token::transfer(
+            accs.send_fees(&accs.quote_vault, &accs.fee_quote_account)
+                .with_signer(signer_seeds),
+            fees_quote,
+        )?;
+    }
+
+    if fees_meme > 0 {
+        token::transfer(
+            accs.send_fees(&accs.meme_vault, &accs.fee_meme_account)
+                .with_signer(signer_seeds),
+            fees_meme,
+        )?;
+    }
+
+    if let (Some(vault), Some(destination)) =
+        (&accs.second_quote_vault, &accs.fee_second_quote_account)
+    {
+        if fees_second_quote > 0 {
+            token::transfer(
+                accs.send_fees(vault, destination).with_signer(signer_seeds),
+                fees_second_quote,
+            )?;
+        }
+    }
+
+    let pool = &mut accs.pool;
+    pool.admin_fees_quote = 0;
+    pool.admin_fees_meme = 0;
+    if let Some(second) = pool.second_quote.as_mut() {
+        second.admin_fees -= fees_second_quote;
+    }
+
+    msg!(
+        "withdrawn_quote: {}\n withdrawn_meme: {}\n withdrawn_second_quote: {}",
+        fees_quote,
+        fees_meme,
+        fees_second_quote
+    );
+
+    Ok(())
+}
+
+/// Represents the accounts required for withdrawing a pool's admin fees.
+#[derive(Accounts)]
+pub struct WithdrawAdminFees<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can withdraw admin fees"),
+    )]
+    /// The global protocol configuration
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    /// The pool whose admin fees are withdrawn
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    /// The pool's quote vault holding quote fees
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.vault == meme_vault.key()
+    )]
+    /// The pool's meme vault holding meme fees
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_quote_account.mint == pool.quote_reserve.mint
+            @ AmmError::InvalidTokenMints,
+        constraint = fee_quote_account.owner == protocol_config.protocol_fee_authority
+            @ err::acc("Quote fees can only be sent to the protocol fee authority"),
+    )]
+    /// The protocol fee authority's quote token account
+    pub fee_quote_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_meme_account.mint == pool.meme_reserve.mint
+            @ AmmError::InvalidTokenMints,
+        constraint = fee_meme_account.owner == protocol_config.protocol_fee_authority
+            @ err::acc("Meme fees can only be sent to the protocol fee authority"),
+    )]
+    /// The protocol fee authority's meme token account
+    pub fee_meme_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.is_second_quote_vault(second_quote_vault.key())
+            @ err::acc("Second quote vault doesn't belong to the pool"),
+    )]
+    /// The pool's second quote vault, if the pool raises in two assets
+    pub second_quote_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = pool.second_quote.is_some_and(|second| second.reserve.mint == fee_second_quote_account.mint)
+            @ AmmError::InvalidTokenMints,
+        constraint = fee_second_quote_account.owner == protocol_config.protocol_fee_authority
+            @ err::acc("Second quote fees can only be sent to the protocol fee authority"),
+    )]
+    /// The protocol fee authority's second quote token account
+    pub fee_second_quote_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    /// The pool signer owning the vaults
+    pub pool_signer: AccountInfo<'info>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
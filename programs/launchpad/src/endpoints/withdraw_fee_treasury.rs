@@ -0,0 +1,77 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::protocol_config::ProtocolConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Withdraws everything the fee treasury of a mint holds to the protocol fee
+/// authority, the protocol fees of every pool routed there.
+///
+/// # Errors
+/// * `AmmError::NoTokensToWithdraw` - If the treasury is empty
+pub fn handle(ctx: Context<WithdrawFeeTreasury>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let amount = accs.fee_treasury.amount;
+    if amount == 0 {
+        return Err(error!(AmmError::NoTokensToWithdraw));
+    }
+
+    let seeds = &[
+        ProtocolConfig::CONFIG_PREFIX.as_ref(),
+        &[ctx.bumps.protocol_config],
+    ];
+    let cpi_accounts = Transfer {
+        from: accs.fee_treasury.to_account_info(),
+        to: accs.fee_account.to_account_info(),
+        authority: accs.protocol_config.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(
+            accs.token_program.to_account_info(),
+            cpi_accounts,
+            &[&seeds[..]],
+        ),
+        amount,
+    )?;
+
+    msg!("withdrawn: {}", amount);
+
+    Ok(())
+}
+
+/// Represents the accounts required for withdrawing a fee treasury.
+#[derive(Accounts)]
+pub struct WithdrawFeeTreasury<'info> {
+    /// The protocol fee authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::CONFIG_PREFIX],
+        bump,
+        constraint = protocol_config.protocol_fee_authority == authority.key()
+            @ err::acc("Only the protocol fee authority can withdraw the fee treasury"),
+    )]
+    /// The global protocol configuration, owning the treasury
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::FEE_TREASURY_PREFIX, fee_treasury.mint.as_ref()],
+        bump,
+    )]
+    /// The fee treasury being withdrawn
+    pub fee_treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_account.mint == fee_treasury.mint @ AmmError::InvalidTokenMints,
+        constraint = fee_account.owner == protocol_config.protocol_fee_authority
+            @ err::acc("Fees can only be sent to the protocol fee authority"),
+    )]
+    /// The protocol fee authority's token account of the treasury's mint
+    pub fee_account: Account<'info, TokenAccount>,
+
+    /// The token program
+    pub token_program: Program<'info, Token>,
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use std::fmt::Display;
 
+/// Errors of the launchpad program.
+///
+/// Anchor numbers the variants from 6000 in declaration order, and clients
+/// map these codes to failures. New variants are only ever appended, and
+/// retired ones are kept in place, so no code shifts between releases.
 #[error_code]
 pub enum AmmError {
     /// Use this error via the [`acc`] function to provide more background
@@ -23,17 +28,26 @@ pub enum AmmError {
     /// wrong token mints
     #[msg("Provided mints are not available on the pool")]
     InvalidTokenMints,
+    #[msg("Arithmetic overflowed")]
     MathOverflow,
+    #[msg("Multiplication and division overflowed")]
     MulDivOverflow,
+    #[msg("Division by zero")]
     DivideByZero,
+    #[msg("Swap computed a zero input amount")]
     ZeroInAmt,
+    #[msg("Meme vault is empty")]
     ZeroMemeVault,
+    #[msg("Pool holds too few tokens for the swap")]
     InsufficientBalance,
     #[msg("Pool can't be interacted with until going into live phase")]
     PoolIsLocked,
     #[msg("Shouldn't provide zero tokens in")]
     NoZeroTokens,
+    #[msg("There are no tokens to withdraw")]
     NoTokensToWithdraw,
+    // Ticket and staking errors of the original program are no longer
+    // raised, but keep their place so later codes don't shift
     #[msg("Amount of tokens in ticket is lower than needed to swap")]
     NotEnoughTicketTokens,
     #[msg("Not enough time passed to unlock tokens bound to the ticket")]
@@ -43,21 +57,28 @@ pub enum AmmError {
     #[msg("Can't unstake the required amount of tokens")]
     NotEnoughTokensToRelease,
     BondingCurveMustBeNegativelySloped,
+    #[msg("Curve parameters give a non-increasing price")]
     BondingCurveMustBePositivelySloped,
+    #[msg("Curve parameters give a non-positive starting price")]
     BondingCurveInterceptMustBePositive,
     BondingCurveInterceptMustBeNegative,
+    #[msg("Quote target is too large relative to the meme supply")]
     EGammaSAboveRelativeLimit,
+    #[msg("Quote decimals are too low to price the curve")]
     EScaleTooLow,
     InvalidAmmAccountOwner,
     ExpectedAccount,
+    #[msg("Pool isn't in the status the instruction requires")]
     InvalidStatus,
     CantUnstakeBeforeCliff,
     NoFeesToAdd,
     #[msg("Staking should be fully initialized before it can be interacted with")]
     StakingIsNotActive,
+    #[msg("Meme mint must have no supply before the pool is created")]
     NonZeroInitialMemeSupply,
     AirdroppedTokensOvercap,
     InvalidVestingPeriod,
+
     #[msg("Migration threshold not reached - need 80% of tokens sold")]
     MigrationThresholdNotReached,
     #[msg("Pool has already been migrated to Raydium")]
@@ -71,6 +92,117 @@ pub enum AmmError {
 
     #[msg("Insufficient reserved tokens available for distribution")]
     InsufficientReservedTokens,
+
+    #[msg("Mint decimals don't match the target config")]
+    DecimalsMismatch,
+
+    #[msg("Pool is refunding, trading is disabled")]
+    PoolIsRefunding,
+
+    #[msg("Refund has already been claimed")]
+    RefundAlreadyClaimed,
+
+    #[msg("LP tokens are still locked")]
+    LpStillLocked,
+
+    #[msg("Not enough quote liquidity to sell yet")]
+    InsufficientQuoteLiquidity,
+
+    #[msg("Quote and meme mints must differ")]
+    IdenticalMints,
+
+    #[msg("Revealed buy doesn't match the commitment")]
+    InvalidCommitment,
+
+    #[msg("Buy can only be revealed in a later slot than its commitment")]
+    RevealTooEarly,
+
+    #[msg("Buy commitment has expired")]
+    CommitmentExpired,
+
+    #[msg("Buy commitment hasn't expired yet")]
+    CommitmentNotExpired,
+
+    #[msg("Trade moves the price too far within a single slot")]
+    CircuitBreakerTripped,
+
+    #[msg("Swap output drifted too far from its preview")]
+    PreviewDrift,
+
+    #[msg("Pool reached its quote raise cap")]
+    RaiseCapReached,
+
+    #[msg("Metadata name, symbol or URI exceeds the Metaplex limits")]
+    MetadataTooLong,
+
+    #[msg("Pool migrates through another path, see its migration target")]
+    WrongMigrationTarget,
+
+    #[msg("Pool already traded and can't be cancelled")]
+    PoolHasTraded,
+
+    #[msg("Transaction was processed after its deadline")]
+    DeadlineExceeded,
+
+    #[msg("Pool is paused")]
+    PoolPaused,
+
+    #[msg("Only whitelisted wallets can buy during the whitelist phase")]
+    NotWhitelisted,
+
+    #[msg("Buy exceeds the wallet's whitelist cap")]
+    WhitelistCapExceeded,
+
+    #[msg("Sells are disabled during the whitelist phase")]
+    WhitelistPhaseActive,
+
+    #[msg("Token account is frozen by its mint's freeze authority")]
+    AccountFrozen,
+
+    #[msg("Nothing vested to claim yet")]
+    NothingVested,
+
+    #[msg("Fee percent exceeds 100%")]
+    InvalidFee,
+
+    #[msg("Pool account is on an older layout, migrate it first")]
+    OutdatedPoolVersion,
+
+    #[msg("Swap is too small to output any tokens")]
+    OutputTooSmall,
+
+    #[msg("Sells are locked after launch")]
+    SellsLocked,
+
+    #[msg("Pool creation is disabled")]
+    PoolCreationDisabled,
+
+    #[msg("Signer isn't allowed to perform this action")]
+    Unauthorized,
+
+    #[msg("Target config is already used by a pool")]
+    TargetConfigInUse,
+
+    #[msg("Meme vault holds less than the pool's reserve")]
+    InsufficientTokenSupply,
+
+    #[msg("Sell would execute below the pool's price floor")]
+    BelowPriceFloor,
+
+    #[msg("Token account isn't the pool creator's account of the expected mint")]
+    InvalidCreatorAccount,
+
+    #[msg("Execution price deviates from the reference price beyond the tolerance")]
+    ReferencePriceDeviation,
+
+    #[msg("Referrer can't be the buyer or the pool creator")]
+    SelfReferral,
+
+    #[msg("Pool is too young to migrate")]
+    PoolTooYoung,
+
+    #[msg("Mint is not an SPL Token mint, Token-2022 extensions are unsupported")]
+    UnsupportedMintExtension,
 }
 
 #[allow(dead_code)]
@@ -85,3 +217,97 @@ pub fn arg(msg: impl Display) -> AmmError {
 
     AmmError::InvalidArg
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_are_stable() {
+        // ARRANGE: Every variant with the code clients map it to
+        let codes = [
+            (AmmError::InvalidAccountInput, 6000),
+            (AmmError::InvalidArg, 6001),
+            (AmmError::SlippageExceeded, 6002),
+            (AmmError::InvariantViolation, 6003),
+            (AmmError::InvalidTokenMints, 6004),
+            (AmmError::MathOverflow, 6005),
+            (AmmError::MulDivOverflow, 6006),
+            (AmmError::DivideByZero, 6007),
+            (AmmError::ZeroInAmt, 6008),
+            (AmmError::ZeroMemeVault, 6009),
+            (AmmError::InsufficientBalance, 6010),
+            (AmmError::PoolIsLocked, 6011),
+            (AmmError::NoZeroTokens, 6012),
+            (AmmError::NoTokensToWithdraw, 6013),
+            (AmmError::NotEnoughTicketTokens, 6014),
+            (AmmError::TicketTokensLocked, 6015),
+            (AmmError::NonZeroAmountTicket, 6016),
+            (AmmError::NotEnoughTokensToRelease, 6017),
+            (AmmError::BondingCurveMustBeNegativelySloped, 6018),
+            (AmmError::BondingCurveMustBePositivelySloped, 6019),
+            (AmmError::BondingCurveInterceptMustBePositive, 6020),
+            (AmmError::BondingCurveInterceptMustBeNegative, 6021),
+            (AmmError::EGammaSAboveRelativeLimit, 6022),
+            (AmmError::EScaleTooLow, 6023),
+            (AmmError::InvalidAmmAccountOwner, 6024),
+            (AmmError::ExpectedAccount, 6025),
+            (AmmError::InvalidStatus, 6026),
+            (AmmError::CantUnstakeBeforeCliff, 6027),
+            (AmmError::NoFeesToAdd, 6028),
+            (AmmError::StakingIsNotActive, 6029),
+            (AmmError::NonZeroInitialMemeSupply, 6030),
+            (AmmError::AirdroppedTokensOvercap, 6031),
+            (AmmError::InvalidVestingPeriod, 6032),
+            (AmmError::MigrationThresholdNotReached, 6033),
+            (AmmError::AlreadyMigrated, 6034),
+            (AmmError::InvalidTokenOrder, 6035),
+            (AmmError::PoolAlreadyMigrated, 6036),
+            (AmmError::InsufficientReservedTokens, 6037),
+            (AmmError::DecimalsMismatch, 6038),
+            (AmmError::PoolIsRefunding, 6039),
+            (AmmError::RefundAlreadyClaimed, 6040),
+            (AmmError::LpStillLocked, 6041),
+            (AmmError::InsufficientQuoteLiquidity, 6042),
+            (AmmError::IdenticalMints, 6043),
+            (AmmError::InvalidCommitment, 6044),
+            (AmmError::RevealTooEarly, 6045),
+            (AmmError::CommitmentExpired, 6046),
+            (AmmError::CommitmentNotExpired, 6047),
+            (AmmError::CircuitBreakerTripped, 6048),
+            (AmmError::PreviewDrift, 6049),
+            (AmmError::RaiseCapReached, 6050),
+            (AmmError::MetadataTooLong, 6051),
+            (AmmError::WrongMigrationTarget, 6052),
+            (AmmError::PoolHasTraded, 6053),
+            (AmmError::DeadlineExceeded, 6054),
+            (AmmError::PoolPaused, 6055),
+            (AmmError::NotWhitelisted, 6056),
+            (AmmError::WhitelistCapExceeded, 6057),
+            (AmmError::WhitelistPhaseActive, 6058),
+            (AmmError::AccountFrozen, 6059),
+            (AmmError::NothingVested, 6060),
+            (AmmError::InvalidFee, 6061),
+            (AmmError::OutdatedPoolVersion, 6062),
+            (AmmError::OutputTooSmall, 6063),
+            (AmmError::SellsLocked, 6064),
+            (AmmError::PoolCreationDisabled, 6065),
+            (AmmError::Unauthorized, 6066),
+            (AmmError::TargetConfigInUse, 6067),
+            (AmmError::InsufficientTokenSupply, 6068),
+            (AmmError::BelowPriceFloor, 6069),
+            (AmmError::InvalidCreatorAccount, 6070),
+            (AmmError::ReferencePriceDeviation, 6071),
+            (AmmError::SelfReferral, 6072),
+            (AmmError::PoolTooYoung, 6073),
+            (AmmError::UnsupportedMintExtension, 6074),
+        ];
+
+        // ACT & ASSERT: No variant moved
+        for (error, code) in codes {
+            assert_eq!(u32::from(error), code, "{} shifted", error.name());
+        }
+
+        println!("✅ Error codes stability test passed!");
+    }
+}
@@ -4,8 +4,13 @@ mod err;
 mod libraries;
 mod math;
 mod models;
+#[cfg(test)]
+mod test_harness;
 
 use crate::endpoints::*;
+use crate::models::bound::{CurveType, MigrationAmm};
+use crate::models::fees::FeeShares;
+use crate::models::protocol_config::FeeTier;
 use anchor_lang::prelude::*;
 use core as core_;
 
@@ -29,18 +34,192 @@ pub mod launchpad {
     ///
     /// # Arguments
     /// * `token_target_amount` - The target amount of tokens (in lamports/smallest unit)
+    /// * `price_factor_num_end` - Price factor numerator ramped to as the
+    ///   supply sells out, zero to keep the static price factor
+    /// * `min_quote_liquidity` - Quote reserve below which sells are
+    ///   rejected, at most 10% of the target, zero for the default share of
+    ///   it
+    /// * `fee_start_bps` - Quote fee with no meme sold, in bps
+    /// * `fee_end_bps` - Quote fee at the migration threshold, in bps, both
+    ///   zero to keep the flat quote fee, either at most 5%
+    /// * `circuit_breaker_bps` - Largest price move allowed within a slot, in
+    ///   bps, zero to disable the circuit breaker
+    /// * `fee_shares` - Split of the quote fee between protocol, creator and
+    ///   referral, adding up to 10000 bps or all zero for the protocol only
+    /// * `max_quote_raise` - Quote reserve above which buys are rejected,
+    ///   zero for no cap
+    /// * `post_migration_fee_bps` - Quote fee of trades against the reserves
+    ///   retained after migration, in bps, at most 5%, zero for the regular
+    ///   fee
+    /// * `lp_lock_duration` - Seconds the LP stays locked after migration, at
+    ///   least 30 days, zero for the default 180 days
+    /// * `max_fee_quote` - Largest quote fee charged on a single buy, zero
+    ///   for no cap
+    /// * `max_fee_meme` - Largest meme fee charged on a single sell, zero for
+    ///   no cap
+    /// * `virtual_quote_reserve` - Quote the curve prices as already raised,
+    ///   zero for none
+    /// * `virtual_meme_reserve` - Meme the curve prices as already sold, zero
+    ///   for none
+    /// * `min_pool_age_secs` - Seconds a pool must have traded before it can
+    ///   migrate, even past the threshold (`PoolTooYoung`), zero for none
+    #[allow(clippy::too_many_arguments)]
     pub fn init_target_config(
         ctx: Context<InitTargetConfig>,
         token_target_amount: u64,
+        price_factor_num_end: u64,
+        min_quote_liquidity: u64,
+        fee_start_bps: u16,
+        fee_end_bps: u16,
+        circuit_breaker_bps: u16,
+        fee_shares: FeeShares,
+        max_quote_raise: u64,
+        post_migration_fee_bps: u16,
+        lp_lock_duration: i64,
+        max_fee_quote: u64,
+        max_fee_meme: u64,
+        virtual_quote_reserve: u64,
+        virtual_meme_reserve: u64,
+        min_pool_age_secs: i64,
     ) -> Result<()> {
-        init_target_config::handle(ctx, token_target_amount)
+        init_target_config::handle(
+            ctx,
+            token_target_amount,
+            price_factor_num_end,
+            min_quote_liquidity,
+            fee_start_bps,
+            fee_end_bps,
+            circuit_breaker_bps,
+            fee_shares,
+            max_quote_raise,
+            post_migration_fee_bps,
+            lp_lock_duration,
+            max_fee_quote,
+            max_fee_meme,
+            virtual_quote_reserve,
+            virtual_meme_reserve,
+            min_pool_age_secs,
+        )
+    }
+
+    /// Corrects the quote target of a target config, signed by its creator
+    /// and only until a pool is created against it
+    ///
+    /// # Arguments
+    /// * `new_amount` - The new target amount of quote tokens
+    pub fn update_target_config(ctx: Context<UpdateTargetConfig>, new_amount: u64) -> Result<()> {
+        update_target_config::handle(ctx, new_amount)
     }
 
     /// Creates a new bonding curve pool for a memecoin launch
     ///
     /// # Arguments
-    pub fn new_pool(ctx: Context<NewPool>) -> Result<()> {
-        new_pool::handle(ctx)
+    /// * `migration_target` - Path the pool graduates through: Raydium CPMM
+    ///   (default), Raydium CLMM or a manual migration to the creator
+    /// * `whitelist_until` - Unix timestamp until which only wallets added
+    ///   with `add_to_whitelist` can buy, up to their cap, and sells are
+    ///   disabled. Zero for no whitelist phase
+    /// * `creator_allocation` - Meme tokens minted to the creator on top of
+    ///   the supply, vesting through `claim_vested`. Zero for none
+    /// * `creator_allocation_bps` - Share of the trading supply, up to 20%,
+    ///   taken off the curve for the creator. Vests with the allocation when
+    ///   `vesting_duration` is set, else goes straight to
+    ///   `creator_meme_account`. Zero for none
+    /// * `vesting_cliff` - Seconds after creation before anything vests
+    /// * `vesting_duration` - Seconds after creation until all of it vested
+    /// * `sell_lock_until` - Unix timestamp until which sells are rejected
+    ///   while buys proceed, at most `MAX_SELL_LOCK_DURATION` after
+    ///   creation. Zero for no lock
+    /// * `first_buyer_bonus_bps` - Meme paid to the first buyer on top of
+    ///   their buy, in bps of it, out of a vault funded at creation. Zero for
+    ///   no bonus
+    /// * `curve_type` - Shape of the curve: the standard curve (default), a
+    ///   linearly or an exponentially rising price
+    /// * `trading_supply` - Meme tradeable on the curve, the migration
+    ///   threshold being 80% of it. Zero for the default 690M
+    /// * `lp_supply` - Meme reserved for the liquidity seeded at migration.
+    ///   Zero for the default 310M. Both add up to at most 1B
+    /// * `price_floor` - Quote per meme, scaled by `SPOT_PRICE_PRECISION`,
+    ///   below which sells are rejected with `BelowPriceFloor`. Sells can
+    ///   then fail until buys lift the price. Zero for no floor
+    /// * `burn_on_sell_bps` - Share of every sell's meme input burned rather
+    ///   than sold, up to 10%. Zero for none
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_pool(
+        ctx: Context<NewPool>,
+        migration_target: MigrationAmm,
+        whitelist_until: i64,
+        creator_allocation: u64,
+        creator_allocation_bps: u16,
+        vesting_cliff: i64,
+        vesting_duration: i64,
+        sell_lock_until: i64,
+        first_buyer_bonus_bps: u16,
+        curve_type: CurveType,
+        trading_supply: u64,
+        lp_supply: u64,
+        price_floor: u128,
+        burn_on_sell_bps: u16,
+    ) -> Result<()> {
+        new_pool::handle(
+            ctx,
+            migration_target,
+            whitelist_until,
+            creator_allocation,
+            creator_allocation_bps,
+            vesting_cliff,
+            vesting_duration,
+            sell_lock_until,
+            first_buyer_bonus_bps,
+            curve_type,
+            trading_supply,
+            lp_supply,
+            price_floor,
+            burn_on_sell_bps,
+        )
+    }
+
+    /// Releases the vested part of the creator allocation to the creator
+    /// Creator only
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        claim_vested::handle(ctx)
+    }
+
+    /// Airdrops meme out of the LP allocation to the token accounts passed
+    /// as remaining accounts, one per amount, at most `MAX_AIRDROP_BATCH`.
+    /// Creator only
+    ///
+    /// # Arguments
+    /// * `amounts` - Meme sent to each recipient, in the order they're passed
+    pub fn distribute_airdrop_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeAirdropBatch<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        distribute_airdrop_batch::handle(ctx, amounts)
+    }
+
+    /// Cancels a pool before its first trade, burning its meme supply and
+    /// closing the pool, its vaults and vesting schedule. Rent goes back to
+    /// the creator, and the target config no longer counts the pool
+    /// Creator only
+    pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
+        cancel_pool::handle(ctx)
+    }
+
+    /// Adds a second quote asset (e.g. USDT to a USDC pool) raised against the
+    /// same curve, pegged stablecoins at par only.
+    /// Protocol fee authority only, as the asset and its rate price the curve
+    ///
+    /// # Arguments
+    /// * `price_num` - Primary quote units per second quote unit (numerator)
+    /// * `price_denom` - Primary quote units per second quote unit
+    ///   (denominator), one whole token for one
+    pub fn init_second_quote(
+        ctx: Context<InitSecondQuote>,
+        price_num: u64,
+        price_denom: u64,
+    ) -> Result<()> {
+        init_second_quote::handle(ctx, price_num, price_denom)
     }
 
     /// Creates token metadata for the launched memecoin
@@ -58,10 +237,154 @@ pub mod launchpad {
         create_metadata::handle(ctx, name, symbol, uri)
     }
 
+    /// Updates the metadata URI of the launched memecoin
+    /// Creator only, until the pool migrates
+    ///
+    /// # Arguments
+    /// * `uri` - New metadata URI, at most 200 bytes
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, uri: String) -> Result<()> {
+        update_metadata::handle(ctx, uri)
+    }
+
+    /// Sets the pool's on-chain website, twitter and telegram links
+    /// Creator only, until the pool migrates
+    ///
+    /// # Arguments
+    /// * `website` - Website link, at most 128 bytes
+    /// * `twitter` - Twitter link, at most 64 bytes
+    /// * `telegram` - Telegram link, at most 64 bytes
+    pub fn set_pool_socials(
+        ctx: Context<SetPoolSocials>,
+        website: String,
+        twitter: String,
+        telegram: String,
+    ) -> Result<()> {
+        set_pool_socials::handle(ctx, website, twitter, telegram)
+    }
+
+    /// Allows a wallet to buy during the pool's whitelist phase, or updates
+    /// its cap. Creator only, until the pool migrates
+    ///
+    /// # Arguments
+    /// * `buyer` - Wallet allowed to buy
+    /// * `cap` - Most quote the wallet can spend during the phase, fees
+    ///   included
+    pub fn add_to_whitelist(ctx: Context<AddToWhitelist>, buyer: Pubkey, cap: u64) -> Result<()> {
+        add_to_whitelist::handle(ctx, buyer, cap)
+    }
+
+    /// Permanently revokes the meme mint authority
+    /// Creator only, opt-in. Create the metadata first, it needs the authority
+    pub fn revoke_mint_authority(ctx: Context<RevokeMintAuthority>) -> Result<()> {
+        revoke_mint_authority::handle(ctx)
+    }
+
+    /// Proposes a new pool creator, who must accept before taking over
+    /// Creator only
+    ///
+    /// # Arguments
+    /// * `new_creator` - Key allowed to accept the transfer
+    pub fn propose_creator_transfer(
+        ctx: Context<ProposeCreatorTransfer>,
+        new_creator: Pubkey,
+    ) -> Result<()> {
+        propose_creator_transfer::handle(ctx, new_creator)
+    }
+
+    /// Completes a creator transfer, signed by the proposed creator
+    pub fn accept_creator_transfer(ctx: Context<AcceptCreatorTransfer>) -> Result<()> {
+        accept_creator_transfer::handle(ctx)
+    }
+
     // ===== Trading Functions =====
 
+    /// Circulating meme supply for market cap display: the curve's initial
+    /// supply minus the meme reserve, meme admin fees and burned tokens
+    /// (as a `u64` in the return data)
+    pub fn get_circulating_supply(ctx: Context<GetCirculatingSupply>) -> Result<()> {
+        get_circulating_supply::handle(ctx)
+    }
+
+    /// Reserves and spot price of up to `PoolReserves::MAX_BATCH` pools
+    /// passed as remaining accounts (as a `Vec<PoolReserves>` in the return
+    /// data, in the order the pools were passed)
+    pub fn get_reserves(ctx: Context<GetReserves>) -> Result<()> {
+        get_reserves::handle(ctx)
+    }
+
+    /// Meme and quote decimals with both reserves, for formatting raw
+    /// amounts without fetching the mints (as a `PoolDisplayInfo` in the
+    /// return data)
+    pub fn get_pool_display_info(ctx: Context<GetPoolDisplayInfo>) -> Result<()> {
+        get_pool_display_info::handle(ctx)
+    }
+
+    /// Spot price the curve would quote with only `meme_reserve_hypothetical`
+    /// meme left on it, for charting the curve across its supply (as a
+    /// `u128` in the return data). Like the pool's spot price, it is raw
+    /// quote units per raw meme unit scaled by `SPOT_PRICE_PRECISION` (1e12),
+    /// before fees and at the current point of the price factor ramp
+    ///
+    /// # Arguments
+    /// * `meme_reserve_hypothetical` - Meme left on the curve, at most its
+    ///   trading supply (`InvalidArg` beyond it)
+    pub fn get_price_at_reserve(
+        ctx: Context<GetPriceAtReserve>,
+        meme_reserve_hypothetical: u64,
+    ) -> Result<()> {
+        get_price_at_reserve::handle(ctx, meme_reserve_hypothetical)
+    }
+
+    /// Tracked vs actual meme and quote vault balances, for monitoring
+    /// drift (as a `PoolIntegrity` in the return data)
+    pub fn verify_pool_integrity(ctx: Context<VerifyPoolIntegrity>) -> Result<()> {
+        verify_pool_integrity::handle(ctx)
+    }
+
+    /// Most recent pool migrations with their slots, newest first (as a
+    /// `Vec<MigrationEntry>` in the return data)
+    pub fn get_recent_migrations(ctx: Context<GetRecentMigrations>) -> Result<()> {
+        get_recent_migrations::handle(ctx)
+    }
+
+    /// Creates the trade history of a pool, recording the latest
+    /// `TRADE_HISTORY_SIZE` swaps. Every swap must pass it from then on
+    pub fn init_trade_history(ctx: Context<InitTradeHistory>) -> Result<()> {
+        init_trade_history::handle(ctx)
+    }
+
+    /// Creates the caller's position record in a pool, tracking their buys
+    /// and sells from then on. Only meme bought while it exists is refundable
+    pub fn init_buyer_record(ctx: Context<InitBuyerRecord>) -> Result<()> {
+        init_buyer_record::handle(ctx)
+    }
+
+    /// Most recent trades of a pool, newest first (as a `Vec<TradeEntry>` in
+    /// the return data)
+    pub fn get_recent_trades(ctx: Context<GetRecentTrades>) -> Result<()> {
+        get_recent_trades::handle(ctx)
+    }
+
+    /// Buyer position: meme held, cost basis and current quote value
+    /// (as a `BuyerPosition` in the return data)
+    pub fn get_buyer_position(ctx: Context<GetBuyerPosition>) -> Result<()> {
+        get_buyer_position::handle(ctx)
+    }
+
+    /// Preview fees: what a swap would be charged at the current state
+    /// (as a `FeePreview` in the return data), tiers, ramps and caps applied
+    ///
+    /// # Arguments
+    /// * `amount_in` - SOL spent by a buy, or meme tokens sold by a sell
+    /// * `is_buy` - True to preview a buy, false a sell
+    pub fn preview_fee(ctx: Context<PreviewFee>, amount_in: u64, is_buy: bool) -> Result<()> {
+        preview_fee::handle(ctx, amount_in, is_buy)
+    }
+
     /// Preview swap: selling meme tokens for SOL
     /// Returns expected amounts without executing trade
+    /// (as a `SwapQuote` in the return data, including the effective rate
+    /// and price impact)
     ///
     /// # Arguments
     /// * `coin_in_amount` - Amount of meme tokens to sell
@@ -76,49 +399,425 @@ pub mod launchpad {
 
     /// Execute swap: sell meme tokens for SOL
     /// Uses direct token transfer with bonding curve pricing
+    /// Pass `native_sol_destination` to receive native SOL instead of WSOL,
+    /// along with the native mint, the system program and the owner's
+    /// `temp_wsol` PDA (`[TEMP_WSOL_PREFIX, owner]`), a WSOL account created
+    /// and closed within the sell. WSOL held in `user_sol` stays wrapped
     ///
     /// # Arguments
     /// * `coin_in_amount` - Amount of meme tokens to sell
-    /// * `coin_y_min_value` - Minimum SOL to receive (slippage protection)
+    /// * `coin_y_min_value` - Minimum SOL to receive, after any platform fee
+    ///   (slippage protection)
+    /// * `platform_fee_bps` - Fee of the routing aggregator, paid to the
+    ///   optional `platform_fee_account` out of the SOL received, at most
+    ///   `MAX_PLATFORM_FEE_BPS`. Zero for none
+    /// * `deadline` - Unix timestamp after which the sell is rejected
+    ///   (`DeadlineExceeded`), zero for none
     pub fn swap_x(
         ctx: Context<SwapCoinX>,
         coin_in_amount: u64,
         coin_y_min_value: u64,
+        platform_fee_bps: u16,
+        deadline: i64,
     ) -> Result<()> {
-        swap_x::handle(ctx, coin_in_amount, coin_y_min_value)
+        swap_x::handle(
+            ctx,
+            coin_in_amount,
+            coin_y_min_value,
+            platform_fee_bps,
+            deadline,
+        )
     }
 
     /// Preview swap: buying meme tokens with SOL
     /// Returns expected amounts without executing trade
+    /// (as a `SwapQuote` in the return data, including the effective rate
+    /// and price impact)
     ///
     /// # Arguments
     /// * `coin_in_amount` - Amount of SOL to spend
     /// * `coin_x_min_value` - Minimum meme tokens to receive (slippage protection)
+    /// * `assume_prior_in` - Size of a buy assumed to land first, e.g. one
+    ///   queued ahead of this trade, zero to quote against the current reserves
     pub fn get_swap_y_amt(
         ctx: Context<GetSwapYAmt>,
         coin_in_amount: u64,
         coin_x_min_value: u64,
+        assume_prior_in: u64,
     ) -> Result<()> {
-        get_swap_y_amt::handle(ctx, coin_in_amount, coin_x_min_value)
+        get_swap_y_amt::handle(ctx, coin_in_amount, coin_x_min_value, assume_prior_in)
+    }
+
+    /// Batch preview: buying meme tokens with SOL at several input sizes
+    /// Returns the meme output of each input against the current reserves
+    /// (as a `Vec<u64>` in the return data), e.g. for price impact charts
+    ///
+    /// # Arguments
+    /// * `amounts` - SOL amounts to preview, at most `MAX_BATCH_PREVIEW_AMOUNTS`
+    pub fn get_swap_y_amounts(ctx: Context<GetSwapYAmounts>, amounts: Vec<u64>) -> Result<()> {
+        get_swap_y_amounts::handle(ctx, amounts)
     }
 
     /// Execute swap: buy meme tokens with SOL
     /// Direct transfer to user's wallet + points rewards for referrers
     /// 🌟 Automatically triggers migration when 80% threshold reached
+    /// Pass `native_sol_source` to pay with native SOL, along with the
+    /// native mint and the owner's `temp_wsol` PDA
+    /// (`[TEMP_WSOL_PREFIX, owner]`), a WSOL account created and closed
+    /// within the buy. WSOL held in `user_sol` stays wrapped
+    /// Buys past the pool's quote raise cap are partially filled up to it
     ///
     /// # Arguments
     /// * `coin_in_amount` - Amount of SOL to spend
     /// * `coin_x_min_value` - Minimum meme tokens to receive (slippage protection)
-    pub fn swap_y(
+    /// * `expected_out` - Meme output previewed by `get_swap_y_amt`
+    /// * `tolerance_bps` - Largest drift from `expected_out` either way, zero
+    ///   to skip the check (`PreviewDrift` beyond it)
+    /// * `platform_fee_bps` - Fee of the routing aggregator, paid to the
+    ///   optional `platform_fee_account` on top of the SOL spent, at most
+    ///   `MAX_PLATFORM_FEE_BPS`. Zero for none
+    /// * `reference_price` - Meme price from the client's own feed, e.g. a
+    ///   USD price converted to the quote asset: raw quote units paid per raw
+    ///   meme unit, fees included, scaled by `SPOT_PRICE_PRECISION` (1e12).
+    ///   Zero to skip the check
+    /// * `max_deviation_bps` - Largest deviation of the execution price from
+    ///   `reference_price` either way (`ReferencePriceDeviation` beyond it),
+    ///   zero to skip the check
+    /// * `deadline` - Unix timestamp after which the buy is rejected
+    ///   (`DeadlineExceeded`), zero for none
+    ///
+    /// On success the return data is a `SwapQuote`. On `SlippageExceeded` it
+    /// is the meme output achievable at current reserves, as a `u64`; no other
+    /// error sets return data.
+    ///
+    /// Passing the optional `referrer_quote` account rebates
+    /// `Fees::referral_fee_bps` of the protocol's fee to it, without changing
+    /// the fee the buyer pays. Its owner must be registered with
+    /// `register_referrer`, that `referrer` registration passed along, and
+    /// can't be the buyer or the pool creator (`SelfReferral`).
+    ///
+    /// Meme is paid to the buyer's associated token account, created on the
+    /// buy with the owner paying rent if it doesn't exist yet. Passing the
+    /// optional `recipient` meme token account delivers it there instead,
+    /// the owner still paying and owning the buyer record.
+    ///
+    /// The owner's `buyer_record` PDA is always passed, and tracks the buy
+    /// once `init_buyer_record` created it.
+    ///
+    /// Passing the optional `callback_program` invokes it after the buy
+    /// executed, with up to `MAX_CALLBACK_ACCOUNTS` remaining accounts
+    /// forwarded in order and a `SwapCallback` as instruction data: the
+    /// Anchor discriminator of `on_swap`, then `pool`, `user`, `buy_meme`,
+    /// `amount_in` (quote paid, fees included) and `amount_out` (meme
+    /// received), borsh encoded. A failing callback reverts the buy.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_y<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapCoinY<'info>>,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+        expected_out: u64,
+        tolerance_bps: u16,
+        platform_fee_bps: u16,
+        reference_price: u128,
+        max_deviation_bps: u16,
+        deadline: i64,
+    ) -> Result<()> {
+        swap_y::handle(
+            ctx,
+            coin_in_amount,
+            coin_x_min_value,
+            expected_out,
+            tolerance_bps,
+            platform_fee_bps,
+            reference_price,
+            max_deviation_bps,
+            deadline,
+        )
+    }
+
+    /// Runs the validation and pricing of a `swap_y` buy with the same
+    /// arguments, without moving tokens or updating the pool. Only takes
+    /// read-only accounts, the `owner` not needing to sign. The whitelist
+    /// phase and the circuit breaker, which need the buy applied, aren't
+    /// checked
+    ///
+    /// On success the return data is the `SwapQuote` the buy would execute,
+    /// on `SlippageExceeded` the achievable meme output like `swap_y`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn dry_run_swap_y(
+        ctx: Context<DryRunSwapY>,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+        expected_out: u64,
+        tolerance_bps: u16,
+        platform_fee_bps: u16,
+        reference_price: u128,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        dry_run_swap_y::handle(
+            ctx,
+            coin_in_amount,
+            coin_x_min_value,
+            expected_out,
+            tolerance_bps,
+            platform_fee_bps,
+            reference_price,
+            max_deviation_bps,
+        )
+    }
+
+    /// Execute swap: buy meme tokens with SOL, the minimum output derived
+    /// from `slippage_bps` below the expected output at current reserves.
+    /// Takes the same accounts as `swap_y`
+    ///
+    /// # Arguments
+    /// * `coin_in_amount` - Amount of SOL to swap
+    /// * `slippage_bps` - Largest shortfall from the expected output, at most
+    ///   10_000
+    ///
+    /// On success the return data is a `SwapQuote`.
+    pub fn swap_y_bps(
+        ctx: Context<SwapCoinY>,
+        coin_in_amount: u64,
+        slippage_bps: u16,
+    ) -> Result<()> {
+        swap_y_bps::handle(ctx, coin_in_amount, slippage_bps)
+    }
+
+    /// Execute swap: buy meme tokens with SOL, like `swap_y` but without
+    /// logs or events, for integrators counting compute units. Takes the
+    /// same accounts as `swap_y`
+    ///
+    /// # Arguments
+    /// * `coin_in_amount` - Amount of SOL to swap
+    /// * `coin_x_min_value` - Minimum amount of meme tokens to receive
+    ///
+    /// On success the return data is a `SwapQuote`.
+    pub fn swap_y_lite(
         ctx: Context<SwapCoinY>,
         coin_in_amount: u64,
         coin_x_min_value: u64,
     ) -> Result<()> {
-        swap_y::handle(ctx, coin_in_amount, coin_x_min_value)
+        swap_y_lite::handle(ctx, coin_in_amount, coin_x_min_value)
+    }
+
+    /// Execute swap: buy exactly `coin_out_amount` meme tokens with SOL
+    /// Takes the same accounts as `swap_y`
+    ///
+    /// # Arguments
+    /// * `coin_out_amount` - Amount of meme tokens to receive
+    /// * `coin_in_max` - Maximum SOL to spend, fees included (slippage protection)
+    ///
+    /// On success the return data is a `SwapQuote`.
+    pub fn swap_y_exact_out(
+        ctx: Context<SwapCoinY>,
+        coin_out_amount: u64,
+        coin_in_max: u64,
+    ) -> Result<()> {
+        swap_y_exact_out::handle(ctx, coin_out_amount, coin_in_max)
+    }
+
+    /// Commits to a hidden buy, revealed by `reveal_buy` in a later slot so
+    /// its size can't be sandwiched
+    ///
+    /// # Arguments
+    /// * `commitment` - `keccak(owner || amount || min_out || nonce)`
+    pub fn commit_buy(ctx: Context<CommitBuy>, commitment: [u8; 32]) -> Result<()> {
+        commit_buy::handle(ctx, commitment)
+    }
+
+    /// Reveals and executes a committed buy, as `swap_y` would
+    ///
+    /// # Arguments
+    /// * `amount` - Amount of SOL to spend
+    /// * `min_out` - Minimum meme tokens to receive (slippage protection)
+    /// * `nonce` - Secret salt of the commitment
+    pub fn reveal_buy(
+        ctx: Context<RevealBuy>,
+        amount: u64,
+        min_out: u64,
+        nonce: [u8; 32],
+    ) -> Result<()> {
+        reveal_buy::handle(ctx, amount, min_out, nonce)
+    }
+
+    /// Closes a committed buy left unrevealed past its window, refunding the
+    /// rent to the buyer
+    pub fn expire_buy(ctx: Context<ExpireBuy>) -> Result<()> {
+        expire_buy::handle(ctx)
+    }
+
+    // ===== Protocol Administration =====
+
+    /// Initializes the global protocol configuration (upgrade authority only)
+    ///
+    /// # Arguments
+    /// * `protocol_fee_authority` - Authority receiving pool admin fees
+    pub fn init_protocol_config(
+        ctx: Context<InitProtocolConfig>,
+        protocol_fee_authority: Pubkey,
+    ) -> Result<()> {
+        init_protocol_config::handle(ctx, protocol_fee_authority)
+    }
+
+    /// Hands protocol revenue over to a new fee authority
+    ///
+    /// # Arguments
+    /// * `new_authority` - Authority receiving pool admin fees from now on
+    pub fn update_protocol_fee_authority(
+        ctx: Context<UpdateProtocolFeeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        update_protocol_fee_authority::handle(ctx, new_authority)
+    }
+
+    /// Turns new pool creation on or off, e.g. during an incident. Existing
+    /// pools and trading are unaffected
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether `new_pool` accepts new launches
+    pub fn set_pool_creation_enabled(
+        ctx: Context<SetPoolCreationEnabled>,
+        enabled: bool,
+    ) -> Result<()> {
+        set_pool_creation_enabled::handle(ctx, enabled)
+    }
+
+    /// Replaces the quote fee tiers granted to pools by cumulative volume
+    ///
+    /// # Arguments
+    /// * `fee_tiers` - Tiers sorted by ascending volume threshold, empty to
+    ///   charge every pool its own quote fee
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        set_fee_tiers::handle(ctx, fee_tiers)
+    }
+
+    /// Exempts a trader, e.g. a market maker or the treasury, from admin
+    /// fees on every pool
+    ///
+    /// # Arguments
+    /// * `account` - The trader to exempt, at most `MAX_FEE_EXEMPT` in total
+    pub fn add_fee_exempt(ctx: Context<AddFeeExempt>, account: Pubkey) -> Result<()> {
+        add_fee_exempt::handle(ctx, account)
+    }
+
+    /// Removes a trader's admin fee exemption
+    ///
+    /// # Arguments
+    /// * `account` - The exempt trader
+    pub fn remove_fee_exempt(ctx: Context<RemoveFeeExempt>, account: Pubkey) -> Result<()> {
+        remove_fee_exempt::handle(ctx, account)
+    }
+
+    /// Registers a wallet as referrer, letting buys rebate part of the
+    /// protocol's fee to it
+    ///
+    /// # Arguments
+    /// * `wallet` - The referrer's wallet
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, wallet: Pubkey) -> Result<()> {
+        register_referrer::handle(ctx, wallet)
+    }
+
+    /// Unregisters a referrer, closing its registration
+    pub fn unregister_referrer(ctx: Context<UnregisterReferrer>) -> Result<()> {
+        unregister_referrer::handle(ctx)
+    }
+
+    /// Withdraws a pool's accumulated admin fees to the protocol fee authority
+    pub fn withdraw_admin_fees(ctx: Context<WithdrawAdminFees>) -> Result<()> {
+        withdraw_admin_fees::handle(ctx)
+    }
+
+    /// Pauses buys, sells or both on a pool, e.g. only sells during an
+    /// incident. Rejected trades fail with `PoolPaused`
+    ///
+    /// # Arguments
+    /// * `trade_flags` - `BoundPool::BUYS_PAUSED` and `BoundPool::SELLS_PAUSED`
+    ///   bits, zero to resume trading both ways
+    pub fn set_trade_flags(ctx: Context<SetTradeFlags>, trade_flags: u8) -> Result<()> {
+        set_trade_flags::handle(ctx, trade_flags)
+    }
+
+    /// Creates the fee treasury of a quote mint, collecting the protocol fees
+    /// of every pool trading in it while fee routing is on
+    pub fn init_fee_treasury(ctx: Context<InitFeeTreasury>) -> Result<()> {
+        init_fee_treasury::handle(ctx)
+    }
+
+    /// Switches paying the protocol's fee share of swaps to the fee treasury
+    /// instead of accruing it per pool, at the cost of one more transfer per
+    /// swap
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether protocol fees are paid to the fee treasury
+    pub fn set_fee_treasury_enabled(
+        ctx: Context<SetFeeTreasuryEnabled>,
+        enabled: bool,
+    ) -> Result<()> {
+        set_fee_treasury_enabled::handle(ctx, enabled)
+    }
+
+    /// Sets the protocol's one-time fee on migrations, paid to the fee
+    /// treasury out of the quote moved out of the curve
+    ///
+    /// # Arguments
+    /// * `migration_fee_bps` - Fee in bps of the migrated quote, at most 5%.
+    ///   Zero for none
+    pub fn set_migration_fee_bps(
+        ctx: Context<SetMigrationFeeBps>,
+        migration_fee_bps: u16,
+    ) -> Result<()> {
+        set_migration_fee_bps::handle(ctx, migration_fee_bps)
+    }
+
+    /// Withdraws the fee treasury of a mint to the protocol fee authority
+    pub fn withdraw_fee_treasury(ctx: Context<WithdrawFeeTreasury>) -> Result<()> {
+        withdraw_fee_treasury::handle(ctx)
+    }
+
+    /// Recovers tokens sent straight to a pool vault, leaving the reserves
+    /// and admin fees untouched
+    pub fn sweep_excess(ctx: Context<SweepExcess>) -> Result<()> {
+        sweep_excess::handle(ctx)
+    }
+
+    /// Folds rounding dust left in a pool vault into its admin fees so the
+    /// vault matches the pool's accounting again
+    pub fn reconcile_dust(ctx: Context<ReconcileDust>) -> Result<()> {
+        reconcile_dust::handle(ctx)
+    }
+
+    // ===== Refund Functions =====
+
+    /// Winds down a stalled launch: disables trading and migration and lets
+    /// buyers reclaim quote tokens (creator or protocol fee authority only)
+    pub fn initiate_refund(ctx: Context<InitiateRefund>) -> Result<()> {
+        initiate_refund::handle(ctx)
+    }
+
+    /// Burns the meme the caller bought from the curve, as tracked by their
+    /// buyer record, for a pro-rata share of the quote reserve of a
+    /// refunding pool, once per wallet
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        claim_refund::handle(ctx)
     }
 
     // ===== Migration Functions =====
 
+    /// Preview of the migration: meme and quote seeding the Raydium pool
+    /// (95%) and kept on the curve (5%), with the Raydium pool's opening
+    /// price (as a `MigrationQuote` in the return data)
+    pub fn get_migration_quote(ctx: Context<GetMigrationQuote>) -> Result<()> {
+        get_migration_quote::handle(ctx)
+    }
+
+    /// Meme left to sell until the pool can migrate, with an estimate of the
+    /// quote buying it before fees (as a `MigrationProgress` in the return
+    /// data)
+    pub fn get_tokens_until_migration(ctx: Context<GetTokensUntilMigration>) -> Result<()> {
+        get_tokens_until_migration::handle(ctx)
+    }
+
     /// 🌟 Migrate bonding curve liquidity to Raydium CPMM
     ///
     /// Graduates the bonding curve to a full AMM when threshold is reached:
@@ -131,9 +830,12 @@ pub mod launchpad {
     /// # Migration Process
     /// 1. Validates 80% threshold reached
     /// 2. Locks bonding curve pool
-    /// 3. Transfers tokens to creator accounts
+    /// 3. Pays the protocol's `migration_fee_bps` of the quote to the fee
+    ///    treasury and transfers the rest, with the meme, to creator accounts
     /// 4. Calls Raydium CPMM initialize via CPI
-    /// 5. Updates pool state and emits event
+    /// 5. Locks the LP tokens under the pool signer for the pool's
+    ///    `lp_lock_duration`
+    /// 6. Updates pool state and emits events
 
     /// # Requirements
     /// - Pool must have reached 80% sell threshold
@@ -143,4 +845,43 @@ pub mod launchpad {
     pub fn migrate_to_raydium(ctx: Context<MigrateToRaydium>) -> Result<()> {
         migrate_to_raydium::handle(ctx)
     }
+
+    /// 🌟 MIGRATION: Graduate bonding curve to a Raydium CLMM pool
+    /// Same checks, fee and liquidity as `migrate_to_raydium`, seeded as a
+    /// single concentrated liquidity position whose NFT is locked like the
+    /// CPMM LP
+    ///
+    /// # Arguments
+    /// * `tick_lower_index` - Lower tick of the position
+    /// * `tick_upper_index` - Upper tick of the position, both must be the
+    ///   config's aligned tick bounds giving a full-range position
+    pub fn migrate_to_raydium_clmm(
+        ctx: Context<MigrateToRaydiumClmm>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        migrate_to_raydium_clmm::handle(ctx, tick_lower_index, tick_upper_index)
+    }
+
+    /// Graduates a pool with the `Manual` migration target by draining its
+    /// reserves to the creator, who deploys them on an AMM of their choice.
+    /// Creator only, same checks and migration fee as `migrate_to_raydium`
+    pub fn migrate_manual(ctx: Context<MigrateManual>) -> Result<()> {
+        migrate_manual::handle(ctx)
+    }
+
+    /// Upgrades a pool account written by an older program version to the
+    /// current layout, the payer covering the extra rent. Permissionless
+    pub fn migrate_pool_account(ctx: Context<MigratePoolAccount>) -> Result<()> {
+        migrate_pool_account::handle(ctx)
+    }
+
+    /// Releases the LP tokens locked at migration to the creator
+    /// Creator only, once the lock expired
+    ///
+    /// # Arguments
+    /// * `unlock_ts` - The pool's `lp_unlock_ts`, guarding against stale locks
+    pub fn claim_lp_after_lock(ctx: Context<ClaimLpAfterLock>, unlock_ts: i64) -> Result<()> {
+        claim_lp_after_lock::handle(ctx, unlock_ts)
+    }
 }
@@ -0,0 +1,402 @@
+//! Alternative bonding curve shapes, selected by [`CurveType`].
+//!
+//! Both sell the whole curve supply `M = gamma_m` for `G = gamma_s` quote and
+//! are given by the quote supply `s(m)` at which `m` meme is sold:
+//!
+//! * `Linear`: the price grows linearly with the meme sold, from `G / (2 * M)`
+//!   to three times that once `M` is sold.
+//!   `s(m) = G * (m^2 + M * m) / (2 * M^2)` and
+//!   `m(s) = (sqrt(M^2 * (G + 8 * s) / G) - M) / 2`.
+//! * `Exponential`: the price doubles every `M / n` meme sold, `n` being
+//!   `EXPONENTIAL_CURVE_DOUBLINGS`.
+//!   `s(m) = G * (2^(n * m / M) - 1) / (2^n - 1)` and
+//!   `m(s) = M * log2(1 + s * (2^n - 1) / G) / n`, evaluated in 64.64 fixed
+//!   point.
+//!
+//! Trades are priced as differences of `m(s)` and `s(m)`, each end rounded in
+//! the pool's favour, so a trade never gets more than the exact curve gives.
+//! The price factor only shapes the standard curve.
+
+use crate::consts::{EXPONENTIAL_CURVE_DOUBLINGS, SPOT_PRICE_PRECISION};
+use crate::models::bound::CurveType;
+use spl_math::uint::U256;
+
+/// ln(2) in 64.64 fixed point
+const LN_2: u128 = 12_786_308_645_202_655_659;
+
+/// Margin covering the fixed point error of the exponential curve, which is
+/// far below one token unit
+const FIXED_POINT_SLACK: u128 = 1;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Rounding {
+    Down,
+    Up,
+}
+
+/// Meme bought when the quote supply grows from `s_a` to `s_b`, rounded down
+pub fn curve_delta_m(
+    curve: CurveType,
+    gamma_s: u128,
+    gamma_m: u128,
+    s_a: u128,
+    s_b: u128,
+) -> Option<u128> {
+    let m_b = meme_sold(curve, gamma_s, gamma_m, s_b, Rounding::Down)?;
+    let m_a = meme_sold(curve, gamma_s, gamma_m, s_a, Rounding::Up)?;
+
+    Some(m_b.saturating_sub(m_a))
+}
+
+/// Quote paid for selling `delta_m` meme at quote supply `s_b`, rounded down
+pub fn curve_delta_s(
+    curve: CurveType,
+    gamma_s: u128,
+    gamma_m: u128,
+    s_b: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    let m_b = meme_sold(curve, gamma_s, gamma_m, s_b, Rounding::Up)?;
+    let s_a = quote_supply(
+        curve,
+        gamma_s,
+        gamma_m,
+        m_b.saturating_sub(delta_m),
+        Rounding::Up,
+    )?;
+
+    Some(s_b.saturating_sub(s_a))
+}
+
+/// Quote needed for [`curve_delta_m`] to return at least `delta_m` meme from
+/// quote supply `s_a`, rounded up
+pub fn curve_delta_s_in(
+    curve: CurveType,
+    gamma_s: u128,
+    gamma_m: u128,
+    s_a: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    if delta_m == 0 {
+        return Some(0);
+    }
+
+    // The forward curve rounds both ends against the trader, by up to the
+    // fixed point slack each
+    let slack = match curve {
+        CurveType::Exponential => 2 * FIXED_POINT_SLACK,
+        _ => 0,
+    };
+    let m_b = meme_sold(curve, gamma_s, gamma_m, s_a, Rounding::Up)?
+        .checked_add(delta_m)?
+        .checked_add(slack)?;
+    let s_b = quote_supply(curve, gamma_s, gamma_m, m_b, Rounding::Up)?;
+
+    Some(s_b.saturating_sub(s_a))
+}
+
+/// Quote paid per meme at quote supply `s`, scaled by `SPOT_PRICE_PRECISION`
+pub fn curve_spot_price(curve: CurveType, gamma_s: u128, gamma_m: u128, s: u128) -> Option<u128> {
+    let g = U256::from(gamma_s);
+    let m = U256::from(gamma_m);
+    let s = U256::from(s);
+    let precision = U256::from(SPOT_PRICE_PRECISION);
+
+    let price = match curve {
+        CurveType::Standard => return None,
+        // G / (2 * M) * sqrt((G + 8 * s) / G)
+        CurveType::Linear => precision
+            .checked_mul(precision)?
+            .checked_mul(g)?
+            .checked_mul(g.checked_add(s.checked_mul(U256::from(8))?)?)?
+            .integer_sqrt()
+            .checked_div(m.checked_mul(U256::from(2))?)?,
+        // n * ln(2) * (G + s * (2^n - 1)) / (M * (2^n - 1))
+        CurveType::Exponential => {
+            let growth = U256::from(exponential_growth());
+            U256::from(EXPONENTIAL_CURVE_DOUBLINGS)
+                .checked_mul(U256::from(LN_2))?
+                .checked_mul(g.checked_add(s.checked_mul(growth)?)?)?
+                .checked_mul(precision)?
+                .checked_div(m.checked_mul(growth)?.checked_mul(one())?)?
+        }
+    };
+
+    u128::try_from(price).ok()
+}
+
+/// `m(s)`, see the module docs
+fn meme_sold(
+    curve: CurveType,
+    gamma_s: u128,
+    gamma_m: u128,
+    s: u128,
+    rounding: Rounding,
+) -> Option<u128> {
+    let g = U256::from(gamma_s);
+    let m = U256::from(gamma_m);
+    let s = U256::from(s);
+
+    let sold = match curve {
+        CurveType::Standard => return None,
+        CurveType::Linear => {
+            let square = div(
+                m.checked_mul(m)?
+                    .checked_mul(g.checked_add(s.checked_mul(U256::from(8))?)?)?,
+                g,
+                rounding,
+            )?;
+            let root = square.integer_sqrt();
+            let root = if rounding == Rounding::Up && root * root < square {
+                root + 1
+            } else {
+                root
+            };
+
+            div(root.checked_sub(m)?, U256::from(2), rounding)?
+        }
+        CurveType::Exponential => {
+            let growth = U256::from(exponential_growth());
+            let y = one().checked_add(div(
+                s.checked_mul(growth)?.checked_mul(one())?,
+                g,
+                rounding,
+            )?)?;
+            let sold = div(
+                m.checked_mul(log2(y)?)?,
+                U256::from(EXPONENTIAL_CURVE_DOUBLINGS).checked_mul(one())?,
+                rounding,
+            )?;
+
+            with_slack(sold, rounding)?
+        }
+    };
+
+    u128::try_from(sold).ok()
+}
+
+/// `s(m)`, see the module docs
+fn quote_supply(
+    curve: CurveType,
+    gamma_s: u128,
+    gamma_m: u128,
+    sold: u128,
+    rounding: Rounding,
+) -> Option<u128> {
+    let g = U256::from(gamma_s);
+    let m = U256::from(gamma_m);
+    let sold = U256::from(sold);
+
+    let supply = match curve {
+        CurveType::Standard => return None,
+        CurveType::Linear => div(
+            g.checked_mul(sold.checked_mul(sold)?.checked_add(m.checked_mul(sold)?)?)?,
+            m.checked_mul(m)?.checked_mul(U256::from(2))?,
+            rounding,
+        )?,
+        CurveType::Exponential => {
+            let x = div(
+                U256::from(EXPONENTIAL_CURVE_DOUBLINGS)
+                    .checked_mul(sold)?
+                    .checked_mul(one())?,
+                m,
+                rounding,
+            )?;
+            let supply = div(
+                g.checked_mul(exp2(x)?.checked_sub(one())?)?,
+                U256::from(exponential_growth()).checked_mul(one())?,
+                rounding,
+            )?;
+
+            with_slack(supply, rounding)?
+        }
+    };
+
+    u128::try_from(supply).ok()
+}
+
+/// `2^n - 1`, the exponential curve's price growth over its supply
+fn exponential_growth() -> u128 {
+    (1u128 << EXPONENTIAL_CURVE_DOUBLINGS) - 1
+}
+
+/// 1.0 in 64.64 fixed point
+fn one() -> U256 {
+    U256::one() << 64
+}
+
+fn div(num: U256, denom: U256, rounding: Rounding) -> Option<U256> {
+    let quotient = num.checked_div(denom)?;
+    if rounding == Rounding::Up && quotient * denom != num {
+        quotient.checked_add(U256::one())
+    } else {
+        Some(quotient)
+    }
+}
+
+fn with_slack(value: U256, rounding: Rounding) -> Option<U256> {
+    match rounding {
+        Rounding::Down => Some(value.saturating_sub(U256::from(FIXED_POINT_SLACK))),
+        Rounding::Up => value.checked_add(U256::from(FIXED_POINT_SLACK)),
+    }
+}
+
+/// `2^x` of a 64.64 fixed point `x`, the fraction expanded as `e^(f * ln(2))`
+fn exp2(x: U256) -> Option<U256> {
+    let int = (x >> 64).as_u32();
+    if int >= 128 {
+        return None;
+    }
+
+    let y = ((x & (one() - 1)) * U256::from(LN_2)) >> 64;
+    let mut term = one();
+    let mut sum = one();
+    let mut k = 1u64;
+    while !term.is_zero() {
+        term = ((term * y) >> 64) / k;
+        sum += term;
+        k += 1;
+    }
+
+    sum.checked_mul(U256::one() << int)
+}
+
+/// `log2(y)` of a 64.64 fixed point `y >= 1`, rounded down
+fn log2(y: U256) -> Option<U256> {
+    if y < one() {
+        return None;
+    }
+
+    let int = (y >> 64).bits() - 1;
+    let mut result = U256::from(int) << 64;
+    let mut z = y >> int;
+
+    // Squaring the mantissa doubles its log, each overflow past 2 is a bit
+    let two = one() << 1;
+    let mut bit = one() >> 1;
+    while !bit.is_zero() {
+        z = (z * z) >> 64;
+        if z >= two {
+            z >>= 1;
+            result += bit;
+        }
+        bit >>= 1;
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAMMA_S: u128 = 85_000_000_000;
+    const GAMMA_M: u128 = 690_000_000_000_000;
+
+    #[test]
+    fn test_curves_sell_the_supply_for_gamma_s() {
+        for curve in [CurveType::Linear, CurveType::Exponential] {
+            // ACT
+            let sold = curve_delta_m(curve, GAMMA_S, GAMMA_M, 0, GAMMA_S).unwrap();
+            let raised = curve_delta_s(curve, GAMMA_S, GAMMA_M, GAMMA_S, sold).unwrap();
+
+            // ASSERT: Both ends of the curve within rounding
+            assert!(
+                sold <= GAMMA_M && GAMMA_M - sold <= 2,
+                "{:?}: {}",
+                curve,
+                sold
+            );
+            assert!(
+                raised <= GAMMA_S && GAMMA_S - raised <= 2,
+                "{:?}: {}",
+                curve,
+                raised
+            );
+        }
+
+        println!("✅ Alternative curves sell gamma_m for gamma_s");
+    }
+
+    #[test]
+    fn test_curve_prices_increase_monotonically() {
+        for curve in [CurveType::Linear, CurveType::Exponential] {
+            let step = GAMMA_S / 20;
+            let mut last_price = 0;
+            let mut last_bought = u128::MAX;
+
+            for i in 0..20 {
+                let s = i * step;
+
+                // ACT
+                let price = curve_spot_price(curve, GAMMA_S, GAMMA_M, s).unwrap();
+                let bought = curve_delta_m(curve, GAMMA_S, GAMMA_M, s, s + step).unwrap();
+
+                // ASSERT: Each step costs more and buys less than the last
+                assert!(price > last_price, "{:?} at {}", curve, s);
+                assert!(bought < last_bought, "{:?} at {}", curve, s);
+                last_price = price;
+                last_bought = bought;
+            }
+        }
+
+        // ASSERT: The linear price triples, the exponential one doubles n times
+        let linear_start = curve_spot_price(CurveType::Linear, GAMMA_S, GAMMA_M, 0).unwrap();
+        let linear_end = curve_spot_price(CurveType::Linear, GAMMA_S, GAMMA_M, GAMMA_S).unwrap();
+        assert_eq!((linear_end + linear_start / 2) / linear_start, 3);
+
+        let exp_start = curve_spot_price(CurveType::Exponential, GAMMA_S, GAMMA_M, 0).unwrap();
+        let exp_end = curve_spot_price(CurveType::Exponential, GAMMA_S, GAMMA_M, GAMMA_S).unwrap();
+        assert_eq!(
+            (exp_end + exp_start / 2) / exp_start,
+            1 << EXPONENTIAL_CURVE_DOUBLINGS
+        );
+
+        println!("✅ Linear and exponential prices increase with supply");
+    }
+
+    #[test]
+    fn test_curve_round_trips_favour_the_pool() {
+        for curve in [CurveType::Linear, CurveType::Exponential] {
+            for s_a in [0, 1_000, GAMMA_S / 3, GAMMA_S - 10_000_000] {
+                for delta_s in [1, 999, 10_000_000] {
+                    // ACT: Buy, then sell what was bought
+                    let bought =
+                        curve_delta_m(curve, GAMMA_S, GAMMA_M, s_a, s_a + delta_s).unwrap();
+                    let paid_back =
+                        curve_delta_s(curve, GAMMA_S, GAMMA_M, s_a + delta_s, bought).unwrap();
+
+                    // ASSERT: The round trip never profits
+                    assert!(paid_back <= delta_s, "{:?} at {}", curve, s_a);
+
+                    // ACT: Quote the buy back from the meme it returned
+                    let needed = curve_delta_s_in(curve, GAMMA_S, GAMMA_M, s_a, bought).unwrap();
+                    let delivered =
+                        curve_delta_m(curve, GAMMA_S, GAMMA_M, s_a, s_a + needed).unwrap();
+
+                    // ASSERT: The inverse always delivers the requested meme
+                    assert!(delivered >= bought, "{:?} at {}", curve, s_a);
+                }
+            }
+        }
+
+        println!("✅ Alternative curves never pay out more than they take in");
+    }
+
+    #[test]
+    fn test_fixed_point_exp2_and_log2() {
+        // ACT & ASSERT: Integer powers are exact
+        assert_eq!(exp2(U256::from(3) << 64).unwrap(), U256::from(8) << 64);
+        assert_eq!(log2(U256::from(8) << 64).unwrap(), U256::from(3) << 64);
+
+        // ACT & ASSERT: sqrt(2) round trips within a few units of 2^-64
+        let half = one() >> 1;
+        let root_two = exp2(half).unwrap();
+        assert_eq!(root_two >> 48, U256::from(92_681u64));
+        let log = log2(root_two).unwrap();
+        let error = if log > half { log - half } else { half - log };
+        assert!(error <= U256::from(64));
+
+        println!("✅ Fixed point exp2 and log2 match their integer values");
+    }
+}
@@ -0,0 +1,49 @@
+//! Bonding curve invariant checked after every swap.
+//!
+//! The meme reserve only moves against the quote supply along the curve: a
+//! buy growing the supply may release at most the meme the curve emits over
+//! the added quote, and a sell shrinking it must take back at least the meme
+//! the curve emitted over the removed quote. Swaps price both sides in the
+//! pool's favour, so a reserve drifting the other way beyond rounding points
+//! at a math bug.
+
+use crate::models::fees::BPS_PRECISION;
+
+/// Returns true if the meme reserve moving from `meme_before` to
+/// `meme_after` is backed by `curve_delta_m`, the meme emitted by the curve
+/// over the quote supply change, negative when the supply shrank.
+///
+/// The reserve may be off by `tolerance_bps` of the curve delta plus one
+/// unit, absorbing the rounding of the quote conversions.
+pub fn curve_invariant_holds(
+    meme_before: u64,
+    meme_after: u64,
+    curve_delta_m: i128,
+    tolerance_bps: u64,
+) -> bool {
+    let released = meme_before as i128 - meme_after as i128;
+    let slack = curve_delta_m.unsigned_abs() * tolerance_bps as u128 / BPS_PRECISION as u128 + 1;
+
+    released <= curve_delta_m + slack as i128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curve_invariant_holds() {
+        // ACT & ASSERT: A buy releasing up to the curve delta and its slack
+        assert!(curve_invariant_holds(10_000, 9_000, 1_000, 10));
+        assert!(curve_invariant_holds(10_000, 8_998, 1_000, 10));
+        assert!(!curve_invariant_holds(10_000, 8_997, 1_000, 10));
+
+        // ACT & ASSERT: A sell taking back at least the curve delta, slack
+        // aside
+        assert!(curve_invariant_holds(9_000, 10_000, -1_000, 10));
+        assert!(curve_invariant_holds(9_000, 9_998, -1_000, 10));
+        assert!(!curve_invariant_holds(9_000, 9_997, -1_000, 10));
+
+        println!("✅ Curve invariant test passed!");
+    }
+}
@@ -0,0 +1,80 @@
+//! Inverse of the bonding curve, for exact-out buys.
+//!
+//! Buying from quote supply `s_a` to `s_b` emits
+//! `delta_m = beta * (s_b - s_a) / (beta_decimals * D) + alpha * (s_b^2 - s_a^2) / (2 * alpha_decimals * D^2)`
+//! meme, `D` being the quote decimals. Solving this quadratic for
+//! `delta_s = s_b - s_a` gives
+//!
+//! `delta_s = (sqrt(u^2 + 8 * alpha * alpha_decimals * beta_decimals^2 * D^2 * delta_m) - u) / (2 * alpha * beta_decimals)`
+//!
+//! with `u = 2 * alpha * s_a * beta_decimals + 2 * beta * alpha_decimals * D`.
+//!
+//! Every step rounds up, so the forward curve evaluated at the result never
+//! emits less than `delta_m`.
+
+use crate::math::utils::CheckedMath256;
+use spl_math::uint::U256;
+
+/// Added to the requested meme, as the exact buy path rounds each of its two
+/// terms down and can end up below the true curve
+const ROUNDING_SLACK: u128 = 1;
+
+/// Quote needed to buy `delta_m` meme from quote supply `s_a` on the exact
+/// curve, rounded up
+pub fn inverse_delta_s(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s_a: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    if delta_m == 0 {
+        return Some(0);
+    }
+
+    let alpha_abs = U256::from(alpha_abs);
+    let alpha_decimals = U256::from(alpha_decimals);
+    let beta_decimals = U256::from(beta_decimals);
+    let decimals_s = U256::from(decimals_s);
+    let delta_m = U256::from(delta_m.checked_add(ROUNDING_SLACK)?);
+
+    let u = U256::from(2)
+        .checked_mul(alpha_abs)
+        .checked_mul(U256::from(s_a))
+        .checked_mul(beta_decimals)
+        .checked_add_(
+            U256::from(2)
+                .checked_mul(U256::from(beta))
+                .checked_mul(alpha_decimals)
+                .checked_mul(decimals_s),
+        )?;
+
+    let discriminant = U256::from(8)
+        .checked_mul(alpha_abs)
+        .checked_mul(alpha_decimals)
+        .checked_mul(beta_decimals.checked_pow(U256::from(2))?)
+        .checked_mul(decimals_s.checked_pow(U256::from(2))?)
+        .checked_mul(delta_m)
+        .checked_add_(u.checked_pow(U256::from(2)))?;
+
+    let root = discriminant.integer_sqrt();
+    let root = if root.checked_mul(root)? < discriminant {
+        root + 1
+    } else {
+        root
+    };
+
+    let denom = U256::from(2)
+        .checked_mul(alpha_abs)
+        .checked_mul(beta_decimals)?;
+    if denom.is_zero() {
+        return None;
+    }
+
+    let num = root.checked_sub(u)?;
+    let delta_s = num.checked_add(denom - 1)?.checked_div(denom)?;
+
+    u128::try_from(delta_s).ok()
+}
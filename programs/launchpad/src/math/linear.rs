@@ -0,0 +1,174 @@
+//! Linear approximation of the bonding curve for small trades.
+//!
+//! The curve emits meme tokens at a rate growing linearly with the quote
+//! supply `s`: `rate(s) = alpha * s / (alpha_decimals * D^2) + beta / (beta_decimals * D)`
+//! meme per quote, `D` being the quote decimals. Over a trade that is small
+//! compared to the reserves the rate barely moves, so a single multiplication
+//! by the rate at the pool's side of the trade replaces the exact integral.
+//!
+//! Both approximations use the rate at the end of the trade that is the least
+//! favourable to the trader, so they never overestimate the output.
+
+use crate::consts::{FAST_PATH_MAX_TRADE_BPS, SPOT_PRICE_PRECISION};
+use crate::models::fees::BPS_PRECISION;
+use spl_math::uint::U256;
+
+/// Subtracted from approximations, as the exact buy path rounds each of its
+/// terms down and can end up one unit below the true curve
+const ROUNDING_SLACK: u128 = 1;
+
+/// Returns true if a trade of `amount` is within the fast path fraction of
+/// `reserve`
+pub fn is_small_trade(amount: u128, reserve: u128) -> bool {
+    reserve > 0
+        && amount
+            .checked_mul(BPS_PRECISION as u128)
+            .is_some_and(|scaled| scaled <= reserve.saturating_mul(FAST_PATH_MAX_TRADE_BPS as u128))
+}
+
+/// Meme tokens bought with `delta_s` quote from `s_a`, priced at `rate(s_a)`.
+///
+/// The rate only grows while buying, so this is a lower bound of the exact
+/// `delta_m`.
+pub fn linear_delta_m(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s_a: u128,
+    delta_s: u128,
+) -> Option<u128> {
+    let (rate_num, rate_denom) = rate(
+        alpha_abs,
+        beta,
+        alpha_decimals,
+        beta_decimals,
+        decimals_s,
+        s_a,
+    )?;
+
+    U256::from(delta_s)
+        .checked_mul(rate_num)?
+        .checked_div(rate_denom)
+        .and_then(|value| u128::try_from(value).ok())
+        .map(|value| value.saturating_sub(ROUNDING_SLACK))
+}
+
+/// Quote tokens paid for selling `delta_m` meme from `s_b`, priced at
+/// `rate(s_b)`.
+///
+/// The rate is at its highest at `s_b` and only decreases while selling, so
+/// this is a lower bound of the exact `delta_s`.
+pub fn linear_delta_s(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s_b: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    let (rate_num, rate_denom) = rate(
+        alpha_abs,
+        beta,
+        alpha_decimals,
+        beta_decimals,
+        decimals_s,
+        s_b,
+    )?;
+
+    U256::from(delta_m)
+        .checked_mul(rate_denom)?
+        .checked_div(rate_num)
+        .and_then(|value| u128::try_from(value).ok())
+        .map(|value| value.saturating_sub(ROUNDING_SLACK))
+}
+
+/// Quote needed for [`linear_delta_m`] to return at least `delta_m` meme
+/// from `s_a`, rounded up
+pub fn linear_inverse_delta_s(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s_a: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    let (rate_num, rate_denom) = rate(
+        alpha_abs,
+        beta,
+        alpha_decimals,
+        beta_decimals,
+        decimals_s,
+        s_a,
+    )?;
+    if rate_num.is_zero() {
+        return None;
+    }
+
+    U256::from(delta_m.checked_add(ROUNDING_SLACK)?)
+        .checked_mul(rate_denom)?
+        .checked_add(rate_num - 1)?
+        .checked_div(rate_num)
+        .and_then(|value| u128::try_from(value).ok())
+}
+
+/// Quote paid per meme at quote supply `s`, i.e. `1 / rate(s)`, scaled by
+/// `SPOT_PRICE_PRECISION`
+pub fn spot_price(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s: u128,
+) -> Option<u128> {
+    let (rate_num, rate_denom) = rate(
+        alpha_abs,
+        beta,
+        alpha_decimals,
+        beta_decimals,
+        decimals_s,
+        s,
+    )?;
+    if rate_num.is_zero() {
+        return None;
+    }
+
+    rate_denom
+        .checked_mul(U256::from(SPOT_PRICE_PRECISION))?
+        .checked_div(rate_num)
+        .and_then(|value| u128::try_from(value).ok())
+}
+
+/// Returns `rate(s)` as a `(numerator, denominator)` pair
+fn rate(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s: u128,
+) -> Option<(U256, U256)> {
+    let alpha_decimals = U256::from(alpha_decimals);
+    let beta_decimals = U256::from(beta_decimals);
+    let decimals_s = U256::from(decimals_s);
+
+    let num = U256::from(alpha_abs)
+        .checked_mul(U256::from(s))?
+        .checked_mul(beta_decimals)?
+        .checked_add(
+            U256::from(beta)
+                .checked_mul(alpha_decimals)?
+                .checked_mul(decimals_s)?,
+        )?;
+
+    let denom = alpha_decimals
+        .checked_mul(beta_decimals)?
+        .checked_mul(decimals_s)?
+        .checked_mul(decimals_s)?;
+
+    Some((num, denom))
+}
@@ -2,7 +2,12 @@
 #![allow(clippy::ptr_offset_with_cast)]
 #![allow(clippy::manual_range_contains)]
 
+pub mod curves;
 pub mod f64;
+pub mod invariant;
+pub mod inverse;
+pub mod linear;
+pub mod rounding;
 pub mod u192_decimal;
 pub mod utils;
 
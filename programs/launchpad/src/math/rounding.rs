@@ -0,0 +1,63 @@
+//! Rounding of swap amounts, always in the pool's favour.
+//!
+//! Every division in the swap math drops a fraction of a unit. Amounts the
+//! pool pays out are rounded down and amounts it charges, fees included, are
+//! rounded up, so no sequence of trades can extract value from the pool one
+//! rounding error at a time.
+
+use crate::libraries::MulDiv;
+use spl_math::uint::U256;
+
+/// `value * num / denom` rounded down, for amounts paid out by the pool or
+/// credited to a trader's input
+pub fn mul_div_out(value: u64, num: u64, denom: u64) -> Option<u64> {
+    if denom == 0 {
+        return None;
+    }
+
+    value.mul_div_floor(num, denom)
+}
+
+/// `value * num / denom` rounded up, for amounts and fees charged by the
+/// pool
+pub fn mul_div_in(value: u64, num: u64, denom: u64) -> Option<u64> {
+    if denom == 0 {
+        return None;
+    }
+
+    value.mul_div_ceil(num, denom)
+}
+
+/// Difference of two terms rounded down separately, rounded down as an
+/// output. Rounding down the subtracted term can raise the difference by up
+/// to one unit, which is taken back.
+pub fn difference_out(difference: U256) -> U256 {
+    difference.saturating_sub(U256::one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding_favours_the_pool() {
+        // ACT & ASSERT: Outputs round down, inputs round up
+        assert_eq!(mul_div_out(10, 1, 3), Some(3));
+        assert_eq!(mul_div_in(10, 1, 3), Some(4));
+
+        // ACT & ASSERT: Exact divisions aren't rounded
+        assert_eq!(mul_div_out(9, 1, 3), Some(3));
+        assert_eq!(mul_div_in(9, 1, 3), Some(3));
+
+        // ACT & ASSERT: Overflow and division by zero fail
+        assert_eq!(mul_div_out(u64::MAX, 2, 1), None);
+        assert_eq!(mul_div_out(1, 1, 0), None);
+        assert_eq!(mul_div_in(1, 1, 0), None);
+
+        // ACT & ASSERT: Differences give back the unit their rounding may add
+        assert_eq!(difference_out(U256::from(6)), U256::from(5));
+        assert_eq!(difference_out(U256::zero()), U256::zero());
+
+        println!("✅ Pool favoured rounding test passed!");
+    }
+}
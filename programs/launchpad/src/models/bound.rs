@@ -1,15 +1,22 @@
 /// Import necessary modules from crate
 use crate::{
-    consts::DECIMALS_S,
-    err::AmmError,
+    err::{self, AmmError},
+    libraries::MulDiv,
+    math::curves::{curve_delta_m, curve_delta_s, curve_delta_s_in, curve_spot_price},
+    math::invariant::curve_invariant_holds,
+    math::inverse::inverse_delta_s,
+    math::linear::{
+        is_small_trade, linear_delta_m, linear_delta_s, linear_inverse_delta_s, spot_price,
+    },
+    math::rounding::{difference_out, mul_div_in, mul_div_out},
     math::utils::{multiply_divide, CheckedMath, CheckedMath256},
 };
 
 /// Import Anchor lang prelude for Solana program development
 use anchor_lang::prelude::*;
 
-/// Import Solana program pubkey type
-use solana_program::pubkey::Pubkey;
+/// Import Solana program pubkey type and return data limit
+use solana_program::{program::MAX_RETURN_DATA, pubkey::Pubkey};
 
 /// Import SPL math library for U256 type
 use spl_math::uint::U256;
@@ -17,12 +24,23 @@ use spl_math::uint::U256;
 /// Import standard library components
 use std::cmp::min;
 
+/// Import curve constants
+use crate::consts::{
+    CURVE_INVARIANT_TOLERANCE_BPS, DEFAULT_MAX_M, DEFAULT_MAX_M_LP, FEE_DENOMINATOR,
+    MAX_AIRDROPPED_TOKENS, MAX_CREATOR_ALLOCATION_BPS, MAX_MEME_TOKENS, MAX_RECONCILE_DUST,
+    MAX_WHITELIST_DURATION, PEGGED_QUOTE_MINTS, PRICE_FACTOR_RAMP_PRECISION, SPOT_PRICE_PRECISION,
+};
+
 /// Import related models
-use super::{fees::Fees, Reserve, SwapAmount};
+use super::{
+    fees::{cap_fee, get_amount_before_fee, FeeShares, FeeSplit, Fees, BPS_PRECISION},
+    protocol_config::ProtocolConfig,
+    Reserve, SwapAmount,
+};
 
 /// Account struct representing a bonding curve pool
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct BoundPool {
     /// Reserve account for meme tokens
     pub meme_reserve: Reserve,
@@ -34,6 +52,12 @@ pub struct BoundPool {
     pub admin_fees_quote: u64,
     /// Public key of fee vault for quote tokens
     pub fee_vault_quote: Pubkey,
+    /// Split of the quote fee between protocol, creator and referral
+    pub fee_shares: FeeShares,
+    /// Quote token account receiving the creator share of the quote fee
+    pub creator_fee_vault: Pubkey,
+    /// Quote token account receiving the referral share of the quote fee
+    pub referral_fee_vault: Pubkey,
     /// Public key of pool creator
     pub creator_addr: Pubkey,
     /// Fee configuration
@@ -46,6 +70,116 @@ pub struct BoundPool {
     pub pool_migration: bool,
     /// Raydium pool public key (if migrated)
     pub migration_pool_key: Pubkey,
+    /// Optional second quote asset raised against the same meme curve
+    pub second_quote: Option<SecondQuote>,
+    /// Running total of quote traded, fees included, in primary quote units
+    pub cumulative_quote_volume: u64,
+    /// Running total of meme tokens traded, fees included
+    pub cumulative_meme_volume: u64,
+    /// Flag indicating the launch is being wound down, buyers can claim
+    /// refunds and trading is disabled
+    pub refunding: bool,
+    /// Meme tokens bought from the curve and still held outside of it
+    pub meme_circulating: u64,
+    /// Meme tokens the curve started with, i.e. its initial meme reserve
+    pub initial_supply: u64,
+    /// Meme tokens burned by the sell tax and by refund claims
+    pub meme_burned: u64,
+    /// Creator proposed by `creator_addr`, until it accepts the transfer
+    pub pending_creator: Option<Pubkey>,
+    /// Flag indicating the meme mint authority was revoked, capping the supply
+    pub mint_authority_revoked: bool,
+    /// Raydium LP mint, once migrated
+    pub lp_mint: Pubkey,
+    /// LP tokens locked under the pool signer until `lp_unlock_ts`
+    pub lp_locked: u64,
+    /// Unix timestamp from which the creator can claim the locked LP, set at
+    /// migration to `lp_lock_duration` after it
+    pub lp_unlock_ts: i64,
+    /// Seconds the LP stays locked after migration, committed at creation
+    pub lp_lock_duration: i64,
+    /// Quote reserve, in primary quote units, below which sells are rejected
+    pub min_quote_liquidity: u64,
+    /// Creation order of the pool, see `PoolRegistry`
+    pub index: u64,
+    /// Canonical bump of the pool signer PDA, stored at creation so swaps
+    /// verify the signer without searching for it
+    pub signer_bump: u8,
+    /// AMM the pool migrated to, once migrated
+    pub migration_amm: Option<MigrationAmm>,
+    /// Migration path chosen at creation, the only one the pool accepts
+    pub migration_target: MigrationAmm,
+    /// Largest spot price move allowed within a slot, in bps, zero when the
+    /// circuit breaker is disabled
+    pub circuit_breaker_bps: u16,
+    /// Slot of the last trade tracked by the circuit breaker
+    pub last_slot: u64,
+    /// Spot price at the first trade of `last_slot`, see [`BoundPool::spot_price`]
+    pub slot_start_price: u128,
+    /// Quote reserve above which buys are rejected, zero when uncapped
+    pub max_quote_raise: u64,
+    /// Quote fee of trades against the reserves retained after migration, in
+    /// bps, zero to keep the pool's regular fee
+    pub post_migration_fee_bps: u16,
+    /// Quote moved to the Raydium pool at migration. The curve keeps pricing
+    /// from the quote supply it reached, retained reserve plus this.
+    pub quote_migrated: u64,
+    /// Quote rebated to referrers of buys, in primary quote units
+    pub referral_fees_paid: u64,
+    /// Unix timestamp until which only whitelisted wallets can buy, up to
+    /// their cap, and sells are disabled, at most `MAX_WHITELIST_DURATION`
+    /// after creation. Zero when the pool has no whitelist phase.
+    pub whitelist_until: i64,
+    /// Layout version of the account, see [`BoundPool::VERSION`]
+    pub version: u8,
+    /// Canonical bump of the meme vault PDA, see [`BoundPool::MEME_VAULT_PREFIX`]
+    pub meme_vault_bump: u8,
+    /// Canonical bump of the quote vault PDA, see [`BoundPool::QUOTE_VAULT_PREFIX`]
+    pub quote_vault_bump: u8,
+    /// Wallets that bought from the curve at least once, counted on their
+    /// first buy. Never decremented, so holders who sold out still count.
+    pub unique_buyers: u64,
+    /// Unix timestamp until which sells are rejected while buys proceed,
+    /// at most `MAX_SELL_LOCK_DURATION` after creation. Zero when sells are
+    /// open from launch.
+    pub sell_lock_until: i64,
+    /// Meme paid to the first buyer on top of their buy, in bps of it, out
+    /// of the bonus vault funded at creation. Zero disables the bonus.
+    pub first_buyer_bonus_bps: u16,
+    /// Set by the first buy, later buys get no bonus
+    pub first_buy_claimed: bool,
+    /// Slot the pool migrated at, zero until it migrated
+    pub migration_slot: u64,
+    /// Shape of the curve, chosen at creation. Kept out of `Config`, which
+    /// sits mid-account, so existing pools keep their layout.
+    pub curve_type: CurveType,
+    /// Quote per meme, scaled by `SPOT_PRICE_PRECISION`, below which sells
+    /// are rejected, see [`BoundPool::ensure_above_price_floor`]. Zero
+    /// disables the floor.
+    pub price_floor: u128,
+    /// Meme airdropped out of the LP allocation, at most
+    /// [`BoundPool::airdrop_allocation`]
+    pub airdropped: u64,
+    /// Meme reserve right before the pool migrated, kept as an audit trail of
+    /// what graduated. Default until it migrated.
+    pub meme_reserve_at_migration: Reserve,
+    /// Quote reserve right before the pool migrated, default until it
+    /// migrated
+    pub quote_reserve_at_migration: Reserve,
+    /// Directions of trading paused by the protocol, a combination of
+    /// [`BoundPool::BUYS_PAUSED`] and [`BoundPool::SELLS_PAUSED`]
+    pub trade_flags: u8,
+    /// Unix timestamp of the pool's creation, zero for pools created before
+    /// it was recorded
+    pub created_at: i64,
+    /// Seconds after `created_at` before the pool can migrate, zero for none
+    pub min_pool_age_secs: i64,
+    /// Creator share of the trading supply minted straight to the creator at
+    /// creation, circulating from launch. Zero when the share vests.
+    pub creator_share_minted: u64,
+    /// Set once `init_trade_history` created the pool's trade history, which
+    /// swaps must then pass
+    pub has_trade_history: bool,
 }
 
 impl BoundPool {
@@ -53,6 +187,208 @@ impl BoundPool {
     pub const POOL_PREFIX: &'static [u8; 10] = b"bound_pool";
     /// Prefix for signer PDA derivation
     pub const SIGNER_PDA_PREFIX: &'static [u8; 6] = b"signer";
+    /// Prefix for meme vault PDA derivation, seeded by the pool key
+    pub const MEME_VAULT_PREFIX: &'static [u8; 10] = b"meme_vault";
+    /// Prefix for quote vault PDA derivation, seeded by the pool key
+    pub const QUOTE_VAULT_PREFIX: &'static [u8; 11] = b"quote_vault";
+    /// Prefix for the first buyer bonus vault PDA, seeded by the pool key
+    pub const BONUS_VAULT_PREFIX: &'static [u8; 11] = b"bonus_vault";
+    /// Meme sold from which a pool with the default supply split can
+    /// migrate, see [`BoundPool::migration_threshold`]
+    pub const MIGRATION_THRESHOLD: u64 = (DEFAULT_MAX_M as u64 * 80) / 100;
+    /// Layout version of pools created now, bumped with every layout change.
+    /// Older accounts are brought up to it by `migrate_pool_account`:
+    /// * 0 - the layout first deployed, see `BoundPoolV0`
+    /// * 1 - `Fees` and `Config` extended, fields appended up to `version`
+    /// * 2 - fields appended up to `min_pool_age_secs`
+    /// * 3 - `creator_share_minted` appended
+    /// * 4 - `has_trade_history` appended
+    ///
+    /// Since version 1 fields are only ever appended, so an older account
+    /// reads as the current layout once zero padded.
+    pub const VERSION: u8 = 4;
+    /// Bit of `trade_flags` rejecting buys
+    pub const BUYS_PAUSED: u8 = 1 << 0;
+    /// Bit of `trade_flags` rejecting sells
+    pub const SELLS_PAUSED: u8 = 1 << 1;
+}
+
+/// AMM receiving a pool's liquidity at migration, stored as a single byte
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum MigrationAmm {
+    /// Raydium constant product pool, see `migrate_to_raydium`
+    #[default]
+    Cpmm,
+    /// Raydium concentrated liquidity pool, see `migrate_to_raydium_clmm`
+    Clmm,
+    /// No AMM, the reserves are drained to the creator to deploy elsewhere,
+    /// see `migrate_manual`
+    Manual,
+}
+
+/// Shape of a pool's bonding curve, stored as a single byte. See
+/// `math::curves` for the formulas of the alternative shapes.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum CurveType {
+    /// Curve set by `alpha_abs` and `beta`, whose meme emission rate grows
+    /// linearly with the quote supply, scaled by the price factor
+    #[default]
+    Standard,
+    /// Price growing linearly with the meme sold, tripling from start to end
+    Linear,
+    /// Price doubling every `gamma_m / EXPONENTIAL_CURVE_DOUBLINGS` meme sold
+    Exponential,
+}
+
+/// Migration preview written to the return data by `get_migration_quote`
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct MigrationQuote {
+    /// Meme seeded into the Raydium pool
+    pub meme_to_amm: u64,
+    /// Quote seeded into the Raydium pool
+    pub quote_to_amm: u64,
+    /// Meme kept on the bonding curve
+    pub meme_retained: u64,
+    /// Quote kept on the bonding curve
+    pub quote_retained: u64,
+    /// Quote per meme the Raydium pool opens at, scaled by
+    /// `SPOT_PRICE_PRECISION`
+    pub initial_price: u128,
+}
+
+/// Distance of a pool to its migration threshold, written to the return
+/// data by `get_tokens_until_migration`
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct MigrationProgress {
+    /// Meme still to be sold before the pool can migrate
+    pub meme_remaining_until_threshold: u64,
+    /// Estimated quote buying that meme on the curve, before fees
+    pub quote_needed: u64,
+}
+
+/// Reserves of one pool, written to the return data by `get_reserves`
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub struct PoolReserves {
+    /// Meme left on the curve
+    pub meme_reserve: u64,
+    /// Primary quote raised by the curve
+    pub quote_reserve: u64,
+    /// See [`BoundPool::spot_price`]
+    pub spot_price: u128,
+}
+
+impl PoolReserves {
+    /// Most pools whose reserves fit in the return data, as a borsh `Vec`
+    pub const MAX_BATCH: usize = (MAX_RETURN_DATA - 4) / PoolReserves::INIT_SPACE;
+}
+
+/// Decimals and reserves of a pool, written to the return data by
+/// `get_pool_display_info` so clients can format raw amounts
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct PoolDisplayInfo {
+    /// Decimals of the meme mint
+    pub meme_decimals: u8,
+    /// Decimals of the primary quote mint
+    pub quote_decimals: u8,
+    /// Meme left on the curve
+    pub meme_reserve: u64,
+    /// Primary quote raised by the curve
+    pub quote_reserve: u64,
+}
+
+/// Tracked and actual vault balances of a pool, written to the return data
+/// by `verify_pool_integrity`
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct PoolIntegrity {
+    /// True when both vaults hold exactly what the pool tracks
+    pub consistent: bool,
+    /// Meme the pool accounts for, see [`BoundPool::tracked_vault_amount`]
+    pub meme_tracked: u64,
+    /// Meme vault balance
+    pub meme_actual: u64,
+    /// Primary quote the pool accounts for
+    pub quote_tracked: u64,
+    /// Primary quote vault balance
+    pub quote_actual: u64,
+}
+
+/// Second quote asset for dual-asset raises (e.g. USDC + USDT)
+///
+/// Its balance is converted into primary quote units at a fixed rate and
+/// summed with `quote_reserve.tokens` to form the curve's quote measure.
+/// Both quotes are stablecoins of `PEGGED_QUOTE_MINTS` exchanged one for one,
+/// as any gap between the fixed rate and the market's would let traders buy
+/// meme with the cheaper asset and sell it for the other, draining its reserve.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub struct SecondQuote {
+    /// Reserve account for the second quote token
+    pub reserve: Reserve,
+    /// Admin fee balance in second quote tokens
+    pub admin_fees: u64,
+    /// Primary quote units per second quote unit (numerator)
+    pub price_num: u64,
+    /// Primary quote units per second quote unit (denominator)
+    pub price_denom: u64,
+}
+
+impl SecondQuote {
+    /// Rejects a second quote that isn't pegged to the primary quote: both
+    /// mints must be in `PEGGED_QUOTE_MINTS` and the rate one whole token for
+    /// one, given the mints' decimal precisions
+    pub fn ensure_pegged(
+        &self,
+        quote_mint: Pubkey,
+        quote_precision: u64,
+        second_quote_precision: u64,
+    ) -> Result<()> {
+        if !PEGGED_QUOTE_MINTS.contains(&quote_mint)
+            || !PEGGED_QUOTE_MINTS.contains(&self.reserve.mint)
+        {
+            return Err(error!(err::acc(
+                "Second quotes pair pegged stablecoins only"
+            )));
+        }
+
+        if self.price_num as u128 * second_quote_precision as u128
+            != self.price_denom as u128 * quote_precision as u128
+        {
+            return Err(error!(err::arg(
+                "Second quote rate must exchange one token for one"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Converts second quote units into primary quote units, rounding down:
+    /// a trader's input is credited, and the reserve measured, low
+    pub fn curve_units(&self, amount: u64) -> Result<u64> {
+        mul_div_out(amount, self.price_num, self.price_denom).ok_or(error!(AmmError::MathOverflow))
+    }
+
+    /// Converts primary quote units into second quote units, rounding down
+    /// as for amounts paid out
+    pub fn second_quote_units(&self, amount: u64) -> Result<u64> {
+        mul_div_out(amount, self.price_denom, self.price_num).ok_or(error!(AmmError::MathOverflow))
+    }
+
+    /// Converts primary quote units into second quote units, rounding up as
+    /// for amounts charged
+    pub fn second_quote_units_ceil(&self, amount: u64) -> Result<u64> {
+        if self.price_num == 0 {
+            return Err(error!(AmmError::DivideByZero));
+        }
+
+        mul_div_in(amount, self.price_denom, self.price_num).ok_or(error!(AmmError::MathOverflow))
+    }
 }
 
 /// Struct holding decimal configuration values
@@ -66,6 +402,27 @@ pub struct Decimals {
     pub beta: u128,
     /// Quote token decimal precision
     pub quote: u64,
+    /// Meme token decimal precision
+    pub meme: u64,
+}
+
+impl Decimals {
+    /// Checks the quote and meme precisions against the decimals of the
+    /// on-chain mints, the curve math being scaled by them
+    pub fn ensure_mint_decimals(&self, quote_decimals: u8, meme_decimals: u8) -> Result<()> {
+        let scale = |decimals: u8| 10_u64.checked_pow(decimals as u32);
+
+        if scale(quote_decimals) != Some(self.quote) || scale(meme_decimals) != Some(self.meme) {
+            return Err(error!(AmmError::DecimalsMismatch));
+        }
+
+        Ok(())
+    }
+
+    /// Mint decimals of a stored precision, e.g. 9 for `1_000_000_000`
+    pub fn mint_decimals(precision: u64) -> u8 {
+        precision.checked_ilog10().unwrap_or(0) as u8
+    }
 }
 
 /// Struct holding pool configuration parameters
@@ -81,6 +438,36 @@ pub struct Config {
     pub price_factor_num: u64,
     /// Price factor denominator
     pub price_factor_denom: u64,
+    /// Price factor numerator reached once `gamma_m` is sold, over
+    /// `price_factor_denom`. Zero disables the ramp, see
+    /// [`BoundPool::price_factor`].
+    pub price_factor_num_end: u64,
+    /// Quote fee with no meme sold, in bps, ramping down linearly to
+    /// `fee_end_bps` as the pool nears its migration threshold. Both zero
+    /// keep the flat `Fees::fee_quote_percent`, see
+    /// [`BoundPool::fee_quote_percent`].
+    pub fee_start_bps: u16,
+    /// Quote fee once the migration threshold is sold, in bps
+    pub fee_end_bps: u16,
+    /// Largest quote fee charged on a single buy, in primary quote units,
+    /// zero for no cap. Whale buys pay the cap instead of the percentage.
+    pub max_fee_quote: u64,
+    /// Largest meme fee charged on a single sell, zero for no cap
+    pub max_fee_meme: u64,
+    /// Quote the curve prices as already raised, on top of the real quote
+    /// reserves, flattening the price impact of the first buys. Includes
+    /// the quote equivalent of `virtual_meme_reserve`, folded in by
+    /// `new_pool`. Zero prices from the start of the curve.
+    ///
+    /// Only pricing sees it: `gamma_s` and the raise cap still bound the
+    /// real quote raised, and sells never pay out more than the real
+    /// reserve. The migration threshold counts real meme sold, so virtual
+    /// reserves don't bring migration closer.
+    pub virtual_quote_reserve: u64,
+    /// Meme the curve prices as already sold, as configured. The curve
+    /// prices meme from its quote measure, so this takes effect through
+    /// `virtual_quote_reserve`.
+    pub virtual_meme_reserve: u64,
     /// Maximum SOL amount in raw denomination
     pub gamma_s: u64,
     /// Maximum meme token amount in raw denomination
@@ -92,608 +479,4290 @@ pub struct Config {
 }
 
 impl BoundPool {
+    /// Prices a swap of `coin_in_amount` against the current reserves.
+    ///
+    /// `coin_out_min_value` is a strict floor on the output, fees deducted:
+    /// an output equal to it goes through, anything below panics with
+    /// `AmmError::SlippageExceeded`. No tolerance is applied on top of it.
+    /// Handlers reverting with an error instead pass zero and compare the
+    /// output themselves.
     pub fn swap_amounts(
         &self,
         coin_in_amount: u64,
         coin_out_min_value: u64,
         buy_meme: bool,
+        protocol_config: Option<&ProtocolConfig>,
+        fee_exempt: bool,
     ) -> SwapAmount {
+        let pool = self.priced(protocol_config, fee_exempt);
         if buy_meme {
-            self.buy_meme_swap_amounts(coin_in_amount, coin_out_min_value)
+            pool.buy_meme_swap_amounts(coin_in_amount, coin_out_min_value)
                 .unwrap()
         } else {
-            self.sell_meme_swap_amounts(coin_in_amount, coin_out_min_value)
+            pool.sell_meme_swap_amounts(coin_in_amount, coin_out_min_value)
                 .unwrap()
         }
     }
 
-    fn buy_meme_swap_amounts(&self, delta_s: u64, min_delta_m: u64) -> Result<SwapAmount> {
-        let (m_t0, s_t0) = self.balances();
-
-        let p = &self.config;
-
-        let max_delta_s = p.gamma_s - s_t0;
-
-        let admin_fee_in = self.fees.get_fee_quote_amount(delta_s).unwrap();
-        let is_max = delta_s - admin_fee_in >= max_delta_s;
-
-        let net_delta_s = min(delta_s - admin_fee_in, max_delta_s);
+    /// Same as [`BoundPool::swap_amounts`], but the quote side of the trade is
+    /// denominated in the second quote asset.
+    ///
+    /// # Errors
+    /// * `AmmError::InsufficientBalance` - If a sell needs more than the
+    ///   second quote reserve holds
+    /// * `AmmError::SlippageExceeded` - If less than `coin_out_min_value` comes out
+    pub fn second_quote_swap_amounts(
+        &self,
+        coin_in_amount: u64,
+        coin_out_min_value: u64,
+        buy_meme: bool,
+        protocol_config: Option<&ProtocolConfig>,
+        fee_exempt: bool,
+    ) -> Result<SwapAmount> {
+        let pool = self.priced(protocol_config, fee_exempt);
+        if buy_meme {
+            pool.buy_meme_second_quote_swap_amounts(coin_in_amount, coin_out_min_value)
+        } else {
+            pool.sell_meme_second_quote_swap_amounts(coin_in_amount, coin_out_min_value)
+        }
+    }
 
-        let delta_m = if is_max {
-            m_t0
+    /// Buys exactly `coin_out_amount` meme, paying at most `coin_in_max` of
+    /// the quote asset, fees included.
+    ///
+    /// The quote input is found by inverting the curve, then run through the
+    /// regular buy to price the trade. Any meme the curve emits above the
+    /// requested amount through rounding stays in the pool.
+    ///
+    /// # Errors
+    /// * `AmmError::SlippageExceeded` - If more than `coin_in_max` is needed
+    /// * `AmmError::InsufficientBalance` - If the pool holds too little meme
+    pub fn exact_out_swap_amounts(
+        &self,
+        coin_out_amount: u64,
+        coin_in_max: u64,
+        second_quote: bool,
+        protocol_config: Option<&ProtocolConfig>,
+        fee_exempt: bool,
+    ) -> Result<SwapAmount> {
+        let pool = self.priced(protocol_config, fee_exempt);
+        let delta_s = pool.buy_meme_quote_in(coin_out_amount)?;
+
+        let swap = if second_quote {
+            let delta_q = pool.second_quote()?.second_quote_units_ceil(delta_s)?;
+            pool.buy_meme_second_quote_swap_amounts(delta_q, coin_out_amount)?
         } else {
-            self.compute_delta_m(s_t0, s_t0 + net_delta_s)?
+            pool.buy_meme_swap_amounts(delta_s, coin_out_amount)?
         };
 
-        let admin_fee_out = self.fees.get_fee_meme_amount(delta_m).unwrap();
-        let net_delta_m = delta_m - admin_fee_out;
-
-        if net_delta_m < min_delta_m {
+        if swap.amount_in + swap.admin_fee_in > coin_in_max {
             return Err(error!(AmmError::SlippageExceeded));
         }
 
         Ok(SwapAmount {
-            amount_in: net_delta_s,
-            amount_out: net_delta_m,
-            admin_fee_in,
-            admin_fee_out,
+            amount_out: coin_out_amount,
+            ..swap
         })
     }
 
-    fn sell_meme_swap_amounts(&self, delta_m: u64, min_delta_s: u64) -> Result<SwapAmount> {
-        let (m_b, s_b) = self.balances();
+    /// Reprices a buy at the least quote buying its meme output, so a buyer
+    /// overpaying through client rounding keeps the surplus. Returns the buy
+    /// unchanged when no smaller input gets the same meme.
+    pub fn without_overpayment(
+        &self,
+        swap: SwapAmount,
+        second_quote: bool,
+        protocol_config: Option<&ProtocolConfig>,
+        fee_exempt: bool,
+    ) -> SwapAmount {
+        let paid = swap.amount_in + swap.admin_fee_in;
+        match self.exact_out_swap_amounts(
+            swap.amount_out,
+            paid,
+            second_quote,
+            protocol_config,
+            fee_exempt,
+        ) {
+            Ok(exact) if exact.amount_in + exact.admin_fee_in < paid => exact,
+            _ => swap,
+        }
+    }
+
+    /// Quote fee charged by the pool, reduced by the protocol fee tier its
+    /// cumulative quote volume reached. Without a protocol config, the pool's
+    /// own graduated fee applies, or its post-migration fee once migrated.
+    pub fn fee_quote_percent(&self, protocol_config: Option<&ProtocolConfig>) -> u64 {
+        let base_fee_percent = match self.post_migration_fee_bps {
+            bps if self.pool_migration && bps != 0 => {
+                bps as u64 * (FEE_DENOMINATOR / BPS_PRECISION)
+            }
+            _ => self.graduated_fee_quote_percent(),
+        };
+        protocol_config.map_or(base_fee_percent, |config| {
+            config.fee_quote_percent(self.cumulative_quote_volume, base_fee_percent)
+        })
+    }
 
+    /// Pool's own quote fee, in `FEE_DENOMINATOR` units.
+    ///
+    /// Without a fee ramp, this is the flat `fees.fee_quote_percent`. With a
+    /// ramp, the fee moves linearly with the meme sold, from `fee_start_bps`
+    /// with nothing sold to `fee_end_bps` at the migration threshold, and stays
+    /// there past it.
+    pub fn graduated_fee_quote_percent(&self) -> u64 {
         let p = &self.config;
+        if p.fee_start_bps == 0 && p.fee_end_bps == 0 {
+            return self.fees.fee_quote_percent;
+        }
 
-        let max_delta_m = p.gamma_m - m_b;
+        let sold = min(
+            p.gamma_m.saturating_sub(self.meme_reserve.tokens),
+            self.migration_threshold(),
+        );
+        let start = p.fee_start_bps as u64 * (FEE_DENOMINATOR / BPS_PRECISION);
+        let end = p.fee_end_bps as u64 * (FEE_DENOMINATOR / BPS_PRECISION);
 
-        let admin_fee_in = self.fees.get_fee_meme_amount(delta_m).unwrap() * 2;
-        let is_max = delta_m - admin_fee_in >= max_delta_m;
+        start
+            - start
+                .saturating_sub(end)
+                .mul_div_floor(sold, self.migration_threshold())
+                .unwrap()
+    }
 
-        let net_delta_m = min(delta_m - admin_fee_in, max_delta_m);
+    /// Price factor `(numerator, denominator)` the curve is priced with.
+    ///
+    /// Without a ramp, this is the static `price_factor_num / price_factor_denom`.
+    /// With a ramp, the factor moves linearly with the fraction `f` of
+    /// `gamma_m` sold, from `price_factor_num` at `f = 0` to
+    /// `price_factor_num_end` at `f = 1`:
+    ///
+    /// `num(f) = price_factor_num + (price_factor_num_end - price_factor_num) * f`
+    ///
+    /// `f` is floored to `1 / PRICE_FACTOR_RAMP_PRECISION` steps, and both
+    /// terms are scaled by `PRICE_FACTOR_RAMP_PRECISION` to stay integers.
+    pub fn price_factor(&self) -> (u64, u64) {
+        let p = &self.config;
+        if p.price_factor_num_end == 0 {
+            return (p.price_factor_num, p.price_factor_denom);
+        }
 
-        let delta_s = if is_max {
-            s_b
+        let sold = p.gamma_m.saturating_sub(self.meme_reserve.tokens);
+        let f = PRICE_FACTOR_RAMP_PRECISION
+            .mul_div_floor(sold, p.gamma_m)
+            .unwrap_or(PRICE_FACTOR_RAMP_PRECISION);
+
+        let start = p.price_factor_num * PRICE_FACTOR_RAMP_PRECISION;
+        let num = if p.price_factor_num_end >= p.price_factor_num {
+            start + (p.price_factor_num_end - p.price_factor_num) * f
         } else {
-            self.compute_delta_s(s_b, net_delta_m)?
+            start - (p.price_factor_num - p.price_factor_num_end) * f
         };
 
-        let admin_fee_out = self.fees.get_fee_quote_amount(delta_s).unwrap() * 2;
-        let net_delta_s = delta_s - admin_fee_out;
+        (num, p.price_factor_denom * PRICE_FACTOR_RAMP_PRECISION)
+    }
 
-        if net_delta_s < min_delta_s {
-            return Err(error!(AmmError::SlippageExceeded));
+    /// Copy of the pool priced at its fee tier, or without fees for a fee
+    /// exempt trader, and at the current point of its price factor ramp,
+    /// leaving the stored fees and curve untouched
+    fn priced(&self, protocol_config: Option<&ProtocolConfig>, fee_exempt: bool) -> BoundPool {
+        let mut pool = self.clone();
+        pool.fees.fee_quote_percent = self.fee_quote_percent(protocol_config);
+        if fee_exempt {
+            pool.fees.fee_quote_percent = 0;
+            pool.fees.fee_meme_percent = 0;
         }
 
-        Ok(SwapAmount {
-            amount_in: net_delta_m,
-            amount_out: net_delta_s,
-            admin_fee_in,
-            admin_fee_out,
-        })
+        if self.config.price_factor_num_end != 0 {
+            // Both ramp endpoints are validated at pool creation, so every
+            // factor in between yields a valid curve
+            let (num, denom) = self.price_factor();
+            let p = &mut pool.config;
+            let (alpha_abs, decimals) = compute_alpha_abs(
+                p.gamma_s as u128,
+                p.decimals.quote as u128,
+                p.gamma_m as u128,
+                p.omega_m as u128,
+                num,
+                denom,
+            )
+            .unwrap();
+            p.beta = compute_beta(
+                p.gamma_s as u128,
+                p.decimals.quote as u128,
+                p.gamma_m as u128,
+                p.omega_m as u128,
+                num,
+                denom,
+                decimals,
+            )
+            .unwrap();
+            p.alpha_abs = alpha_abs;
+            p.decimals.alpha = decimals;
+            p.decimals.beta = decimals;
+        }
+
+        pool
     }
 
-    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
-    /// Formula: price = +alpha_abs * supply + beta (positive intercept)
-    pub fn compute_delta_m(&self, s_a: u64, s_b: u64) -> Result<u64> {
-        let s_a = s_a as u128;
-        let s_b = s_b as u128;
+    /// Quote paid per meme at the pool's current quote supply, scaled by
+    /// `SPOT_PRICE_PRECISION` and priced at the current point of its price
+    /// factor ramp
+    pub fn spot_price(&self) -> Result<u128> {
+        let (_, s) = self.balances()?;
+        self.priced(None, false).spot_price_at_supply(s)
+    }
 
-        let alpha_abs = self.config.alpha_abs;
-        let beta = self.config.beta;
-        let alpha_decimals = self.config.decimals.alpha;
-        let beta_decimals = self.config.decimals.beta;
+    /// Spot price the curve would quote once only `meme_reserve` of its
+    /// trading supply is left, in the format of [`BoundPool::spot_price`].
+    /// Chart clients sample it across `0..=gamma_m` to draw the curve.
+    ///
+    /// # Errors
+    /// * `AmmError::InvalidArg` - If `meme_reserve` exceeds the trading supply
+    pub fn spot_price_at_reserve(&self, meme_reserve: u64) -> Result<u128> {
+        if meme_reserve > self.config.gamma_m {
+            return Err(error!(err::arg("Meme reserve exceeds the trading supply")));
+        }
 
-        return match delta_m1_positive_strategy(
-            alpha_abs,
-            beta,
-            alpha_decimals,
-            beta_decimals,
-            s_a,
-            s_b,
-        ) {
-            Some(delta_m) => Ok(delta_m as u64),
-            None => {
-                match delta_m2_positive_strategy(
-                    alpha_abs,
-                    beta,
-                    alpha_decimals,
-                    beta_decimals,
-                    s_a,
-                    s_b,
-                ) {
-                    Some(delta_m) => Ok(delta_m as u64),
-                    None => Err(error!(AmmError::MathOverflow)),
-                }
-            }
+        // Quote supply at which that much meme was sold off the curve
+        let pool = self.priced(None, false);
+        let s_0 = pool.config.virtual_quote_reserve;
+        let sold = pool.config.gamma_m - meme_reserve;
+        let delta_s = match sold {
+            0 => 0,
+            sold => min(pool.compute_delta_s_in(s_0, sold)?, pool.config.gamma_s),
         };
-    }
 
-    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
-    pub fn compute_delta_s(&self, s_b: u64, delta_m: u64) -> Result<u64> {
-        let s_b = s_b as u128;
-        let delta_m = delta_m as u128;
+        pool.spot_price_at_supply(s_0 + delta_s)
+    }
 
-        let alpha_abs = self.config.alpha_abs;
-        let beta = self.config.beta;
-        let alpha_decimals = self.config.decimals.alpha;
-        let beta_decimals = self.config.decimals.beta;
+    // Spot price at quote supply `s`, on this pool's curve as it is
+    fn spot_price_at_supply(&self, s: u64) -> Result<u128> {
+        let p = &self.config;
 
-        match delta_s_positive_strategy(
-            alpha_abs,
-            beta,
-            alpha_decimals,
-            beta_decimals,
-            s_b,
-            delta_m,
-        ) {
-            Some(delta_s) => Ok(delta_s as u64),
-            None => Err(error!(AmmError::MathOverflow)),
+        if self.curve_type != CurveType::Standard {
+            return curve_spot_price(
+                self.curve_type,
+                p.gamma_s as u128,
+                p.gamma_m as u128,
+                s as u128,
+            )
+            .ok_or_else(|| error!(AmmError::MathOverflow));
         }
-    }
 
-    fn balances(&self) -> (u64, u64) {
-        (self.meme_reserve.tokens, self.quote_reserve.tokens)
+        spot_price(
+            p.alpha_abs,
+            p.beta,
+            p.decimals.alpha,
+            p.decimals.beta,
+            p.decimals.quote as u128,
+            s as u128,
+        )
+        .ok_or_else(|| error!(AmmError::MathOverflow))
     }
-}
 
-/// CHANGED: Updated for positive slope calculation with POSITIVE intercept
-pub fn compute_alpha_abs(
-    gamma_s: u128,
-    gamma_s_denom: u128,
-    gamma_m: u128,
-    omega_m: u128,
-    price_factor_num: u64,
-    price_factor_denom: u64,
-) -> Result<(u128, u128)> {
-    check_slope(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+    /// Difference between the spot price before `swap` and its execution
+    /// price, fees included, in bps of the spot price. A buy getting no meme
+    /// loses everything, i.e. `BPS_PRECISION`.
+    pub fn price_impact_bps(
+        &self,
+        swap: &SwapAmount,
+        buy_meme: bool,
+        second_quote: bool,
+    ) -> Result<u64> {
+        let (quote, meme) = if buy_meme {
+            (swap.amount_in + swap.admin_fee_in, swap.amount_out)
+        } else {
+            (swap.amount_out, swap.amount_in + swap.admin_fee_in)
+        };
+        if meme == 0 {
+            return Ok(BPS_PRECISION);
+        }
 
-    // left = omega_m * price_factor_num / price_factor_denom
-    let left = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+        let spot = self.spot_price()?;
+        let execution =
+            self.quote_units(quote, second_quote) as u128 * SPOT_PRICE_PRECISION / meme as u128;
 
-    // For positive slope: price increases with supply
-    // Formula: alpha = 2 * (left - gamma_m) * gamma_s_denom^2 / gamma_s^2
-    let num = U256::from(2 * (left - gamma_m)) * U256::from(gamma_s_denom * gamma_s_denom);
-    let denom = U256::from(gamma_s * gamma_s);
+        let impact = execution.abs_diff(spot) * BPS_PRECISION as u128 / spot;
 
-    if num <= denom {
-        return Err(error!(AmmError::EGammaSAboveRelativeLimit));
+        Ok(u64::try_from(impact).unwrap_or(u64::MAX))
     }
 
-    // Calculate the scale (order of magnitude) of numerator and denominator
-    let num_scale = compute_scale(num.as_u128());
-    let denom_scale = compute_scale(denom.as_u128());
+    /// Records the spot price before the first trade of `slot`, which later
+    /// trades of the slot are held to by [`BoundPool::ensure_circuit_breaker`]
+    pub fn open_slot(&mut self, slot: u64) -> Result<()> {
+        if self.circuit_breaker_bps == 0 || slot == self.last_slot {
+            return Ok(());
+        }
 
-    // Get the difference in scales to determine required decimal precision
-    let net_scale = num_scale - denom_scale;
+        self.slot_start_price = self.spot_price()?;
+        self.last_slot = slot;
 
-    // Convert net_scale to appropriate decimal precision for alpha
-    let alpha_decimals = U256::from(compute_decimals(net_scale)?);
+        Ok(())
+    }
 
-    // For positive slope, we keep the sign positive
-    Ok((
-        ((num * alpha_decimals) / denom).as_u128(),
-        alpha_decimals.as_u128(),
-    ))
-}
+    /// Rejects a trade leaving the spot price more than `circuit_breaker_bps`
+    /// away from its price at the start of the slot
+    pub fn ensure_circuit_breaker(&self) -> Result<()> {
+        if self.circuit_breaker_bps == 0 {
+            return Ok(());
+        }
 
-pub fn compute_decimals(scale: u64) -> Result<u128> {
-    match scale {
-        0..=4 => return Err(error!(AmmError::EScaleTooLow)),
-        5 => Ok(100_000_000),
-        6 => Ok(10_000_000),
-        7 => Ok(1_000_000),
-        8 => Ok(100_000),
-        9 => Ok(10_000),
-        10 => Ok(1_000),
-        11 => Ok(100),
-        12 => Ok(10),
-        _ => Ok(1), // If scale is above 12
+        let price = self.spot_price()?;
+        let max_move = self
+            .slot_start_price
+            .checked_mul(self.circuit_breaker_bps as u128)
+            .ok_or_else(|| error!(AmmError::MathOverflow))?
+            / BPS_PRECISION as u128;
+
+        if price.abs_diff(self.slot_start_price) > max_move {
+            return Err(error!(AmmError::CircuitBreakerTripped));
+        }
+
+        Ok(())
     }
-}
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept
-pub fn compute_beta(
-    gamma_s: u128,
-    gamma_s_denom: u128,
-    gamma_m: u128,
-    omega_m: u128,
-    price_factor_num: u64,
-    price_factor_denom: u64,
-    beta_decimals: u128,
-) -> Result<u128> {
-    check_intercept(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+    /// Rejects a trade from the `before` state of the pool whose meme reserve
+    /// moved further than the curve allows, see [`curve_invariant_holds`].
+    /// The curve is priced at the `before` state, as the trade was, and
+    /// given one more second quote unit to absorb the rounding of second
+    /// quote conversions.
+    pub fn ensure_curve_invariant(&self, before: &BoundPool) -> Result<()> {
+        let (m_a, s_a) = before.balances()?;
+        let (m_b, s_b) = self.balances()?;
+
+        let rounding = match self.second_quote {
+            Some(second) => second.curve_units(1)? + 1,
+            None => 1,
+        };
 
-    let left = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+        let curve = before.priced(None, false);
+        let curve_delta_m = if s_b >= s_a {
+            curve.compute_delta_m_exact(s_a, s_b + rounding)? as i128
+        } else {
+            -(curve.compute_delta_m_exact(min(s_b + rounding, s_a), s_a)? as i128)
+        };
 
-    // For positive slope with positive intercept:
-    // beta = (2 * gamma_m - left) * gamma_s_denom / gamma_s
-    let right = 2 * gamma_m;
+        if !curve_invariant_holds(m_a, m_b, curve_delta_m, CURVE_INVARIANT_TOLERANCE_BPS) {
+            return Err(error!(AmmError::InvariantViolation));
+        }
 
-    // Now we want a positive beta, so we calculate (right - left)
-    let num = (right - left) * gamma_s_denom;
-    let denom = gamma_s;
+        Ok(())
+    }
 
-    Ok((num * beta_decimals) / denom)
-}
+    /// Largest part of a `coin_in_amount` quote buy, fees included, which
+    /// keeps the quote reserve within `max_quote_raise` at a quote fee of
+    /// `fee_quote_percent`. Buys past the cap are partially filled up to it.
+    ///
+    /// # Errors
+    /// * `AmmError::RaiseCapReached` - If the quote reserve already is at the
+    ///   cap
+    pub fn capped_quote_in(&self, coin_in_amount: u64, fee_quote_percent: u64) -> Result<u64> {
+        if self.max_quote_raise == 0 {
+            return Ok(coin_in_amount);
+        }
 
-/// CHANGED: For positive slope bonding curve - price increases as supply increases
-pub fn check_slope(
-    gamma_m: u128,
-    omega_m: u128,
-    price_factor_num: u64,
-    price_factor_denom: u64,
-) -> Result<()> {
-    // omega_m * price_factor_num / price_factor_denom
-    let pfo = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+        let room = self
+            .max_quote_raise
+            .saturating_sub(self.quote_reserve.tokens);
+        if room == 0 {
+            return Err(error!(AmmError::RaiseCapReached));
+        }
 
-    //    // omega_m * price_factor_num / price_factor_denom  <= gamma_m
+        // Rounded down, so the amount left once the fee is taken fits
+        let max_in = room
+            .mul_div_floor(FEE_DENOMINATOR, FEE_DENOMINATOR - fee_quote_percent)
+            .ok_or_else(|| error!(AmmError::MathOverflow))?;
 
-    // For positive slope: omega_m * price_factor must be GREATER than gamma_m
-    // This ensures positive slope (price increases with supply)
-    if pfo <= gamma_m {
-        return Err(error!(AmmError::BondingCurveMustBePositivelySloped));
+        Ok(min(coin_in_amount, max_in))
     }
 
-    Ok(())
-}
+    /// Rejects a buy adding `amount_in` to the quote reserve past
+    /// `max_quote_raise`
+    pub fn ensure_raise_cap(&self, amount_in: u64) -> Result<()> {
+        if self.max_quote_raise != 0
+            && self.quote_reserve.tokens.saturating_add(amount_in) > self.max_quote_raise
+        {
+            return Err(error!(AmmError::RaiseCapReached));
+        }
 
-/// CHANGED: For positive slope bonding curve with POSITIVE intercept
-pub fn check_intercept(
-    gamma_m: u128,
-    omega_m: u128,
-    price_factor_num: u64,
-    price_factor_denom: u64,
-) -> Result<()> {
-    let omp = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+        Ok(())
+    }
 
-    // For positive slope with positive intercept:
-    // This means 2 * gamma_m > omega_m * price_factor
-    if 2 * gamma_m <= omp {
-        return Err(error!(AmmError::BondingCurveInterceptMustBePositive));
+    /// Rejects trading while the pool is locked, e.g. during or after
+    /// migration, or while it is refunding
+    pub fn ensure_unlocked(&self) -> Result<()> {
+        if self.locked {
+            return Err(error!(AmmError::PoolIsLocked));
+        }
+
+        if self.refunding {
+            return Err(error!(AmmError::PoolIsRefunding));
+        }
+
+        Ok(())
     }
 
-    Ok(())
-}
+    /// Rejects pools whose account wasn't migrated to the current layout yet
+    pub fn ensure_version(&self) -> Result<()> {
+        if self.version != Self::VERSION {
+            return Err(error!(AmmError::OutdatedPoolVersion));
+        }
 
-fn compute_scale(num_: u128) -> u64 {
-    let mut num = num_;
+        Ok(())
+    }
 
-    return if num == 0 {
-        1
-    } else {
-        let mut scale = 1;
+    /// Brings a pool read from an older layout up to [`BoundPool::VERSION`].
+    /// Fields its layout lacked read as zero, each version step sets the ones
+    /// a zero doesn't suit. `meme_decimals` are the meme mint's decimals,
+    /// which version 0 didn't store.
+    pub fn upgrade(&mut self, pool_key: Pubkey, meme_decimals: u8) -> Result<()> {
+        if self.version >= Self::VERSION {
+            return Err(error!(err::acc("Pool account is already up to date")));
+        }
 
-        while num >= 10 {
-            num = num / 10;
-            scale += 1;
+        if self.version < 1 {
+            // Version 0 tracked neither the meme precision, the curve's
+            // supply and what it sold, nor the signer bump
+            self.config.decimals.meme = 10_u64
+                .checked_pow(meme_decimals as u32)
+                .ok_or(error!(AmmError::MathOverflow))?;
+            self.initial_supply = self.config.gamma_m;
+            self.meme_circulating = self
+                .initial_supply
+                .saturating_sub(self.meme_reserve.tokens + self.admin_fees_meme);
+            self.signer_bump = Pubkey::find_program_address(
+                &[Self::SIGNER_PDA_PREFIX, pool_key.as_ref()],
+                &crate::ID,
+            )
+            .1;
         }
 
-        scale
-    };
-}
+        // Versions 2 to 4 only appended fields defaulting to zero
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept delta_s calculation
-fn delta_s_positive_strategy(
-    alpha_abs: u128,
-    beta: u128,
-    alpha_decimals: u128,
-    beta_decimals: u128,
-    s_b: u128,
-    delta_m: u128,
-) -> Option<u128> {
-    let alpha_abs = U256::from(alpha_abs);
-    let beta = U256::from(beta);
-    let alpha_decimals = U256::from(alpha_decimals);
-    let beta_decimals = U256::from(beta_decimals);
-    let s_b = U256::from(s_b);
-    let delta_m = U256::from(delta_m);
-    let decimals_s = U256::from(DECIMALS_S);
+        self.version = Self::VERSION;
 
-    // For positive slope: price = +alpha_abs * supply + beta
-    // The u term now ADDS beta
-    let u = U256::from(2)
-        .checked_mul(alpha_abs)
-        .checked_mul(s_b)
-        .checked_mul(beta_decimals)
-        .checked_add_(
-            U256::from(2)
-                .checked_mul(beta)
-                .checked_mul(alpha_decimals)
-                .checked_mul(decimals_s),
-        )?;
+        Ok(())
+    }
 
-    let v = alpha_decimals
-        .checked_mul(beta_decimals)
-        .checked_mul(decimals_s)?;
+    /// Rejects `signer` unless it's the pool creator
+    pub fn ensure_creator(&self, signer: Pubkey) -> Result<()> {
+        if signer != self.creator_addr {
+            return Err(error!(AmmError::Unauthorized));
+        }
 
-    let w = U256::from(8).checked_mul(delta_m).checked_mul(alpha_abs)?;
+        Ok(())
+    }
 
-    let a = compute_a_positive(u, alpha_decimals, w, v, U256::from(1))?;
+    /// Meme the creator can airdrop out of the LP allocation, which is
+    /// `MAX_AIRDROPPED_TOKENS` unless the allocation itself is smaller
+    pub fn airdrop_allocation(&self) -> u64 {
+        min(MAX_AIRDROPPED_TOKENS, self.config.omega_m)
+    }
 
-    let b = v
-        .checked_pow(U256::from(2))
-        .checked_mul(alpha_decimals)
-        .sqrt()?;
+    /// Counts `total` meme airdropped out of the LP allocation
+    ///
+    /// # Errors
+    /// * `AmmError::AirdroppedTokensOvercap` - If it takes the pool past its
+    ///   airdrop allocation
+    pub fn record_airdrop(&mut self, total: u64) -> Result<()> {
+        let airdropped = self
+            .airdropped
+            .checked_add(total)
+            .filter(|airdropped| *airdropped <= self.airdrop_allocation())
+            .ok_or_else(|| error!(AmmError::AirdroppedTokensOvercap))?;
+        self.airdropped = airdropped;
+
+        Ok(())
+    }
 
-    let num_1 = vec![decimals_s, alpha_decimals, a, v];
-    let num_2 = vec![decimals_s, alpha_decimals, u, b];
-    let denom_ = vec![U256::from(2), alpha_abs, b, v];
+    /// Rejects cancelling a pool once anyone bought from the curve. A pool
+    /// with no volume and no circulating meme holds nothing but its own
+    /// supply, so it can be closed without owing anyone.
+    pub fn ensure_cancellable(&self) -> Result<()> {
+        if self.pool_migration {
+            return Err(error!(AmmError::AlreadyMigrated));
+        }
 
-    let left = multiply_divide(num_1, denom_.clone());
-    let right = multiply_divide(num_2, denom_);
+        if self.cumulative_quote_volume != 0 || self.meme_circulating != 0 {
+            return Err(error!(AmmError::PoolHasTraded));
+        }
 
-    // We subtract here because of the quadratic formula structure
-    left.checked_sub_(right).map(|value| value.as_u128())
-}
+        Ok(())
+    }
 
-fn compute_a_positive(
-    u: U256,
-    alpha_decimals: U256,
-    w: U256,
-    v: U256,
-    scale: U256,
-) -> Option<U256> {
-    let left = u
-        .checked_div(scale)
-        .checked_mul(u)
-        .checked_mul(alpha_decimals);
+    /// Rejects migrating from a meme vault holding less than the reserve and
+    /// admin fees the pool accounts for, e.g. after burns drained it, which
+    /// would otherwise fail halfway through the migration's transfers
+    pub fn ensure_meme_supply(&self, meme_vault_amount: u64) -> Result<()> {
+        if meme_vault_amount < self.meme_reserve.tokens + self.admin_fees_meme {
+            return Err(error!(AmmError::InsufficientTokenSupply));
+        }
 
-    let right = v.checked_div(scale).checked_mul(v).checked_mul(w);
+        Ok(())
+    }
 
-    let result = left
-        .checked_add_(right)
-        .sqrt()
-        .checked_mul(scale.integer_sqrt());
+    /// True while only whitelisted wallets can trade, see
+    /// [`BoundPool::whitelist_until`]
+    pub fn in_whitelist_phase(&self, now: i64) -> bool {
+        now < self.whitelist_until
+    }
 
-    match result {
-        Some(value) => Some(value),
-        None => compute_a_positive(
-            u,
-            alpha_decimals,
-            w,
-            v,
-            scale.checked_mul(U256::from(100)).unwrap(),
-        ),
+    /// Rejects a whitelist phase ending more than `MAX_WHITELIST_DURATION`
+    /// after `now`, holders can't sell until it ends
+    pub fn ensure_whitelist_duration(whitelist_until: i64, now: i64) -> Result<()> {
+        if whitelist_until > now + MAX_WHITELIST_DURATION {
+            return Err(error!(err::arg(
+                "Whitelist phase exceeds the maximum duration"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Meme the first buyer gets on top of `amount_out`, capped by what the
+    /// bonus vault holds. Zero once the first buy happened or when disabled.
+    pub fn first_buyer_bonus(&self, amount_out: u64, bonus_vault_amount: u64) -> u64 {
+        if self.first_buy_claimed {
+            return 0;
+        }
+
+        let bonus = amount_out as u128 * self.first_buyer_bonus_bps as u128 / BPS_PRECISION as u128;
+        min(bonus, bonus_vault_amount as u128) as u64
+    }
+
+    /// Meme minted into the bonus vault at creation, enough for a first buy
+    /// of the pool's whole `trading_supply`, the creator's share excluded
+    pub fn first_buyer_bonus_supply(trading_supply: u64, first_buyer_bonus_bps: u16) -> u64 {
+        (trading_supply as u128 * first_buyer_bonus_bps as u128 / BPS_PRECISION as u128) as u64
+    }
+
+    /// Rejects sells until [`BoundPool::sell_lock_until`] passed
+    pub fn ensure_sells_unlocked(&self, now: i64) -> Result<()> {
+        if now < self.sell_lock_until {
+            return Err(error!(AmmError::SellsLocked));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects a swap leaving out the pool's trade history once it exists,
+    /// so the feed never misses a trade
+    pub fn ensure_trade_history_passed(&self, passed: bool) -> Result<()> {
+        if self.has_trade_history && !passed {
+            return Err(error!(err::acc("Trade history of the pool is required")));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects buys while the protocol paused them
+    pub fn ensure_buys_enabled(&self) -> Result<()> {
+        if self.trade_flags & Self::BUYS_PAUSED != 0 {
+            return Err(error!(AmmError::PoolPaused));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects sells while the protocol paused them
+    pub fn ensure_sells_enabled(&self) -> Result<()> {
+        if self.trade_flags & Self::SELLS_PAUSED != 0 {
+            return Err(error!(AmmError::PoolPaused));
+        }
+
+        Ok(())
+    }
+
+    /// Validates `trade_flags` only sets known pause bits
+    pub fn validate_trade_flags(trade_flags: u8) -> Result<()> {
+        if trade_flags & !(Self::BUYS_PAUSED | Self::SELLS_PAUSED) != 0 {
+            return Err(error!(err::arg("Unknown trade flags")));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects a sell executing below [`BoundPool::price_floor`], priced as
+    /// the quote the curve pays out, fees included, per meme sold into it.
+    ///
+    /// A deliberate tradeoff against panic dumps: near the floor, sells fail
+    /// outright rather than move the price further, and holders can only
+    /// sell once buys lifted the price again, or in amounts small enough to
+    /// stay above it.
+    pub fn ensure_above_price_floor(&self, swap: &SwapAmount, second_quote: bool) -> Result<()> {
+        if self.price_floor == 0 {
+            return Ok(());
+        }
+
+        let meme = (swap.amount_in + swap.admin_fee_in) as u128;
+        let quote = self.quote_units(swap.amount_out + swap.admin_fee_out, second_quote) as u128;
+        if meme == 0 || quote * SPOT_PRICE_PRECISION / meme < self.price_floor {
+            return Err(error!(AmmError::BelowPriceFloor));
+        }
+
+        Ok(())
+    }
+
+    /// Checks shared by every migration path: `amm` is the pool's migration
+    /// target, the pool is still trading, was not migrated yet, its mints are
+    /// in the token order Raydium expects and enough meme was sold
+    pub fn ensure_migratable(
+        &self,
+        amm: MigrationAmm,
+        meme_mint: Pubkey,
+        quote_mint: Pubkey,
+    ) -> Result<()> {
+        if self.pool_migration {
+            return Err(error!(AmmError::AlreadyMigrated));
+        }
+
+        if amm != self.migration_target {
+            return Err(error!(AmmError::WrongMigrationTarget));
+        }
+
+        self.ensure_version()?;
+        self.ensure_unlocked()?;
+
+        // Raydium pools order their tokens by key, the meme being token 0
+        if amm != MigrationAmm::Manual && meme_mint >= quote_mint {
+            return Err(error!(AmmError::InvalidTokenOrder));
+        }
+
+        // Sold off the curve's supply, the vault also holds the LP supply and
+        // the meme admin fees so its balance says nothing about it
+        let sold = self.config.gamma_m.saturating_sub(self.meme_reserve.tokens);
+        if sold < self.migration_threshold() {
+            return Err(error!(AmmError::MigrationThresholdNotReached));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects migrating before the pool traded for `min_pool_age_secs`, so
+    /// it can't be pumped and graduated within a few blocks
+    pub fn ensure_min_age(&self, now: i64) -> Result<()> {
+        if now - self.created_at < self.min_pool_age_secs {
+            return Err(error!(AmmError::PoolTooYoung));
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if a token account of `mint` owned by `owner` is the
+    /// creator's account of `reserve_mint`, as the accounts a migration pays
+    /// out to must be
+    pub fn is_creator_account(&self, owner: Pubkey, mint: Pubkey, reserve_mint: Pubkey) -> bool {
+        owner == self.creator_addr && mint == reserve_mint
+    }
+
+    /// Rejects a referrer rebate paid to the buyer themselves or to the pool
+    /// creator, who could otherwise farm rebates on their own volume
+    pub fn ensure_valid_referrer(&self, referrer: Pubkey, buyer: Pubkey) -> Result<()> {
+        if referrer == buyer || referrer == self.creator_addr {
+            return Err(error!(AmmError::SelfReferral));
+        }
+
+        Ok(())
+    }
+
+    /// Meme sold from which the pool can migrate, 80% of its trading supply
+    pub fn migration_threshold(&self) -> u64 {
+        (self.config.gamma_m as u128 * 80 / 100) as u64
+    }
+
+    /// Meme left to sell until [`BoundPool::migration_threshold`], and the
+    /// quote buying it found by inverting the curve from its current supply.
+    /// Fees are left out, they depend on the fee tier and size of each buy.
+    pub fn tokens_until_migration(&self) -> Result<MigrationProgress> {
+        let sold = self.config.gamma_m.saturating_sub(self.meme_reserve.tokens);
+        let meme_remaining_until_threshold = self.migration_threshold().saturating_sub(sold);
+
+        let quote_needed = match meme_remaining_until_threshold {
+            0 => 0,
+            remaining => {
+                let (_, s_t0) = self.balances()?;
+                let max_delta_s =
+                    (self.config.gamma_s + self.config.virtual_quote_reserve).saturating_sub(s_t0);
+                min(self.compute_delta_s_in(s_t0, remaining)?, max_delta_s)
+            }
+        };
+
+        Ok(MigrationProgress {
+            meme_remaining_until_threshold,
+            quote_needed,
+        })
+    }
+
+    /// Trading and LP supply of a new pool, `0` standing for the default
+    /// `DEFAULT_MAX_M` and `DEFAULT_MAX_M_LP` respectively. Both are minted
+    /// to the meme vault, so together they can't exceed `MAX_MEME_TOKENS`.
+    ///
+    /// # Errors
+    /// * `AmmError::InvalidArg` - If the split exceeds the total supply
+    pub fn supply_split(trading_supply: u64, lp_supply: u64) -> Result<(u64, u64)> {
+        let trading_supply = match trading_supply {
+            0 => DEFAULT_MAX_M as u64,
+            supply => supply,
+        };
+        let lp_supply = match lp_supply {
+            0 => DEFAULT_MAX_M_LP as u64,
+            supply => supply,
+        };
+
+        if trading_supply as u128 + lp_supply as u128 > MAX_MEME_TOKENS {
+            return Err(error!(err::arg("Supply split exceeds the total supply")));
+        }
+
+        Ok((trading_supply, lp_supply))
+    }
+
+    /// Splits `trading_supply` between the curve and the creator, who takes
+    /// `creator_allocation_bps` of it out of what buyers can trade
+    ///
+    /// # Errors
+    /// * `AmmError::InvalidArg` - If the share exceeds
+    ///   `MAX_CREATOR_ALLOCATION_BPS`
+    pub fn creator_allocation_split(
+        trading_supply: u64,
+        creator_allocation_bps: u16,
+    ) -> Result<(u64, u64)> {
+        if creator_allocation_bps > MAX_CREATOR_ALLOCATION_BPS {
+            return Err(error!(err::arg(
+                "Creator allocation bps exceeds the maximum"
+            )));
+        }
+
+        let creator_share = (trading_supply as u128 * creator_allocation_bps as u128
+            / BPS_PRECISION as u128) as u64;
+
+        Ok((trading_supply - creator_share, creator_share))
+    }
+
+    /// Meme and quote seeded into the Raydium pool at migration.
+    /// 5% of each reserve is kept for continued bonding curve trading, see
+    /// [`BoundPool::complete_migration`].
+    pub fn migration_amounts(&self) -> (u64, u64) {
+        let meme_amount = (self.meme_reserve.tokens * 95) / 100;
+        let quote_amount = (self.quote_reserve.tokens * 95) / 100;
+
+        (meme_amount, quote_amount)
+    }
+
+    /// Sets the virtual reserves the curve prices from, converting
+    /// `virtual_meme_reserve` into the quote the curve takes to sell that
+    /// much meme past `virtual_quote_reserve`, see
+    /// [`Config::virtual_quote_reserve`]
+    ///
+    /// # Errors
+    /// * `AmmError::InvalidArg` - If the virtual quote exceeds `gamma_s`
+    pub fn set_virtual_reserves(
+        &mut self,
+        virtual_quote_reserve: u64,
+        virtual_meme_reserve: u64,
+    ) -> Result<()> {
+        let meme_as_quote = match virtual_meme_reserve {
+            0 => 0,
+            meme => self.compute_delta_s_in(virtual_quote_reserve, meme)?,
+        };
+        let virtual_quote = virtual_quote_reserve
+            .checked_add(meme_as_quote)
+            .ok_or_else(|| error!(AmmError::MathOverflow))?;
+
+        if virtual_quote > self.config.gamma_s {
+            return Err(error!(err::arg(
+                "Virtual reserves can't exceed the quote target"
+            )));
+        }
+
+        self.config.virtual_quote_reserve = virtual_quote;
+        self.config.virtual_meme_reserve = virtual_meme_reserve;
+
+        Ok(())
+    }
+
+    /// Reserves and spot price reported by `get_reserves`
+    pub fn reserves(&self) -> Result<PoolReserves> {
+        Ok(PoolReserves {
+            meme_reserve: self.meme_reserve.tokens,
+            quote_reserve: self.quote_reserve.tokens,
+            spot_price: self.spot_price()?,
+        })
+    }
+
+    /// Decimals and reserves reported by `get_pool_display_info`, the
+    /// decimals recovered from the stored `Config.decimals` precisions
+    pub fn display_info(&self) -> PoolDisplayInfo {
+        let decimals = &self.config.decimals;
+
+        PoolDisplayInfo {
+            meme_decimals: Decimals::mint_decimals(decimals.meme),
+            quote_decimals: Decimals::mint_decimals(decimals.quote),
+            meme_reserve: self.meme_reserve.tokens,
+            quote_reserve: self.quote_reserve.tokens,
+        }
+    }
+
+    /// Split of the reserves [`BoundPool::migration_amounts`] would make if
+    /// the pool migrated now, and the price the Raydium pool would open at
+    pub fn migration_quote(&self) -> MigrationQuote {
+        let (meme_to_amm, quote_to_amm) = self.migration_amounts();
+        let initial_price = match meme_to_amm {
+            0 => 0,
+            _ => quote_to_amm as u128 * SPOT_PRICE_PRECISION / meme_to_amm as u128,
+        };
+
+        MigrationQuote {
+            meme_to_amm,
+            quote_to_amm,
+            meme_retained: self.meme_reserve.tokens - meme_to_amm,
+            quote_retained: self.quote_reserve.tokens - quote_to_amm,
+            initial_price,
+        }
+    }
+
+    /// Records a migration at `slot` moving `meme_migrated` and
+    /// `quote_migrated` out of the reserves to `migration_pool_key`,
+    /// snapshotting the reserves it migrated from, and reopens the pool for
+    /// trading
+    /// against what it retained. The curve keeps pricing from the quote
+    /// supply it reached, so trades continue at the pre-migration price.
+    pub fn complete_migration(
+        &mut self,
+        migration_amm: MigrationAmm,
+        migration_pool_key: Pubkey,
+        meme_migrated: u64,
+        quote_migrated: u64,
+        slot: u64,
+    ) {
+        // A pool migrates once, see `ensure_migratable`
+        self.meme_reserve_at_migration = self.meme_reserve;
+        self.quote_reserve_at_migration = self.quote_reserve;
+
+        self.meme_reserve.tokens -= meme_migrated;
+        self.quote_reserve.tokens -= quote_migrated;
+        self.quote_migrated += quote_migrated;
+
+        self.pool_migration = true;
+        self.migration_amm = Some(migration_amm);
+        self.migration_pool_key = migration_pool_key;
+        self.migration_slot = slot;
+        self.locked = false;
+    }
+
+    /// Takes the whole second quote reserve out at migration, as the AMM
+    /// pool only pairs meme with the primary quote. The Raydium migrations
+    /// pay it to the fee treasury of its mint, see
+    /// [`BoundPool::is_second_quote_treasury`], rather than unlocked to the
+    /// creator. It's counted in `quote_migrated`, in primary quote units,
+    /// so the curve keeps pricing from the quote supply it reached. Returns
+    /// the second quote tokens to pay out, zero for single quote pools.
+    pub fn migrate_second_quote(&mut self) -> Result<u64> {
+        let Some(second) = self.second_quote.as_mut() else {
+            return Ok(0);
+        };
+
+        let amount = second.reserve.tokens;
+        self.quote_migrated = self
+            .quote_migrated
+            .checked_add(second.curve_units(amount)?)
+            .ok_or(error!(AmmError::MathOverflow))?;
+        second.reserve.tokens = 0;
+
+        Ok(amount)
+    }
+
+    /// Rejects sells paid out of a quote reserve holding less than
+    /// `min_quote_liquidity`, e.g. right after launch, before buys seeded it
+    pub fn ensure_quote_liquidity(&self, second_quote: bool) -> Result<()> {
+        let reserve = match self.second_quote.filter(|_| second_quote) {
+            Some(second) => self.quote_units(second.reserve.tokens, true),
+            None => self.quote_reserve.tokens,
+        };
+
+        if reserve < self.min_quote_liquidity {
+            return Err(error!(AmmError::InsufficientQuoteLiquidity));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the locked LP tokens the creator can claim at `now`
+    pub fn claimable_lp(&self, now: i64) -> Result<u64> {
+        if self.lp_locked == 0 {
+            return Err(error!(AmmError::NoTokensToWithdraw));
+        }
+
+        if now < self.lp_unlock_ts {
+            return Err(error!(AmmError::LpStillLocked));
+        }
+
+        Ok(self.lp_locked)
+    }
+
+    /// Adds a trade to the pool's cumulative volume. Second quote amounts are
+    /// converted into primary quote units. These are statistics only, so
+    /// they saturate instead of failing the trade on overflow.
+    pub fn record_volume(&mut self, quote_amount: u64, meme_amount: u64, second_quote: bool) {
+        let quote_amount = self.quote_units(quote_amount, second_quote);
+
+        self.cumulative_quote_volume = self.cumulative_quote_volume.saturating_add(quote_amount);
+        self.cumulative_meme_volume = self.cumulative_meme_volume.saturating_add(meme_amount);
+    }
+
+    /// Books a settled buy. `quote_fee` is the split of the quote fee as paid
+    /// out, rebate carved and vault shares folded, of which only the
+    /// protocol's share not routed to the fee treasury (`treasury_fee`) stays
+    /// in the pool. The first buyer `bonus` came from the bonus vault.
+    pub fn apply_buy(
+        &mut self,
+        swap_amount: &SwapAmount,
+        quote_fee: &FeeSplit,
+        treasury_fee: u64,
+        second_quote: bool,
+        bonus: u64,
+    ) {
+        self.referral_fees_paid += self.quote_units(quote_fee.rebate, second_quote);
+        if let Some(second) = self.second_quote.as_mut().filter(|_| second_quote) {
+            second.admin_fees += quote_fee.protocol - treasury_fee;
+            second.reserve.tokens += swap_amount.amount_in;
+        } else {
+            self.admin_fees_quote += quote_fee.protocol - treasury_fee;
+            self.quote_reserve.tokens += swap_amount.amount_in;
+        }
+        self.admin_fees_meme += swap_amount.admin_fee_out;
+
+        // Meme reserve and the meme held by buyers
+        self.meme_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
+        self.meme_circulating += swap_amount.amount_out + bonus;
+        self.first_buy_claimed = true;
+
+        self.record_volume(
+            swap_amount.amount_in + swap_amount.admin_fee_in,
+            swap_amount.amount_out + swap_amount.admin_fee_out,
+            second_quote,
+        );
+    }
+
+    /// Books a settled sell of `coin_in_amount` meme, of which `burn_amount`
+    /// was burned before pricing. `quote_fee` and `treasury_fee` as in
+    /// [`BoundPool::apply_buy`].
+    pub fn apply_sell(
+        &mut self,
+        swap_amount: &SwapAmount,
+        quote_fee: &FeeSplit,
+        treasury_fee: u64,
+        second_quote: bool,
+        coin_in_amount: u64,
+        burn_amount: u64,
+    ) {
+        self.admin_fees_meme += swap_amount.admin_fee_in;
+        if let Some(second) = self.second_quote.as_mut().filter(|_| second_quote) {
+            second.admin_fees += quote_fee.protocol - treasury_fee;
+            second.reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
+        } else {
+            self.admin_fees_quote += quote_fee.protocol - treasury_fee;
+            self.quote_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
+        }
+
+        // Meme reserve, the meme held by buyers and the burned meme
+        self.meme_reserve.tokens += swap_amount.amount_in;
+        self.meme_burned += burn_amount;
+        self.meme_circulating = self.meme_circulating.saturating_sub(coin_in_amount);
+
+        // Volume includes the burned sell tax
+        self.record_volume(
+            swap_amount.amount_out + swap_amount.admin_fee_out,
+            coin_in_amount,
+            second_quote,
+        );
+    }
+
+    /// Returns a copy of the pool as it would be after a buy of `quote_in`,
+    /// so previews can assume a pending trade lands first. Only the reserves
+    /// and volume the pricing depends on are moved, fees stay untouched.
+    pub fn after_buy(
+        &self,
+        quote_in: u64,
+        second_quote: bool,
+        protocol_config: Option<&ProtocolConfig>,
+    ) -> Result<BoundPool> {
+        let priced = self.priced(protocol_config, false);
+        let swap = if second_quote {
+            priced.buy_meme_second_quote_swap_amounts(quote_in, 0)?
+        } else {
+            priced.buy_meme_swap_amounts(quote_in, 0)?
+        };
+
+        let mut pool = self.clone();
+        match pool.second_quote.as_mut().filter(|_| second_quote) {
+            Some(second) => second.reserve.tokens += swap.amount_in,
+            None => pool.quote_reserve.tokens += swap.amount_in,
+        }
+        pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+        pool.meme_circulating += swap.amount_out;
+        pool.record_volume(
+            swap.amount_in + swap.admin_fee_in,
+            swap.amount_out + swap.admin_fee_out,
+            second_quote,
+        );
+
+        Ok(pool)
+    }
+
+    /// Converts a quote amount into primary quote units, saturating, when it
+    /// is denominated in the second quote asset
+    pub fn quote_units(&self, quote_amount: u64, second_quote: bool) -> u64 {
+        match self.second_quote.filter(|_| second_quote) {
+            Some(second) => second.curve_units(quote_amount).unwrap_or(u64::MAX),
+            None => quote_amount,
+        }
+    }
+
+    /// Returns the quote and second quote refunded for burning `meme_amount`
+    /// tokens of a refunding pool, pro-rata to the meme in circulation.
+    /// `meme_amount` must have been bought from the curve, see
+    /// `BuyerRecord::refundable`, as only those tokens are in circulation.
+    pub fn refund_amounts(&self, meme_amount: u64) -> Result<(u64, u64)> {
+        // Bought tokens can't circulate without being counted, this only
+        // guards against pools predating the counter
+        let meme_circulating = self.meme_circulating.max(meme_amount);
+
+        let pro_rata = |tokens: u64| {
+            tokens
+                .mul_div_floor(meme_amount, meme_circulating)
+                .ok_or(error!(AmmError::MathOverflow))
+        };
+
+        let refund_second_quote = match self.second_quote {
+            Some(second) => pro_rata(second.reserve.tokens)?,
+            None => 0,
+        };
+
+        Ok((pro_rata(self.quote_reserve.tokens)?, refund_second_quote))
+    }
+
+    /// Returns the meme tokens that left the curve and still exist.
+    ///
+    /// Only the curve's own supply is counted: the airdrop / LP allocation
+    /// minted alongside it never enters `initial_supply`, so it isn't
+    /// circulating until migrated or airdropped out of the pool. The creator
+    /// share minted straight to the creator circulates from launch. Burned
+    /// tokens are subtracted, as they no longer exist.
+    pub fn circulating_supply(&self) -> u64 {
+        self.initial_supply
+            .saturating_sub(self.meme_reserve.tokens)
+            .saturating_sub(self.admin_fees_meme)
+            .saturating_sub(self.meme_burned)
+            + self.airdropped
+            + self.creator_share_minted
+    }
+
+    /// Tokens the pool accounts for in `vault`: the reserve and admin fees of
+    /// its asset, plus what's left of the LP meme supply kept in the meme
+    /// vault. `None` if `vault` isn't one of the pool's vaults.
+    pub fn tracked_vault_amount(&self, vault: Pubkey) -> Option<u64> {
+        if vault == self.quote_reserve.vault {
+            Some(self.quote_reserve.tokens + self.admin_fees_quote)
+        } else if vault == self.meme_reserve.vault {
+            Some(
+                self.meme_reserve.tokens + self.admin_fees_meme + self.config.omega_m
+                    - self.airdropped,
+            )
+        } else {
+            self.second_quote
+                .filter(|second| second.reserve.vault == vault)
+                .map(|second| second.reserve.tokens + second.admin_fees)
+        }
+    }
+
+    /// Balance of `vault` above what the pool accounts for in it, see
+    /// [`BoundPool::tracked_vault_amount`]. Swept by `sweep_excess`, or
+    /// folded into the admin fees by [`BoundPool::reconcile_dust`] while
+    /// it's only dust.
+    ///
+    /// # Errors
+    /// * If `vault` isn't one of the pool's vaults
+    pub fn vault_surplus(&self, vault: Pubkey, vault_amount: u64) -> Result<u64> {
+        let tracked = self
+            .tracked_vault_amount(vault)
+            .ok_or_else(|| error!(err::acc("Vault doesn't belong to the pool")))?;
+
+        Ok(vault_amount.saturating_sub(tracked))
+    }
+
+    /// Folds the surplus of `vault`, rounding dust left by token transfers or
+    /// amounts too small to sweep, into the admin fees of its asset so the
+    /// vault and the pool's accounting match again. The reserves are left
+    /// alone, folding into them would move the curve. Returns the amount
+    /// folded, zero if the vault held no surplus.
+    ///
+    /// # Errors
+    /// * If `vault` isn't one of the pool's vaults
+    /// * If the surplus is above `MAX_RECONCILE_DUST`, it's swept instead
+    pub fn reconcile_dust(&mut self, vault: Pubkey, vault_amount: u64) -> Result<u64> {
+        let dust = self.vault_surplus(vault, vault_amount)?;
+
+        if dust > MAX_RECONCILE_DUST {
+            return Err(error!(err::arg(
+                "Vault surplus is above the dust threshold, sweep it instead"
+            )));
+        }
+
+        if vault == self.quote_reserve.vault {
+            self.admin_fees_quote += dust;
+        } else if vault == self.meme_reserve.vault {
+            self.admin_fees_meme += dust;
+        } else if let Some(second) = self.second_quote.as_mut() {
+            second.admin_fees += dust;
+        }
+
+        Ok(dust)
+    }
+
+    /// Compares the meme and primary quote vault balances with what the pool
+    /// tracks in them. A surplus is tokens sent to the vault directly and can
+    /// be swept, a shortfall means the accounting drifted.
+    pub fn integrity(&self, meme_vault_amount: u64, quote_vault_amount: u64) -> PoolIntegrity {
+        let meme_tracked = self
+            .tracked_vault_amount(self.meme_reserve.vault)
+            .unwrap_or_default();
+        let quote_tracked = self
+            .tracked_vault_amount(self.quote_reserve.vault)
+            .unwrap_or_default();
+
+        PoolIntegrity {
+            consistent: meme_vault_amount == meme_tracked && quote_vault_amount == quote_tracked,
+            meme_tracked,
+            meme_actual: meme_vault_amount,
+            quote_tracked,
+            quote_actual: quote_vault_amount,
+        }
+    }
+
+    /// Address of the primary quote vault of the pool at `pool_key`, derived
+    /// with the bump stored at creation rather than searched for
+    pub fn derived_quote_vault(&self, pool_key: Pubkey) -> Pubkey {
+        Pubkey::create_program_address(
+            &[
+                Self::QUOTE_VAULT_PREFIX,
+                pool_key.as_ref(),
+                &[self.quote_vault_bump],
+            ],
+            &crate::ID,
+        )
+        .unwrap_or_default()
+    }
+
+    /// Returns true if `vault` is the vault of the second quote reserve
+    pub fn is_second_quote_vault(&self, vault: Pubkey) -> bool {
+        self.second_quote
+            .is_some_and(|second| second.reserve.vault == vault)
+    }
+
+    /// Returns true if `treasury` is the protocol's fee treasury of the
+    /// second quote mint, receiving the second quote reserve at migration
+    pub fn is_second_quote_treasury(&self, treasury: Pubkey) -> bool {
+        self.second_quote.is_some_and(|second| {
+            let (address, _) = Pubkey::find_program_address(
+                &[
+                    ProtocolConfig::FEE_TREASURY_PREFIX,
+                    second.reserve.mint.as_ref(),
+                ],
+                &crate::ID,
+            );
+            address == treasury
+        })
+    }
+
+    fn buy_meme_swap_amounts(&self, delta_s: u64, min_delta_m: u64) -> Result<SwapAmount> {
+        let (m_t0, s_t0) = self.balances()?;
+
+        let p = &self.config;
+
+        let max_delta_s = p.gamma_s + p.virtual_quote_reserve - s_t0;
+
+        let fee = self.fees.get_fee_quote_amount(delta_s).unwrap();
+        let admin_fee_in = cap_fee(fee, p.max_fee_quote);
+        let is_max = delta_s - admin_fee_in >= max_delta_s;
+
+        let net_delta_s = min(delta_s - admin_fee_in, max_delta_s);
+
+        let delta_m = if is_max {
+            m_t0
+        } else {
+            self.compute_delta_m(s_t0, s_t0 + net_delta_s)?
+        };
+
+        // After migration the curve can emit more than the retained reserve
+        if delta_m > m_t0 {
+            return Err(error!(AmmError::InsufficientBalance));
+        }
+
+        let admin_fee_out = self.fees.get_fee_meme_amount(delta_m).unwrap();
+        let net_delta_m = delta_m - admin_fee_out;
+
+        if net_delta_m < min_delta_m {
+            return Err(error!(AmmError::SlippageExceeded));
+        }
+
+        Ok(SwapAmount {
+            amount_in: net_delta_s,
+            amount_out: net_delta_m,
+            admin_fee_in,
+            admin_fee_out,
+            quote_fee: self.fee_shares.split(admin_fee_in),
+            fee_capped: admin_fee_in < fee,
+        })
+    }
+
+    fn sell_meme_swap_amounts(&self, delta_m: u64, min_delta_s: u64) -> Result<SwapAmount> {
+        let (m_b, s_b) = self.balances()?;
+
+        let p = &self.config;
+
+        let max_delta_m = p.gamma_m - m_b;
+
+        let fee = self.fees.get_fee_meme_amount(delta_m).unwrap() * 2;
+        let admin_fee_in = cap_fee(fee, p.max_fee_meme);
+        let is_max = delta_m - admin_fee_in >= max_delta_m;
+
+        let net_delta_m = min(delta_m - admin_fee_in, max_delta_m);
+
+        // Virtual quote was never paid in, so it is never paid out
+        let delta_s = if is_max {
+            s_b - p.virtual_quote_reserve
+        } else {
+            self.compute_delta_s(s_b, net_delta_m)?
+        };
+
+        let admin_fee_out = self.fees.get_fee_quote_amount(delta_s).unwrap() * 2;
+        let net_delta_s = delta_s - admin_fee_out;
+
+        if net_delta_s < min_delta_s {
+            return Err(error!(AmmError::SlippageExceeded));
+        }
+
+        Ok(SwapAmount {
+            amount_in: net_delta_m,
+            amount_out: net_delta_s,
+            admin_fee_in,
+            admin_fee_out,
+            quote_fee: self.fee_shares.split(admin_fee_out),
+            fee_capped: admin_fee_in < fee,
+        })
+    }
+
+    /// Quote, fees included, for which `buy_meme_swap_amounts` returns at
+    /// least `net_delta_m` meme
+    fn buy_meme_quote_in(&self, net_delta_m: u64) -> Result<u64> {
+        let (m_t0, s_t0) = self.balances()?;
+
+        let p = &self.config;
+
+        let delta_m = get_amount_before_fee(net_delta_m, self.fees.fee_meme_percent)?;
+        if delta_m > m_t0 {
+            return Err(error!(AmmError::InsufficientBalance));
+        }
+
+        // Past the quote target the whole meme reserve is sold anyway
+        let max_delta_s = p.gamma_s + p.virtual_quote_reserve - s_t0;
+        let net_delta_s = min(self.compute_delta_s_in(s_t0, delta_m)?, max_delta_s);
+
+        let delta_s = get_amount_before_fee(net_delta_s, self.fees.fee_quote_percent)?;
+
+        // A capped fee never takes more than the cap on top of the net input
+        match p.max_fee_quote {
+            0 => Ok(delta_s),
+            max_fee => Ok(min(delta_s, net_delta_s + max_fee)),
+        }
+    }
+
+    fn second_quote(&self) -> Result<&SecondQuote> {
+        self.second_quote
+            .as_ref()
+            .ok_or(error!(AmmError::InvalidTokenMints))
+    }
+
+    fn buy_meme_second_quote_swap_amounts(
+        &self,
+        delta_q: u64,
+        min_delta_m: u64,
+    ) -> Result<SwapAmount> {
+        let second = self.second_quote()?;
+
+        let swap = self.buy_meme_swap_amounts(second.curve_units(delta_q)?, min_delta_m)?;
+
+        // Express the quote side back in second quote units, rounding in the
+        // pool's favour but never charging more than was provided
+        let admin_fee_in = min(second.second_quote_units_ceil(swap.admin_fee_in)?, delta_q);
+        let amount_in = min(
+            second.second_quote_units_ceil(swap.amount_in)?,
+            delta_q - admin_fee_in,
+        );
+
+        // Second quote fees all accrue to the protocol
+        Ok(SwapAmount {
+            amount_in,
+            amount_out: swap.amount_out,
+            admin_fee_in,
+            admin_fee_out: swap.admin_fee_out,
+            quote_fee: FeeSplit::protocol_only(admin_fee_in),
+            fee_capped: swap.fee_capped,
+        })
+    }
+
+    fn sell_meme_second_quote_swap_amounts(
+        &self,
+        delta_m: u64,
+        min_delta_q: u64,
+    ) -> Result<SwapAmount> {
+        let second = self.second_quote()?;
+
+        let swap = self.sell_meme_swap_amounts(delta_m, 0)?;
+
+        let amount_out = second.second_quote_units(swap.amount_out)?;
+        let admin_fee_out = second.second_quote_units(swap.admin_fee_out)?;
+
+        if amount_out + admin_fee_out > second.reserve.tokens {
+            return Err(error!(AmmError::InsufficientBalance));
+        }
+
+        if amount_out < min_delta_q {
+            return Err(error!(AmmError::SlippageExceeded));
+        }
+
+        // Second quote fees all accrue to the protocol
+        Ok(SwapAmount {
+            amount_in: swap.amount_in,
+            amount_out,
+            admin_fee_in: swap.admin_fee_in,
+            admin_fee_out,
+            quote_fee: FeeSplit::protocol_only(admin_fee_out),
+            fee_capped: swap.fee_capped,
+        })
+    }
+
+    /// Meme tokens bought when the quote supply grows from `s_a` to `s_b`.
+    /// Trades small compared to the quote reserve of a standard curve take
+    /// the linear fast path.
+    pub fn compute_delta_m(&self, s_a: u64, s_b: u64) -> Result<u64> {
+        if self.curve_type == CurveType::Standard
+            && is_small_trade((s_b - s_a) as u128, s_a as u128)
+        {
+            let decimals = &self.config.decimals;
+            if let Some(delta_m) = linear_delta_m(
+                self.config.alpha_abs,
+                self.config.beta,
+                decimals.alpha,
+                decimals.beta,
+                decimals.quote as u128,
+                s_a as u128,
+                (s_b - s_a) as u128,
+            ) {
+                return Ok(delta_m as u64);
+            }
+        }
+
+        self.compute_delta_m_exact(s_a, s_b)
+    }
+
+    /// Quote tokens paid for selling `delta_m` meme at quote supply `s_b`.
+    /// Trades small compared to the quote reserve of a standard curve take
+    /// the linear fast path.
+    pub fn compute_delta_s(&self, s_b: u64, delta_m: u64) -> Result<u64> {
+        if self.curve_type != CurveType::Standard {
+            return self.compute_delta_s_exact(s_b, delta_m);
+        }
+
+        let decimals = &self.config.decimals;
+        let fast_delta_s = linear_delta_s(
+            self.config.alpha_abs,
+            self.config.beta,
+            decimals.alpha,
+            decimals.beta,
+            decimals.quote as u128,
+            s_b as u128,
+            delta_m as u128,
+        );
+
+        match fast_delta_s {
+            Some(delta_s) if is_small_trade(delta_s, s_b as u128) => Ok(delta_s as u64),
+            _ => self.compute_delta_s_exact(s_b, delta_m),
+        }
+    }
+
+    /// Quote needed for [`BoundPool::compute_delta_m`] to return at least
+    /// `delta_m` meme from quote supply `s_a`.
+    /// Small trades are priced by the linear fast path, below the exact curve,
+    /// so they need the larger of both inverses.
+    pub fn compute_delta_s_in(&self, s_a: u64, delta_m: u64) -> Result<u64> {
+        if self.curve_type != CurveType::Standard {
+            let p = &self.config;
+            return curve_delta_s_in(
+                self.curve_type,
+                p.gamma_s as u128,
+                p.gamma_m as u128,
+                s_a as u128,
+                delta_m as u128,
+            )
+            .and_then(|delta_s| u64::try_from(delta_s).ok())
+            .ok_or_else(|| error!(AmmError::MathOverflow));
+        }
+
+        let decimals = &self.config.decimals;
+        let delta_s = inverse_delta_s(
+            self.config.alpha_abs,
+            self.config.beta,
+            decimals.alpha,
+            decimals.beta,
+            decimals.quote as u128,
+            s_a as u128,
+            delta_m as u128,
+        )
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+        let delta_s = if is_small_trade(delta_s, s_a as u128) {
+            linear_inverse_delta_s(
+                self.config.alpha_abs,
+                self.config.beta,
+                decimals.alpha,
+                decimals.beta,
+                decimals.quote as u128,
+                s_a as u128,
+                delta_m as u128,
+            )
+            .map_or(delta_s, |linear| linear.max(delta_s))
+        } else {
+            delta_s
+        };
+
+        u64::try_from(delta_s).map_err(|_| error!(AmmError::MathOverflow))
+    }
+
+    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
+    /// Formula: price = +alpha_abs * supply + beta (positive intercept)
+    fn compute_delta_m_exact(&self, s_a: u64, s_b: u64) -> Result<u64> {
+        let s_a = s_a as u128;
+        let s_b = s_b as u128;
+
+        if self.curve_type != CurveType::Standard {
+            let p = &self.config;
+            return curve_delta_m(
+                self.curve_type,
+                p.gamma_s as u128,
+                p.gamma_m as u128,
+                s_a,
+                s_b,
+            )
+            .and_then(|delta_m| u64::try_from(delta_m).ok())
+            .ok_or_else(|| error!(AmmError::MathOverflow));
+        }
+
+        let alpha_abs = self.config.alpha_abs;
+        let beta = self.config.beta;
+        let alpha_decimals = self.config.decimals.alpha;
+        let beta_decimals = self.config.decimals.beta;
+        let decimals_s = self.config.decimals.quote as u128;
+
+        return match delta_m1_positive_strategy(
+            alpha_abs,
+            beta,
+            alpha_decimals,
+            beta_decimals,
+            decimals_s,
+            s_a,
+            s_b,
+        ) {
+            Some(delta_m) => Ok(delta_m as u64),
+            None => {
+                match delta_m2_positive_strategy(
+                    alpha_abs,
+                    beta,
+                    alpha_decimals,
+                    beta_decimals,
+                    decimals_s,
+                    s_a,
+                    s_b,
+                ) {
+                    Some(delta_m) => Ok(delta_m as u64),
+                    None => Err(error!(AmmError::MathOverflow)),
+                }
+            }
+        };
+    }
+
+    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
+    fn compute_delta_s_exact(&self, s_b: u64, delta_m: u64) -> Result<u64> {
+        let s_b = s_b as u128;
+        let delta_m = delta_m as u128;
+
+        if self.curve_type != CurveType::Standard {
+            let p = &self.config;
+            return curve_delta_s(
+                self.curve_type,
+                p.gamma_s as u128,
+                p.gamma_m as u128,
+                s_b,
+                delta_m,
+            )
+            .and_then(|delta_s| u64::try_from(delta_s).ok())
+            .ok_or_else(|| error!(AmmError::MathOverflow));
+        }
+
+        let alpha_abs = self.config.alpha_abs;
+        let beta = self.config.beta;
+        let alpha_decimals = self.config.decimals.alpha;
+        let beta_decimals = self.config.decimals.beta;
+        let decimals_s = self.config.decimals.quote as u128;
+
+        match delta_s_positive_strategy(
+            alpha_abs,
+            beta,
+            alpha_decimals,
+            beta_decimals,
+            decimals_s,
+            s_b,
+            delta_m,
+        ) {
+            Some(delta_s) => Ok(delta_s as u64),
+            None => Err(error!(AmmError::MathOverflow)),
+        }
+    }
+
+    /// Returns the meme reserve and the curve's quote measure, which sums the
+    /// primary quote reserve with the second quote reserve (if any) expressed
+    /// in primary quote units, the quote migrated and the virtual quote
+    /// reserve
+    ///
+    /// # Errors
+    /// * `AmmError::MathOverflow` - If the quote measure overflows
+    fn balances(&self) -> Result<(u64, u64)> {
+        let second_quote_tokens = match self.second_quote {
+            Some(second) => second.curve_units(second.reserve.tokens)?,
+            None => 0,
+        };
+
+        let quote_measure = [
+            second_quote_tokens,
+            self.quote_migrated,
+            self.config.virtual_quote_reserve,
+        ]
+        .into_iter()
+        .try_fold(self.quote_reserve.tokens, u64::checked_add)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok((self.meme_reserve.tokens, quote_measure))
+    }
+}
+
+/// CHANGED: Updated for positive slope calculation with POSITIVE intercept
+pub fn compute_alpha_abs(
+    gamma_s: u128,
+    gamma_s_denom: u128,
+    gamma_m: u128,
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+) -> Result<(u128, u128)> {
+    check_slope(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+
+    // left = omega_m * price_factor_num / price_factor_denom
+    let left = omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .unwrap();
+
+    // For positive slope: price increases with supply
+    // Formula: alpha = 2 * (left - gamma_m) * gamma_s_denom^2 / gamma_s^2
+    let num = U256::from(2 * (left - gamma_m)) * U256::from(gamma_s_denom * gamma_s_denom);
+    let denom = U256::from(gamma_s * gamma_s);
+
+    if num <= denom {
+        return Err(error!(AmmError::EGammaSAboveRelativeLimit));
+    }
+
+    // Calculate the scale (order of magnitude) of numerator and denominator
+    let num_scale = compute_scale(num.as_u128());
+    let denom_scale = compute_scale(denom.as_u128());
+
+    // Get the difference in scales to determine required decimal precision
+    let net_scale = num_scale - denom_scale;
+
+    // Convert net_scale to appropriate decimal precision for alpha
+    let alpha_decimals = U256::from(compute_decimals(net_scale)?);
+
+    // For positive slope, we keep the sign positive
+    Ok((
+        ((num * alpha_decimals) / denom).as_u128(),
+        alpha_decimals.as_u128(),
+    ))
+}
+
+pub fn compute_decimals(scale: u64) -> Result<u128> {
+    match scale {
+        0..=4 => return Err(error!(AmmError::EScaleTooLow)),
+        5 => Ok(100_000_000),
+        6 => Ok(10_000_000),
+        7 => Ok(1_000_000),
+        8 => Ok(100_000),
+        9 => Ok(10_000),
+        10 => Ok(1_000),
+        11 => Ok(100),
+        12 => Ok(10),
+        _ => Ok(1), // If scale is above 12
+    }
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept
+pub fn compute_beta(
+    gamma_s: u128,
+    gamma_s_denom: u128,
+    gamma_m: u128,
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+    beta_decimals: u128,
+) -> Result<u128> {
+    check_intercept(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+
+    let left = omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .unwrap();
+
+    // For positive slope with positive intercept:
+    // beta = (2 * gamma_m - left) * gamma_s_denom / gamma_s
+    let right = 2 * gamma_m;
+
+    // Now we want a positive beta, so we calculate (right - left)
+    let num = (right - left) * gamma_s_denom;
+    let denom = gamma_s;
+
+    Ok((num * beta_decimals) / denom)
+}
+
+/// CHANGED: For positive slope bonding curve - price increases as supply increases
+pub fn check_slope(
+    gamma_m: u128,
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+) -> Result<()> {
+    // omega_m * price_factor_num / price_factor_denom
+    let pfo = omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .unwrap();
+
+    //    // omega_m * price_factor_num / price_factor_denom  <= gamma_m
+
+    // For positive slope: omega_m * price_factor must be GREATER than gamma_m
+    // This ensures positive slope (price increases with supply)
+    if pfo <= gamma_m {
+        return Err(error!(AmmError::BondingCurveMustBePositivelySloped));
+    }
+
+    Ok(())
+}
+
+/// CHANGED: For positive slope bonding curve with POSITIVE intercept
+pub fn check_intercept(
+    gamma_m: u128,
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+) -> Result<()> {
+    let omp = omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .unwrap();
+
+    // For positive slope with positive intercept:
+    // This means 2 * gamma_m > omega_m * price_factor
+    if 2 * gamma_m <= omp {
+        return Err(error!(AmmError::BondingCurveInterceptMustBePositive));
+    }
+
+    Ok(())
+}
+
+fn compute_scale(num_: u128) -> u64 {
+    let mut num = num_;
+
+    return if num == 0 {
+        1
+    } else {
+        let mut scale = 1;
+
+        while num >= 10 {
+            num = num / 10;
+            scale += 1;
+        }
+
+        scale
+    };
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept delta_s calculation
+fn delta_s_positive_strategy(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s_b: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    let alpha_abs = U256::from(alpha_abs);
+    let beta = U256::from(beta);
+    let alpha_decimals = U256::from(alpha_decimals);
+    let beta_decimals = U256::from(beta_decimals);
+    let s_b = U256::from(s_b);
+    let delta_m = U256::from(delta_m);
+    let decimals_s = U256::from(decimals_s);
+
+    // For positive slope: price = +alpha_abs * supply + beta
+    // The u term now ADDS beta
+    let u = U256::from(2)
+        .checked_mul(alpha_abs)
+        .checked_mul(s_b)
+        .checked_mul(beta_decimals)
+        .checked_add_(
+            U256::from(2)
+                .checked_mul(beta)
+                .checked_mul(alpha_decimals)
+                .checked_mul(decimals_s),
+        )?;
+
+    let v = alpha_decimals
+        .checked_mul(beta_decimals)
+        .checked_mul(decimals_s)?;
+
+    let w = U256::from(8).checked_mul(delta_m).checked_mul(alpha_abs)?;
+
+    let a = compute_a_positive(u, alpha_decimals, w, v, U256::from(1))?;
+
+    let b = v
+        .checked_pow(U256::from(2))
+        .checked_mul(alpha_decimals)
+        .sqrt()?;
+
+    let num_1 = vec![decimals_s, alpha_decimals, a, v];
+    let num_2 = vec![decimals_s, alpha_decimals, u, b];
+    let denom_ = vec![U256::from(2), alpha_abs, b, v];
+
+    let left = multiply_divide(num_1, denom_.clone());
+    let right = multiply_divide(num_2, denom_);
+
+    // We subtract here because of the quadratic formula structure, the quote
+    // paid out rounded down
+    left.checked_sub_(right)
+        .map(|value| difference_out(value).as_u128())
+}
+
+fn compute_a_positive(
+    u: U256,
+    alpha_decimals: U256,
+    w: U256,
+    v: U256,
+    scale: U256,
+) -> Option<U256> {
+    let left = u
+        .checked_div(scale)
+        .checked_mul(u)
+        .checked_mul(alpha_decimals);
+
+    let right = v.checked_div(scale).checked_mul(v).checked_mul(w);
+
+    let result = left
+        .checked_add_(right)
+        .sqrt()
+        .checked_mul(scale.integer_sqrt());
+
+    match result {
+        Some(value) => Some(value),
+        None => compute_a_positive(
+            u,
+            alpha_decimals,
+            w,
+            v,
+            scale.checked_mul(U256::from(100)).unwrap(),
+        ),
+    }
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 1)
+fn delta_m1_positive_strategy(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s_a: u128,
+    s_b: u128,
+) -> Option<u128> {
+    // For positive intercept, we ADD the beta term
+    let left_num = s_b.checked_sub(s_a)?.checked_mul(beta)?;
+    let left_denom = beta_decimals.checked_mul(decimals_s)?;
+    let left = Some(left_num).checked_div_(Some(left_denom))?;
+
+    let s_b_squared = s_b.checked_pow(2)?;
+    let s_a_squared = s_a.checked_pow(2)?;
+    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
+    let decimals_s_squared = decimals_s.checked_pow(2)?;
+
+    let right = power_diff
+        .checked_mul(alpha_abs)
+        .checked_div(decimals_s_squared)?
+        .checked_div(2 * alpha_decimals)?;
+
+    // For positive slope with positive intercept, we ADD beta term
+    Some(left).checked_add_(Some(right))
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 2)
+fn delta_m2_positive_strategy(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    decimals_s: u128,
+    s_a: u128,
+    s_b: u128,
+) -> Option<u128> {
+    // For positive slope: price = +alpha_abs * supply + beta
+    // We ADD beta term
+    let left = (beta * 2)
+        .checked_mul(decimals_s)
+        .checked_mul(alpha_decimals)
+        .checked_mul(s_b - s_a)?;
+
+    let s_b_squared = s_b.checked_pow(2)?;
+    let s_a_squared = s_a.checked_pow(2)?;
+    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
+
+    let right = alpha_abs
+        .checked_mul(beta_decimals)
+        .checked_mul(power_diff)?;
+
+    let denom = (2 * alpha_decimals)
+        .checked_mul(beta_decimals)
+        .checked_mul(decimals_s.checked_pow(2)?)?;
+
+    // For positive slope with positive intercept, we ADD both terms
+    left.checked_add(right)?.checked_div(denom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Reserve;
+    use super::*;
+    use crate::models::fees::FEE;
+
+    // Helper function to create a test pool configuration
+    fn create_test_config() -> Config {
+        Config {
+            alpha_abs: 1_000_000, // Smaller alpha for gentler slope
+            beta: 1_000_000_000,  // 1.0 with 9 decimals (positive intercept)
+            price_factor_num: 1,  // Simple 1:1 ratio
+            price_factor_num_end: 0,
+            fee_start_bps: 0,
+            fee_end_bps: 0,
+            max_fee_quote: 0,
+            max_fee_meme: 0,
+            virtual_quote_reserve: 0,
+            virtual_meme_reserve: 0,
+            price_factor_denom: 10, // This gives omega_m * 1/10 = 300, clearly satisfying 2*gamma_m > omega_m*price_factor (6000 > 300)
+            gamma_s: 1_000_000_000_000, // 1000 SOL
+            gamma_m: 3_000_000_000_000, // 3000 tokens (increased to satisfy constraint better)
+            omega_m: 3_000_000_000_000, // 3000 tokens (same as gamma_m)
+            decimals: Decimals {
+                alpha: 1_000_000,     // 6 decimals for alpha
+                beta: 1_000_000_000,  // 9 decimals for beta
+                quote: 1_000_000_000, // 9 decimals (SOL)
+                meme: 1_000_000,      // 6 decimals
+            },
+        }
+    }
+
+    // Helper function to create a test pool
+    fn create_test_pool() -> BoundPool {
+        BoundPool {
+            meme_reserve: Reserve {
+                tokens: 500_000_000_000, // 500 tokens
+                mint: Pubkey::default(),
+                vault: Pubkey::default(),
+            },
+            quote_reserve: Reserve {
+                tokens: 250_000_000_000, // 250 SOL
+                mint: Pubkey::default(),
+                vault: Pubkey::default(),
+            },
+            admin_fees_meme: 0,
+            admin_fees_quote: 0,
+            fee_vault_quote: Pubkey::default(),
+            fee_shares: FeeShares::default(),
+            creator_fee_vault: Pubkey::default(),
+            referral_fee_vault: Pubkey::default(),
+            creator_addr: Pubkey::default(),
+            fees: Fees {
+                fee_meme_percent: 0,
+                fee_quote_percent: FEE, // 1%
+                burn_on_sell_bps: 0,
+                referral_fee_bps: 0,
+            },
+            config: create_test_config(),
+            locked: false,
+            pool_migration: false,
+            migration_pool_key: Pubkey::default(),
+            second_quote: None,
+            cumulative_quote_volume: 0,
+            cumulative_meme_volume: 0,
+            refunding: false,
+            meme_circulating: 0,
+            initial_supply: 500_000_000_000,
+            meme_burned: 0,
+            pending_creator: None,
+            mint_authority_revoked: false,
+            lp_mint: Pubkey::default(),
+            lp_locked: 0,
+            lp_unlock_ts: 0,
+            lp_lock_duration: 0,
+            min_quote_liquidity: 0,
+            index: 0,
+            signer_bump: 0,
+            migration_amm: None,
+            migration_target: MigrationAmm::Cpmm,
+            circuit_breaker_bps: 0,
+            last_slot: 0,
+            slot_start_price: 0,
+            max_quote_raise: 0,
+            post_migration_fee_bps: 0,
+            quote_migrated: 0,
+            referral_fees_paid: 0,
+            whitelist_until: 0,
+            version: BoundPool::VERSION,
+            meme_vault_bump: 0,
+            quote_vault_bump: 0,
+            unique_buyers: 0,
+            sell_lock_until: 0,
+            first_buyer_bonus_bps: 0,
+            first_buy_claimed: false,
+            migration_slot: 0,
+            curve_type: CurveType::Standard,
+            price_floor: 0,
+            airdropped: 0,
+            meme_reserve_at_migration: Reserve::default(),
+            quote_reserve_at_migration: Reserve::default(),
+            trade_flags: 0,
+            created_at: 0,
+            min_pool_age_secs: 0,
+            creator_share_minted: 0,
+            has_trade_history: false,
+        }
+    }
+
+    // Helper function to create a test pool raising in SOL and a 6-decimal
+    // stablecoin priced at 100 per SOL (1 stable unit = 10 lamports)
+    fn create_dual_quote_test_pool() -> BoundPool {
+        let mut pool = create_test_pool();
+        pool.second_quote = Some(SecondQuote {
+            reserve: Reserve {
+                tokens: 0,
+                mint: Pubkey::new_unique(),
+                vault: Pubkey::new_unique(),
+            },
+            admin_fees: 0,
+            price_num: 10,
+            price_denom: 1,
+        });
+        pool
+    }
+
+    #[test]
+    fn test_compute_delta_m_basic() {
+        // ARRANGE: Set up test data
+        let pool = create_test_pool();
+        let s_a = 100_000_000_000; // 100 SOL
+        let s_b = 200_000_000_000; // 200 SOL
+
+        println!("🧪 Testing compute_delta_m with:");
+        println!("   s_a (start): {} SOL", s_a / 1_000_000_000);
+        println!("   s_b (end): {} SOL", s_b / 1_000_000_000);
+
+        // ACT: Calculate delta_m
+        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+
+        // ASSERT: Check result is reasonable
+        assert!(delta_m > 0, "Delta_m should be positive");
+        assert!(
+            delta_m < pool.config.gamma_m,
+            "Delta_m should be less than max meme tokens"
+        );
+
+        println!(
+            "✅ Test passed! Delta_m = {} tokens",
+            delta_m / 1_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_compute_delta_m_uses_quote_decimals() {
+        // ARRANGE: Same curve with a 9 and a 6 decimals quote token
+        let pool_9 = create_test_pool();
+        let mut pool_6 = create_test_pool();
+        pool_6.config.decimals.quote = 1_000_000;
+
+        // ACT: Buy with 100 -> 200 whole quote tokens on both
+        let delta_m_9 = pool_9
+            .compute_delta_m(100_000_000_000, 200_000_000_000)
+            .unwrap();
+        let delta_m_6 = pool_6.compute_delta_m(100_000_000, 200_000_000).unwrap();
+
+        // ASSERT: Both are priced in whole quote tokens, up to rounding
+        assert!(
+            delta_m_9.abs_diff(delta_m_6) <= 1,
+            "9 decimals: {}, 6 decimals: {}",
+            delta_m_9,
+            delta_m_6
+        );
+
+        println!("✅ Quote decimals test passed! Delta_m = {}", delta_m_6);
+    }
+
+    /// Helper function to create a pool priced like `new_pool` does, for a
+    /// 100 SOL target
+    fn create_launch_pool() -> BoundPool {
+        use crate::consts::{
+            DEFAULT_MAX_M, DEFAULT_MAX_M_LP, DEFAULT_PRICE_FACTOR_DENOMINATOR,
+            DEFAULT_PRICE_FACTOR_NUMERATOR,
+        };
+
+        let gamma_s = 100_000_000_000;
+        let denom = 1_000_000_000;
+        let (alpha_abs, decimals) = compute_alpha_abs(
+            gamma_s,
+            denom,
+            DEFAULT_MAX_M,
+            DEFAULT_MAX_M_LP,
+            DEFAULT_PRICE_FACTOR_NUMERATOR,
+            DEFAULT_PRICE_FACTOR_DENOMINATOR,
+        )
+        .unwrap();
+        let beta = compute_beta(
+            gamma_s,
+            denom,
+            DEFAULT_MAX_M,
+            DEFAULT_MAX_M_LP,
+            DEFAULT_PRICE_FACTOR_NUMERATOR,
+            DEFAULT_PRICE_FACTOR_DENOMINATOR,
+            decimals,
+        )
+        .unwrap();
+
+        let mut pool = create_test_pool();
+        pool.config = Config {
+            alpha_abs,
+            beta,
+            price_factor_num: DEFAULT_PRICE_FACTOR_NUMERATOR,
+            price_factor_denom: DEFAULT_PRICE_FACTOR_DENOMINATOR,
+            price_factor_num_end: 0,
+            fee_start_bps: 0,
+            fee_end_bps: 0,
+            max_fee_quote: 0,
+            max_fee_meme: 0,
+            virtual_quote_reserve: 0,
+            virtual_meme_reserve: 0,
+            gamma_s: gamma_s as u64,
+            gamma_m: DEFAULT_MAX_M as u64,
+            omega_m: DEFAULT_MAX_M_LP as u64,
+            decimals: Decimals {
+                alpha: decimals,
+                beta: decimals,
+                quote: denom as u64,
+                meme: 1_000_000,
+            },
+        };
+        pool
+    }
+
+    #[test]
+    fn test_virtual_reserves_flatten_early_impact() {
+        // ARRANGE: Fresh launch, and the same launch priced 10 SOL in
+        let mut plain = create_launch_pool();
+        plain.quote_reserve.tokens = 0;
+        plain.meme_reserve.tokens = plain.config.gamma_m;
+        let mut virtual_pool = plain.clone();
+        virtual_pool
+            .set_virtual_reserves(10_000_000_000, 0)
+            .unwrap();
+
+        // ACT: The same 1 SOL first buy on both
+        let buy = |pool: &mut BoundPool| {
+            let before = pool.spot_price().unwrap();
+            let swap = pool.swap_amounts(1_000_000_000, 0, true, None, true);
+            pool.quote_reserve.tokens += swap.amount_in;
+            pool.meme_reserve.tokens -= swap.amount_out;
+            (before, pool.spot_price().unwrap(), swap.amount_out)
+        };
+        let (plain_before, plain_after, _) = buy(&mut plain);
+        let (virtual_before, virtual_after, virtual_out) = buy(&mut virtual_pool);
+
+        // ASSERT: The virtual pool's price moves relatively less
+        assert!(
+            virtual_after.abs_diff(virtual_before) * plain_before
+                < plain_after.abs_diff(plain_before) * virtual_before
+        );
+
+        // ASSERT: Only real meme counts as sold towards migration
+        let p = &virtual_pool.config;
+        assert_eq!(p.gamma_m - virtual_pool.meme_reserve.tokens, virtual_out);
+
+        // ACT: Sell everything bought back
+        let sell = virtual_pool.swap_amounts(virtual_out, 0, false, None, true);
+
+        // ASSERT: Only real quote is paid out
+        assert_eq!(sell.amount_out, virtual_pool.quote_reserve.tokens);
+
+        // ACT & ASSERT: Virtual meme is folded into the virtual quote, and
+        // virtual reserves can't exceed the quote target
+        let mut meme_pool = create_launch_pool();
+        meme_pool
+            .set_virtual_reserves(0, 1_000_000_000_000)
+            .unwrap();
+        assert!(meme_pool.config.virtual_quote_reserve > 0);
+        assert_eq!(meme_pool.config.virtual_meme_reserve, 1_000_000_000_000);
+        assert!(meme_pool
+            .set_virtual_reserves(meme_pool.config.gamma_s + 1, 0)
+            .is_err());
+
+        println!("✅ Virtual reserves test passed!");
+    }
+
+    #[test]
+    fn test_after_buy_moves_the_quote() {
+        // ARRANGE: Fresh launch and a 5 SOL buy queued ahead of ours
+        let mut pool = create_launch_pool();
+        pool.quote_reserve.tokens = 0;
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        let prior_in = 5_000_000_000;
+
+        // ACT: Quote 1 SOL after the prior buy
+        let after = pool.after_buy(prior_in, false, None).unwrap();
+        let quote = after.swap_amounts(1_000_000_000, 0, true, None, false);
+
+        // ASSERT: Same as settling the prior buy by hand, then quoting
+        let prior = pool.swap_amounts(prior_in, 0, true, None, false);
+        let mut settled = pool.clone();
+        settled.quote_reserve.tokens += prior.amount_in;
+        settled.meme_reserve.tokens -= prior.amount_out + prior.admin_fee_out;
+        let expected = settled.swap_amounts(1_000_000_000, 0, true, None, false);
+        assert_eq!(quote.amount_out, expected.amount_out);
+        assert_eq!(after.meme_circulating, prior.amount_out);
+
+        // ASSERT: The prior buy moves the quote, the pool itself is untouched
+        let current = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        assert_ne!(quote.amount_out, current.amount_out);
+        assert_eq!(pool.quote_reserve.tokens, 0);
+
+        println!("✅ Prior buy preview test passed!");
+    }
+
+    #[test]
+    fn test_fast_path_never_overestimates() {
+        // ARRANGE: Realistic curve, trades up to the fast path limit
+        let pool = create_launch_pool();
+        let config = &pool.config;
+        let quote_supplies: [u64; 3] = [1_000_000_000, 10_000_000_000, 50_000_000_000];
+        let trade_bps: [u64; 4] = [1, 2, 5, 10];
+
+        for s in quote_supplies {
+            for bps in trade_bps {
+                let delta_s = s * bps / 10_000;
+
+                // ACT: Buy through both paths
+                let exact_m = pool.compute_delta_m_exact(s, s + delta_s).unwrap();
+                let fast_m = linear_delta_m(
+                    config.alpha_abs,
+                    config.beta,
+                    config.decimals.alpha,
+                    config.decimals.beta,
+                    config.decimals.quote as u128,
+                    s as u128,
+                    delta_s as u128,
+                )
+                .unwrap() as u64;
+
+                // ASSERT: Never more than exact, within bps / 2 of it
+                assert!(
+                    fast_m <= exact_m,
+                    "buy {} at {}: {} > {}",
+                    delta_s,
+                    s,
+                    fast_m,
+                    exact_m
+                );
+                assert!(
+                    (exact_m - fast_m) as u128 * 20_000 <= exact_m as u128 * bps as u128 + 20_000,
+                    "buy {} at {}: {} vs {}",
+                    delta_s,
+                    s,
+                    fast_m,
+                    exact_m
+                );
+
+                // ACT: Sell the exact output back through both paths
+                let s_b = s + delta_s;
+                let exact_s = pool.compute_delta_s_exact(s_b, exact_m).unwrap();
+                let fast_s = linear_delta_s(
+                    config.alpha_abs,
+                    config.beta,
+                    config.decimals.alpha,
+                    config.decimals.beta,
+                    config.decimals.quote as u128,
+                    s_b as u128,
+                    exact_m as u128,
+                )
+                .unwrap() as u64;
+
+                // ASSERT: The quote paid out buys back at most the meme sold
+                assert!(
+                    pool.compute_delta_m_exact(s_b - fast_s, s_b).unwrap() <= exact_m,
+                    "sell {} at {}: {} pays too much",
+                    exact_m,
+                    s_b,
+                    fast_s
+                );
+                assert!(
+                    exact_s.abs_diff(fast_s) as u128 * 20_000
+                        <= exact_s as u128 * bps as u128 + 20_000,
+                    "sell {} at {}: {} vs {}",
+                    exact_m,
+                    s_b,
+                    fast_s,
+                    exact_s
+                );
+            }
+        }
+
+        println!("✅ Fast path error bound test passed!");
+    }
+
+    #[test]
+    fn test_inverse_curve_matches_compute_delta_m() {
+        // ARRANGE: Realistic curve, buys from dust to most of the reserve
+        let pool = create_launch_pool();
+        let quote_supplies: [u64; 4] = [0, 1_000_000_000, 10_000_000_000, 50_000_000_000];
+        let meme_amounts: [u64; 5] = [
+            1,
+            1_000,
+            1_000_000_000,
+            1_000_000_000_000,
+            100_000_000_000_000,
+        ];
+
+        for s in quote_supplies {
+            for delta_m in meme_amounts {
+                // ACT: Invert the curve
+                let delta_s = pool.compute_delta_s_in(s, delta_m).unwrap();
+
+                // ASSERT: The forward curve emits at least the requested meme
+                let bought = pool.compute_delta_m(s, s + delta_s).unwrap();
+                assert!(
+                    bought >= delta_m,
+                    "{} at {}: {} quote buys {}",
+                    delta_m,
+                    s,
+                    delta_s,
+                    bought
+                );
+
+                // ASSERT: The input is tight, a couple units less falls short
+                if delta_s >= 2 {
+                    let short = pool.compute_delta_m(s, s + delta_s - 2).unwrap();
+                    assert!(
+                        short < delta_m,
+                        "{} at {}: {} quote buys {}",
+                        delta_m,
+                        s,
+                        delta_s - 2,
+                        short
+                    );
+                }
+            }
+        }
+
+        println!("✅ Inverse curve test passed!");
+    }
+
+    #[test]
+    fn test_curve_types_price_increases_with_each_buy() {
+        for curve_type in [CurveType::Linear, CurveType::Exponential] {
+            // ARRANGE: Fresh launch on the alternative curve
+            let mut pool = create_launch_pool();
+            pool.curve_type = curve_type;
+            pool.meme_reserve.tokens = DEFAULT_MAX_M as u64;
+            pool.quote_reserve.tokens = 0;
+            let quote_in = 5_000_000_000; // 5 SOL
+            let mut last_price = 0;
+            let mut last_out = u64::MAX;
+
+            for _ in 0..15 {
+                // ACT
+                let price = pool.spot_price().unwrap();
+                let swap = pool.swap_amounts(quote_in, 0, true, None, false);
+                let after = pool.after_buy(quote_in, false, None).unwrap();
+
+                // ASSERT: Each buy starts higher and gets less meme
+                assert!(price > last_price, "{:?}", curve_type);
+                assert!(swap.amount_out < last_out, "{:?}", curve_type);
+                after.ensure_curve_invariant(&pool).unwrap();
+
+                // ASSERT: Selling the buy back never returns more than paid
+                let sell = after.swap_amounts(swap.amount_out, 0, false, None, false);
+                assert!(sell.amount_out + sell.admin_fee_out <= swap.amount_in);
+
+                // ASSERT: The inverse buys at least the meme it is asked for
+                let delta_s = pool
+                    .compute_delta_s_in(1_000_000_000, swap.amount_out)
+                    .unwrap();
+                assert!(
+                    pool.compute_delta_m(1_000_000_000, 1_000_000_000 + delta_s)
+                        .unwrap()
+                        >= swap.amount_out
+                );
+
+                last_price = price;
+                last_out = swap.amount_out;
+                pool = after;
+            }
+        }
+
+        println!("✅ Linear and exponential curves price each buy higher");
+    }
+
+    #[test]
+    fn test_exact_out_swap_amounts() {
+        // ARRANGE: Pool charging a 1% quote fee
+        let pool = create_test_pool();
+        let meme_out = 100_000_000_000; // 100 tokens
+
+        // ACT: Buy exactly 100 tokens
+        let swap = pool
+            .exact_out_swap_amounts(meme_out, u64::MAX, false, None, false)
+            .unwrap();
+        let paid = swap.amount_in + swap.admin_fee_in;
+
+        // ASSERT: Exactly the requested meme, for an input the forward
+        // swap agrees with
+        assert_eq!(swap.amount_out, meme_out);
+        let forward = pool.swap_amounts(paid, 0, true, None, false);
+        assert_eq!(forward.admin_fee_in, swap.admin_fee_in);
+        assert!(forward.amount_out >= meme_out);
+
+        // ACT & ASSERT: Capping the input below the price reverts
+        assert_eq!(
+            pool.exact_out_swap_amounts(meme_out, paid - 1, false, None, false)
+                .unwrap_err(),
+            error!(AmmError::SlippageExceeded)
+        );
+        assert!(pool
+            .exact_out_swap_amounts(meme_out, paid, false, None, false)
+            .is_ok());
+
+        // ACT & ASSERT: More meme than the pool holds can't be bought
+        assert_eq!(
+            pool.exact_out_swap_amounts(pool.meme_reserve.tokens + 1, u64::MAX, false, None, false)
+                .unwrap_err(),
+            error!(AmmError::InsufficientBalance)
+        );
+
+        println!("✅ Exact out swap test passed!");
+    }
+
+    #[test]
+    fn test_overpaid_buy_is_repriced() {
+        // ARRANGE: Meme priced above one quote unit, so a unit more buys
+        // nothing more
+        let pool = create_test_pool();
+        let exact = pool
+            .exact_out_swap_amounts(1_000, u64::MAX, false, None, false)
+            .unwrap();
+        let paid = exact.amount_in + exact.admin_fee_in;
+
+        // ACT: Inflate the input without reaching the next meme unit
+        let inflated = pool.swap_amounts(paid + 1, 0, true, None, false);
+        let meme_out = inflated.amount_out;
+        let repriced = pool.without_overpayment(inflated, false, None, false);
+
+        // ASSERT: Same meme, at the exact price, the extra unit refunded
+        assert_eq!(meme_out, exact.amount_out);
+        assert_eq!(repriced.amount_out, meme_out);
+        assert_eq!(repriced.amount_in + repriced.admin_fee_in, paid);
+
+        // ACT & ASSERT: An exactly priced buy is left as is
+        let unchanged = pool.without_overpayment(exact, false, None, false);
+        assert_eq!(unchanged.amount_in + unchanged.admin_fee_in, paid);
+
+        println!("✅ Overpaid buys only pay for the meme they get");
+    }
+
+    #[test]
+    fn test_compute_delta_s_basic() {
+        // ARRANGE: Set up test data
+        let pool = create_test_pool();
+        let s_b = 100_000_000_000; // 100 SOL
+        let delta_m = 1_000_000_000; // 1 token to sell (much smaller amount)
+
+        println!("🧪 Testing compute_delta_s with:");
+        println!("   s_b (current): {} SOL", s_b / 1_000_000_000);
+        println!("   delta_m: {} tokens", delta_m / 1_000_000_000);
+
+        // ACT: Calculate delta_s
+        let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap();
+
+        // ASSERT: Check result is reasonable
+        assert!(delta_s > 0, "Delta_s should be positive");
+        // Use a more reasonable upper bound check
+        assert!(
+            delta_s < 100_000_000_000_000, // 100k SOL as reasonable upper bound
+            "Delta_s should be reasonable: got {} SOL",
+            delta_s / 1_000_000_000
+        );
+
+        println!("✅ Test passed! Delta_s = {} SOL", delta_s / 1_000_000_000);
+    }
+
+    #[test]
+    fn test_positive_intercept_at_zero_supply() {
+        // ARRANGE: Test that price = beta when supply = 0
+        let pool = create_test_pool();
+        let s_a = 0; // Start from 0 supply
+        let s_b = 1_000_000_000; // Small amount of SOL
+
+        println!("🧪 Testing positive intercept (beta) at zero supply");
+
+        // ACT: Calculate delta_m
+        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+
+        // The price at zero should be beta, so initial tokens should be higher
+        // because we're starting from a positive price
+        assert!(
+            delta_m > 0,
+            "Should get tokens even at zero supply due to positive intercept"
+        );
+
+        println!(
+            "✅ Positive intercept confirmed! Got {} tokens for {} SOL at zero supply",
+            delta_m / 1_000_000_000,
+            s_b / 1_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_buy_meme_swap_amounts() {
+        // ARRANGE: Set up test data
+        let pool = create_test_pool();
+        let sol_amount = 10_000_000_000; // 10 SOL to buy with
+        let min_meme_out = 0; // No slippage protection for test
+
+        println!(
+            "🧪 Testing buy_meme_swap_amounts with {} SOL",
+            sol_amount / 1_000_000_000
+        );
+
+        // ACT: Calculate swap amounts
+        let swap = pool
+            .buy_meme_swap_amounts(sol_amount, min_meme_out)
+            .unwrap();
+
+        // ASSERT: Check all values are correct
+        assert!(swap.amount_out > 0, "Should receive meme tokens");
+        assert_eq!(
+            swap.admin_fee_in,
+            sol_amount / 100, // 1% fee
+            "Admin fee should be 1% of input"
+        );
+        assert!(
+            swap.amount_in <= sol_amount - swap.admin_fee_in,
+            "Net amount in should be less than input minus fees"
+        );
+
+        println!("✅ Buy swap test passed!");
+        println!("   Input: {} SOL", sol_amount / 1_000_000_000);
+        println!("   Output: {} MEME", swap.amount_out / 1_000_000_000);
+        println!("   Fee: {} SOL", swap.admin_fee_in / 1_000_000_000);
+    }
+
+    #[test]
+    fn test_effective_rate_matches_manual_computation() {
+        // ARRANGE: Buy with 10 SOL against the test pool
+        let pool = create_test_pool();
+        let sol_amount = 10_000_000_000; // 10 SOL
+        let scale = pool.config.decimals.quote;
+
+        // ACT: Build the quote that the swap handlers return
+        let swap = pool.buy_meme_swap_amounts(sol_amount, 0).unwrap();
+        let quote = swap.to_quote(scale).unwrap();
+
+        // ASSERT: Rate is meme out per whole SOL actually paid (incl. fee)
+        let manual = (swap.amount_out as u128 * scale as u128
+            / (swap.amount_in + swap.admin_fee_in) as u128) as u64;
+        assert_eq!(quote.effective_rate, manual);
+        assert_eq!(quote.amount_out, swap.amount_out);
+        assert_eq!(quote.admin_fee_in, swap.admin_fee_in);
+
+        // ACT: Sell the meme back, the rate scaled by the meme precision
+        let mut pool = pool;
+        pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+        pool.quote_reserve.tokens += swap.amount_in;
+        let meme_scale = pool.config.decimals.meme;
+        let sell = pool.swap_amounts(swap.amount_out, 0, false, None, false);
+        let sell_quote = sell.to_quote(meme_scale).unwrap();
+
+        // ASSERT: Rate is SOL out per whole meme sold (incl. fee)
+        let manual_sell = (sell.amount_out as u128 * meme_scale as u128
+            / (sell.amount_in + sell.admin_fee_in) as u128) as u64;
+        assert_eq!(sell_quote.effective_rate, manual_sell);
+
+        // ACT & ASSERT: A rate past u64 fails instead of truncating
+        let dust_in = SwapAmount {
+            amount_in: 1,
+            amount_out: u64::MAX,
+            ..swap
+        };
+        assert_eq!(
+            dust_in.to_quote(scale).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        println!("✅ Effective rate test passed! 1 SOL = {} MEME", manual);
+    }
+
+    #[test]
+    fn test_buy_with_each_quote_asset() {
+        // ARRANGE: 10 SOL and 1000 USDC are worth the same on this curve
+        let pool = create_dual_quote_test_pool();
+        let sol_amount = 10_000_000_000; // 10 SOL
+        let usdc_amount = 1_000_000_000; // 1000 USDC
+
+        // ACT: Quote the same buy with either asset
+        let sol_swap = pool.swap_amounts(sol_amount, 0, true, None, false);
+        let usdc_swap = pool
+            .second_quote_swap_amounts(usdc_amount, 0, true, None, false)
+            .unwrap();
+
+        // ASSERT: Both buy the same meme and charge in their own units
+        assert_eq!(sol_swap.amount_out, usdc_swap.amount_out);
+        assert_eq!(usdc_swap.admin_fee_in, usdc_amount / 100);
+        assert_eq!(
+            usdc_swap.amount_in + usdc_swap.admin_fee_in,
+            usdc_amount,
+            "Whole stablecoin input should be used"
+        );
+
+        println!("✅ Dual quote buy test passed!");
+        println!("   SOL buy: {} MEME", sol_swap.amount_out);
+        println!("   USDC buy: {} MEME", usdc_swap.amount_out);
+    }
+
+    #[test]
+    fn test_second_quote_moves_shared_curve() {
+        // ARRANGE: Buy with the stablecoin first
+        let mut pool = create_dual_quote_test_pool();
+        let sol_amount = 10_000_000_000; // 10 SOL
+        let before = pool.swap_amounts(sol_amount, 0, true, None, false);
+
+        let usdc_swap = pool
+            .second_quote_swap_amounts(1_000_000_000, 0, true, None, false)
+            .unwrap();
+        let second = pool.second_quote.as_mut().unwrap();
+        second.reserve.tokens += usdc_swap.amount_in;
+        pool.meme_reserve.tokens -= usdc_swap.amount_out + usdc_swap.admin_fee_out;
+
+        // ACT: Quote the same SOL buy again
+        let after = pool.swap_amounts(sol_amount, 0, true, None, false);
+
+        // ASSERT: Stablecoin raised counts towards the curve's quote measure
+        let (_, quote_measure) = pool.balances().unwrap();
+        assert_eq!(
+            quote_measure,
+            pool.quote_reserve.tokens + usdc_swap.amount_in * 10
+        );
+        assert_ne!(before.amount_out, after.amount_out);
+
+        println!("✅ Shared curve test passed!");
+    }
+
+    #[test]
+    fn test_second_quote_paid_out_at_migration() {
+        // ARRANGE: Dual quote pool holding 100 stablecoin worth 1_000 quote
+        let mut pool = create_dual_quote_test_pool();
+        pool.second_quote.as_mut().unwrap().reserve.tokens = 100;
+        let (_, quote_measure) = pool.balances().unwrap();
+
+        // ACT: Take the second quote reserve out like the migrations do
+        let paid_out = pool.migrate_second_quote().unwrap();
+
+        // ASSERT: All of it leaves, the curve keeps its quote measure
+        assert_eq!(paid_out, 100);
+        assert_eq!(pool.second_quote.unwrap().reserve.tokens, 0);
+        assert_eq!(pool.quote_migrated, 1_000);
+        assert_eq!(pool.balances().unwrap().1, quote_measure);
+
+        // ACT & ASSERT: Single quote pools have nothing to pay out
+        assert_eq!(create_test_pool().migrate_second_quote().unwrap(), 0);
+
+        println!("✅ Second quote migration test passed!");
+    }
+
+    #[test]
+    fn test_second_quote_migrates_to_fee_treasury() {
+        // ARRANGE: Dual quote pool and the fee treasury of its second mint
+        let pool = create_dual_quote_test_pool();
+        let second = pool.second_quote.unwrap();
+        let (treasury, _) = Pubkey::find_program_address(
+            &[
+                ProtocolConfig::FEE_TREASURY_PREFIX,
+                second.reserve.mint.as_ref(),
+            ],
+            &crate::ID,
+        );
+
+        // ACT & ASSERT: Only that treasury takes the reserve, never the
+        // creator's wallet
+        assert!(pool.is_second_quote_treasury(treasury));
+        assert!(!pool.is_second_quote_treasury(pool.creator_addr));
+        assert!(!create_test_pool().is_second_quote_treasury(treasury));
+
+        println!("✅ Second quote treasury test passed!");
+    }
+
+    #[test]
+    fn test_second_quote_must_be_pegged() {
+        // ARRANGE: USDC pool taking USDT one for one, both 6 decimals
+        let [usdc, usdt] = PEGGED_QUOTE_MINTS;
+        let mut second = SecondQuote {
+            reserve: Reserve {
+                tokens: 0,
+                mint: usdt,
+                vault: Pubkey::new_unique(),
+            },
+            admin_fees: 0,
+            price_num: 1,
+            price_denom: 1,
+        };
+
+        // ACT & ASSERT: Pegged pair at par passes
+        assert!(second.ensure_pegged(usdc, 1_000_000, 1_000_000).is_ok());
+
+        // ACT & ASSERT: A rate off par could be arbitraged against the curve
+        second.price_num = 2;
+        assert!(second.ensure_pegged(usdc, 1_000_000, 1_000_000).is_err());
+
+        // ACT & ASSERT: So could a floating asset, e.g. a SOL primary quote
+        second.price_num = 1;
+        assert!(second
+            .ensure_pegged(Pubkey::new_unique(), 1_000_000, 1_000_000)
+            .is_err());
+        second.reserve.mint = Pubkey::new_unique();
+        assert!(second.ensure_pegged(usdc, 1_000_000, 1_000_000).is_err());
+
+        println!("✅ Pegged second quote test passed!");
+    }
+
+    #[test]
+    fn test_quote_measure_overflow_is_an_error() {
+        // ARRANGE: Second quote reserve worth more than a u64 of quote
+        let mut pool = create_dual_quote_test_pool();
+        pool.second_quote.as_mut().unwrap().reserve.tokens = u64::MAX / 2;
+
+        // ACT & ASSERT: Pricing fails instead of panicking
+        assert_eq!(pool.balances().unwrap_err(), error!(AmmError::MathOverflow));
+        assert!(pool.spot_price().is_err());
+
+        println!("✅ Quote measure overflow test passed!");
+    }
+
+    #[test]
+    fn test_sell_into_empty_second_quote_reserve() {
+        // ARRANGE: The curve holds SOL only
+        let pool = create_dual_quote_test_pool();
+
+        // ACT & ASSERT: The stablecoin side can't pay out, an error rather
+        // than a panic for callers to propagate
+        assert_eq!(
+            pool.second_quote_swap_amounts(10_000_000_000, 0, false, None, false)
+                .unwrap_err(),
+            error!(AmmError::InsufficientBalance)
+        );
+
+        println!("✅ Empty second quote reserve sell test passed!");
+    }
+
+    #[test]
+    fn test_locked_pool_rejects_swaps() {
+        // ARRANGE: Migration locks the pool before its CPI runs
+        let mut pool = create_test_pool();
+        assert!(pool.ensure_unlocked().is_ok());
+
+        // ACT: Lock it the same way migrate_to_raydium does
+        pool.locked = true;
+
+        // ASSERT: Both swap directions share this check
+        assert_eq!(
+            pool.ensure_unlocked().unwrap_err(),
+            error!(AmmError::PoolIsLocked)
+        );
+
+        println!("✅ Locked pool rejection test passed!");
+    }
+
+    #[test]
+    fn test_refunding_pool_rejects_swaps() {
+        // ARRANGE: A pool being wound down
+        let mut pool = create_test_pool();
+
+        // ACT: Start the refund the same way initiate_refund does
+        pool.refunding = true;
+
+        // ASSERT: Both swap directions share this check
+        assert_eq!(
+            pool.ensure_unlocked().unwrap_err(),
+            error!(AmmError::PoolIsRefunding)
+        );
+
+        println!("✅ Refunding pool rejection test passed!");
+    }
+
+    #[test]
+    fn test_sell_requires_quote_liquidity() {
+        // ARRANGE: Freshly created pool, nothing bought yet
+        let mut pool = create_test_pool();
+        pool.quote_reserve.tokens = 0;
+        pool.min_quote_liquidity = 100_000_000;
+
+        // ACT & ASSERT: Sells are rejected until the floor is reached
+        assert!(pool.ensure_quote_liquidity(false).is_err());
+        pool.quote_reserve.tokens = 100_000_000;
+        assert!(pool.ensure_quote_liquidity(false).is_ok());
+
+        println!("✅ Quote liquidity floor test passed!");
+    }
+
+    #[test]
+    fn test_decimals_must_match_mints() {
+        // ARRANGE: 9 decimals quote, 6 decimals meme
+        let decimals = create_test_config().decimals;
+
+        // ACT & ASSERT: Only the matching mint decimals are accepted
+        assert!(decimals.ensure_mint_decimals(9, 6).is_ok());
+        assert_eq!(
+            decimals.ensure_mint_decimals(6, 6).unwrap_err(),
+            error!(AmmError::DecimalsMismatch)
+        );
+        assert_eq!(
+            decimals.ensure_mint_decimals(9, 9).unwrap_err(),
+            error!(AmmError::DecimalsMismatch)
+        );
+
+        println!("✅ Mint decimals validation test passed!");
+    }
+
+    #[test]
+    fn test_undersupplied_meme_vault_is_rejected() {
+        // ARRANGE: A traded pool owing its reserve and meme admin fees
+        let mut pool = create_test_pool();
+        pool.meme_reserve.tokens = 400_000_000;
+        pool.admin_fees_meme = 1_000;
+        let owed = 400_001_000;
+
+        // ACT & ASSERT: A vault one unit short can't be migrated from
+        assert_eq!(
+            pool.ensure_meme_supply(owed - 1).unwrap_err(),
+            error!(AmmError::InsufficientTokenSupply)
+        );
+
+        // ACT & ASSERT: Holding what's owed, or more, passes
+        assert!(pool.ensure_meme_supply(owed).is_ok());
+        assert!(pool.ensure_meme_supply(owed + 1).is_ok());
+
+        println!("✅ Meme supply check test passed!");
+    }
+
+    #[test]
+    fn test_supply_split() {
+        // ACT & ASSERT: Zero keeps the default split
+        assert_eq!(
+            BoundPool::supply_split(0, 0).unwrap(),
+            (DEFAULT_MAX_M as u64, DEFAULT_MAX_M_LP as u64)
+        );
+
+        // ACT & ASSERT: A custom split within the total supply
+        let trading = 800_000_000_000_000;
+        let lp = MAX_MEME_TOKENS as u64 - trading;
+        assert_eq!(BoundPool::supply_split(trading, lp).unwrap(), (trading, lp));
+
+        // ACT & ASSERT: Past the total supply, even with a defaulted side
+        assert!(BoundPool::supply_split(trading, lp + 1).is_err());
+        assert!(BoundPool::supply_split(trading, 0).is_err());
+
+        // ARRANGE: Pool trading the custom supply
+        let mut pool = create_launch_pool();
+        pool.config.gamma_m = trading;
+
+        // ACT & ASSERT: The threshold follows the trading supply
+        assert_eq!(pool.migration_threshold(), trading / 100 * 80);
+        pool.config.gamma_m = DEFAULT_MAX_M as u64;
+        assert_eq!(pool.migration_threshold(), BoundPool::MIGRATION_THRESHOLD);
+
+        println!("✅ Supply split test passed!");
+    }
+
+    #[test]
+    fn test_creator_allocation_split() {
+        // ACT & ASSERT: No share leaves the whole supply on the curve
+        let trading = DEFAULT_MAX_M as u64;
+        assert_eq!(
+            BoundPool::creator_allocation_split(trading, 0).unwrap(),
+            (trading, 0)
+        );
+
+        // ACT & ASSERT: The share comes out of the curve, nothing is minted on top
+        let (curve, creator) = BoundPool::creator_allocation_split(trading, 1_000).unwrap();
+        assert_eq!(creator, trading / 10);
+        assert_eq!(curve + creator, trading);
+
+        // ACT & ASSERT: Capped at 20% of the trading supply
+        assert!(BoundPool::creator_allocation_split(trading, MAX_CREATOR_ALLOCATION_BPS).is_ok());
+        assert!(
+            BoundPool::creator_allocation_split(trading, MAX_CREATOR_ALLOCATION_BPS + 1).is_err()
+        );
+
+        println!("✅ Creator allocation split test passed!");
+    }
+
+    #[test]
+    fn test_sells_rejected_below_price_floor() {
+        // ARRANGE: A sell and the price it executes at
+        let mut pool = create_test_pool();
+        let swap = pool.swap_amounts(1_000_000_000, 0, false, None, false);
+        let price = (swap.amount_out + swap.admin_fee_out) as u128 * SPOT_PRICE_PRECISION
+            / (swap.amount_in + swap.admin_fee_in) as u128;
+
+        // ACT & ASSERT: Without a floor, any sell passes
+        assert!(pool.ensure_above_price_floor(&swap, false).is_ok());
+
+        // ACT & ASSERT: A floor at the execution price still lets it through
+        pool.price_floor = price;
+        assert!(pool.ensure_above_price_floor(&swap, false).is_ok());
+
+        // ACT & ASSERT: One unit above it, the sell is rejected
+        pool.price_floor = price + 1;
+        assert_eq!(
+            pool.ensure_above_price_floor(&swap, false).unwrap_err(),
+            error!(AmmError::BelowPriceFloor)
+        );
+
+        println!("✅ Price floor boundary test passed!");
+    }
+
+    #[test]
+    fn test_airdrops_are_capped_by_the_allocation() {
+        // ARRANGE: Launch pool with the default LP allocation
+        let mut pool = create_launch_pool();
+        pool.meme_reserve.vault = Pubkey::new_unique();
+        let allocation = pool.airdrop_allocation();
+        let circulating = pool.circulating_supply();
+        let tracked = pool.tracked_vault_amount(pool.meme_reserve.vault).unwrap();
+        assert_eq!(allocation, MAX_AIRDROPPED_TOKENS);
+
+        // ACT: Airdrop all but one unit of the allocation
+        pool.record_airdrop(allocation - 1).unwrap();
+
+        // ASSERT: The airdrop left the vault and circulates
+        assert_eq!(pool.circulating_supply(), circulating + allocation - 1);
+        assert_eq!(
+            pool.tracked_vault_amount(pool.meme_reserve.vault).unwrap(),
+            tracked - (allocation - 1)
+        );
+
+        // ACT & ASSERT: Past the allocation is rejected, up to it passes
+        assert_eq!(
+            pool.record_airdrop(2).unwrap_err(),
+            error!(AmmError::AirdroppedTokensOvercap)
+        );
+        pool.record_airdrop(1).unwrap();
+        assert_eq!(pool.airdropped, allocation);
+
+        // ACT & ASSERT: A small LP allocation caps airdrops below the maximum
+        pool.config.omega_m = 1_000;
+        assert_eq!(pool.airdrop_allocation(), 1_000);
+
+        println!("✅ Airdrop allocation test passed!");
+    }
+
+    #[test]
+    fn test_ensure_migratable() {
+        // ARRANGE: Pool exactly at the migration threshold, mints in Raydium
+        // order. Its vault holds far more than the reserve, as on chain
+        let mut pool = create_test_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m - pool.migration_threshold();
+        pool.admin_fees_meme = 1_000;
+        let meme_mint = Pubkey::new_from_array([1; 32]);
+        let quote_mint = Pubkey::new_from_array([2; 32]);
+
+        // ACT & ASSERT: Migration is allowed
+        assert!(pool
+            .ensure_migratable(MigrationAmm::Cpmm, meme_mint, quote_mint)
+            .is_ok());
+
+        // ACT & ASSERT: Mints must be ordered meme first
+        assert_eq!(
+            pool.ensure_migratable(MigrationAmm::Cpmm, quote_mint, meme_mint)
+                .unwrap_err(),
+            error!(AmmError::InvalidTokenOrder)
+        );
+
+        // ACT & ASSERT: Only the pool's migration target is accepted
+        assert_eq!(
+            pool.ensure_migratable(MigrationAmm::Clmm, meme_mint, quote_mint)
+                .unwrap_err(),
+            error!(AmmError::WrongMigrationTarget)
+        );
+
+        // ACT & ASSERT: Manual migrations don't need Raydium's token order
+        pool.migration_target = MigrationAmm::Manual;
+        assert!(pool
+            .ensure_migratable(MigrationAmm::Manual, quote_mint, meme_mint)
+            .is_ok());
+        pool.migration_target = MigrationAmm::Cpmm;
+
+        // ACT & ASSERT: Not enough meme sold
+        pool.meme_reserve.tokens += 1;
+        assert_eq!(
+            pool.ensure_migratable(MigrationAmm::Cpmm, meme_mint, quote_mint)
+                .unwrap_err(),
+            error!(AmmError::MigrationThresholdNotReached)
+        );
+        pool.meme_reserve.tokens -= 1;
+
+        // ACT & ASSERT: A pool migrates once, whichever the AMM
+        pool.pool_migration = true;
+        pool.locked = true;
+        pool.migration_amm = Some(MigrationAmm::Clmm);
+        assert_eq!(
+            pool.ensure_migratable(MigrationAmm::Cpmm, meme_mint, quote_mint)
+                .unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Migration validation test passed!");
+    }
+
+    #[test]
+    fn test_min_pool_age_before_migration() {
+        // ARRANGE: Pool created at 1_000 with a one hour discovery window
+        let mut pool = create_test_pool();
+        pool.created_at = 1_000;
+        pool.min_pool_age_secs = 3_600;
+
+        // ACT & ASSERT: Below the age, migration is rejected
+        assert_eq!(
+            pool.ensure_min_age(1_000).unwrap_err(),
+            error!(AmmError::PoolTooYoung)
+        );
+        assert_eq!(
+            pool.ensure_min_age(4_599).unwrap_err(),
+            error!(AmmError::PoolTooYoung)
+        );
+
+        // ACT & ASSERT: At and past the age, it is allowed
+        assert!(pool.ensure_min_age(4_600).is_ok());
+        assert!(pool.ensure_min_age(i64::MAX / 2).is_ok());
+
+        // ACT & ASSERT: Zero disables the window
+        pool.min_pool_age_secs = 0;
+        assert!(pool.ensure_min_age(1_000).is_ok());
+
+        println!("✅ Minimum pool age test passed!");
+    }
+
+    #[test]
+    fn test_claimable_lp_after_lock() {
+        // ARRANGE: Migrated pool with LP locked until t = 1_000
+        let mut pool = create_test_pool();
+        pool.lp_locked = 5_000;
+        pool.lp_unlock_ts = 1_000;
+
+        // ACT & ASSERT: Nothing can be claimed before the unlock
+        assert!(pool.claimable_lp(999).is_err());
+        assert_eq!(pool.claimable_lp(1_000).unwrap(), 5_000);
+
+        // ACT & ASSERT: Claimed LP can't be claimed again
+        pool.lp_locked = 0;
+        assert!(pool.claimable_lp(2_000).is_err());
+
+        println!("✅ LP lock test passed!");
+    }
+
+    #[test]
+    fn test_circulating_supply() {
+        // ARRANGE: 500 tokens on the curve, nothing sold yet
+        let mut pool = create_test_pool();
+        assert_eq!(pool.circulating_supply(), 0);
+
+        // ACT: 100 tokens bought (1 as admin fee), 10 sold back of which 2 burned
+        pool.meme_reserve.tokens -= 100_000_000_000;
+        pool.admin_fees_meme += 1_000_000_000;
+        pool.meme_reserve.tokens += 8_000_000_000;
+        pool.meme_burned += 2_000_000_000;
+
+        // ASSERT: Only tokens held by traders are circulating
+        assert_eq!(pool.circulating_supply(), 89_000_000_000);
+
+        // ACT: The creator got a share of the supply at launch
+        pool.creator_share_minted = 5_000_000_000;
+
+        // ASSERT: It circulates, though it never paid into the curve
+        assert_eq!(pool.circulating_supply(), 94_000_000_000);
+
+        println!("✅ Circulating supply test passed!");
+    }
+
+    #[test]
+    fn test_trade_history_required_once_created() {
+        // ARRANGE: Pool without a trade history
+        let mut pool = create_test_pool();
+
+        // ACT & ASSERT: Swaps may leave it out
+        assert!(pool.ensure_trade_history_passed(false).is_ok());
+
+        // ACT: The history got created
+        pool.has_trade_history = true;
+
+        // ASSERT: Swaps must pass it from then on
+        assert!(pool.ensure_trade_history_passed(false).is_err());
+        assert!(pool.ensure_trade_history_passed(true).is_ok());
+
+        println!("✅ Trade history requirement test passed!");
+    }
+
+    #[test]
+    fn test_tracked_vault_amounts() {
+        // ARRANGE: Pool with traded reserves, fees and a second quote
+        let mut pool = create_dual_quote_test_pool();
+        pool.quote_reserve.vault = Pubkey::new_unique();
+        pool.meme_reserve.vault = Pubkey::new_unique();
+        pool.quote_reserve.tokens = 1_000;
+        pool.admin_fees_quote = 10;
+        pool.meme_reserve.tokens = 2_000;
+        pool.admin_fees_meme = 20;
+        let second = pool.second_quote.as_mut().unwrap();
+        second.reserve.tokens = 3_000;
+        second.admin_fees = 30;
+
+        // ACT & ASSERT: Each vault tracks its reserve and fees, the meme vault
+        // the LP supply too
+        assert_eq!(
+            pool.tracked_vault_amount(pool.quote_reserve.vault),
+            Some(1_010)
+        );
+        assert_eq!(
+            pool.tracked_vault_amount(pool.meme_reserve.vault),
+            Some(2_020 + pool.config.omega_m)
+        );
+        let second_vault = pool.second_quote.unwrap().reserve.vault;
+        assert_eq!(pool.tracked_vault_amount(second_vault), Some(3_030));
+
+        // ACT & ASSERT: Other accounts aren't the pool's vaults
+        assert_eq!(pool.tracked_vault_amount(Pubkey::new_unique()), None);
+
+        println!("✅ Tracked vault amounts test passed!");
+    }
+
+    #[test]
+    fn test_integrity_reports_vault_drift() {
+        // ARRANGE: Pool tracking 1_010 quote and 2_020 meme plus the LP supply
+        let mut pool = create_test_pool();
+        pool.quote_reserve.vault = Pubkey::new_unique();
+        pool.meme_reserve.vault = Pubkey::new_unique();
+        pool.quote_reserve.tokens = 1_000;
+        pool.admin_fees_quote = 10;
+        pool.meme_reserve.tokens = 2_000;
+        pool.admin_fees_meme = 20;
+        let meme_tracked = 2_020 + pool.config.omega_m;
+
+        // ACT & ASSERT: Matching balances are consistent
+        let report = pool.integrity(meme_tracked, 1_010);
+        assert!(report.consistent);
+        assert_eq!(report.meme_tracked, meme_tracked);
+        assert_eq!(report.quote_tracked, 1_010);
+
+        // ACT & ASSERT: A shortfall on either side is reported with amounts
+        let report = pool.integrity(meme_tracked, 1_009);
+        assert!(!report.consistent);
+        assert_eq!(report.quote_actual, 1_009);
+        assert!(!pool.integrity(meme_tracked - 1, 1_010).consistent);
+
+        println!("✅ Pool integrity report test passed!");
+    }
+
+    #[test]
+    fn test_swap_rounding_leaves_no_dust() {
+        // ARRANGE: Launch pool with split fees, referrer rebates and a burn on
+        // sells, vaults simulated next to the pool's accounting
+        let mut pool = create_launch_pool();
+        pool.quote_reserve.tokens = 0;
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.vault = Pubkey::new_unique();
+        pool.meme_reserve.vault = Pubkey::new_unique();
+        pool.fees.burn_on_sell_bps = 100;
+        pool.fees.referral_fee_bps = 1_234;
+        pool.fee_shares = FeeShares {
+            protocol_bps: 5_003,
+            creator_bps: 3_331,
+            referral_bps: 1_666,
+        };
+        let mut config = ProtocolConfig {
+            protocol_fee_authority: Pubkey::default(),
+            fee_tiers: vec![],
+            fee_exempt: vec![],
+            pool_creation_enabled: true,
+            fee_treasury_enabled: false,
+            migration_fee_bps: 0,
+        };
+        let quote_key = pool.quote_reserve.vault;
+        let meme_key = pool.meme_reserve.vault;
+        let mut meme_vault = pool.tracked_vault_amount(meme_key).unwrap();
+        let mut quote_vault = pool.tracked_vault_amount(quote_key).unwrap();
+        let mut held = 0u64;
+
+        // ACT: Alternate odd sized buys and partial sells, moving the vaults
+        // by the transfers of the swap handlers and booking them with the
+        // handlers' own accounting
+        for i in 0..600u64 {
+            config.fee_treasury_enabled = i % 4 == 0;
+            let referred = i % 5 == 0;
+
+            if i % 3 != 2 {
+                let quote_in = 1_000_003 + i * 7_919_993 % 150_000_000;
+                let swap = pool.swap_amounts(quote_in, 0, true, None, false);
+                if swap.amount_out == 0 {
+                    continue;
+                }
+                let mut fee = swap.quote_fee;
+                if referred {
+                    fee.carve_rebate(pool.fees.referral_fee_bps);
+                }
+                let treasury_fee = config.treasury_fee(fee.protocol);
+
+                // The platform fee goes from the buyer to the aggregator,
+                // never through the vault
+                quote_vault += swap.amount_in + swap.admin_fee_in;
+                quote_vault -= fee.creator + fee.referral + fee.rebate + treasury_fee;
+                meme_vault -= swap.amount_out;
+                pool.apply_buy(&swap, &fee, treasury_fee, false, 0);
+                held += swap.amount_out;
+            } else {
+                let meme_in = held * (i % 7 + 1) / 9;
+                let burn = pool.fees.get_burn_on_sell_amount(meme_in).unwrap();
+                let swap = pool.swap_amounts(meme_in - burn, 0, false, None, false);
+                if swap.amount_out == 0
+                    || swap.amount_out + swap.admin_fee_out > pool.quote_reserve.tokens
+                {
+                    continue;
+                }
+                let fee = swap.quote_fee;
+                let treasury_fee = config.treasury_fee(fee.protocol);
+
+                // The burn passes through the vault. The platform fee is split
+                // off the seller's proceeds, the vault pays `amount_out` either
+                // way
+                meme_vault += swap.amount_in + swap.admin_fee_in + burn;
+                meme_vault -= burn;
+                quote_vault -= swap.amount_out + fee.creator + fee.referral + treasury_fee;
+                pool.apply_sell(&swap, &fee, treasury_fee, false, meme_in, burn);
+                held -= meme_in;
+            }
+
+            // ASSERT: Every trade moves the vaults by exactly what's booked
+            assert_eq!(
+                quote_vault,
+                pool.quote_reserve.tokens + pool.admin_fees_quote,
+                "quote vault drifted at swap {}",
+                i
+            );
+            assert_eq!(
+                Some(meme_vault),
+                pool.tracked_vault_amount(meme_key),
+                "meme vault drifted at swap {}",
+                i
+            );
+        }
+
+        // ASSERT: Hundreds of trades left no dust to reconcile
+        assert_eq!(pool.vault_surplus(quote_key, quote_vault).unwrap(), 0);
+        assert_eq!(pool.vault_surplus(meme_key, meme_vault).unwrap(), 0);
+
+        // ACT: Dust landing in the vaults from outside, e.g. transfer rounding
+        let fees_quote = pool.admin_fees_quote;
+        let fees_meme = pool.admin_fees_meme;
+        quote_vault += 7;
+        meme_vault += MAX_RECONCILE_DUST;
+        assert!(!pool.integrity(meme_vault, quote_vault).consistent);
+
+        // ASSERT: Reconciling folds it into the admin fees, zeroing the dust
+        assert_eq!(pool.reconcile_dust(quote_key, quote_vault).unwrap(), 7);
+        assert_eq!(
+            pool.reconcile_dust(meme_key, meme_vault).unwrap(),
+            MAX_RECONCILE_DUST
+        );
+        assert_eq!(pool.admin_fees_quote, fees_quote + 7);
+        assert_eq!(pool.admin_fees_meme, fees_meme + MAX_RECONCILE_DUST);
+        assert!(pool.integrity(meme_vault, quote_vault).consistent);
+        assert_eq!(pool.reconcile_dust(quote_key, quote_vault).unwrap(), 0);
+
+        // ASSERT: More than dust is left for sweeping, foreign vaults rejected
+        assert!(pool
+            .reconcile_dust(quote_key, quote_vault + MAX_RECONCILE_DUST + 1)
+            .is_err());
+        assert_eq!(pool.admin_fees_quote, fees_quote + 7);
+        assert!(pool.reconcile_dust(Pubkey::new_unique(), 1).is_err());
+
+        println!("✅ Swap rounding dust test passed!");
+    }
+
+    #[test]
+    fn test_reserves_batch_fits_return_data() {
+        // ARRANGE: The largest batch of pool reserves
+        let pool = create_test_pool();
+        let reserves = pool.reserves().unwrap();
+        let batch = vec![reserves; PoolReserves::MAX_BATCH];
+
+        // ACT: Pack it like get_reserves
+        let data = batch.try_to_vec().unwrap();
+
+        // ASSERT: It fits, one more pool wouldn't
+        assert_eq!(reserves.meme_reserve, pool.meme_reserve.tokens);
+        assert_eq!(reserves.quote_reserve, pool.quote_reserve.tokens);
+        assert!(data.len() <= MAX_RETURN_DATA);
+        assert!(data.len() + PoolReserves::INIT_SPACE > MAX_RETURN_DATA);
+
+        println!("✅ Reserves batch size test passed!");
+    }
+
+    #[test]
+    fn test_display_info_decimals() {
+        // ARRANGE: Pool with 9 decimal quote and 6 decimal meme precisions
+        let pool = create_test_pool();
+
+        // ACT
+        let info = pool.display_info();
+
+        // ASSERT: The precisions map back to the mint decimals
+        assert_eq!(info.quote_decimals, 9);
+        assert_eq!(info.meme_decimals, 6);
+        assert_eq!(info.meme_reserve, pool.meme_reserve.tokens);
+        assert_eq!(info.quote_reserve, pool.quote_reserve.tokens);
+        assert_eq!(Decimals::mint_decimals(1), 0);
+        assert_eq!(Decimals::mint_decimals(0), 0);
+
+        println!("✅ Display info decimals test passed!");
+    }
+
+    #[test]
+    fn test_fee_cap_boundary() {
+        // ARRANGE: 1% quote fee capped at 0.01 SOL, 2% sell fee capped at
+        // 20_000 meme units
+        let mut pool = create_test_pool();
+        pool.fees.fee_meme_percent = FEE;
+        pool.config.max_fee_quote = 10_000_000;
+        pool.config.max_fee_meme = 20_000;
+
+        // ACT: Buys whose percentage fee is at and above the cap
+        let at_cap = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        let above_cap = pool.swap_amounts(2_000_000_000, 0, true, None, false);
+
+        // ASSERT: The cap is only applied once exceeded
+        assert_eq!(at_cap.admin_fee_in, 10_000_000);
+        assert!(!at_cap.fee_capped);
+        assert_eq!(above_cap.admin_fee_in, 10_000_000);
+        assert_eq!(above_cap.amount_in, 1_990_000_000);
+        assert!(above_cap.fee_capped);
+        let capped_buy_out = above_cap.amount_out;
+
+        // ACT: Sells whose percentage fee is at and above the cap
+        let at_cap = pool.swap_amounts(1_000_000, 0, false, None, false);
+        let above_cap = pool.swap_amounts(2_000_000, 0, false, None, false);
+
+        // ASSERT: Same for the meme fee
+        assert_eq!(at_cap.admin_fee_in, 20_000);
+        assert!(!at_cap.fee_capped);
+        assert_eq!(above_cap.admin_fee_in, 20_000);
+        assert_eq!(above_cap.amount_in, 1_980_000);
+        assert!(above_cap.fee_capped);
+
+        // ACT: Exact out buy of what the capped buy returned
+        let exact = pool
+            .exact_out_swap_amounts(capped_buy_out, u64::MAX, false, None, false)
+            .unwrap();
+
+        // ASSERT: The exact out input is priced with the capped fee too
+        assert_eq!(exact.admin_fee_in, 10_000_000);
+        assert!(exact.fee_capped);
+
+        // ACT & ASSERT: Zero leaves the fee uncapped
+        pool.config.max_fee_quote = 0;
+        let uncapped = pool.swap_amounts(2_000_000_000, 0, true, None, false);
+        assert_eq!(uncapped.admin_fee_in, 20_000_000);
+        assert!(!uncapped.fee_capped);
+
+        println!("✅ Fee cap boundary test passed!");
+    }
+
+    #[test]
+    fn test_cancel_only_before_first_trade() {
+        // ARRANGE: Fresh pool, nothing bought yet
+        let mut pool = create_test_pool();
+
+        // ACT & ASSERT: An untouched pool can be cancelled
+        assert!(pool.ensure_cancellable().is_ok());
+
+        // ACT & ASSERT: Any volume rejects the cancel
+        pool.cumulative_quote_volume = 1;
+        assert!(pool.ensure_cancellable().is_err());
+
+        // ACT & ASSERT: So does meme held by a buyer
+        pool.cumulative_quote_volume = 0;
+        pool.meme_circulating = 1;
+        assert!(pool.ensure_cancellable().is_err());
+
+        // ACT & ASSERT: Migrated pools can't be cancelled
+        pool.meme_circulating = 0;
+        pool.pool_migration = true;
+        assert!(pool.ensure_cancellable().is_err());
+
+        println!("✅ Cancel before first trade test passed!");
+    }
+
+    #[test]
+    fn test_price_impact_grows_with_size() {
+        // ARRANGE: Fresh launch, priced without fees
+        let mut pool = create_launch_pool();
+        pool.quote_reserve.tokens = 0;
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        let impact = |pool: &BoundPool, amount: u64, buy_meme: bool| {
+            let swap = pool.swap_amounts(amount, 0, buy_meme, None, true);
+            pool.price_impact_bps(&swap, buy_meme, false).unwrap()
+        };
+
+        // ACT
+        let small_buy = impact(&pool, 10_000_000, true);
+        let large_buy = impact(&pool, 20_000_000_000, true);
+
+        // ASSERT: Small trades execute at the spot price, large ones move it
+        assert!(small_buy <= 1);
+        assert!(large_buy > 100);
+
+        // ACT & ASSERT: Fees count towards the execution price
+        let swap = pool.swap_amounts(10_000_000, 0, true, None, false);
+        let with_fee = pool.price_impact_bps(&swap, true, false).unwrap();
+        assert!((99..=101).contains(&with_fee));
+
+        // ACT & ASSERT: A sell back moves the price too
+        pool.quote_reserve.tokens = 20_000_000_000;
+        pool.meme_reserve.tokens -= pool
+            .swap_amounts(20_000_000_000, 0, true, None, true)
+            .amount_out;
+        let sell = impact(&pool, 1_000_000_000_000, false);
+        assert!(sell > 0);
+
+        println!("✅ Price impact test passed!");
+    }
+
+    #[test]
+    fn test_dust_swap_outputs_nothing() {
+        // ARRANGE: Launch curve, where a unit of quote's fee rounds up to
+        // the whole unit
+        let mut pool = create_launch_pool();
+        pool.quote_reserve.tokens = 0;
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+
+        // ACT: Dust buy
+        let dust = pool.swap_amounts(1, 0, true, None, false);
+
+        // ASSERT: Nothing comes out, so the swap is rejected before its fee
+        // is taken
+        assert_eq!(dust.amount_out, 0);
+        assert_eq!(
+            dust.ensure_nonzero_output().unwrap_err(),
+            error!(AmmError::OutputTooSmall)
+        );
+
+        // ACT & ASSERT: A regular buy goes through
+        let buy = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        assert!(buy.ensure_nonzero_output().is_ok());
+
+        println!("✅ Dust swap test passed!");
+    }
+
+    #[test]
+    fn test_fee_preview_matches_swap() {
+        // ARRANGE: Pool with some quote raised and a capped quote fee
+        let mut pool = create_launch_pool();
+        pool.quote_reserve.tokens = 10_000_000_000;
+        pool.meme_reserve.tokens = pool.config.gamma_m / 2;
+        pool.fees.fee_meme_percent = FEE;
+        let fee_quote_percent = pool.fee_quote_percent(None);
+
+        // ACT: Preview a buy and a sell
+        let buy = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        let buy_preview = buy.to_fee_preview(true, 0, fee_quote_percent);
+        let sell = pool.swap_amounts(1_000_000_000_000, 0, false, None, false);
+        let sell_preview = sell.to_fee_preview(false, 7, fee_quote_percent);
+
+        // ASSERT: Each fee lands on the asset the swap charges it in
+        assert_eq!(buy_preview.quote_fee, buy.admin_fee_in);
+        assert_eq!(buy_preview.meme_fee, buy.admin_fee_out);
+        assert_eq!(sell_preview.quote_fee, sell.admin_fee_out);
+        assert_eq!(sell_preview.meme_fee, sell.admin_fee_in);
+        assert_eq!(sell_preview.burn, 7);
+        assert!(buy_preview.quote_fee > 0 && sell_preview.meme_fee > 0);
+
+        // ACT & ASSERT: A fee cap shows in the preview
+        pool.config.max_fee_quote = 1_000;
+        let capped = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        let capped_preview = capped.to_fee_preview(true, 0, fee_quote_percent);
+        assert_eq!(capped_preview.quote_fee, 1_000);
+        assert!(capped_preview.fee_capped);
+
+        println!("✅ Fee preview test passed!");
+    }
+
+    #[test]
+    fn test_quote_vault_is_derived_from_pool() {
+        // ARRANGE: Pool storing the canonical quote vault bump
+        let pool_key = Pubkey::new_unique();
+        let (quote_vault, bump) = Pubkey::find_program_address(
+            &[BoundPool::QUOTE_VAULT_PREFIX, pool_key.as_ref()],
+            &crate::ID,
+        );
+        let mut pool = create_test_pool();
+        pool.quote_vault_bump = bump;
+
+        // ACT & ASSERT: The stored bump derives the same address
+        assert_eq!(pool.derived_quote_vault(pool_key), quote_vault);
+
+        // ACT & ASSERT: Another pool's vault doesn't match
+        assert_ne!(pool.derived_quote_vault(Pubkey::new_unique()), quote_vault);
+
+        println!("✅ Derived quote vault test passed!");
+    }
+
+    #[test]
+    fn test_only_creator_passes_creator_check() {
+        // ARRANGE: Pool with a known creator
+        let pool = create_test_pool();
+
+        // ACT & ASSERT: The creator passes, anyone else is unauthorized
+        assert!(pool.ensure_creator(pool.creator_addr).is_ok());
+        assert_eq!(
+            pool.ensure_creator(Pubkey::new_unique()).unwrap_err(),
+            error!(AmmError::Unauthorized)
+        );
+
+        println!("✅ Creator check test passed!");
+    }
+
+    #[test]
+    fn test_pool_version_upgrade() {
+        // ARRANGE: A version 1 account, the fields appended since reading as
+        // zero
+        let mut pool = create_test_pool();
+        pool.version = 1;
+        let mut data = Vec::new();
+        pool.try_serialize(&mut data).unwrap();
+        let pool_key = Pubkey::new_unique();
+
+        // ACT: Read it back the way the migration does
+        let mut old = BoundPool::try_deserialize(&mut &data[..]).unwrap();
+
+        // ASSERT: Handlers reject it until it's upgraded
+        assert_eq!(
+            old.ensure_version().unwrap_err(),
+            error!(AmmError::OutdatedPoolVersion)
+        );
+        old.upgrade(pool_key, 6).unwrap();
+        assert!(old.ensure_version().is_ok());
+        assert_eq!(old.quote_reserve, pool.quote_reserve);
+        assert_eq!(old.config, pool.config);
+
+        // ACT & ASSERT: A current account can't be upgraded again
+        assert!(old.upgrade(pool_key, 6).is_err());
+
+        println!("✅ Pool version upgrade test passed!");
+    }
+
+    #[test]
+    fn test_pool_v0_layout_upgrade() {
+        use crate::consts::ANCHOR_DISCRIMINATOR;
+        use crate::models::bound_v0::{BoundPoolV0, ConfigV0, DecimalsV0, FeesV0};
+        use anchor_lang::Discriminator;
+
+        // ARRANGE: A version 0 account with 1_000 meme sold, 10 taken as fees
+        let config = create_test_config();
+        let v0 = BoundPoolV0 {
+            meme_reserve: Reserve {
+                tokens: config.gamma_m - 1_010,
+                mint: Pubkey::new_unique(),
+                vault: Pubkey::new_unique(),
+            },
+            quote_reserve: Reserve {
+                tokens: 5_000,
+                mint: Pubkey::new_unique(),
+                vault: Pubkey::new_unique(),
+            },
+            admin_fees_meme: 10,
+            admin_fees_quote: 50,
+            fee_vault_quote: Pubkey::new_unique(),
+            creator_addr: Pubkey::new_unique(),
+            fees: FeesV0 {
+                fee_meme_percent: 0,
+                fee_quote_percent: FEE,
+            },
+            config: ConfigV0 {
+                alpha_abs: config.alpha_abs,
+                beta: config.beta,
+                price_factor_num: config.price_factor_num,
+                price_factor_denom: config.price_factor_denom,
+                gamma_s: config.gamma_s,
+                gamma_m: config.gamma_m,
+                omega_m: config.omega_m,
+                decimals: DecimalsV0 {
+                    alpha: config.decimals.alpha,
+                    beta: config.decimals.beta,
+                    quote: config.decimals.quote,
+                },
+            },
+            locked: false,
+            pool_migration: false,
+            migration_pool_key: Pubkey::default(),
+        };
+        let mut data = BoundPool::DISCRIMINATOR.to_vec();
+        v0.serialize(&mut data).unwrap();
+        let pool_key = Pubkey::new_unique();
+
+        // ASSERT: Its size tells it apart from every later layout, which
+        // can't be read as one
+        assert_eq!(data.len(), BoundPoolV0::SPACE);
+        assert!(BoundPool::try_deserialize(&mut &data[..]).is_err());
+
+        // ACT: Decode and upgrade it the way the migration does
+        let mut pool =
+            BoundPool::from(BoundPoolV0::deserialize(&mut &data[ANCHOR_DISCRIMINATOR..]).unwrap());
+        assert!(pool.ensure_version().is_err());
+        pool.upgrade(pool_key, 6).unwrap();
+
+        // ASSERT: Old fields carry over, the missing ones are backfilled
+        assert!(pool.ensure_version().is_ok());
+        assert_eq!(pool.meme_reserve, v0.meme_reserve);
+        assert_eq!(pool.quote_reserve, v0.quote_reserve);
+        assert_eq!(pool.admin_fees_quote, 50);
+        assert_eq!(pool.creator_addr, v0.creator_addr);
+        assert_eq!(pool.fees.fee_quote_percent, FEE);
+        assert_eq!(pool.fees.burn_on_sell_bps, 0);
+        assert_eq!(pool.config.gamma_m, config.gamma_m);
+        assert_eq!(pool.config.virtual_quote_reserve, 0);
+        assert_eq!(pool.config.decimals.quote, config.decimals.quote);
+        assert_eq!(pool.config.decimals.meme, 1_000_000);
+        assert_eq!(pool.initial_supply, config.gamma_m);
+        assert_eq!(pool.meme_circulating, 1_000);
+        let (_, signer_bump) = Pubkey::find_program_address(
+            &[BoundPool::SIGNER_PDA_PREFIX, pool_key.as_ref()],
+            &crate::ID,
+        );
+        assert_eq!(pool.signer_bump, signer_bump);
+
+        println!("✅ Version 0 pool upgrade test passed!");
+    }
+
+    #[test]
+    fn test_sells_locked_until_timestamp() {
+        // ARRANGE: Pool whose sells open at t = 1_000
+        let mut pool = create_test_pool();
+        pool.sell_lock_until = 1_000;
+
+        // ACT & ASSERT: Rejected before, open from the timestamp on
+        assert_eq!(
+            pool.ensure_sells_unlocked(999).unwrap_err(),
+            error!(AmmError::SellsLocked)
+        );
+        assert!(pool.ensure_sells_unlocked(1_000).is_ok());
+
+        // ACT & ASSERT: No lock by default
+        pool.sell_lock_until = 0;
+        assert!(pool.ensure_sells_unlocked(0).is_ok());
+
+        println!("✅ Sell lock test passed!");
+    }
+
+    #[test]
+    fn test_whitelist_duration_bounded() {
+        // ARRANGE: Pool created at t = 1_000
+        let now = 1_000;
+
+        // ACT & ASSERT: Accepted up to the maximum, rejected a second past it
+        assert!(BoundPool::ensure_whitelist_duration(now + MAX_WHITELIST_DURATION, now).is_ok());
+        assert_eq!(
+            BoundPool::ensure_whitelist_duration(now + MAX_WHITELIST_DURATION + 1, now)
+                .unwrap_err(),
+            error!(err::arg("Whitelist phase exceeds the maximum duration"))
+        );
+
+        // ACT & ASSERT: No whitelist phase is always fine
+        assert!(BoundPool::ensure_whitelist_duration(0, now).is_ok());
+
+        println!("✅ Whitelist duration test passed!");
+    }
+
+    #[test]
+    fn test_trade_flags_pause_each_direction() {
+        // ARRANGE: Pool trading both ways
+        let mut pool = create_test_pool();
+        assert!(pool.ensure_buys_enabled().is_ok());
+        assert!(pool.ensure_sells_enabled().is_ok());
+
+        // ACT & ASSERT: Pausing sells leaves buys open
+        pool.trade_flags = BoundPool::SELLS_PAUSED;
+        assert!(pool.ensure_buys_enabled().is_ok());
+        assert_eq!(
+            pool.ensure_sells_enabled().unwrap_err(),
+            error!(AmmError::PoolPaused)
+        );
+
+        // ACT & ASSERT: Pausing buys leaves sells open
+        pool.trade_flags = BoundPool::BUYS_PAUSED;
+        assert_eq!(
+            pool.ensure_buys_enabled().unwrap_err(),
+            error!(AmmError::PoolPaused)
+        );
+        assert!(pool.ensure_sells_enabled().is_ok());
+
+        // ACT & ASSERT: Both can be paused, unknown bits are rejected
+        pool.trade_flags = BoundPool::BUYS_PAUSED | BoundPool::SELLS_PAUSED;
+        assert!(pool.ensure_buys_enabled().is_err());
+        assert!(pool.ensure_sells_enabled().is_err());
+        assert!(BoundPool::validate_trade_flags(pool.trade_flags).is_ok());
+        assert!(BoundPool::validate_trade_flags(1 << 2).is_err());
+
+        println!("✅ Trade flags test passed!");
+    }
+
+    #[test]
+    fn test_first_buyer_bonus() {
+        // ARRANGE: 5% bonus, vault funded for a first buy of a reduced
+        // trading supply
+        let mut pool = create_test_pool();
+        pool.first_buyer_bonus_bps = 500;
+        let trading_supply = 100_000_000_000_000;
+        let vault = BoundPool::first_buyer_bonus_supply(trading_supply, 500);
+
+        // ACT & ASSERT: The vault is sized by the pool's supply, not the default
+        assert_eq!(vault, 5_000_000_000_000);
+        assert!(vault < BoundPool::first_buyer_bonus_supply(DEFAULT_MAX_M as u64, 500));
+
+        // ACT & ASSERT: The first buyer gets 5% of their buy
+        assert_eq!(pool.first_buyer_bonus(1_000_000, vault), 50_000);
+        assert_eq!(pool.first_buyer_bonus(trading_supply, vault), vault);
+
+        // ACT & ASSERT: Never more than the vault holds
+        assert_eq!(pool.first_buyer_bonus(1_000_000, 10), 10);
+
+        // ACT & ASSERT: Nothing once claimed, or when disabled
+        pool.first_buy_claimed = true;
+        assert_eq!(pool.first_buyer_bonus(1_000_000, vault), 0);
+        pool.first_buy_claimed = false;
+        pool.first_buyer_bonus_bps = 0;
+        assert_eq!(pool.first_buyer_bonus(1_000_000, vault), 0);
+
+        println!("✅ First buyer bonus test passed!");
+    }
+
+    #[test]
+    fn test_refund_amounts_are_pro_rata() {
+        // ARRANGE: 1_000 meme tokens out, backed by 400 quote + 40 second quote
+        let mut pool = create_dual_quote_test_pool();
+        pool.quote_reserve.tokens = 400;
+        pool.second_quote.as_mut().unwrap().reserve.tokens = 40;
+        pool.meme_circulating = 1_000;
+
+        // ACT: A holder of a quarter of the circulating supply claims
+        let (quote, second_quote) = pool.refund_amounts(250).unwrap();
+
+        // ASSERT: They get a quarter of each reserve
+        assert_eq!(quote, 100);
+        assert_eq!(second_quote, 10);
+
+        // ACT: The last holder claims after the pool was updated
+        pool.quote_reserve.tokens -= quote;
+        pool.second_quote.as_mut().unwrap().reserve.tokens -= second_quote;
+        pool.meme_circulating -= 250;
+        let (quote, second_quote) = pool.refund_amounts(750).unwrap();
+
+        // ASSERT: The remaining reserves are fully paid out
+        assert_eq!(quote, 300);
+        assert_eq!(second_quote, 30);
+
+        println!("✅ Pro-rata refund test passed!");
+    }
+
+    #[test]
+    fn test_record_volume_converts_and_saturates() {
+        // ARRANGE: Dual quote pool, 1 second quote unit = 10 quote units
+        let mut pool = create_dual_quote_test_pool();
+
+        // ACT: One trade per quote asset
+        pool.record_volume(1_000, 500, false);
+        pool.record_volume(100, 300, true);
+
+        // ASSERT: Second quote volume is measured in primary quote units
+        assert_eq!(pool.cumulative_quote_volume, 2_000);
+        assert_eq!(pool.cumulative_meme_volume, 800);
+
+        // ACT: Overflowing trades saturate instead of panicking
+        pool.record_volume(u64::MAX, u64::MAX, false);
+
+        // ASSERT: Both totals stay at the maximum
+        assert_eq!(pool.cumulative_quote_volume, u64::MAX);
+        assert_eq!(pool.cumulative_meme_volume, u64::MAX);
+
+        println!("✅ Cumulative volume test passed!");
+    }
+
+    #[test]
+    fn test_swap_amounts_apply_fee_tier() {
+        // ARRANGE: A tier halving the quote fee above 1_000 quote volume
+        let mut pool = create_test_pool();
+        let config = ProtocolConfig {
+            protocol_fee_authority: Pubkey::default(),
+            fee_tiers: vec![crate::models::protocol_config::FeeTier {
+                volume_threshold: 1_000,
+                fee_quote_percent: FEE / 2,
+            }],
+            fee_exempt: vec![],
+            pool_creation_enabled: true,
+            fee_treasury_enabled: false,
+            migration_fee_bps: 0,
+        };
+        let sol_amount = 1_000_000_000;
+
+        // ACT & ASSERT: Below the threshold the pool's own fee applies
+        let base = pool.swap_amounts(sol_amount, 0, true, None, false);
+        let below = pool.swap_amounts(sol_amount, 0, true, Some(&config), false);
+        assert_eq!(pool.fee_quote_percent(Some(&config)), FEE);
+        assert_eq!(below.admin_fee_in, base.admin_fee_in);
+
+        // ACT: Reach the tier
+        pool.record_volume(1_000, 0, false);
+        let tiered = pool.swap_amounts(sol_amount, 0, true, Some(&config), false);
+
+        // ASSERT: The reduced fee is charged, the stored fee is unchanged
+        assert_eq!(pool.fee_quote_percent(Some(&config)), FEE / 2);
+        assert_eq!(tiered.admin_fee_in, base.admin_fee_in / 2);
+        assert!(tiered.amount_out > base.amount_out);
+        assert_eq!(pool.fees.fee_quote_percent, FEE);
+
+        println!("✅ Fee tier swap test passed!");
+    }
+
+    #[test]
+    fn test_fee_exempt_swaps_pay_no_fees() {
+        // ARRANGE: Pool charging a 1% quote fee
+        let pool = create_test_pool();
+        let sol_amount = 1_000_000_000;
+
+        // ACT: Buy and sell as a regular and as an exempt trader
+        let regular_buy = pool.swap_amounts(sol_amount, 0, true, None, false);
+        let exempt_buy = pool.swap_amounts(sol_amount, 0, true, None, true);
+        let exempt_sell = pool.swap_amounts(exempt_buy.amount_out, 0, false, None, true);
+
+        // ASSERT: Exempt trades charge nothing and trade the whole input
+        assert!(regular_buy.admin_fee_in > 0);
+        assert_eq!(exempt_buy.admin_fee_in, 0);
+        assert_eq!(exempt_buy.admin_fee_out, 0);
+        assert_eq!(exempt_buy.amount_in, sol_amount);
+        assert!(exempt_buy.amount_out > regular_buy.amount_out);
+        assert_eq!(exempt_sell.admin_fee_in, 0);
+        assert_eq!(exempt_sell.admin_fee_out, 0);
+
+        // ASSERT: The stored fees are unchanged
+        assert_eq!(pool.fees.fee_quote_percent, FEE);
+
+        println!("✅ Fee exempt swap test passed!");
+    }
+
+    #[test]
+    fn test_migration_quote() {
+        // ARRANGE: Pool past its migration threshold
+        let mut pool = create_test_pool();
+        pool.meme_reserve.tokens = 100_000_000;
+        pool.quote_reserve.tokens = 85_000_000_000;
+
+        // ACT
+        let quote = pool.migration_quote();
+
+        // ASSERT: 95% of each reserve seeds the AMM, the rest stays
+        assert_eq!(quote.meme_to_amm, 95_000_000);
+        assert_eq!(quote.quote_to_amm, 80_750_000_000);
+        assert_eq!(quote.meme_retained, 5_000_000);
+        assert_eq!(quote.quote_retained, 4_250_000_000);
+        assert_eq!(
+            (quote.meme_to_amm, quote.quote_to_amm),
+            pool.migration_amounts()
+        );
+
+        // ASSERT: The AMM opens at the ratio of the seeded reserves
+        assert_eq!(quote.initial_price, 850 * SPOT_PRICE_PRECISION);
+
+        // ACT & ASSERT: An empty meme reserve has no price
+        pool.meme_reserve.tokens = 0;
+        assert_eq!(pool.migration_quote().initial_price, 0);
+
+        println!("✅ Migration quote test passed!");
     }
-}
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 1)
-fn delta_m1_positive_strategy(
-    alpha_abs: u128,
-    beta: u128,
-    alpha_decimals: u128,
-    beta_decimals: u128,
-    s_a: u128,
-    s_b: u128,
-) -> Option<u128> {
-    // For positive intercept, we ADD the beta term
-    let left_num = s_b.checked_sub(s_a)?.checked_mul(beta)?;
-    let left_denom = beta_decimals.checked_mul(DECIMALS_S)?;
-    let left = Some(left_num).checked_div_(Some(left_denom))?;
+    #[test]
+    fn test_migration_pays_only_creator_accounts() {
+        // ARRANGE: Pool with a known creator and distinct mints
+        let mut pool = create_test_pool();
+        pool.creator_addr = Pubkey::new_unique();
+        pool.meme_reserve.mint = Pubkey::new_unique();
+        pool.quote_reserve.mint = Pubkey::new_unique();
+        let (creator, meme, quote) = (
+            pool.creator_addr,
+            pool.meme_reserve.mint,
+            pool.quote_reserve.mint,
+        );
 
-    let s_b_squared = s_b.checked_pow(2)?;
-    let s_a_squared = s_a.checked_pow(2)?;
-    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
-    let decimals_s_squared = DECIMALS_S.checked_pow(2)?;
+        // ACT & ASSERT: The creator's accounts of each mint are accepted
+        assert!(pool.is_creator_account(creator, meme, meme));
+        assert!(pool.is_creator_account(creator, quote, quote));
 
-    let right = power_diff
-        .checked_mul(alpha_abs)
-        .checked_div(decimals_s_squared)?
-        .checked_div(2 * alpha_decimals)?;
+        // ACT & ASSERT: Another owner is rejected, e.g. a keeper's account
+        let keeper = Pubkey::new_unique();
+        assert!(!pool.is_creator_account(keeper, meme, meme));
+        assert!(!pool.is_creator_account(keeper, quote, quote));
 
-    // For positive slope with positive intercept, we ADD beta term
-    Some(left).checked_add_(Some(right))
-}
+        // ACT & ASSERT: The creator's account of the wrong mint is rejected
+        assert!(!pool.is_creator_account(creator, quote, meme));
+        assert!(!pool.is_creator_account(creator, Pubkey::new_unique(), quote));
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 2)
-fn delta_m2_positive_strategy(
-    alpha_abs: u128,
-    beta: u128,
-    alpha_decimals: u128,
-    beta_decimals: u128,
-    s_a: u128,
-    s_b: u128,
-) -> Option<u128> {
-    // For positive slope: price = +alpha_abs * supply + beta
-    // We ADD beta term
-    let left = (beta * 2)
-        .checked_mul(DECIMALS_S)
-        .checked_mul(alpha_decimals)
-        .checked_mul(s_b - s_a)?;
+        println!("✅ Migration creator accounts test passed!");
+    }
 
-    let s_b_squared = s_b.checked_pow(2)?;
-    let s_a_squared = s_a.checked_pow(2)?;
-    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
+    #[test]
+    fn test_migration_fee_deducted_from_seeded_quote() {
+        // ARRANGE: Pool at the threshold and a 2.5% migration fee
+        let pool = create_test_pool();
+        let mut config = ProtocolConfig {
+            protocol_fee_authority: Pubkey::default(),
+            fee_tiers: vec![],
+            fee_exempt: vec![],
+            pool_creation_enabled: true,
+            fee_treasury_enabled: true,
+            migration_fee_bps: 250,
+        };
+        let (_, quote_amount) = pool.migration_amounts();
 
-    let right = alpha_abs
-        .checked_mul(beta_decimals)
-        .checked_mul(power_diff)?;
+        // ACT
+        let fee = config.migration_fee(quote_amount);
+        let seeded = quote_amount - fee;
 
-    let denom = (2 * alpha_decimals)
-        .checked_mul(beta_decimals)
-        .checked_mul(DECIMALS_S.checked_pow(2)?)?;
+        // ASSERT: The AMM is seeded with the quote less the fee
+        assert_eq!(fee, quote_amount / 40);
+        assert_eq!(seeded + fee, quote_amount);
 
-    // For positive slope with positive intercept, we ADD both terms
-    left.checked_add(right)?.checked_div(denom)
-}
+        // ACT & ASSERT: No fee by default, the AMM gets all of it
+        config.migration_fee_bps = 0;
+        assert_eq!(config.migration_fee(quote_amount), 0);
 
-#[cfg(test)]
-mod tests {
-    use super::Reserve;
-    use super::*;
-    use crate::models::fees::FEE;
+        println!("✅ Migration fee test passed!");
+    }
 
-    // Helper function to create a test pool configuration
-    fn create_test_config() -> Config {
-        Config {
-            alpha_abs: 1_000_000,       // Smaller alpha for gentler slope
-            beta: 1_000_000_000,        // 1.0 with 9 decimals (positive intercept)
-            price_factor_num: 1,        // Simple 1:1 ratio
-            price_factor_denom: 10, // This gives omega_m * 1/10 = 300, clearly satisfying 2*gamma_m > omega_m*price_factor (6000 > 300)
-            gamma_s: 1_000_000_000_000, // 1000 SOL
-            gamma_m: 3_000_000_000_000, // 3000 tokens (increased to satisfy constraint better)
-            omega_m: 3_000_000_000_000, // 3000 tokens (same as gamma_m)
-            decimals: Decimals {
-                alpha: 1_000_000,     // 6 decimals for alpha
-                beta: 1_000_000_000,  // 9 decimals for beta
-                quote: 1_000_000_000, // 9 decimals (SOL)
-            },
-        }
+    #[test]
+    fn test_self_referral_rejected() {
+        // ARRANGE: Pool with a known creator and a buyer
+        let mut pool = create_test_pool();
+        pool.creator_addr = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+
+        // ACT & ASSERT: A third party can refer the buyer
+        assert!(pool
+            .ensure_valid_referrer(Pubkey::new_unique(), buyer)
+            .is_ok());
+
+        // ACT & ASSERT: The buyer can't refer themselves
+        let err = pool.ensure_valid_referrer(buyer, buyer).unwrap_err();
+        assert_eq!(err, error!(AmmError::SelfReferral));
+
+        // ACT & ASSERT: Nor can the creator refer buys of their own pool
+        let err = pool
+            .ensure_valid_referrer(pool.creator_addr, buyer)
+            .unwrap_err();
+        assert_eq!(err, error!(AmmError::SelfReferral));
+
+        println!("✅ Self referral test passed!");
     }
 
-    // Helper function to create a test pool
-    fn create_test_pool() -> BoundPool {
-        BoundPool {
-            meme_reserve: Reserve {
-                tokens: 500_000_000_000, // 500 tokens
-                mint: Pubkey::default(),
-                vault: Pubkey::default(),
-            },
-            quote_reserve: Reserve {
-                tokens: 250_000_000_000, // 250 SOL
-                mint: Pubkey::default(),
-                vault: Pubkey::default(),
-            },
-            admin_fees_meme: 0,
-            admin_fees_quote: 0,
-            fee_vault_quote: Pubkey::default(),
-            creator_addr: Pubkey::default(),
-            fees: Fees {
-                fee_meme_percent: 0,
-                fee_quote_percent: FEE, // 1%
-            },
-            config: create_test_config(),
-            locked: false,
-            pool_migration: false,
-            migration_pool_key: Pubkey::default(),
-        }
+    #[test]
+    fn test_tokens_until_migration() {
+        // ARRANGE: Pool nobody bought from yet
+        let mut pool = create_test_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 0;
+        let threshold = pool.migration_threshold();
+
+        // ACT
+        let progress = pool.tokens_until_migration().unwrap();
+
+        // ASSERT: The whole threshold is left, bought by the quote found
+        assert_eq!(progress.meme_remaining_until_threshold, threshold);
+        assert!(progress.quote_needed > 0);
+        let swap = pool.swap_amounts(progress.quote_needed, 0, true, None, true);
+        assert!(swap.amount_out + swap.admin_fee_out >= threshold);
+
+        // ACT & ASSERT: Halfway there, less meme and quote are left
+        pool.meme_reserve.tokens = pool.config.gamma_m - threshold / 2;
+        pool.quote_reserve.tokens = pool.compute_delta_s_in(0, threshold / 2).unwrap();
+        let halfway = pool.tokens_until_migration().unwrap();
+        assert_eq!(
+            halfway.meme_remaining_until_threshold,
+            threshold - threshold / 2
+        );
+        assert!(halfway.quote_needed < progress.quote_needed);
+
+        // ACT & ASSERT: Nothing is left past the threshold
+        pool.meme_reserve.tokens = pool.config.gamma_m - threshold - 1;
+        assert_eq!(
+            pool.tokens_until_migration().unwrap(),
+            MigrationProgress::default()
+        );
+
+        println!("✅ Tokens until migration test passed!");
     }
 
     #[test]
-    fn test_compute_delta_m_basic() {
-        // ARRANGE: Set up test data
-        let pool = create_test_pool();
-        let s_a = 100_000_000_000; // 100 SOL
-        let s_b = 200_000_000_000; // 200 SOL
+    fn test_spot_price_at_reserve() {
+        // ARRANGE: Fresh launch pool
+        let mut pool = create_launch_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 0;
+        let gamma_m = pool.config.gamma_m;
+
+        // ACT & ASSERT: The full reserve is priced at the current spot price
+        let start = pool.spot_price_at_reserve(gamma_m).unwrap();
+        assert_eq!(start, pool.spot_price().unwrap());
+
+        // ACT & ASSERT: On a rising curve, the price rises as the reserve is
+        // sold down
+        let mut linear = pool.clone();
+        linear.curve_type = CurveType::Linear;
+        let mut last = linear.spot_price_at_reserve(gamma_m).unwrap();
+        for reserve in [gamma_m / 4 * 3, gamma_m / 2, gamma_m / 4, 0] {
+            let price = linear.spot_price_at_reserve(reserve).unwrap();
+            assert!(price > last);
+            last = price;
+        }
 
-        println!("🧪 Testing compute_delta_m with:");
-        println!("   s_a (start): {} SOL", s_a / 1_000_000_000);
-        println!("   s_b (end): {} SOL", s_b / 1_000_000_000);
+        // ACT & ASSERT: The price after a buy matches its hypothetical reserve
+        let swap = pool.swap_amounts(10_000_000_000, 0, true, None, true);
+        pool.quote_reserve.tokens += swap.amount_in;
+        pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+        let at_reserve = pool
+            .spot_price_at_reserve(pool.meme_reserve.tokens)
+            .unwrap();
+        let spot = pool.spot_price().unwrap();
+        assert!(at_reserve.abs_diff(spot) * 1_000 <= spot);
 
-        // ACT: Calculate delta_m
-        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+        // ACT & ASSERT: A reserve past the trading supply is rejected
+        assert!(pool.spot_price_at_reserve(gamma_m + 1).is_err());
 
-        // ASSERT: Check result is reasonable
-        assert!(delta_m > 0, "Delta_m should be positive");
-        assert!(
-            delta_m < pool.config.gamma_m,
-            "Delta_m should be less than max meme tokens"
-        );
+        println!("✅ Spot price at reserve test passed!");
+    }
 
-        println!(
-            "✅ Test passed! Delta_m = {} tokens",
-            delta_m / 1_000_000_000
+    #[test]
+    fn test_circuit_breaker() {
+        // ARRANGE: Launch pool with some quote raised and a 5% breaker
+        let mut pool = create_launch_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 10_000_000_000;
+        pool.circuit_breaker_bps = 500;
+
+        // ACT: First trade of slot 7 records the price before it moves
+        pool.open_slot(7).unwrap();
+        let start_price = pool.slot_start_price;
+
+        // ASSERT: Price is recorded and a small move passes
+        assert_eq!(pool.last_slot, 7);
+        assert_eq!(start_price, pool.spot_price().unwrap());
+        pool.quote_reserve.tokens += 10_000_000;
+        assert!(pool.ensure_circuit_breaker().is_ok());
+
+        // ACT & ASSERT: Later trades of the slot keep the start price and a
+        // large move trips the breaker
+        pool.open_slot(7).unwrap();
+        assert_eq!(pool.slot_start_price, start_price);
+        pool.quote_reserve.tokens += 40_000_000_000;
+        assert_ne!(pool.spot_price().unwrap(), start_price);
+        assert_eq!(
+            pool.ensure_circuit_breaker().unwrap_err(),
+            error!(AmmError::CircuitBreakerTripped)
         );
+
+        // ACT & ASSERT: A new slot starts from the moved price
+        pool.open_slot(8).unwrap();
+        assert!(pool.ensure_circuit_breaker().is_ok());
+
+        // ACT & ASSERT: A disabled breaker never trips nor tracks slots
+        pool.circuit_breaker_bps = 0;
+        pool.open_slot(9).unwrap();
+        assert_eq!(pool.last_slot, 8);
+        pool.quote_reserve.tokens = 0;
+        assert!(pool.ensure_circuit_breaker().is_ok());
+
+        println!("✅ Circuit breaker test passed!");
     }
 
     #[test]
-    fn test_compute_delta_s_basic() {
-        // ARRANGE: Set up test data
-        let pool = create_test_pool();
-        let s_b = 100_000_000_000; // 100 SOL
-        let delta_m = 1_000_000_000; // 1 token to sell (much smaller amount)
+    fn test_swap_amounts_split_quote_fee() {
+        // ARRANGE: Dual quote pool sharing its fee with creator and referral
+        let mut pool = create_dual_quote_test_pool();
+        pool.fee_shares = FeeShares {
+            protocol_bps: 5_000,
+            creator_bps: 3_000,
+            referral_bps: 2_000,
+        };
 
-        println!("🧪 Testing compute_delta_s with:");
-        println!("   s_b (current): {} SOL", s_b / 1_000_000_000);
-        println!("   delta_m: {} tokens", delta_m / 1_000_000_000);
+        // ACT
+        let buy = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        let second_buy = pool
+            .second_quote_swap_amounts(100_000_000, 0, true, None, false)
+            .unwrap();
 
-        // ACT: Calculate delta_s
-        let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap();
+        // ASSERT: The buy's quote fee is split by the shares
+        assert_eq!(buy.quote_fee, pool.fee_shares.split(buy.admin_fee_in));
+        assert_eq!(buy.quote_fee.creator, buy.admin_fee_in * 3 / 10);
+        assert_eq!(buy.quote_fee.referral, buy.admin_fee_in / 5);
 
-        // ASSERT: Check result is reasonable
-        assert!(delta_s > 0, "Delta_s should be positive");
-        // Use a more reasonable upper bound check
-        assert!(
-            delta_s < 100_000_000_000_000, // 100k SOL as reasonable upper bound
-            "Delta_s should be reasonable: got {} SOL",
-            delta_s / 1_000_000_000
+        // ASSERT: Second quote fees all accrue to the protocol
+        assert_eq!(
+            second_buy.quote_fee,
+            FeeSplit::protocol_only(second_buy.admin_fee_in)
         );
 
-        println!("✅ Test passed! Delta_s = {} SOL", delta_s / 1_000_000_000);
+        println!("✅ Quote fee split test passed!");
     }
 
     #[test]
-    fn test_positive_intercept_at_zero_supply() {
-        // ARRANGE: Test that price = beta when supply = 0
-        let pool = create_test_pool();
-        let s_a = 0; // Start from 0 supply
-        let s_b = 1_000_000_000; // Small amount of SOL
+    fn test_raise_cap() {
+        // ARRANGE: Pool capped at 10 SOL, 9.5 SOL raised
+        let mut pool = create_test_pool();
+        pool.max_quote_raise = 10_000_000_000;
+        pool.quote_reserve.tokens = 9_500_000_000;
+        let fee_quote_percent = pool.fees.fee_quote_percent;
 
-        println!("🧪 Testing positive intercept (beta) at zero supply");
+        // ACT: A small buy fits, a larger one is partially filled
+        let small = pool
+            .capped_quote_in(100_000_000, fee_quote_percent)
+            .unwrap();
+        let large = pool
+            .capped_quote_in(1_000_000_000, fee_quote_percent)
+            .unwrap();
+        let swap = pool.swap_amounts(large, 0, true, None, false);
 
-        // ACT: Calculate delta_m
-        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+        // ASSERT: The partial fill brings the reserve right up to the cap
+        assert_eq!(small, 100_000_000);
+        assert!(large < 1_000_000_000);
+        assert!(pool.ensure_raise_cap(swap.amount_in).is_ok());
+        assert!(pool.max_quote_raise - (pool.quote_reserve.tokens + swap.amount_in) <= 1);
 
-        // The price at zero should be beta, so initial tokens should be higher
-        // because we're starting from a positive price
-        assert!(
-            delta_m > 0,
-            "Should get tokens even at zero supply due to positive intercept"
+        // ACT & ASSERT: Buys past the cap are rejected
+        assert_eq!(
+            pool.ensure_raise_cap(500_000_001).unwrap_err(),
+            error!(AmmError::RaiseCapReached)
         );
 
-        println!(
-            "✅ Positive intercept confirmed! Got {} tokens for {} SOL at zero supply",
-            delta_m / 1_000_000_000,
-            s_b / 1_000_000_000
+        // ACT & ASSERT: A full pool takes no more buys
+        pool.quote_reserve.tokens = pool.max_quote_raise;
+        assert_eq!(
+            pool.capped_quote_in(1, fee_quote_percent).unwrap_err(),
+            error!(AmmError::RaiseCapReached)
         );
+
+        // ACT & ASSERT: Zero leaves the pool uncapped
+        pool.max_quote_raise = 0;
+        assert_eq!(
+            pool.capped_quote_in(u64::MAX, fee_quote_percent).unwrap(),
+            u64::MAX
+        );
+        assert!(pool.ensure_raise_cap(u64::MAX).is_ok());
+
+        println!("✅ Raise cap test passed!");
     }
 
     #[test]
-    fn test_buy_meme_swap_amounts() {
-        // ARRANGE: Set up test data
-        let pool = create_test_pool();
-        let sol_amount = 10_000_000_000; // 10 SOL to buy with
-        let min_meme_out = 0; // No slippage protection for test
+    fn test_buy_after_migration() {
+        // ARRANGE: Launch pool past its threshold, 0.5% fee after migration
+        let mut pool = create_launch_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m - BoundPool::MIGRATION_THRESHOLD;
+        pool.quote_reserve.tokens = 80_000_000_000;
+        pool.post_migration_fee_bps = 50;
+        pool.locked = true;
+        let price_before = pool.spot_price().unwrap();
+        let fee_before = pool.fee_quote_percent(None);
+        let (meme_before, quote_before) = (pool.meme_reserve, pool.quote_reserve);
+
+        // ACT: Migrate 95% of the reserves
+        let (meme_migrated, quote_migrated) = pool.migration_amounts();
+        let (meme_retained, quote_retained) = (
+            pool.meme_reserve.tokens - meme_migrated,
+            pool.quote_reserve.tokens - quote_migrated,
+        );
+        pool.complete_migration(
+            MigrationAmm::Cpmm,
+            Pubkey::new_unique(),
+            meme_migrated,
+            quote_migrated,
+            1_234,
+        );
 
-        println!(
-            "🧪 Testing buy_meme_swap_amounts with {} SOL",
-            sol_amount / 1_000_000_000
+        // ASSERT: Reserves hold the retained 5%, trading resumes at the same
+        // price with the post-migration fee
+        assert_eq!(pool.migration_slot, 1_234);
+        assert_eq!(pool.meme_reserve.tokens, meme_retained);
+        assert_eq!(pool.quote_reserve.tokens, quote_retained);
+
+        // ASSERT: The snapshot holds the reserves migrated from
+        assert_eq!(pool.meme_reserve_at_migration, meme_before);
+        assert_eq!(pool.quote_reserve_at_migration, quote_before);
+        assert_eq!(
+            pool.meme_reserve_at_migration.tokens,
+            meme_migrated + meme_retained
+        );
+        assert_eq!(
+            pool.quote_reserve_at_migration.tokens,
+            quote_migrated + quote_retained
+        );
+        assert!(pool.ensure_unlocked().is_ok());
+        assert_eq!(pool.spot_price().unwrap(), price_before);
+        assert_eq!(pool.fee_quote_percent(None), FEE / 2);
+        assert_ne!(pool.fee_quote_percent(None), fee_before);
+
+        // ACT: Buy against the residual liquidity
+        let buy = pool.swap_amounts(100_000_000, 0, true, None, false);
+
+        // ASSERT: Priced at the post-migration fee, within the retained meme
+        assert_eq!(buy.admin_fee_in, 500_000);
+        assert!(buy.amount_out > 0);
+        assert!(buy.amount_out + buy.admin_fee_out <= meme_retained);
+
+        // ACT & ASSERT: A buy larger than the retained meme is rejected
+        let too_large = pool
+            .priced(None, false)
+            .buy_meme_swap_amounts(10_000_000_000, 0);
+        assert_eq!(
+            too_large.unwrap_err(),
+            error!(AmmError::InsufficientBalance)
         );
 
-        // ACT: Calculate swap amounts
-        let swap = pool
-            .buy_meme_swap_amounts(sol_amount, min_meme_out)
-            .unwrap();
+        println!("✅ Buy after migration test passed!");
+    }
 
-        // ASSERT: Check all values are correct
-        assert!(swap.amount_out > 0, "Should receive meme tokens");
+    #[test]
+    fn test_graduated_fee_ramp() {
+        // ARRANGE: Launch pool ramping its fee from 1% down to 0.2%
+        let mut pool = create_launch_pool();
+        pool.config.fee_start_bps = 100;
+        pool.config.fee_end_bps = 20;
+        let gamma_m = pool.config.gamma_m;
+        let threshold = BoundPool::MIGRATION_THRESHOLD;
+        pool.meme_reserve.tokens = gamma_m;
+        pool.quote_reserve.tokens = 0;
+
+        // ACT & ASSERT: 0% of the threshold sold, the start fee applies
+        assert_eq!(pool.fee_quote_percent(None), FEE);
+
+        // ACT & ASSERT: 50% of the threshold sold, halfway down the ramp
+        pool.meme_reserve.tokens = gamma_m - threshold / 2;
+        assert_eq!(pool.fee_quote_percent(None), FEE * 6 / 10);
+
+        // ACT & ASSERT: 100% of the threshold sold, the end fee applies
+        pool.meme_reserve.tokens = gamma_m - threshold;
+        assert_eq!(pool.fee_quote_percent(None), FEE / 5);
+        let swap = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        assert_eq!(swap.admin_fee_in, 2_000_000);
+
+        // ACT & ASSERT: The fee stays at its end past the threshold
+        pool.meme_reserve.tokens = 0;
+        assert_eq!(pool.fee_quote_percent(None), FEE / 5);
+
+        // ACT & ASSERT: Without a ramp, the flat fee applies unchanged
+        pool.config.fee_start_bps = 0;
+        pool.config.fee_end_bps = 0;
+        assert_eq!(pool.fee_quote_percent(None), pool.fees.fee_quote_percent);
+
+        println!("✅ Graduated fee test passed!");
+    }
+
+    #[test]
+    fn test_price_factor_ramp() {
+        // ARRANGE: Launch curve with nothing sold, then ramped from 3 to 4
+        let mut pool = create_launch_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 0;
+        let static_pool = pool.clone();
+        pool.config.price_factor_num_end = 4;
+        let sol_amount = 1_000_000_000;
+
+        // ACT & ASSERT: Unset ramp keeps the static factor
+        assert_eq!(static_pool.price_factor(), (3, 1));
+
+        // ACT & ASSERT: The ramp starts at the static factor and prices alike
+        assert_eq!(pool.price_factor(), (3_000, 1_000));
         assert_eq!(
-            swap.admin_fee_in,
-            sol_amount / 100, // 1% fee
-            "Admin fee should be 1% of input"
+            pool.swap_amounts(sol_amount, 0, true, None, false)
+                .amount_out,
+            static_pool
+                .swap_amounts(sol_amount, 0, true, None, false)
+                .amount_out
         );
-        assert!(
-            swap.amount_in <= sol_amount - swap.admin_fee_in,
-            "Net amount in should be less than input minus fees"
+
+        // ACT: Sell half of the curve supply
+        pool.meme_reserve.tokens = pool.config.gamma_m / 2;
+
+        // ASSERT: The factor is halfway, and the stored curve is untouched
+        assert_eq!(pool.price_factor(), (3_500, 1_000));
+        assert_eq!(pool.config.alpha_abs, static_pool.config.alpha_abs);
+
+        // ACT & ASSERT: Trades are priced on the steeper curve
+        pool.quote_reserve.tokens = 30_000_000_000;
+        let mut static_half = static_pool.clone();
+        static_half.meme_reserve.tokens = pool.meme_reserve.tokens;
+        static_half.quote_reserve.tokens = pool.quote_reserve.tokens;
+        assert_ne!(
+            pool.swap_amounts(sol_amount, 0, true, None, false)
+                .amount_out,
+            static_half
+                .swap_amounts(sol_amount, 0, true, None, false)
+                .amount_out
         );
 
-        println!("✅ Buy swap test passed!");
-        println!("   Input: {} SOL", sol_amount / 1_000_000_000);
-        println!("   Output: {} MEME", swap.amount_out / 1_000_000_000);
-        println!("   Fee: {} SOL", swap.admin_fee_in / 1_000_000_000);
+        // ACT & ASSERT: The ramp ends at the end factor
+        pool.meme_reserve.tokens = 0;
+        assert_eq!(pool.price_factor(), (4_000, 1_000));
+
+        println!("✅ Price factor ramp test passed!");
     }
 
     #[test]
@@ -845,6 +4914,50 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_min_out_is_an_inclusive_floor() {
+        // ARRANGE: Pool holding both quote assets, and the floor check every
+        // swap direction must pass
+        let mut pool = create_dual_quote_test_pool();
+        pool.quote_reserve.tokens = 100_000_000_000;
+        pool.second_quote.as_mut().unwrap().reserve.tokens = 1_000_000_000;
+        let assert_floor = |swap: &dyn Fn(u64) -> Result<SwapAmount>| {
+            let out = swap(0).unwrap().amount_out;
+            assert!(out > 0);
+
+            // ACT & ASSERT: Output equal to the floor is accepted
+            assert_eq!(swap(out).unwrap().amount_out, out);
+
+            // ACT & ASSERT: One unit above the output reverts
+            assert_eq!(
+                swap(out + 1).unwrap_err(),
+                error!(AmmError::SlippageExceeded)
+            );
+        };
+
+        // ACT & ASSERT: Buys and sells in either quote asset
+        assert_floor(&|min| pool.buy_meme_swap_amounts(1_000_000_000, min));
+        assert_floor(&|min| pool.sell_meme_swap_amounts(1_000_000_000, min));
+        assert_floor(&|min| pool.buy_meme_second_quote_swap_amounts(100_000_000, min));
+        assert_floor(&|min| pool.sell_meme_second_quote_swap_amounts(1_000, min));
+
+        // ACT & ASSERT: The exact output cost is a floor on the other side
+        let exact = pool
+            .exact_out_swap_amounts(1_000_000, u64::MAX, false, None, false)
+            .unwrap();
+        let cost = exact.amount_in + exact.admin_fee_in;
+        assert!(pool
+            .exact_out_swap_amounts(1_000_000, cost, false, None, false)
+            .is_ok());
+        assert_eq!(
+            pool.exact_out_swap_amounts(1_000_000, cost - 1, false, None, false)
+                .unwrap_err(),
+            error!(AmmError::SlippageExceeded)
+        );
+
+        println!("✅ Inclusive min out floor test passed!");
+    }
+
     #[test]
     fn test_alpha_and_beta_calculation() {
         // ARRANGE: Test the compute_alpha_abs and compute_beta functions
@@ -889,4 +5002,96 @@ mod tests {
         println!("   Alpha: {} (decimals: {})", alpha, alpha_decimals_result);
         println!("   Beta: {} (decimals: {})", beta, beta_decimals);
     }
+
+    #[test]
+    fn test_curve_invariant_catches_corrupted_reserves() {
+        // ARRANGE: Fresh launch pool
+        let mut pool = create_launch_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 0;
+
+        // ACT & ASSERT: A buy applied as swap_y does keeps the invariant
+        let before = pool.clone();
+        let buy = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+        assert!(pool.ensure_curve_invariant(&before).is_ok());
+
+        // ACT & ASSERT: A sell applied as swap_x does keeps the invariant
+        let before = pool.clone();
+        let sell = pool.swap_amounts(buy.amount_out / 2, 0, false, None, false);
+        pool.meme_reserve.tokens += sell.amount_in;
+        pool.quote_reserve.tokens -= sell.amount_out + sell.admin_fee_out;
+        assert!(pool.ensure_curve_invariant(&before).is_ok());
+
+        // ACT & ASSERT: A buy releasing 2% more meme than priced reverts
+        let before = pool.clone();
+        let buy = pool.swap_amounts(1_000_000_000, 0, true, None, false);
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= (buy.amount_out + buy.admin_fee_out) * 102 / 100;
+        assert_eq!(
+            pool.ensure_curve_invariant(&before).unwrap_err(),
+            error!(AmmError::InvariantViolation)
+        );
+
+        // ACT & ASSERT: A sell paying out quote without taking meme reverts
+        let before = pool.clone();
+        pool.quote_reserve.tokens -= 100_000_000;
+        assert_eq!(
+            pool.ensure_curve_invariant(&before).unwrap_err(),
+            error!(AmmError::InvariantViolation)
+        );
+
+        println!("✅ Curve invariant corruption test passed!");
+    }
+
+    #[test]
+    fn test_tiny_round_trips_never_profit() {
+        // ARRANGE: Launch pool traded without fees, the worst case for the
+        // pool, and a deterministic sequence of tiny amounts
+        let mut pool = create_launch_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 0;
+        let mut seed: u64 = 42;
+        let mut quote_spent = 0u64;
+        let mut quote_received = 0u64;
+
+        for i in 0..2_000u64 {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let amount = 1 + (seed >> 33) % 1_000;
+
+            // ACT: Buy, then immediately sell everything bought
+            let buy = pool.swap_amounts(amount, 0, true, None, true);
+            pool.quote_reserve.tokens += buy.amount_in;
+            pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+
+            let sell = pool.swap_amounts(buy.amount_out, 0, false, None, true);
+            pool.quote_reserve.tokens -= sell.amount_out + sell.admin_fee_out;
+            pool.meme_reserve.tokens += sell.amount_in;
+
+            // ASSERT: No single round trip returns more than it paid
+            assert!(
+                sell.amount_out <= amount,
+                "round trip {} of {} returned {}",
+                i,
+                amount,
+                sell.amount_out
+            );
+            quote_spent += amount;
+            quote_received += sell.amount_out;
+
+            // Move along the curve between round trips
+            let step = pool.swap_amounts(seed % 100_000_000, 0, true, None, true);
+            pool.quote_reserve.tokens += step.amount_in;
+            pool.meme_reserve.tokens -= step.amount_out + step.admin_fee_out;
+        }
+
+        // ASSERT: Nor does the whole sequence
+        assert!(quote_received <= quote_spent);
+
+        println!("✅ Tiny round trips test passed!");
+        println!("   Spent: {}, received: {}", quote_spent, quote_received);
+    }
 }
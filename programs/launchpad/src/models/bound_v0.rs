@@ -0,0 +1,104 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use anchor_lang::prelude::*;
+
+use super::{
+    bound::{BoundPool, Config, Decimals},
+    fees::Fees,
+    Reserve,
+};
+
+/// Layout of pool accounts written before the layout was versioned, i.e.
+/// version 0 of [`BoundPool`].
+///
+/// `Fees`, `Config` and `Decimals` were extended mid-account since, so such
+/// an account can't be read as a zero padded `BoundPool`. `migrate_pool_account`
+/// decodes it with this struct instead and converts it.
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Default, InitSpace)]
+pub struct BoundPoolV0 {
+    pub meme_reserve: Reserve,
+    pub quote_reserve: Reserve,
+    pub admin_fees_meme: u64,
+    pub admin_fees_quote: u64,
+    pub fee_vault_quote: Pubkey,
+    pub creator_addr: Pubkey,
+    pub fees: FeesV0,
+    pub config: ConfigV0,
+    pub locked: bool,
+    pub pool_migration: bool,
+    pub migration_pool_key: Pubkey,
+}
+
+/// `Fees` of a version 0 pool
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Default, InitSpace)]
+pub struct FeesV0 {
+    pub fee_meme_percent: u64,
+    pub fee_quote_percent: u64,
+}
+
+/// `Config` of a version 0 pool
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Default, InitSpace)]
+pub struct ConfigV0 {
+    pub alpha_abs: u128,
+    pub beta: u128,
+    pub price_factor_num: u64,
+    pub price_factor_denom: u64,
+    pub gamma_s: u64,
+    pub gamma_m: u64,
+    pub omega_m: u64,
+    pub decimals: DecimalsV0,
+}
+
+/// `Decimals` of a version 0 pool, which didn't store the meme precision
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Default, InitSpace)]
+pub struct DecimalsV0 {
+    pub alpha: u128,
+    pub beta: u128,
+    pub quote: u64,
+}
+
+impl BoundPoolV0 {
+    /// Size of a version 0 pool account, discriminator included. Every later
+    /// layout is larger.
+    pub const SPACE: usize = ANCHOR_DISCRIMINATOR + Self::INIT_SPACE;
+}
+
+impl From<BoundPoolV0> for BoundPool {
+    /// Carries the version 0 fields over, everything it lacked reads as zero
+    /// until [`BoundPool::upgrade`] backfills it
+    fn from(old: BoundPoolV0) -> Self {
+        BoundPool {
+            meme_reserve: old.meme_reserve,
+            quote_reserve: old.quote_reserve,
+            admin_fees_meme: old.admin_fees_meme,
+            admin_fees_quote: old.admin_fees_quote,
+            fee_vault_quote: old.fee_vault_quote,
+            creator_addr: old.creator_addr,
+            fees: Fees {
+                fee_meme_percent: old.fees.fee_meme_percent,
+                fee_quote_percent: old.fees.fee_quote_percent,
+                ..Default::default()
+            },
+            config: Config {
+                alpha_abs: old.config.alpha_abs,
+                beta: old.config.beta,
+                price_factor_num: old.config.price_factor_num,
+                price_factor_denom: old.config.price_factor_denom,
+                gamma_s: old.config.gamma_s,
+                gamma_m: old.config.gamma_m,
+                omega_m: old.config.omega_m,
+                decimals: Decimals {
+                    alpha: old.config.decimals.alpha,
+                    beta: old.config.decimals.beta,
+                    quote: old.config.decimals.quote,
+                    meme: 0,
+                },
+                ..Default::default()
+            },
+            locked: old.locked,
+            pool_migration: old.pool_migration,
+            migration_pool_key: old.migration_pool_key,
+            version: 0,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::libraries::MulDiv;
+use anchor_lang::prelude::*;
+
+/// Per wallet, per pool record of a buyer's interactions with the curve
+#[account]
+#[derive(InitSpace)]
+pub struct BuyerRecord {
+    /// Pool the record belongs to
+    pub pool: Pubkey,
+    /// Wallet the record belongs to
+    pub buyer: Pubkey,
+    /// Set once the buyer claimed their share of a refunding pool
+    pub refunded: bool,
+    /// Meme tokens bought from the curve, first buyer bonus included, and
+    /// not sold back or refunded yet
+    pub meme_held: u64,
+    /// Quote paid for `meme_held`, fees included, in primary quote units
+    pub cost_basis: u64,
+}
+
+impl BuyerRecord {
+    pub const RECORD_PREFIX: &'static [u8; 12] = b"buyer_record";
+
+    /// True until the record's first buy is settled. Records are created
+    /// empty by `init_buyer_record` and get their pool on the first buy.
+    pub fn is_new(&self) -> bool {
+        self.pool == Pubkey::default()
+    }
+
+    /// Reads the record at the PDA `info`, `None` until `init_buyer_record`
+    /// created it. Swaps always pass the PDA so a created record can't be
+    /// left out and go stale.
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.owner != &crate::ID {
+            return Ok(None);
+        }
+
+        let data = info.try_borrow_data()?;
+        BuyerRecord::try_deserialize(&mut &data[..]).map(Some)
+    }
+
+    /// Writes the record back to `info`, see [`BuyerRecord::load`]
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Adds a buy of `meme_amount` tokens for `quote_amount` to the position
+    pub fn record_buy(&mut self, meme_amount: u64, quote_amount: u64) {
+        self.meme_held = self.meme_held.saturating_add(meme_amount);
+        self.cost_basis = self.cost_basis.saturating_add(quote_amount);
+    }
+
+    /// Meme out of a `balance` that can be burned for a refund: only what
+    /// the buyer got from the curve and still holds. Tokens received any
+    /// other way, e.g. a creator allocation or an airdrop, never paid into
+    /// the reserve.
+    pub fn refundable(&self, balance: u64) -> u64 {
+        balance.min(self.meme_held)
+    }
+
+    /// Removes a sale of `meme_amount` tokens from the position, releasing
+    /// the matching share of the cost basis. Tokens not bought from the curve
+    /// carry no cost, so selling more than held empties the position.
+    pub fn record_sell(&mut self, meme_amount: u64) {
+        let sold = meme_amount.min(self.meme_held);
+        let released_cost = self
+            .cost_basis
+            .mul_div_floor(sold, self.meme_held)
+            .unwrap_or(self.cost_basis);
+
+        self.meme_held -= sold;
+        self.cost_basis -= released_cost;
+    }
+}
+
+/// Buyer position written to the return data by `get_buyer_position`
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct BuyerPosition {
+    /// See [`BuyerRecord::meme_held`]
+    pub meme_held: u64,
+    /// See [`BuyerRecord::cost_basis`]
+    pub cost_basis: u64,
+    /// Quote received selling `meme_held` to the curve now, fees and price
+    /// impact included
+    pub current_value: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sell_releases_pro_rata_cost() {
+        // ARRANGE: Two buys at different prices
+        let mut record = BuyerRecord {
+            pool: Pubkey::default(),
+            buyer: Pubkey::default(),
+            refunded: false,
+            meme_held: 0,
+            cost_basis: 0,
+        };
+        record.record_buy(1_000, 100);
+        record.record_buy(1_000, 300);
+
+        // ACT: Sell a quarter of the position
+        record.record_sell(500);
+
+        // ASSERT: The average entry price is kept
+        assert_eq!(record.meme_held, 1_500);
+        assert_eq!(record.cost_basis, 300);
+
+        // ACT: Sell more than held, e.g. tokens received by transfer
+        record.record_sell(10_000);
+
+        // ASSERT: The position is emptied
+        assert_eq!(record.meme_held, 0);
+        assert_eq!(record.cost_basis, 0);
+
+        println!("✅ Buyer record sell test passed!");
+    }
+
+    #[test]
+    fn test_record_is_new_only_before_first_buy() {
+        // ARRANGE: Record as created by `init_buyer_record`
+        let mut record = BuyerRecord {
+            pool: Pubkey::default(),
+            buyer: Pubkey::default(),
+            refunded: false,
+            meme_held: 0,
+            cost_basis: 0,
+        };
+        assert!(record.is_new());
+
+        // ACT: First buy settles, then the buyer sells out
+        record.pool = Pubkey::new_unique();
+        record.record_buy(1_000, 100);
+        record.record_sell(1_000);
+
+        // ASSERT: A repeat buyer is never counted again
+        assert!(!record.is_new());
+
+        println!("✅ Buyer record first buy test passed!");
+    }
+
+    #[test]
+    fn test_only_curve_bought_meme_is_refundable() {
+        // ARRANGE: A wallet that bought 1_000 meme from the curve
+        let mut record = BuyerRecord {
+            pool: Pubkey::new_unique(),
+            buyer: Pubkey::default(),
+            refunded: false,
+            meme_held: 0,
+            cost_basis: 0,
+        };
+        record.record_buy(1_000, 100);
+
+        // ACT & ASSERT: Tokens on top, e.g. a creator allocation, aren't
+        // refundable, tokens sent away can't be claimed either
+        assert_eq!(record.refundable(1_000_000), 1_000);
+        assert_eq!(record.refundable(400), 400);
+
+        // ACT & ASSERT: A wallet that never bought has nothing to refund
+        record.record_sell(1_000);
+        assert_eq!(record.refundable(1_000_000), 0);
+
+        println!("✅ Refundable meme test passed!");
+    }
+
+    #[test]
+    fn test_load_only_created_records() {
+        // ARRANGE: The record PDA before and after `init_buyer_record`
+        let key = Pubkey::new_unique();
+        let system_owner = Pubkey::default();
+        let (mut lamports, mut empty) = (0, vec![]);
+        let missing = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut empty,
+            &system_owner,
+            false,
+            0,
+        );
+
+        let mut record = BuyerRecord {
+            pool: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            refunded: false,
+            meme_held: 0,
+            cost_basis: 0,
+        };
+        let mut data = vec![0; 8 + BuyerRecord::INIT_SPACE];
+        record.try_serialize(&mut &mut data[..]).unwrap();
+        let mut rent = 1;
+        let created = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut rent,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        // ACT & ASSERT: Nothing to track before it's created
+        assert!(BuyerRecord::load(&missing).unwrap().is_none());
+
+        // ACT: A buy is tracked in the created record
+        record.record_buy(1_000, 100);
+        record.store(&created).unwrap();
+
+        // ASSERT: It reads back as written
+        let loaded = BuyerRecord::load(&created).unwrap().unwrap();
+        assert_eq!(loaded.meme_held, 1_000);
+        assert_eq!(loaded.cost_basis, 100);
+        assert_eq!(loaded.buyer, record.buyer);
+
+        println!("✅ Buyer record load test passed!");
+    }
+}
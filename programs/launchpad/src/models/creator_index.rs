@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Count of the pools launched by a creator, so a creator's pools can be
+/// listed without scanning every pool.
+///
+/// Derivation scheme for clients:
+/// 1. `CreatorIndex` at `[CREATOR_INDEX_PREFIX, creator]` gives `pool_count`
+/// 2. for each `i` in `0..pool_count`, `CreatorPool` at
+///    `[CREATOR_POOL_PREFIX, creator, i.to_le_bytes()]` holds the pool address
+///
+/// The creator is the signer of `new_pool`, entries stay with it when the
+/// pool is later transferred to another creator.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorIndex {
+    /// Number of pools launched so far, also the index of the next pool
+    pub pool_count: u64,
+}
+
+impl CreatorIndex {
+    pub const CREATOR_INDEX_PREFIX: &'static [u8; 13] = b"creator_index";
+
+    /// Assigns the next creator index to a new pool
+    pub fn register_pool(&mut self) -> u64 {
+        let index = self.pool_count;
+        self.pool_count += 1;
+        index
+    }
+}
+
+/// Pool launched by a creator at a given index, see [`CreatorIndex`]
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorPool {
+    pub pool: Pubkey,
+}
+
+impl CreatorPool {
+    pub const CREATOR_POOL_PREFIX: &'static [u8; 12] = b"creator_pool";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creator_index_counts_pools() {
+        // ARRANGE: Creator without pools
+        let mut creator_index = CreatorIndex { pool_count: 0 };
+
+        // ACT: Launch two pools
+        let indexes: Vec<u64> = (0..2).map(|_| creator_index.register_pool()).collect();
+
+        // ASSERT: Entries are derived from sequential indexes
+        assert_eq!(indexes, vec![0, 1]);
+        assert_eq!(creator_index.pool_count, 2);
+
+        println!("✅ Creator index test passed!");
+    }
+}
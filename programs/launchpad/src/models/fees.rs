@@ -1,19 +1,44 @@
+use crate::consts::FEE_DENOMINATOR;
+use crate::err;
+use crate::err::AmmError;
 use crate::libraries::MulDiv;
+use crate::math::rounding::mul_div_in;
 use anchor_lang::prelude::*;
 
 pub const MEME_FEE: u64 = 0; // 0%
 pub const FEE: u64 = 10_000_000; // 1%
-pub const FEE_PRECISION: u64 = 1_000_000_000;
+pub const REFERRAL_FEE_BPS: u16 = 1_000; // 10% of the protocol fee
+pub const BPS_PRECISION: u64 = 10_000;
+pub const MAX_PLATFORM_FEE_BPS: u16 = 100; // aggregators add at most 1% per swap
 
 #[derive(
     AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
 )]
 pub struct Fees {
+    /// Fee on the meme side of swaps, in `FEE_DENOMINATOR` units
     pub fee_meme_percent: u64,
+    /// Fee on the quote side of swaps, in `FEE_DENOMINATOR` units
     pub fee_quote_percent: u64,
+    /// Share of every sell's meme input burned instead of traded, in bps
+    pub burn_on_sell_bps: u16,
+    /// Share of the protocol's quote fee rebated to a buy's referrer, in bps
+    pub referral_fee_bps: u16,
 }
 
 impl Fees {
+    /// Rejects fee percents above `FEE_DENOMINATOR` and a sell burn above
+    /// `BPS_PRECISION`, i.e. 100%
+    pub fn validate(&self) -> Result<()> {
+        if self.fee_meme_percent > FEE_DENOMINATOR
+            || self.fee_quote_percent > FEE_DENOMINATOR
+            || self.burn_on_sell_bps as u64 > BPS_PRECISION
+        {
+            return Err(error!(AmmError::InvalidFee));
+        }
+
+        Ok(())
+    }
+
     pub fn get_fee_meme_amount(&self, amount: u64) -> Result<u64> {
         get_fee_amount(amount, self.fee_meme_percent)
     }
@@ -21,10 +46,138 @@ impl Fees {
     pub fn get_fee_quote_amount(&self, amount: u64) -> Result<u64> {
         get_fee_amount(amount, self.fee_quote_percent)
     }
+
+    /// Meme burned off a sell of `amount`, rounded down
+    pub fn get_burn_on_sell_amount(&self, amount: u64) -> Result<u64> {
+        if self.burn_on_sell_bps as u64 > BPS_PRECISION {
+            return Err(error!(AmmError::InvalidFee));
+        }
+
+        amount
+            .mul_div_floor(self.burn_on_sell_bps as u64, BPS_PRECISION)
+            .ok_or(error!(AmmError::MathOverflow))
+    }
+}
+
+/// Split of the quote fee between its recipients, in bps of the fee. All
+/// zero sends the whole fee to the protocol.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub struct FeeShares {
+    pub protocol_bps: u16,
+    pub creator_bps: u16,
+    pub referral_bps: u16,
+}
+
+impl FeeShares {
+    /// Rejects shares not adding up to the whole fee, unless all zero
+    pub fn validate(&self) -> Result<()> {
+        let total = self.protocol_bps as u64 + self.creator_bps as u64 + self.referral_bps as u64;
+        if total != 0 && total != BPS_PRECISION {
+            return Err(error!(err::arg(format!(
+                "Fee shares must add up to {} bps",
+                BPS_PRECISION
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /// Splits `fee` by the shares. Creator and referral portions are rounded
+    /// down, the protocol getting the remainder.
+    pub fn split(&self, fee: u64) -> FeeSplit {
+        let creator = fee
+            .mul_div_floor(self.creator_bps as u64, BPS_PRECISION)
+            .unwrap();
+        let referral = fee
+            .mul_div_floor(self.referral_bps as u64, BPS_PRECISION)
+            .unwrap();
+
+        FeeSplit {
+            protocol: fee - creator - referral,
+            creator,
+            referral,
+            rebate: 0,
+        }
+    }
+}
+
+/// Quote fee of a swap broken down by recipient, see [`FeeShares::split`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct FeeSplit {
+    /// Accrued to the pool's admin fees, withdrawn by the protocol
+    pub protocol: u64,
+    /// Paid to the pool's creator fee vault
+    pub creator: u64,
+    /// Paid to the pool's referral fee vault
+    pub referral: u64,
+    /// Paid to the trader's referrer, carved from `protocol`
+    pub rebate: u64,
+}
+
+impl FeeSplit {
+    /// Whole fee accrued to the protocol
+    pub fn protocol_only(fee: u64) -> Self {
+        FeeSplit {
+            protocol: fee,
+            ..Default::default()
+        }
+    }
+
+    /// Moves `bps` of the protocol portion, rounded down, to the referrer
+    /// rebate. The fee paid by the trader doesn't change.
+    pub fn carve_rebate(&mut self, bps: u16) {
+        let rebate = self
+            .protocol
+            .mul_div_floor(bps as u64, BPS_PRECISION)
+            .unwrap();
+
+        self.protocol -= rebate;
+        self.rebate += rebate;
+    }
+
+    /// Folds the creator and referral shares into `protocol`, for fees paid
+    /// in a quote asset their vaults can't hold
+    pub fn fold_vault_shares(&mut self) {
+        self.protocol += self.creator + self.referral;
+        self.creator = 0;
+        self.referral = 0;
+    }
 }
 
+/// Fee charged on `x` at `percent`, rounded up
 pub fn get_fee_amount(x: u64, percent: u64) -> Result<u64> {
-    Ok(x.mul_div_ceil(percent, FEE_PRECISION).unwrap())
+    Ok(mul_div_in(x, percent, FEE_DENOMINATOR).unwrap())
+}
+
+/// `fee` limited to `max_fee`, zero standing for no cap
+pub fn cap_fee(fee: u64, max_fee: u64) -> u64 {
+    if max_fee == 0 {
+        return fee;
+    }
+
+    fee.min(max_fee)
+}
+
+/// Aggregator fee of `bps` on `amount`, rounded down, rejecting anything
+/// above `MAX_PLATFORM_FEE_BPS`
+pub fn get_platform_fee(amount: u64, bps: u16) -> Result<u64> {
+    if bps > MAX_PLATFORM_FEE_BPS {
+        return Err(error!(err::arg(format!(
+            "Platform fee can't exceed {} bps",
+            MAX_PLATFORM_FEE_BPS
+        ))));
+    }
+
+    Ok(amount.mul_div_floor(bps as u64, BPS_PRECISION).unwrap())
+}
+
+/// Smallest amount that is still worth at least `net` once its fee at
+/// `percent` is taken, i.e. the inverse of `x - get_fee_amount(x, percent)`
+pub fn get_amount_before_fee(net: u64, percent: u64) -> Result<u64> {
+    mul_div_in(net, FEE_DENOMINATOR, FEE_DENOMINATOR - percent)
+        .ok_or(error!(AmmError::MathOverflow))
 }
 
 #[cfg(test)]
@@ -61,4 +214,190 @@ mod tests {
             amount, actual_fee
         );
     }
+
+    #[test]
+    fn test_burn_on_sell_amount() {
+        // ARRANGE: A 2.5% sell burn
+        let fees = Fees {
+            fee_meme_percent: MEME_FEE,
+            fee_quote_percent: FEE,
+            burn_on_sell_bps: 250,
+            referral_fee_bps: 0,
+        };
+
+        // ACT & ASSERT: Burn is rounded down and disabled by default
+        assert_eq!(fees.get_burn_on_sell_amount(1_000).unwrap(), 25);
+        assert_eq!(fees.get_burn_on_sell_amount(39).unwrap(), 0);
+        assert_eq!(Fees::default().get_burn_on_sell_amount(1_000).unwrap(), 0);
+
+        // ACT & ASSERT: Burning more than the sell is rejected
+        let invalid = Fees {
+            burn_on_sell_bps: 10_001,
+            ..fees
+        };
+        assert_eq!(
+            invalid.get_burn_on_sell_amount(1_000).unwrap_err(),
+            error!(AmmError::InvalidFee)
+        );
+        assert_eq!(
+            invalid.validate().unwrap_err(),
+            error!(AmmError::InvalidFee)
+        );
+
+        println!("✅ Burn on sell amount test passed!");
+    }
+
+    #[test]
+    fn test_platform_fee_is_bounded() {
+        // ACT & ASSERT: Rounded down, nothing when omitted
+        assert_eq!(get_platform_fee(10_000, 25).unwrap(), 25);
+        assert_eq!(get_platform_fee(399, 25).unwrap(), 0);
+        assert_eq!(get_platform_fee(10_000, 0).unwrap(), 0);
+
+        // ACT & ASSERT: Up to the cap and no further
+        assert_eq!(get_platform_fee(10_000, MAX_PLATFORM_FEE_BPS).unwrap(), 100);
+        assert!(get_platform_fee(10_000, MAX_PLATFORM_FEE_BPS + 1).is_err());
+
+        println!("✅ Platform fee bounds test passed!");
+    }
+
+    #[test]
+    fn test_fee_percent_bounds() {
+        // ARRANGE: Fees at 0%, 100% and just past it
+        let fees = |percent| Fees {
+            fee_meme_percent: percent,
+            fee_quote_percent: percent,
+            ..Default::default()
+        };
+
+        // ACT & ASSERT: 0% is valid and charges nothing
+        fees(0).validate().unwrap();
+        assert_eq!(fees(0).get_fee_quote_amount(1_000).unwrap(), 0);
+
+        // ACT & ASSERT: 100% is valid and charges everything
+        fees(FEE_DENOMINATOR).validate().unwrap();
+        assert_eq!(
+            fees(FEE_DENOMINATOR).get_fee_quote_amount(1_000).unwrap(),
+            1_000
+        );
+
+        // ACT & ASSERT: Anything above 100% is rejected, on either side
+        assert_eq!(
+            fees(FEE_DENOMINATOR + 1).validate().unwrap_err(),
+            error!(AmmError::InvalidFee)
+        );
+        let meme_only = Fees {
+            fee_meme_percent: FEE_DENOMINATOR + 1,
+            ..Default::default()
+        };
+        assert!(meme_only.validate().is_err());
+
+        println!("✅ Fee percent bounds test passed!");
+    }
+
+    #[test]
+    fn test_fee_shares_split() {
+        // ARRANGE: 70% protocol, 20% creator, 10% referral
+        let shares = FeeShares {
+            protocol_bps: 7_000,
+            creator_bps: 2_000,
+            referral_bps: 1_000,
+        };
+
+        // ACT & ASSERT: Portions add up to the fee, rounding to the protocol
+        assert!(shares.validate().is_ok());
+        assert_eq!(
+            shares.split(1_000),
+            FeeSplit {
+                protocol: 700,
+                creator: 200,
+                referral: 100,
+                rebate: 0,
+            }
+        );
+        assert_eq!(
+            shares.split(19),
+            FeeSplit {
+                protocol: 15,
+                creator: 3,
+                referral: 1,
+                rebate: 0,
+            }
+        );
+
+        // ACT & ASSERT: Unset shares send everything to the protocol
+        assert!(FeeShares::default().validate().is_ok());
+        assert_eq!(
+            FeeShares::default().split(1_000),
+            FeeSplit::protocol_only(1_000)
+        );
+
+        // ACT & ASSERT: Shares must cover exactly the whole fee
+        let partial = FeeShares {
+            creator_bps: 5_000,
+            ..shares
+        };
+        assert_eq!(
+            partial.validate().unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+
+        println!("✅ Fee shares split test passed!");
+    }
+
+    #[test]
+    fn test_referral_rebate_is_carved_from_protocol() {
+        // ARRANGE: 1_000 fee, 800 to the protocol, 10% rebated to referrers
+        let shares = FeeShares {
+            protocol_bps: 8_000,
+            creator_bps: 2_000,
+            referral_bps: 0,
+        };
+
+        // ACT: Buy without a referrer
+        let without = shares.split(1_000);
+
+        // ASSERT: Nothing is rebated
+        assert_eq!(without.protocol, 800);
+        assert_eq!(without.rebate, 0);
+
+        // ACT: Buy with a referrer
+        let mut with = shares.split(1_000);
+        with.carve_rebate(REFERRAL_FEE_BPS);
+
+        // ASSERT: The rebate only comes out of the protocol's portion, so the
+        // trader pays the same fee
+        assert_eq!(with.protocol, 720);
+        assert_eq!(with.rebate, 80);
+        assert_eq!(with.creator, without.creator);
+        assert_eq!(
+            with.protocol + with.creator + with.referral + with.rebate,
+            1_000
+        );
+
+        println!("✅ Referral rebate test passed!");
+    }
+
+    #[test]
+    fn test_fold_vault_shares() {
+        // ARRANGE: 1_000 fee split three ways, with a referrer rebate
+        let shares = FeeShares {
+            protocol_bps: 6_000,
+            creator_bps: 3_000,
+            referral_bps: 1_000,
+        };
+        let mut split = shares.split(1_000);
+        split.carve_rebate(REFERRAL_FEE_BPS);
+
+        // ACT: Paid in the second quote, which the vaults don't hold
+        split.fold_vault_shares();
+
+        // ASSERT: The vault shares accrue to the protocol, the rebate stays
+        assert_eq!(split.creator, 0);
+        assert_eq!(split.referral, 0);
+        assert_eq!(split.protocol, 940);
+        assert_eq!(split.rebate, 60);
+
+        println!("✅ Fold vault shares test passed!");
+    }
 }
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+/// Number of recent migrations the registry keeps
+pub const MIGRATION_REGISTRY_SIZE: usize = 16;
+
+/// Ring buffer of the most recent pool migrations, a single PDA
+#[account]
+#[derive(InitSpace)]
+pub struct MigrationRegistry {
+    /// Migrations recorded so far, the oldest entries being overwritten
+    /// once `MIGRATION_REGISTRY_SIZE` is reached
+    pub migration_count: u64,
+    /// Recorded migrations, entry `migration_count % MIGRATION_REGISTRY_SIZE`
+    /// is overwritten next
+    #[max_len(MIGRATION_REGISTRY_SIZE)]
+    pub migrations: Vec<MigrationEntry>,
+}
+
+impl MigrationRegistry {
+    pub const REGISTRY_PREFIX: &'static [u8; 18] = b"migration_registry";
+
+    /// Records the migration of `pool` at `slot`, overwriting the oldest
+    /// entry once the buffer is full
+    pub fn record(&mut self, pool: Pubkey, slot: u64) {
+        let entry = MigrationEntry { pool, slot };
+        let index = (self.migration_count % MIGRATION_REGISTRY_SIZE as u64) as usize;
+
+        if index < self.migrations.len() {
+            self.migrations[index] = entry;
+        } else {
+            self.migrations.push(entry);
+        }
+
+        self.migration_count += 1;
+    }
+
+    /// Recorded migrations, most recent first
+    pub fn recent(&self) -> Vec<MigrationEntry> {
+        let len = self.migrations.len();
+        if len == 0 {
+            return vec![];
+        }
+        let newest = (self.migration_count as usize - 1) % len;
+
+        (0..len)
+            .map(|age| self.migrations[(newest + len - age) % len])
+            .collect()
+    }
+}
+
+/// A pool and the slot it migrated at
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub struct MigrationEntry {
+    /// The migrated pool
+    pub pool: Pubkey,
+    /// Slot of the migration
+    pub slot: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program::MAX_RETURN_DATA;
+
+    #[test]
+    fn test_registry_keeps_most_recent_migrations() {
+        // ARRANGE: Empty registry
+        let mut registry = MigrationRegistry {
+            migration_count: 0,
+            migrations: vec![],
+        };
+        let pools: Vec<Pubkey> = (0..MIGRATION_REGISTRY_SIZE + 3)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+
+        // ACT: Record two migrations
+        registry.record(pools[0], 10);
+        registry.record(pools[1], 11);
+
+        // ASSERT: Newest first
+        let recent = registry.recent();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].pool, pools[1]);
+        assert_eq!(
+            recent[1],
+            MigrationEntry {
+                pool: pools[0],
+                slot: 10
+            }
+        );
+
+        // ACT: Overflow the buffer
+        for (slot, pool) in pools.iter().enumerate().skip(2) {
+            registry.record(*pool, 10 + slot as u64);
+        }
+
+        // ASSERT: Bounded, the oldest entries were overwritten
+        let recent = registry.recent();
+        assert_eq!(recent.len(), MIGRATION_REGISTRY_SIZE);
+        assert_eq!(registry.migration_count, pools.len() as u64);
+        assert_eq!(recent[0].pool, *pools.last().unwrap());
+        assert_eq!(recent[MIGRATION_REGISTRY_SIZE - 1].pool, pools[3]);
+        assert!(recent.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+
+        println!("✅ Migration registry ring buffer test passed!");
+    }
+}
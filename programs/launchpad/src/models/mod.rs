@@ -1,8 +1,25 @@
 pub mod bound;
+pub mod bound_v0;
+pub mod buyer_record;
+pub mod creator_index;
 pub mod fees;
+pub mod migration_registry;
+pub mod pending_buy;
+pub mod pool_registry;
+pub mod pool_socials;
+pub mod protocol_config;
+pub mod referrer;
 pub mod target_config;
+pub mod trade_history;
+pub mod vesting_schedule;
+pub mod whitelist_entry;
 
+use crate::consts::SPOT_PRICE_PRECISION;
+use crate::err::{self, AmmError};
+use crate::models::fees::{FeeSplit, BPS_PRECISION};
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use spl_math::uint::U256;
 
 extern crate std;
 
@@ -15,9 +32,208 @@ pub struct Reserve {
     pub vault: Pubkey,
 }
 
+#[derive(Debug)]
 pub struct SwapAmount {
     pub amount_in: u64,
     pub amount_out: u64,
     pub admin_fee_in: u64,
     pub admin_fee_out: u64,
+    /// Quote side fee, `admin_fee_in` of buys or `admin_fee_out` of sells,
+    /// broken down by recipient
+    pub quote_fee: FeeSplit,
+    /// True if `admin_fee_in` was cut down to the pool's per swap fee cap
+    pub fee_capped: bool,
+}
+
+impl SwapAmount {
+    /// Output tokens received per whole input token, i.e.
+    /// `amount_out * scale / (amount_in + admin_fee_in)`.
+    ///
+    /// `scale` is the decimal precision of the input asset,
+    /// `config.decimals.quote` for buys and `config.decimals.meme` for sells,
+    /// so `1 SOL = N MEME` can be rendered without further lookups.
+    ///
+    /// # Errors
+    /// * `AmmError::MathOverflow` - If the rate doesn't fit a `u64`
+    pub fn effective_rate(&self, scale: u64) -> Result<u64> {
+        let gross_in = self.amount_in as u128 + self.admin_fee_in as u128;
+        if gross_in == 0 {
+            return Ok(0);
+        }
+
+        u64::try_from(self.amount_out as u128 * scale as u128 / gross_in)
+            .map_err(|_| error!(AmmError::MathOverflow))
+    }
+
+    /// Rejects a swap rounding down to no output, which would still charge
+    /// its fee
+    pub fn ensure_nonzero_output(&self) -> Result<()> {
+        if self.amount_out == 0 {
+            return Err(error!(AmmError::OutputTooSmall));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects an output more than `tolerance_bps` above or below the
+    /// `expected_out` of a preview, a zero tolerance disabling the check
+    pub fn ensure_near_preview(&self, expected_out: u64, tolerance_bps: u16) -> Result<()> {
+        if tolerance_bps == 0 {
+            return Ok(());
+        }
+
+        let drift = self.amount_out.abs_diff(expected_out) as u128 * BPS_PRECISION as u128;
+        if drift > expected_out as u128 * tolerance_bps as u128 {
+            return Err(error!(AmmError::PreviewDrift));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects a buy whose execution price, the quote paid in (fees
+    /// included) per meme received scaled by `SPOT_PRICE_PRECISION`, is more
+    /// than `max_deviation_bps` above or below `reference_price`. A zero
+    /// price or tolerance disables the check.
+    pub fn ensure_near_reference_price(
+        &self,
+        reference_price: u128,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        if reference_price == 0 || max_deviation_bps == 0 {
+            return Ok(());
+        }
+
+        let execution_price = (self.amount_in as u128 + self.admin_fee_in as u128)
+            * SPOT_PRICE_PRECISION
+            / self.amount_out.max(1) as u128;
+        let deviation =
+            U256::from(execution_price.abs_diff(reference_price)) * U256::from(BPS_PRECISION);
+        if deviation > U256::from(reference_price) * U256::from(max_deviation_bps) {
+            return Err(error!(AmmError::ReferencePriceDeviation));
+        }
+
+        Ok(())
+    }
+
+    /// Minimum output allowing `slippage_bps` below this swap's output,
+    /// i.e. `amount_out * (BPS_PRECISION - slippage_bps) / BPS_PRECISION`
+    pub fn min_out(&self, slippage_bps: u16) -> Result<u64> {
+        if slippage_bps as u64 > BPS_PRECISION {
+            return Err(error!(err::arg(format!(
+                "Slippage can't exceed {} bps",
+                BPS_PRECISION
+            ))));
+        }
+
+        Ok(
+            (self.amount_out as u128 * (BPS_PRECISION - slippage_bps as u64) as u128
+                / BPS_PRECISION as u128) as u64,
+        )
+    }
+
+    /// Return data of the swap, its rate scaled by `scale` as in
+    /// [`SwapAmount::effective_rate`]
+    pub fn to_quote(&self, scale: u64) -> Result<SwapQuote> {
+        Ok(SwapQuote {
+            amount_in: self.amount_in,
+            amount_out: self.amount_out,
+            admin_fee_in: self.admin_fee_in,
+            admin_fee_out: self.admin_fee_out,
+            effective_rate: self.effective_rate(scale)?,
+            price_impact_bps: 0,
+        })
+    }
+
+    /// Fees of the swap by asset. `burn` is the sell tax taken before the
+    /// swap was priced and `fee_quote_percent` the quote fee it was priced at.
+    pub fn to_fee_preview(&self, buy_meme: bool, burn: u64, fee_quote_percent: u64) -> FeePreview {
+        let (quote_fee, meme_fee) = if buy_meme {
+            (self.admin_fee_in, self.admin_fee_out)
+        } else {
+            (self.admin_fee_out, self.admin_fee_in)
+        };
+
+        FeePreview {
+            quote_fee,
+            meme_fee,
+            burn,
+            fee_quote_percent,
+            fee_capped: self.fee_capped,
+        }
+    }
+}
+
+/// Rejects a frozen user token account up front, which the token program
+/// would otherwise fail the swap's transfers on with an opaque error.
+/// `name` tells the user which of their accounts is frozen.
+pub fn ensure_not_frozen(account: &TokenAccount, name: &str) -> Result<()> {
+    if account.is_frozen() {
+        msg!("[AccountFrozen] {} token account is frozen", name);
+        return Err(error!(AmmError::AccountFrozen));
+    }
+
+    Ok(())
+}
+
+/// Rejects a swap landing after the client's `deadline`, a unix timestamp,
+/// so a transaction held back by the network doesn't execute at prices the
+/// trader no longer expects. Zero for no deadline.
+pub fn ensure_before_deadline(deadline: i64, now: i64) -> Result<()> {
+    if deadline != 0 && now > deadline {
+        return Err(error!(AmmError::DeadlineExceeded));
+    }
+
+    Ok(())
+}
+
+/// Swap result written to the instruction return data by the swap handlers
+/// and their previews
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct SwapQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub admin_fee_in: u64,
+    pub admin_fee_out: u64,
+    /// See [`SwapAmount::effective_rate`]
+    pub effective_rate: u64,
+    /// See [`BoundPool::price_impact_bps`](bound::BoundPool::price_impact_bps),
+    /// only set by the `get_swap_y_amt` / `get_swap_x_amt` previews
+    pub price_impact_bps: u64,
+}
+
+/// Fees a swap would be charged, written to the return data by `preview_fee`
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct FeePreview {
+    /// Fee in the quote asset the swap trades against
+    pub quote_fee: u64,
+    /// Fee in meme tokens
+    pub meme_fee: u64,
+    /// Meme burned by the sell tax, zero for buys
+    pub burn: u64,
+    /// Quote fee after fee tier resolution, in `FEE_DENOMINATOR` units
+    pub fee_quote_percent: u64,
+    /// True if the fee was cut down to the pool's per swap fee cap
+    pub fee_capped: bool,
+}
+
+/// Emitted by every executed swap
+#[event]
+pub struct SwapEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    /// True for quote to meme swaps, false for meme to quote swaps
+    pub buy_meme: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub admin_fee_in: u64,
+    pub admin_fee_out: u64,
+    /// Quote fee applied to the swap, in `FEE_DENOMINATOR` units, after fee
+    /// tier resolution
+    pub fee_quote_percent: u64,
+    /// True if `admin_fee_in` is the pool's per swap fee cap rather than the
+    /// percentage fee
+    pub fee_capped: bool,
+    /// Quote paid to the routing aggregator's platform fee account, on top of
+    /// a buy's input or out of a sell's `amount_out`
+    pub platform_fee: u64,
 }
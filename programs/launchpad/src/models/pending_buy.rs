@@ -0,0 +1,128 @@
+use crate::consts::COMMIT_REVEAL_WINDOW_SLOTS;
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// Hidden buy committed by a wallet, revealed and executed in a later slot
+#[account]
+#[derive(InitSpace)]
+pub struct PendingBuy {
+    /// Pool the buy is committed against
+    pub pool: Pubkey,
+    /// Wallet which committed the buy
+    pub owner: Pubkey,
+    /// See [`PendingBuy::commitment`]
+    pub commitment: [u8; 32],
+    /// Slot of the commitment
+    pub slot: u64,
+}
+
+impl PendingBuy {
+    pub const PENDING_BUY_PREFIX: &'static [u8; 11] = b"pending_buy";
+
+    /// Hash committed to by `commit_buy`:
+    /// `keccak(owner || amount || min_out || nonce)`, integers little endian.
+    /// Binding the owner stops a commitment from being replayed by another
+    /// wallet.
+    pub fn commitment(owner: &Pubkey, amount: u64, min_out: u64, nonce: &[u8; 32]) -> [u8; 32] {
+        keccak::hashv(&[
+            owner.as_ref(),
+            &amount.to_le_bytes(),
+            &min_out.to_le_bytes(),
+            nonce,
+        ])
+        .to_bytes()
+    }
+
+    /// Last slot in which the buy can be revealed
+    pub fn expiry_slot(&self) -> u64 {
+        self.slot.saturating_add(COMMIT_REVEAL_WINDOW_SLOTS)
+    }
+
+    /// Checks that the revealed buy matches the commitment and is revealed
+    /// in a later slot, but within the reveal window
+    pub fn verify_reveal(
+        &self,
+        amount: u64,
+        min_out: u64,
+        nonce: &[u8; 32],
+        current_slot: u64,
+    ) -> Result<()> {
+        if Self::commitment(&self.owner, amount, min_out, nonce) != self.commitment {
+            return Err(error!(AmmError::InvalidCommitment));
+        }
+
+        if current_slot <= self.slot {
+            return Err(error!(AmmError::RevealTooEarly));
+        }
+
+        if current_slot > self.expiry_slot() {
+            return Err(error!(AmmError::CommitmentExpired));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_pending_buy(amount: u64, min_out: u64, nonce: &[u8; 32]) -> PendingBuy {
+        let owner = Pubkey::new_unique();
+        PendingBuy {
+            pool: Pubkey::new_unique(),
+            owner,
+            commitment: PendingBuy::commitment(&owner, amount, min_out, nonce),
+            slot: 100,
+        }
+    }
+
+    #[test]
+    fn test_reveal_matches_commitment() {
+        // ARRANGE: A buy of 5 SOL committed in slot 100
+        let nonce = [7u8; 32];
+        let pending = create_pending_buy(5_000_000_000, 1, &nonce);
+
+        // ACT & ASSERT: Only the committed values reveal
+        assert!(pending.verify_reveal(5_000_000_000, 1, &nonce, 101).is_ok());
+        assert_eq!(
+            pending
+                .verify_reveal(6_000_000_000, 1, &nonce, 101)
+                .unwrap_err(),
+            error!(AmmError::InvalidCommitment)
+        );
+        assert_eq!(
+            pending
+                .verify_reveal(5_000_000_000, 1, &[8u8; 32], 101)
+                .unwrap_err(),
+            error!(AmmError::InvalidCommitment)
+        );
+
+        println!("✅ Commitment reveal test passed!");
+    }
+
+    #[test]
+    fn test_reveal_window() {
+        // ARRANGE: A buy committed in slot 100
+        let nonce = [7u8; 32];
+        let pending = create_pending_buy(1_000, 0, &nonce);
+
+        // ACT & ASSERT: Same slot reveals are front-runnable, late ones expire
+        assert_eq!(
+            pending.verify_reveal(1_000, 0, &nonce, 100).unwrap_err(),
+            error!(AmmError::RevealTooEarly)
+        );
+        assert!(pending
+            .verify_reveal(1_000, 0, &nonce, pending.expiry_slot())
+            .is_ok());
+        assert_eq!(
+            pending
+                .verify_reveal(1_000, 0, &nonce, pending.expiry_slot() + 1)
+                .unwrap_err(),
+            error!(AmmError::CommitmentExpired)
+        );
+
+        println!("✅ Reveal window test passed!");
+    }
+}
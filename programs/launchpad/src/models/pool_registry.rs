@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+/// Global count of the pools created by the program, a single PDA
+#[account]
+#[derive(InitSpace)]
+pub struct PoolRegistry {
+    /// Number of pools created so far, also the index of the next pool
+    pub pool_count: u64,
+}
+
+impl PoolRegistry {
+    pub const REGISTRY_PREFIX: &'static [u8; 13] = b"pool_registry";
+
+    /// Assigns the next index to a new pool
+    pub fn register_pool(&mut self) -> u64 {
+        let index = self.pool_count;
+        self.pool_count += 1;
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_pool_assigns_sequential_indexes() {
+        // ARRANGE: Empty registry
+        let mut registry = PoolRegistry { pool_count: 0 };
+
+        // ACT: Register three pools
+        let indexes: Vec<u64> = (0..3).map(|_| registry.register_pool()).collect();
+
+        // ASSERT: Indexes are stable and the count follows
+        assert_eq!(indexes, vec![0, 1, 2]);
+        assert_eq!(registry.pool_count, 3);
+
+        println!("✅ Pool registry test passed!");
+    }
+}
@@ -0,0 +1,119 @@
+use crate::err;
+use anchor_lang::prelude::*;
+
+/// Maximum length of the website link, in bytes
+pub const MAX_WEBSITE_LEN: usize = 128;
+
+/// Maximum length of the twitter and telegram links, in bytes
+pub const MAX_SOCIAL_LEN: usize = 64;
+
+/// Social links of a pool, set by its creator so discovery tools can show
+/// them without trusting the off-chain metadata
+#[account]
+#[derive(InitSpace)]
+pub struct PoolSocials {
+    /// Pool the links belong to
+    pub pool: Pubkey,
+    #[max_len(MAX_WEBSITE_LEN)]
+    pub website: String,
+    #[max_len(MAX_SOCIAL_LEN)]
+    pub twitter: String,
+    #[max_len(MAX_SOCIAL_LEN)]
+    pub telegram: String,
+}
+
+impl PoolSocials {
+    pub const SOCIALS_PREFIX: &'static [u8; 12] = b"pool_socials";
+
+    /// Replaces the links, empty strings clearing them. Strings are valid
+    /// UTF-8 once deserialized, so only their length needs checking.
+    pub fn set(&mut self, website: String, twitter: String, telegram: String) -> Result<()> {
+        ensure_len("Website", &website, MAX_WEBSITE_LEN)?;
+        ensure_len("Twitter", &twitter, MAX_SOCIAL_LEN)?;
+        ensure_len("Telegram", &telegram, MAX_SOCIAL_LEN)?;
+
+        self.website = website;
+        self.twitter = twitter;
+        self.telegram = telegram;
+
+        Ok(())
+    }
+}
+
+fn ensure_len(name: &str, value: &str, max_len: usize) -> Result<()> {
+    if value.len() > max_len {
+        return Err(error!(err::arg(format!(
+            "{} must be at most {} bytes",
+            name, max_len
+        ))));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::err::AmmError;
+
+    #[test]
+    fn test_set_socials() {
+        // ARRANGE
+        let mut socials = PoolSocials {
+            pool: Pubkey::new_unique(),
+            website: String::new(),
+            twitter: String::new(),
+            telegram: String::new(),
+        };
+
+        // ACT
+        socials
+            .set(
+                "https://meme.fun".to_string(),
+                "https://x.com/meme".to_string(),
+                "https://t.me/meme".to_string(),
+            )
+            .unwrap();
+
+        // ASSERT
+        assert_eq!(socials.website, "https://meme.fun");
+        assert_eq!(socials.twitter, "https://x.com/meme");
+        assert_eq!(socials.telegram, "https://t.me/meme");
+
+        // ACT & ASSERT: Oversized links are rejected, multi-byte characters
+        // counted in bytes, and nothing is overwritten
+        assert_eq!(
+            socials
+                .set(
+                    "a".repeat(MAX_WEBSITE_LEN + 1),
+                    String::new(),
+                    String::new()
+                )
+                .unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        assert_eq!(
+            socials
+                .set(
+                    String::new(),
+                    "é".repeat(MAX_SOCIAL_LEN / 2 + 1),
+                    String::new()
+                )
+                .unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        assert_eq!(socials.website, "https://meme.fun");
+
+        // ACT & ASSERT: Links at their maximum length fit the account
+        socials
+            .set(
+                "a".repeat(MAX_WEBSITE_LEN),
+                "b".repeat(MAX_SOCIAL_LEN),
+                String::new(),
+            )
+            .unwrap();
+        assert!(socials.try_to_vec().unwrap().len() <= PoolSocials::INIT_SPACE);
+
+        println!("✅ Pool socials test passed!");
+    }
+}
@@ -0,0 +1,219 @@
+use crate::err::AmmError;
+use crate::models::fees::BPS_PRECISION;
+use anchor_lang::prelude::*;
+use std::cmp::min;
+
+/// Maximum number of volume based fee tiers
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Maximum number of fee exempt accounts
+pub const MAX_FEE_EXEMPT: usize = 8;
+
+/// Global protocol settings, a single PDA shared by all pools
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    /// Authority that owns protocol revenue (pool admin fees) and manages
+    /// this config. Admin instructions only require it as signer, so it can
+    /// be a multisig PDA (e.g. a Squads vault) signing via CPI.
+    pub protocol_fee_authority: Pubkey,
+    /// Reduced quote fees for pools above a cumulative quote volume, sorted
+    /// by ascending threshold. Empty means every pool pays its own quote fee.
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>,
+    /// Traders paying no admin fees, e.g. designated market makers and the
+    /// protocol treasury
+    #[max_len(MAX_FEE_EXEMPT)]
+    pub fee_exempt: Vec<Pubkey>,
+    /// Whether `new_pool` accepts new launches, switched off during
+    /// incidents. Existing pools keep trading either way.
+    pub pool_creation_enabled: bool,
+    /// Whether swaps pay the protocol's share of the quote fee straight to
+    /// the fee treasury of the quote mint, instead of accruing it per pool
+    pub fee_treasury_enabled: bool,
+    /// Share of the quote a migration moves out of the curve that is paid to
+    /// the fee treasury instead, in bps. Zero for no migration fee
+    pub migration_fee_bps: u16,
+}
+
+impl ProtocolConfig {
+    pub const CONFIG_PREFIX: &'static [u8; 15] = b"protocol_config";
+    /// Prefix of the fee treasury PDA of a quote mint, a token account owned
+    /// by this config
+    pub const FEE_TREASURY_PREFIX: &'static [u8; 12] = b"fee_treasury";
+
+    /// Returns the quote fee of the highest tier reached by
+    /// `cumulative_quote_volume`. Tiers only ever reduce `base_fee_percent`.
+    pub fn fee_quote_percent(&self, cumulative_quote_volume: u64, base_fee_percent: u64) -> u64 {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| cumulative_quote_volume >= tier.volume_threshold)
+            .map_or(base_fee_percent, |tier| {
+                min(tier.fee_quote_percent, base_fee_percent)
+            })
+    }
+
+    /// Returns true if `account` trades without admin fees
+    pub fn is_fee_exempt(&self, account: &Pubkey) -> bool {
+        self.fee_exempt.contains(account)
+    }
+
+    /// Part of a swap's `protocol_fee` paid to the fee treasury rather than
+    /// accrued by the pool: all of it while routing is on, none otherwise
+    pub fn treasury_fee(&self, protocol_fee: u64) -> u64 {
+        if self.fee_treasury_enabled {
+            protocol_fee
+        } else {
+            0
+        }
+    }
+
+    /// Quote carved from the `quote_amount` a migration seeds as the
+    /// protocol's migration fee, rounded down
+    pub fn migration_fee(&self, quote_amount: u64) -> u64 {
+        (quote_amount as u128 * self.migration_fee_bps as u128 / BPS_PRECISION as u128) as u64
+    }
+
+    /// Migration fee on `quote_amount` read from the config account, which
+    /// migrations take unchecked so they still run before it's initialized.
+    /// Zero until then.
+    pub fn migration_fee_of(config: &AccountInfo, quote_amount: u64) -> Result<u64> {
+        if config.owner != &crate::ID {
+            return Ok(0);
+        }
+
+        let config = ProtocolConfig::try_deserialize(&mut &config.try_borrow_data()?[..])?;
+        Ok(config.migration_fee(quote_amount))
+    }
+
+    /// Rejects new launches while the kill switch is off
+    pub fn ensure_pool_creation_enabled(&self) -> Result<()> {
+        if !self.pool_creation_enabled {
+            return Err(error!(AmmError::PoolCreationDisabled));
+        }
+
+        Ok(())
+    }
+}
+
+/// Quote fee applied once a pool's cumulative quote volume reaches a threshold
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub struct FeeTier {
+    /// Cumulative quote volume, in primary quote units, unlocking the tier
+    pub volume_threshold: u64,
+    /// Quote fee of the tier, in `FEE_DENOMINATOR` units
+    pub fee_quote_percent: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fees::FEE;
+
+    fn create_tiered_config() -> ProtocolConfig {
+        ProtocolConfig {
+            protocol_fee_authority: Pubkey::default(),
+            fee_tiers: vec![
+                FeeTier {
+                    volume_threshold: 1_000,
+                    fee_quote_percent: FEE / 2,
+                },
+                FeeTier {
+                    volume_threshold: 10_000,
+                    fee_quote_percent: FEE / 4,
+                },
+            ],
+            fee_exempt: vec![],
+            pool_creation_enabled: true,
+            fee_treasury_enabled: false,
+            migration_fee_bps: 0,
+        }
+    }
+
+    #[test]
+    fn test_fee_tier_resolution() {
+        // ARRANGE: Two tiers, at 1_000 and 10_000 volume
+        let config = create_tiered_config();
+
+        // ACT & ASSERT: The highest reached tier applies
+        assert_eq!(config.fee_quote_percent(0, FEE), FEE);
+        assert_eq!(config.fee_quote_percent(999, FEE), FEE);
+        assert_eq!(config.fee_quote_percent(1_000, FEE), FEE / 2);
+        assert_eq!(config.fee_quote_percent(50_000, FEE), FEE / 4);
+
+        println!("✅ Fee tier resolution test passed!");
+    }
+
+    #[test]
+    fn test_fee_tiers_only_reduce_fees() {
+        // ARRANGE: No tiers, and tiers above a pool's own fee
+        let untiered = ProtocolConfig {
+            protocol_fee_authority: Pubkey::default(),
+            fee_tiers: vec![],
+            fee_exempt: vec![],
+            pool_creation_enabled: true,
+            fee_treasury_enabled: false,
+            migration_fee_bps: 0,
+        };
+        let config = create_tiered_config();
+
+        // ACT & ASSERT: The pool's own fee is kept
+        assert_eq!(untiered.fee_quote_percent(u64::MAX, FEE), FEE);
+        assert_eq!(config.fee_quote_percent(50_000, FEE / 10), FEE / 10);
+
+        println!("✅ Fee tiers reduction test passed!");
+    }
+
+    #[test]
+    fn test_fee_exempt_lookup() {
+        // ARRANGE: A single exempt market maker
+        let market_maker = Pubkey::new_unique();
+        let mut config = create_tiered_config();
+        config.fee_exempt.push(market_maker);
+
+        // ACT & ASSERT: Only the listed account is exempt
+        assert!(config.is_fee_exempt(&market_maker));
+        assert!(!config.is_fee_exempt(&Pubkey::new_unique()));
+
+        println!("✅ Fee exempt lookup test passed!");
+    }
+
+    #[test]
+    fn test_pool_creation_kill_switch() {
+        // ARRANGE: Config with pool creation enabled
+        let mut config = create_tiered_config();
+        assert!(config.ensure_pool_creation_enabled().is_ok());
+
+        // ACT: Flip the kill switch
+        config.pool_creation_enabled = false;
+
+        // ASSERT: New launches are rejected
+        assert_eq!(
+            config.ensure_pool_creation_enabled().unwrap_err(),
+            error!(AmmError::PoolCreationDisabled)
+        );
+
+        println!("✅ Pool creation kill switch test passed!");
+    }
+
+    #[test]
+    fn test_treasury_fee_routing() {
+        // ARRANGE: Config with fees accruing per pool
+        let mut config = create_tiered_config();
+
+        // ACT & ASSERT: Nothing goes to the treasury
+        assert_eq!(config.treasury_fee(1_000), 0);
+
+        // ACT: Route protocol fees to the treasury
+        config.fee_treasury_enabled = true;
+
+        // ASSERT: The whole protocol fee goes to the treasury
+        assert_eq!(config.treasury_fee(1_000), 1_000);
+        assert_eq!(config.treasury_fee(0), 0);
+
+        println!("✅ Treasury fee routing test passed!");
+    }
+}
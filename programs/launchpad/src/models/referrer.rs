@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Registration of a wallet the protocol lets earn the referral rebate of
+/// buys, see `Fees::referral_fee_bps`. The rebate is carved from the
+/// protocol's fee, so only wallets it registered can receive it.
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    /// Wallet whose quote token accounts receive the rebates
+    pub wallet: Pubkey,
+}
+
+impl Referrer {
+    pub const REFERRER_PREFIX: &'static [u8; 8] = b"referrer";
+}
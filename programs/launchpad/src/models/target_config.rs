@@ -1,3 +1,6 @@
+use crate::consts::{MAX_FEE_BPS, MAX_MIN_QUOTE_LIQUIDITY_BPS, MIN_LP_LOCK_DURATION};
+use crate::err::{self, AmmError};
+use crate::models::fees::{FeeShares, BPS_PRECISION};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -6,8 +9,283 @@ pub struct TargetConfig {
     pub token_target_amount: u64,
     pub token_mint: Pubkey,
     pub pair_token_mint: Pubkey,
+    pub token_decimals: u8,
+    pub pair_token_decimals: u8,
+    /// Price factor numerator the curve ramps to as its supply sells out,
+    /// zero for a static curve
+    pub price_factor_num_end: u64,
+    /// Quote reserve below which sells are rejected, zero for the default
+    /// share of the target, at most `MAX_MIN_QUOTE_LIQUIDITY_BPS` of it
+    pub min_quote_liquidity: u64,
+    /// Quote fee in bps with no meme sold, see `Config::fee_start_bps`
+    pub fee_start_bps: u16,
+    /// Quote fee in bps at the migration threshold, see `Config::fee_end_bps`
+    pub fee_end_bps: u16,
+    /// Largest price move within a slot, in bps, zero to disable the
+    /// circuit breaker
+    pub circuit_breaker_bps: u16,
+    /// Split of the quote fee between protocol, creator and referral
+    pub fee_shares: FeeShares,
+    /// Quote reserve above which buys are rejected, zero for no cap
+    pub max_quote_raise: u64,
+    /// Quote fee in bps of trades after migration, zero for the regular fee
+    pub post_migration_fee_bps: u16,
+    /// Seconds the LP stays locked after migration, zero for the default
+    /// `LP_LOCK_DURATION`
+    pub lp_lock_duration: i64,
+    /// Largest quote fee charged on a single buy, zero for no cap, see
+    /// `Config::max_fee_quote`
+    pub max_fee_quote: u64,
+    /// Largest meme fee charged on a single sell, zero for no cap, see
+    /// `Config::max_fee_meme`
+    pub max_fee_meme: u64,
+    /// Quote the curve prices as already raised, zero for none, see
+    /// `Config::virtual_quote_reserve`
+    pub virtual_quote_reserve: u64,
+    /// Meme the curve prices as already sold, zero for none, see
+    /// `Config::virtual_meme_reserve`
+    pub virtual_meme_reserve: u64,
+    /// Signer of `init_target_config`, the only one who can update it
+    pub creator: Pubkey,
+    /// Pools created against the config, which freezes it once non-zero
+    pub pools_created: u64,
+    /// Seconds a pool must have traded before it can migrate, even past the
+    /// threshold, zero for none
+    pub min_pool_age_secs: i64,
 }
 
 impl TargetConfig {
     pub const CONFIG_PREFIX: &'static [u8; 6] = b"config";
+
+    /// Rejects a self-referential pair, where the quote and meme mints are
+    /// the same
+    pub fn ensure_distinct_mints(token_mint: Pubkey, pair_token_mint: Pubkey) -> Result<()> {
+        if token_mint == pair_token_mint {
+            return Err(error!(AmmError::IdenticalMints));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects mints not owned by the SPL Token program. A Token-2022
+    /// transfer fee would make the vaults receive less than the reserves
+    /// account for
+    pub fn ensure_spl_token_mints(
+        token_mint_owner: &Pubkey,
+        pair_token_mint_owner: &Pubkey,
+    ) -> Result<()> {
+        if *token_mint_owner != anchor_spl::token::ID
+            || *pair_token_mint_owner != anchor_spl::token::ID
+        {
+            return Err(error!(AmmError::UnsupportedMintExtension));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects updates once a pool was created against the config, as the
+    /// pool was priced from it
+    pub fn ensure_unused(&self) -> Result<()> {
+        if self.pools_created != 0 {
+            return Err(error!(AmmError::TargetConfigInUse));
+        }
+
+        Ok(())
+    }
+
+    /// Counts a pool created against the config
+    pub fn record_pool(&mut self) {
+        self.pools_created += 1;
+    }
+
+    /// Uncounts a pool cancelled before it traded, see `cancel_pool`, so the
+    /// config unfreezes once none is left
+    pub fn release_pool(&mut self) {
+        self.pools_created = self.pools_created.saturating_sub(1);
+    }
+
+    /// Rejects an LP lock shorter than `MIN_LP_LOCK_DURATION`, zero standing
+    /// for the default lock
+    pub fn ensure_lp_lock_duration(lp_lock_duration: i64) -> Result<()> {
+        if lp_lock_duration != 0 && lp_lock_duration < MIN_LP_LOCK_DURATION {
+            return Err(error!(err::arg(format!(
+                "LP must be locked for at least {} seconds",
+                MIN_LP_LOCK_DURATION
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects a sell floor above `MAX_MIN_QUOTE_LIQUIDITY_BPS` of the
+    /// target, which would keep sells closed for most of the curve
+    pub fn ensure_min_quote_liquidity(
+        min_quote_liquidity: u64,
+        token_target_amount: u64,
+    ) -> Result<()> {
+        if min_quote_liquidity as u128 * BPS_PRECISION as u128
+            > token_target_amount as u128 * MAX_MIN_QUOTE_LIQUIDITY_BPS as u128
+        {
+            return Err(error!(err::arg(format!(
+                "Minimum quote liquidity can't exceed {} bps of the target",
+                MAX_MIN_QUOTE_LIQUIDITY_BPS
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects configured quote fees above `MAX_FEE_BPS`, so a config can't
+    /// take most of every trade
+    pub fn ensure_fee_bps(
+        fee_start_bps: u16,
+        fee_end_bps: u16,
+        post_migration_fee_bps: u16,
+    ) -> Result<()> {
+        if fee_start_bps > MAX_FEE_BPS
+            || fee_end_bps > MAX_FEE_BPS
+            || post_migration_fee_bps > MAX_FEE_BPS
+        {
+            return Err(error!(err::arg(format!(
+                "Fees can't exceed {} bps",
+                MAX_FEE_BPS
+            ))));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_mints_are_rejected() {
+        // ARRANGE: Two distinct mints
+        let token_mint = Pubkey::new_unique();
+        let pair_token_mint = Pubkey::new_unique();
+
+        // ACT & ASSERT: A pair of the same mint is rejected
+        assert_eq!(
+            TargetConfig::ensure_distinct_mints(token_mint, token_mint).unwrap_err(),
+            error!(AmmError::IdenticalMints)
+        );
+        assert!(TargetConfig::ensure_distinct_mints(token_mint, pair_token_mint).is_ok());
+
+        println!("✅ Identical mints rejection test passed!");
+    }
+
+    #[test]
+    fn test_token_2022_mints_are_rejected() {
+        // ARRANGE: The SPL Token and Token-2022 programs
+        let spl_token = anchor_spl::token::ID;
+        let token_2022 = anchor_spl::token_2022::ID;
+
+        // ACT & ASSERT: Either mint owned by Token-2022 is rejected
+        assert_eq!(
+            TargetConfig::ensure_spl_token_mints(&token_2022, &spl_token).unwrap_err(),
+            error!(AmmError::UnsupportedMintExtension)
+        );
+        assert_eq!(
+            TargetConfig::ensure_spl_token_mints(&spl_token, &token_2022).unwrap_err(),
+            error!(AmmError::UnsupportedMintExtension)
+        );
+        assert!(TargetConfig::ensure_spl_token_mints(&spl_token, &spl_token).is_ok());
+
+        println!("✅ Token-2022 mints rejection test passed!");
+    }
+
+    #[test]
+    fn test_lp_lock_duration_minimum() {
+        // ACT & ASSERT: The default and locks of at least the minimum pass
+        assert!(TargetConfig::ensure_lp_lock_duration(0).is_ok());
+        assert!(TargetConfig::ensure_lp_lock_duration(MIN_LP_LOCK_DURATION).is_ok());
+
+        // ACT & ASSERT: Shorter or negative locks are rejected
+        assert!(TargetConfig::ensure_lp_lock_duration(MIN_LP_LOCK_DURATION - 1).is_err());
+        assert!(TargetConfig::ensure_lp_lock_duration(-1).is_err());
+
+        println!("✅ LP lock duration minimum test passed!");
+    }
+
+    #[test]
+    fn test_fee_bps_capped() {
+        // ACT & ASSERT: Every fee at the cap passes
+        assert!(TargetConfig::ensure_fee_bps(MAX_FEE_BPS, MAX_FEE_BPS, MAX_FEE_BPS).is_ok());
+        assert!(TargetConfig::ensure_fee_bps(0, 0, 0).is_ok());
+
+        // ACT & ASSERT: Any fee a bps above it is rejected
+        assert!(TargetConfig::ensure_fee_bps(MAX_FEE_BPS + 1, 0, 0).is_err());
+        assert!(TargetConfig::ensure_fee_bps(0, MAX_FEE_BPS + 1, 0).is_err());
+        assert!(TargetConfig::ensure_fee_bps(0, 0, MAX_FEE_BPS + 1).is_err());
+
+        println!("✅ Fee cap test passed!");
+    }
+
+    #[test]
+    fn test_min_quote_liquidity_capped() {
+        // ARRANGE: 100 SOL target, capped at 10 SOL of sell floor
+        let target = 100_000_000_000;
+        let cap = target * MAX_MIN_QUOTE_LIQUIDITY_BPS / BPS_PRECISION;
+
+        // ACT & ASSERT: The default and a floor at the cap pass
+        assert!(TargetConfig::ensure_min_quote_liquidity(0, target).is_ok());
+        assert!(TargetConfig::ensure_min_quote_liquidity(cap, target).is_ok());
+
+        // ACT & ASSERT: A unit above it is rejected
+        assert!(TargetConfig::ensure_min_quote_liquidity(cap + 1, target).is_err());
+
+        println!("✅ Minimum quote liquidity cap test passed!");
+    }
+
+    #[test]
+    fn test_update_only_while_unused() {
+        // ARRANGE: A config no pool was created against
+        let mut config = TargetConfig {
+            token_target_amount: 100_000_000_000,
+            token_mint: Pubkey::new_unique(),
+            pair_token_mint: Pubkey::new_unique(),
+            token_decimals: 9,
+            pair_token_decimals: 6,
+            price_factor_num_end: 0,
+            min_quote_liquidity: 0,
+            fee_start_bps: 0,
+            fee_end_bps: 0,
+            circuit_breaker_bps: 0,
+            fee_shares: FeeShares::default(),
+            max_quote_raise: 0,
+            post_migration_fee_bps: 0,
+            lp_lock_duration: 0,
+            max_fee_quote: 0,
+            max_fee_meme: 0,
+            virtual_quote_reserve: 0,
+            virtual_meme_reserve: 0,
+            creator: Pubkey::new_unique(),
+            pools_created: 0,
+            min_pool_age_secs: 0,
+        };
+
+        // ACT & ASSERT: Updates pass until a pool uses it
+        assert!(config.ensure_unused().is_ok());
+
+        // ACT
+        config.record_pool();
+
+        // ASSERT: The first pool freezes the config
+        assert_eq!(config.pools_created, 1);
+        assert_eq!(
+            config.ensure_unused().unwrap_err(),
+            error!(AmmError::TargetConfigInUse)
+        );
+
+        // ACT: Cancel that pool
+        config.release_pool();
+
+        // ASSERT: The config is free to update again
+        assert_eq!(config.pools_created, 0);
+        assert!(config.ensure_unused().is_ok());
+
+        println!("✅ Target config in-use guard test passed!");
+    }
 }
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+/// Number of recent trades a pool's trade history keeps
+pub const TRADE_HISTORY_SIZE: usize = 16;
+
+/// Ring buffer of a pool's most recent trades, for front-ends showing a
+/// trades feed without an indexer
+#[account]
+#[derive(InitSpace)]
+pub struct TradeHistory {
+    /// Pool whose trades are recorded
+    pub pool: Pubkey,
+    /// Trades recorded so far, the oldest entries being overwritten once
+    /// `TRADE_HISTORY_SIZE` is reached
+    pub trade_count: u64,
+    /// Recorded trades, entry `trade_count % TRADE_HISTORY_SIZE` is
+    /// overwritten next
+    #[max_len(TRADE_HISTORY_SIZE)]
+    pub trades: Vec<TradeEntry>,
+}
+
+impl TradeHistory {
+    pub const TRADE_HISTORY_PREFIX: &'static [u8; 13] = b"trade_history";
+
+    /// Records a trade, overwriting the oldest entry once the buffer is full
+    pub fn record(&mut self, entry: TradeEntry) {
+        let index = (self.trade_count % TRADE_HISTORY_SIZE as u64) as usize;
+
+        if index < self.trades.len() {
+            self.trades[index] = entry;
+        } else {
+            self.trades.push(entry);
+        }
+
+        self.trade_count += 1;
+    }
+
+    /// Recorded trades, most recent first
+    pub fn recent(&self) -> Vec<TradeEntry> {
+        let len = self.trades.len();
+        if len == 0 {
+            return vec![];
+        }
+        let newest = (self.trade_count as usize - 1) % len;
+
+        (0..len)
+            .map(|age| self.trades[(newest + len - age) % len])
+            .collect()
+    }
+}
+
+/// A trade of a pool, amounts as in its `SwapEvent`
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub struct TradeEntry {
+    /// Quote paid in by a buy, meme sold by a sell, before fees
+    pub amount_in: u64,
+    /// Meme received by a buy, quote paid out by a sell, after fees
+    pub amount_out: u64,
+    /// True for a buy of meme
+    pub is_buy: bool,
+    /// Unix timestamp of the trade
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program::MAX_RETURN_DATA;
+
+    fn trade(amount_in: u64, timestamp: i64) -> TradeEntry {
+        TradeEntry {
+            amount_in,
+            amount_out: amount_in * 2,
+            is_buy: amount_in & 1 == 0,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_history_keeps_most_recent_trades() {
+        // ARRANGE: Empty history
+        let mut history = TradeHistory {
+            pool: Pubkey::new_unique(),
+            trade_count: 0,
+            trades: vec![],
+        };
+
+        // ACT: Record two trades
+        history.record(trade(1, 100));
+        history.record(trade(2, 101));
+
+        // ASSERT: Newest first
+        let recent = history.recent();
+        assert_eq!(recent, vec![trade(2, 101), trade(1, 100)]);
+
+        // ACT: Overflow the buffer
+        let total = TRADE_HISTORY_SIZE as u64 + 3;
+        for amount in 3..=total {
+            history.record(trade(amount, 100 + amount as i64));
+        }
+
+        // ASSERT: Bounded, the oldest entries were overwritten
+        let recent = history.recent();
+        assert_eq!(recent.len(), TRADE_HISTORY_SIZE);
+        assert_eq!(history.trade_count, total);
+        assert_eq!(recent[0], trade(total, 100 + total as i64));
+        assert_eq!(recent[TRADE_HISTORY_SIZE - 1].amount_in, 4);
+        assert!(recent.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+
+        println!("✅ Trade history ring buffer test passed!");
+    }
+}
@@ -0,0 +1,110 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Linear vesting of the creator's meme allocation, minted at pool creation
+/// into a dedicated vault and released through `claim_vested`
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    /// Pool the allocation belongs to, claims go to its current creator
+    pub pool: Pubkey,
+    /// Pool signer owned vault holding the unclaimed allocation
+    pub vault: Pubkey,
+    /// Meme tokens allocated to the creator
+    pub total: u64,
+    /// Meme tokens claimed so far
+    pub claimed: u64,
+    /// Unix timestamp vesting starts from
+    pub start: i64,
+    /// Unix timestamp before which nothing can be claimed
+    pub cliff: i64,
+    /// Seconds from `start` until the whole allocation is vested
+    pub duration: i64,
+}
+
+impl VestingSchedule {
+    pub const VESTING_PREFIX: &'static [u8; 7] = b"vesting";
+
+    /// Reads the schedule from `info`, `None` while the pool has none, e.g.
+    /// for accounts passed unconditionally like in `cancel_pool`
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.owner != &crate::ID {
+            return Ok(None);
+        }
+
+        let data = info.try_borrow_data()?;
+        VestingSchedule::try_deserialize(&mut &data[..]).map(Some)
+    }
+
+    /// Returns the amount vested at `now`, linearly from `start` to
+    /// `start + duration` and nothing before the cliff
+    pub fn vested(&self, now: i64) -> u64 {
+        if now < self.cliff {
+            return 0;
+        }
+
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed >= self.duration {
+            return self.total;
+        }
+
+        // elapsed < duration, so the result is below total
+        (self.total as u128 * elapsed as u128 / self.duration as u128) as u64
+    }
+
+    /// Marks everything vested but unclaimed at `now` as claimed and returns
+    /// it, so the allocation can never be claimed twice
+    pub fn claim(&mut self, now: i64) -> Result<u64> {
+        let amount = self.vested(now).saturating_sub(self.claimed);
+        if amount == 0 {
+            return Err(error!(AmmError::NothingVested));
+        }
+
+        self.claimed += amount;
+
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vesting_is_linear_after_cliff() {
+        // ARRANGE: 1_000 tokens over 100s with a 25s cliff
+        let mut schedule = VestingSchedule {
+            pool: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            total: 1_000,
+            claimed: 0,
+            start: 1_000,
+            cliff: 1_025,
+            duration: 100,
+        };
+
+        // ACT & ASSERT: Nothing before the cliff
+        assert_eq!(schedule.vested(1_024), 0);
+        assert_eq!(
+            schedule.claim(1_024).unwrap_err(),
+            error!(AmmError::NothingVested)
+        );
+
+        // ACT & ASSERT: The linear share once the cliff passes
+        assert_eq!(schedule.claim(1_025).unwrap(), 250);
+        assert_eq!(schedule.claim(1_050).unwrap(), 250);
+
+        // ACT & ASSERT: Claiming again at the same time releases nothing
+        assert_eq!(
+            schedule.claim(1_050).unwrap_err(),
+            error!(AmmError::NothingVested)
+        );
+
+        // ACT & ASSERT: The rest after the duration, and never more
+        assert_eq!(schedule.claim(2_000).unwrap(), 500);
+        assert_eq!(schedule.claimed, schedule.total);
+        assert!(schedule.claim(i64::MAX).is_err());
+
+        println!("✅ Vesting schedule test passed!");
+    }
+}
@@ -0,0 +1,69 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Allowance of one wallet to buy from a pool during its whitelist phase,
+/// see `BoundPool::whitelist_until`
+#[account]
+#[derive(InitSpace)]
+pub struct WhitelistEntry {
+    /// Pool the entry belongs to
+    pub pool: Pubkey,
+    /// Wallet allowed to buy
+    pub buyer: Pubkey,
+    /// Most quote the wallet can spend during the phase, fees included, in
+    /// primary quote units
+    pub cap: u64,
+    /// Quote spent so far during the phase
+    pub spent: u64,
+}
+
+impl WhitelistEntry {
+    pub const WHITELIST_PREFIX: &'static [u8; 9] = b"whitelist";
+
+    /// Adds a buy costing `quote_amount` to the entry, rejecting it if it
+    /// would take the wallet past its cap
+    pub fn record_buy(&mut self, quote_amount: u64) -> Result<()> {
+        let spent = self
+            .spent
+            .checked_add(quote_amount)
+            .ok_or_else(|| error!(AmmError::MathOverflow))?;
+
+        if spent > self.cap {
+            return Err(error!(AmmError::WhitelistCapExceeded));
+        }
+
+        self.spent = spent;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitelist_cap() {
+        // ARRANGE: Entry allowed to spend 1_000
+        let mut entry = WhitelistEntry {
+            pool: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            cap: 1_000,
+            spent: 0,
+        };
+
+        // ACT & ASSERT: Buys up to the cap go through
+        entry.record_buy(600).unwrap();
+        entry.record_buy(400).unwrap();
+        assert_eq!(entry.spent, 1_000);
+
+        // ACT & ASSERT: Anything past it is rejected and not recorded
+        assert_eq!(
+            entry.record_buy(1).unwrap_err(),
+            error!(AmmError::WhitelistCapExceeded)
+        );
+        assert_eq!(entry.spent, 1_000);
+
+        println!("✅ Whitelist cap test passed!");
+    }
+}
@@ -0,0 +1,872 @@
+//! Deterministic harness running full instruction flows against an
+//! in-process validator (`solana-program-test`).
+//!
+//! Every keypair is derived from a fixed seed, so mints, pools and vaults get
+//! the same addresses on every run. Instruction tests build on
+//! [`LaunchpadTest::setup_pool`] and the swap helpers instead of wiring
+//! accounts by hand.
+
+use crate::consts::{BP_FEE_KEY, TEMP_WSOL_PREFIX};
+use crate::endpoints::swap_y::SwapCallback;
+use crate::err::AmmError;
+use crate::models::bound::{BoundPool, CurveType, MigrationAmm};
+use crate::models::buyer_record::BuyerRecord;
+use crate::models::creator_index::{CreatorIndex, CreatorPool};
+use crate::models::fees::FeeShares;
+use crate::models::pool_registry::PoolRegistry;
+use crate::models::protocol_config::ProtocolConfig;
+use crate::models::target_config::TargetConfig;
+use crate::models::vesting_schedule::VestingSchedule;
+use crate::models::SwapQuote;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{keypair_from_seed, Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+/// Program id of the harness' swap callback, see `check_swap_callback`
+pub const SWAP_CALLBACK_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Quote mint decimals used by the harness (WSOL-like)
+pub const QUOTE_DECIMALS: u8 = 9;
+/// Meme mint decimals used by the harness
+pub const MEME_DECIMALS: u8 = 6;
+/// Default SOL target for pools created by the harness (100 SOL)
+pub const DEFAULT_TARGET: u64 = 100_000_000_000;
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // Anchor's entrypoint ties the slice and the account infos to a single
+    // lifetime, which the native processor signature doesn't provide
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    crate::entry(program_id, accounts, data)
+}
+
+/// Swap callback of the harness, failing unless the pool it's passed first
+/// already counts the meme bought as circulating
+fn check_swap_callback(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let callback = SwapCallback::try_from_slice(&data[8..])
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let pool = BoundPool::try_deserialize(&mut &accounts[0].try_borrow_data()?[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    if pool.meme_circulating < callback.amount_out {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Derives a deterministic keypair from a one byte seed
+pub fn seeded_keypair(seed: u8) -> Keypair {
+    keypair_from_seed(&[seed; 32]).unwrap()
+}
+
+/// Asserts that a transaction failed with the program error `error`
+pub fn assert_amm_error<T: std::fmt::Debug>(result: Result<T, BanksClientError>, error: AmmError) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, u32::from(error), "unexpected program error"),
+        other => panic!("expected {:?}, got {:?}", error, other),
+    }
+}
+
+/// A trader with funded quote and empty meme token accounts
+pub struct TestUser {
+    pub owner: Keypair,
+    pub user_sol: Pubkey,
+    pub user_meme: Pubkey,
+}
+
+/// Program test environment with a quote mint, a meme mint and (once
+/// created) a bonding curve pool between them
+pub struct LaunchpadTest {
+    pub ctx: ProgramTestContext,
+    pub quote_mint: Pubkey,
+    pub meme_mint: Keypair,
+    pub pool: Pubkey,
+    pub pool_signer: Pubkey,
+    pub target_config: Pubkey,
+    pub quote_vault: Pubkey,
+    pub meme_vault: Pubkey,
+    pub fee_quote_vault: Pubkey,
+    pub vesting_vault: Option<Pubkey>,
+    next_seed: u8,
+}
+
+impl LaunchpadTest {
+    /// Starts the validator and creates both mints. The meme mint authority
+    /// is the pool signer, as required by `new_pool`.
+    pub async fn start() -> Self {
+        Self::launch(false).await
+    }
+
+    /// Starts the validator like `start`, the quote being native SOL
+    pub async fn start_native() -> Self {
+        Self::launch(true).await
+    }
+
+    async fn launch(native_quote: bool) -> Self {
+        let mut program_test =
+            ProgramTest::new("launchpad", crate::ID, processor!(process_instruction));
+        program_test.prefer_bpf(false);
+        program_test.add_program(
+            "swap_callback",
+            SWAP_CALLBACK_ID,
+            processor!(check_swap_callback),
+        );
+        let ctx = program_test.start_with_context().await;
+
+        let quote_mint = match native_quote {
+            true => spl_token::native_mint::id(),
+            false => seeded_keypair(1).pubkey(),
+        };
+        let meme_mint = seeded_keypair(2);
+
+        let (pool, _) = Pubkey::find_program_address(
+            &[
+                BoundPool::POOL_PREFIX,
+                meme_mint.pubkey().as_ref(),
+                quote_mint.as_ref(),
+            ],
+            &crate::ID,
+        );
+        let (pool_signer, _) = Pubkey::find_program_address(
+            &[BoundPool::SIGNER_PDA_PREFIX, pool.as_ref()],
+            &crate::ID,
+        );
+        let (target_config, _) = Pubkey::find_program_address(
+            &[
+                TargetConfig::CONFIG_PREFIX,
+                quote_mint.as_ref(),
+                meme_mint.pubkey().as_ref(),
+            ],
+            &crate::ID,
+        );
+
+        let mut test = LaunchpadTest {
+            ctx,
+            quote_mint,
+            meme_mint,
+            pool,
+            pool_signer,
+            target_config,
+            quote_vault: Pubkey::default(),
+            meme_vault: Pubkey::default(),
+            fee_quote_vault: Pubkey::default(),
+            vesting_vault: None,
+            next_seed: 10,
+        };
+
+        // The native mint exists from genesis
+        let payer = test.ctx.payer.pubkey();
+        if !native_quote {
+            test.create_mint(&seeded_keypair(1), &payer, QUOTE_DECIMALS)
+                .await;
+        }
+        let meme_mint = test.meme_mint.insecure_clone();
+        test.create_mint(&meme_mint, &pool_signer, MEME_DECIMALS)
+            .await;
+
+        test
+    }
+
+    /// Starts the validator, initializes the target config and creates the
+    /// pool with its vaults
+    pub async fn setup_pool(token_target_amount: u64) -> Self {
+        let test = Self::start().await;
+        test.with_pool(token_target_amount).await
+    }
+
+    /// Sets up a pool like `setup_pool`, quoted in native SOL
+    pub async fn setup_native_pool(token_target_amount: u64) -> Self {
+        let test = Self::start_native().await;
+        test.with_pool(token_target_amount).await
+    }
+
+    async fn with_pool(mut self, token_target_amount: u64) -> Self {
+        self.init_target_config(token_target_amount).await.unwrap();
+        self.new_pool().await.unwrap();
+        self
+    }
+
+    /// Returns the next deterministic keypair
+    pub fn next_keypair(&mut self) -> Keypair {
+        self.next_seed += 1;
+        seeded_keypair(self.next_seed)
+    }
+
+    /// Signs and processes a transaction paid by the context payer
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Option<Vec<u8>>, BanksClientError> {
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+
+        let mut all_signers = vec![&self.ctx.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.ctx.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        let result = self
+            .ctx
+            .banks_client
+            .process_transaction_with_metadata(tx)
+            .await?;
+        result.result.map_err(BanksClientError::TransactionError)?;
+
+        Ok(result
+            .metadata
+            .and_then(|metadata| metadata.return_data)
+            .map(|return_data| return_data.data))
+    }
+
+    pub async fn create_mint(&mut self, mint: &Keypair, authority: &Pubkey, decimals: u8) {
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let payer = self.ctx.payer.pubkey();
+
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::id(),
+                    &mint.pubkey(),
+                    authority,
+                    None,
+                    decimals,
+                )
+                .unwrap(),
+            ],
+            &[mint],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Creates a token account of `mint` owned by `owner`
+    pub async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let account = self.next_keypair();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let payer = self.ctx.payer.pubkey();
+
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &account.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_account(
+                    &spl_token::id(),
+                    &account.pubkey(),
+                    mint,
+                    owner,
+                )
+                .unwrap(),
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+
+        account.pubkey()
+    }
+
+    /// Mints quote tokens to `account`, the payer being the quote mint
+    /// authority. Native SOL is wrapped from the payer's lamports instead
+    pub async fn mint_quote(&mut self, account: &Pubkey, amount: u64) {
+        let payer = self.ctx.payer.pubkey();
+
+        let instructions = if self.quote_mint == spl_token::native_mint::id() {
+            vec![
+                system_instruction::transfer(&payer, account, amount),
+                spl_token::instruction::sync_native(&spl_token::id(), account).unwrap(),
+            ]
+        } else {
+            vec![spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &self.quote_mint,
+                account,
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap()]
+        };
+
+        self.process(&instructions, &[]).await.unwrap();
+    }
+
+    /// Sends `lamports` from the payer to `account`
+    pub async fn fund(&mut self, account: &Pubkey, lamports: u64) {
+        let payer = self.ctx.payer.pubkey();
+
+        self.process(
+            &[system_instruction::transfer(&payer, account, lamports)],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    pub async fn init_target_config(
+        &mut self,
+        token_target_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::InitTargetConfig {
+                creator: self.ctx.payer.pubkey(),
+                target_config: self.target_config,
+                token_mint: self.quote_mint,
+                pair_token_mint: self.meme_mint.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::InitTargetConfig {
+                token_target_amount,
+                price_factor_num_end: 0,
+                min_quote_liquidity: 0,
+                fee_start_bps: 0,
+                fee_end_bps: 0,
+                circuit_breaker_bps: 0,
+                fee_shares: FeeShares::default(),
+                max_quote_raise: 0,
+                post_migration_fee_bps: 0,
+                lp_lock_duration: 0,
+                max_fee_quote: 0,
+                max_fee_meme: 0,
+                virtual_quote_reserve: 0,
+                virtual_meme_reserve: 0,
+                min_pool_age_secs: 0,
+            }
+            .data(),
+        };
+
+        self.process(&[ix], &[]).await.map(|_| ())
+    }
+
+    /// Derives the pool vaults and calls `new_pool`, which creates them and
+    /// seeds the meme vault with the full meme supply
+    pub async fn new_pool(&mut self) -> Result<(), BanksClientError> {
+        self.new_pool_with(0, 0).await
+    }
+
+    /// Creates the pool like `new_pool`, with a first buyer bonus and a
+    /// creator allocation vesting over a day, either zero for none
+    pub async fn new_pool_with(
+        &mut self,
+        first_buyer_bonus_bps: u16,
+        creator_allocation: u64,
+    ) -> Result<(), BanksClientError> {
+        let quote_mint = self.quote_mint;
+        let meme_mint = self.meme_mint.pubkey();
+        let pool_signer = self.pool_signer;
+        let creator = self.ctx.payer.pubkey();
+        let creator_index = Pubkey::find_program_address(
+            &[CreatorIndex::CREATOR_INDEX_PREFIX, creator.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        let pool_count = match self
+            .ctx
+            .banks_client
+            .get_account(creator_index)
+            .await
+            .unwrap()
+        {
+            Some(account) => {
+                CreatorIndex::try_deserialize(&mut account.data.as_slice())
+                    .unwrap()
+                    .pool_count
+            }
+            None => 0,
+        };
+
+        self.quote_vault = Pubkey::find_program_address(
+            &[BoundPool::QUOTE_VAULT_PREFIX, self.pool.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        self.meme_vault = Pubkey::find_program_address(
+            &[BoundPool::MEME_VAULT_PREFIX, self.pool.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        self.fee_quote_vault = self.create_token_account(&quote_mint, &BP_FEE_KEY).await;
+        self.vesting_vault = match creator_allocation {
+            0 => None,
+            _ => Some(self.create_token_account(&meme_mint, &pool_signer).await),
+        };
+
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::NewPool {
+                sender: self.ctx.payer.pubkey(),
+                pool: self.pool,
+                pool_registry: Pubkey::find_program_address(
+                    &[PoolRegistry::REGISTRY_PREFIX],
+                    &crate::ID,
+                )
+                .0,
+                creator_index,
+                creator_pool: Pubkey::find_program_address(
+                    &[
+                        CreatorPool::CREATOR_POOL_PREFIX,
+                        creator.as_ref(),
+                        &pool_count.to_le_bytes(),
+                    ],
+                    &crate::ID,
+                )
+                .0,
+                meme_mint,
+                quote_vault: self.quote_vault,
+                quote_mint,
+                fee_quote_vault: self.fee_quote_vault,
+                meme_vault: self.meme_vault,
+                target_config: self.target_config,
+                pool_signer,
+                system_program: system_program::ID,
+                token_program: spl_token::id(),
+                creator_fee_vault: None,
+                referral_fee_vault: None,
+                vesting_schedule: self.vesting_vault.map(|_| self.vesting_schedule()),
+                vesting_vault: self.vesting_vault,
+                creator_meme_account: None,
+                bonus_vault: (first_buyer_bonus_bps != 0).then(|| self.bonus_vault()),
+                protocol_config: Pubkey::find_program_address(
+                    &[ProtocolConfig::CONFIG_PREFIX],
+                    &crate::ID,
+                )
+                .0,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::NewPool {
+                migration_target: MigrationAmm::Cpmm,
+                whitelist_until: 0,
+                creator_allocation,
+                creator_allocation_bps: 0,
+                vesting_cliff: 0,
+                vesting_duration: if creator_allocation == 0 { 0 } else { 86_400 },
+                sell_lock_until: 0,
+                first_buyer_bonus_bps,
+                curve_type: CurveType::Standard,
+                trading_supply: 0,
+                lp_supply: 0,
+                price_floor: 0,
+                burn_on_sell_bps: 0,
+            }
+            .data(),
+        };
+
+        self.process(&[ix], &[]).await.map(|_| ())
+    }
+
+    /// Cancels the untraded pool, burning its meme and closing its accounts
+    pub async fn cancel_pool(&mut self) -> Result<(), BanksClientError> {
+        let pool = self.pool().await;
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::CancelPool {
+                creator: self.ctx.payer.pubkey(),
+                pool: self.pool,
+                meme_mint: self.meme_mint.pubkey(),
+                meme_vault: self.meme_vault,
+                quote_vault: self.quote_vault,
+                second_quote_vault: None,
+                target_config: self.target_config,
+                bonus_vault: (pool.first_buyer_bonus_bps != 0).then(|| self.bonus_vault()),
+                vesting_schedule: self.vesting_schedule(),
+                vesting_vault: self.vesting_vault,
+                pool_signer: self.pool_signer,
+                token_program: spl_token::id(),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::CancelPool {}.data(),
+        };
+
+        self.process(&[ix], &[]).await.map(|_| ())
+    }
+
+    /// The pool's first buyer bonus vault
+    pub fn bonus_vault(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[BoundPool::BONUS_VAULT_PREFIX, self.pool.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// The pool's vesting schedule
+    pub fn vesting_schedule(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[VestingSchedule::VESTING_PREFIX, self.pool.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Creates a trader holding `quote_amount` quote tokens
+    pub async fn create_user(&mut self, quote_amount: u64) -> TestUser {
+        let owner = self.next_keypair();
+        let quote_mint = self.quote_mint;
+        let meme_mint = self.meme_mint.pubkey();
+
+        let user_sol = self
+            .create_token_account(&quote_mint, &owner.pubkey())
+            .await;
+        // Created by the first buy
+        let user_meme = get_associated_token_address(&owner.pubkey(), &meme_mint);
+        self.mint_quote(&user_sol, quote_amount).await;
+
+        TestUser {
+            owner,
+            user_sol,
+            user_meme,
+        }
+    }
+
+    /// Buys meme tokens with `coin_in_amount` quote tokens
+    pub async fn swap_y(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+    ) -> Result<SwapQuote, BanksClientError> {
+        self.swap_y_to(user, coin_in_amount, coin_x_min_value, None)
+            .await
+    }
+
+    /// Prices a buy like `swap_y` without executing it
+    pub async fn dry_run_swap_y(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+    ) -> Result<SwapQuote, BanksClientError> {
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::DryRunSwapY {
+                pool: self.pool,
+                quote_vault: self.quote_vault,
+                owner: user.owner.pubkey(),
+                protocol_config: None,
+                referrer_quote: None,
+                referrer: None,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::DryRunSwapY {
+                coin_in_amount,
+                coin_x_min_value,
+                expected_out: 0,
+                tolerance_bps: 0,
+                platform_fee_bps: 0,
+                reference_price: 0,
+                max_deviation_bps: 0,
+            }
+            .data(),
+        };
+
+        let return_data = self.process(&[ix], &[&user.owner]).await?;
+        Ok(SwapQuote::try_from_slice(&return_data.unwrap()).unwrap())
+    }
+
+    /// Buys meme tokens like `swap_y`, delivering them to `recipient` when
+    /// given
+    pub async fn swap_y_to(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+        recipient: Option<Pubkey>,
+    ) -> Result<SwapQuote, BanksClientError> {
+        self.buy(
+            user,
+            coin_in_amount,
+            coin_x_min_value,
+            recipient,
+            false,
+            false,
+        )
+        .await
+    }
+
+    /// Buys meme tokens like `swap_y`, calling back `SWAP_CALLBACK_ID` with
+    /// the pool as its only account
+    pub async fn swap_y_with_callback(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+    ) -> Result<SwapQuote, BanksClientError> {
+        self.buy(user, coin_in_amount, coin_x_min_value, None, false, true)
+            .await
+    }
+
+    /// Buys meme tokens like `swap_y`, paying with the owner's native SOL
+    /// through its temporary WSOL account
+    pub async fn swap_y_native(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+    ) -> Result<SwapQuote, BanksClientError> {
+        self.buy(user, coin_in_amount, coin_x_min_value, None, true, false)
+            .await
+    }
+
+    /// The owner's temporary WSOL account of a native SOL buy
+    pub fn temp_wsol(&self, owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[TEMP_WSOL_PREFIX, owner.as_ref()], &crate::ID).0
+    }
+
+    async fn buy(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+        recipient: Option<Pubkey>,
+        native_sol: bool,
+        callback: bool,
+    ) -> Result<SwapQuote, BanksClientError> {
+        let owner = user.owner.pubkey();
+        let mut ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::SwapCoinY {
+                pool: self.pool,
+                meme_vault: self.meme_vault,
+                quote_vault: self.quote_vault,
+                user_sol: user.user_sol,
+                user_meme: user.user_meme,
+                owner: user.owner.pubkey(),
+                pool_signer_pda: self.pool_signer,
+                token_program: spl_token::id(),
+                protocol_config: None,
+                native_sol_source: native_sol.then_some(owner),
+                buyer_record: self.buyer_record(&owner),
+                system_program: system_program::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                creator_fee_vault: None,
+                referral_fee_vault: None,
+                referrer_quote: None,
+                referrer: None,
+                whitelist_entry: None,
+                platform_fee_account: None,
+                bonus_vault: None,
+                meme_mint: self.meme_mint.pubkey(),
+                fee_treasury: None,
+                trade_history: None,
+                recipient,
+                callback_program: callback.then_some(SWAP_CALLBACK_ID),
+                native_mint: native_sol.then(spl_token::native_mint::id),
+                temp_wsol: native_sol.then(|| self.temp_wsol(&owner)),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::SwapY {
+                coin_in_amount,
+                coin_x_min_value,
+                expected_out: 0,
+                tolerance_bps: 0,
+                platform_fee_bps: 0,
+                reference_price: 0,
+                max_deviation_bps: 0,
+                deadline: 0,
+            }
+            .data(),
+        };
+        if callback {
+            ix.accounts
+                .push(AccountMeta::new_readonly(self.pool, false));
+        }
+
+        let return_data = self.process(&[ix], &[&user.owner]).await?;
+        Ok(SwapQuote::try_from_slice(&return_data.unwrap()).unwrap())
+    }
+
+    /// Sells `coin_in_amount` meme tokens for quote tokens
+    pub async fn swap_x(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_y_min_value: u64,
+    ) -> Result<SwapQuote, BanksClientError> {
+        self.sell(user, coin_in_amount, coin_y_min_value, false)
+            .await
+    }
+
+    /// Sells meme tokens like `swap_x`, receiving native SOL unwrapped
+    /// through the owner's temporary WSOL account
+    pub async fn swap_x_native(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_y_min_value: u64,
+    ) -> Result<SwapQuote, BanksClientError> {
+        self.sell(user, coin_in_amount, coin_y_min_value, true)
+            .await
+    }
+
+    async fn sell(
+        &mut self,
+        user: &TestUser,
+        coin_in_amount: u64,
+        coin_y_min_value: u64,
+        native_sol: bool,
+    ) -> Result<SwapQuote, BanksClientError> {
+        let owner = user.owner.pubkey();
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::SwapCoinX {
+                pool: self.pool,
+                meme_vault: self.meme_vault,
+                quote_vault: self.quote_vault,
+                user_meme: user.user_meme,
+                user_sol: user.user_sol,
+                owner,
+                meme_mint: self.meme_mint.pubkey(),
+                pool_signer: self.pool_signer,
+                token_program: spl_token::id(),
+                protocol_config: None,
+                native_sol_destination: native_sol.then_some(owner),
+                buyer_record: self.buyer_record(&owner),
+                creator_fee_vault: None,
+                referral_fee_vault: None,
+                platform_fee_account: None,
+                fee_treasury: None,
+                trade_history: None,
+                native_mint: native_sol.then(spl_token::native_mint::id),
+                temp_wsol: native_sol.then(|| self.temp_wsol(&owner)),
+                system_program: native_sol.then_some(system_program::ID),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::SwapX {
+                coin_in_amount,
+                coin_y_min_value,
+                platform_fee_bps: 0,
+                deadline: 0,
+            }
+            .data(),
+        };
+
+        let return_data = self.process(&[ix], &[&user.owner]).await?;
+        Ok(SwapQuote::try_from_slice(&return_data.unwrap()).unwrap())
+    }
+
+    /// Creates the position record of `user` in the pool
+    pub async fn init_buyer_record(&mut self, user: &TestUser) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::InitBuyerRecord {
+                owner: user.owner.pubkey(),
+                pool: self.pool,
+                buyer_record: self.buyer_record(&user.owner.pubkey()),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::InitBuyerRecord {}.data(),
+        };
+
+        self.process(&[ix], &[&user.owner]).await.map(|_| ())
+    }
+
+    /// Address of the position record of `owner` in the pool
+    pub fn buyer_record(&self, owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                BuyerRecord::RECORD_PREFIX,
+                self.pool.as_ref(),
+                owner.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Fetches and deserializes the pool account
+    pub async fn pool(&mut self) -> BoundPool {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(self.pool)
+            .await
+            .unwrap()
+            .unwrap();
+
+        BoundPool::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Fetches and deserializes the target config account
+    pub async fn target_config_account(&mut self) -> TargetConfig {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(self.target_config)
+            .await
+            .unwrap()
+            .unwrap();
+
+        TargetConfig::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Returns the lamports of `account`
+    pub async fn lamports(&mut self, account: Pubkey) -> u64 {
+        self.ctx
+            .banks_client
+            .get_account(account)
+            .await
+            .unwrap()
+            .map_or(0, |account| account.lamports)
+    }
+
+    /// Whether `account` exists
+    pub async fn account_exists(&mut self, account: Pubkey) -> bool {
+        self.ctx
+            .banks_client
+            .get_account(account)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    /// Returns the token balance of `account`
+    pub async fn token_balance(&mut self, account: Pubkey) -> u64 {
+        self.ctx
+            .banks_client
+            .get_packed_account_data::<spl_token::state::Account>(account)
+            .await
+            .unwrap()
+            .amount
+    }
+}